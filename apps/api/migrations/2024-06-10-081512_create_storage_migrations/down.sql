@@ -0,0 +1,3 @@
+DROP TABLE storage_migration_items;
+DROP TABLE storage_migrations;
+DROP TYPE storage_migration_statuses;
@@ -0,0 +1,33 @@
+CREATE EXTENSION IF NOT EXISTS "uuid-ossp";
+
+CREATE TYPE storage_migration_statuses AS ENUM('PENDING', 'RUNNING', 'COMPLETED', 'FAILED');
+
+CREATE TABLE storage_migrations (
+	id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+	site_id UUID NOT NULL REFERENCES sites (id) ON DELETE CASCADE,
+	source_storage_repository_id UUID NOT NULL REFERENCES storage_repositories (id) ON DELETE CASCADE,
+	target_storage_repository_id UUID NOT NULL REFERENCES storage_repositories (id) ON DELETE CASCADE,
+	status storage_migration_statuses NOT NULL DEFAULT 'PENDING',
+	dry_run BOOLEAN NOT NULL DEFAULT FALSE,
+	rewrite_content_references BOOLEAN NOT NULL DEFAULT FALSE,
+	total_items INTEGER NOT NULL DEFAULT 0,
+	processed_items INTEGER NOT NULL DEFAULT 0,
+	failed_items INTEGER NOT NULL DEFAULT 0,
+	last_error TEXT,
+	created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX storage_migrations_site_id_idx ON storage_migrations (site_id);
+
+CREATE TABLE storage_migration_items (
+	id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+	storage_migration_id UUID NOT NULL REFERENCES storage_migrations (id) ON DELETE CASCADE,
+	path TEXT NOT NULL,
+	kind TEXT NOT NULL,
+	status storage_migration_statuses NOT NULL DEFAULT 'PENDING',
+	error TEXT,
+	UNIQUE (storage_migration_id, path)
+);
+
+CREATE INDEX storage_migration_items_storage_migration_id_idx ON storage_migration_items (storage_migration_id);
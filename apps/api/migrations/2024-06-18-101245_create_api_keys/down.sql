@@ -0,0 +1,2 @@
+DROP TABLE api_keys_roles;
+DROP TABLE api_keys;
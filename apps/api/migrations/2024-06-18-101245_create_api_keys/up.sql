@@ -0,0 +1,24 @@
+CREATE EXTENSION IF NOT EXISTS "uuid-ossp";
+
+CREATE TABLE api_keys (
+	id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+	site_id UUID NOT NULL REFERENCES sites (id) ON DELETE CASCADE,
+	user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+	name TEXT NOT NULL,
+	prefix TEXT NOT NULL,
+	key_hash TEXT NOT NULL UNIQUE,
+	last_used_at TIMESTAMP,
+	expires_at TIMESTAMP,
+	created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX api_keys_site_id_idx ON api_keys (site_id);
+
+CREATE TABLE api_keys_roles (
+	api_key_id UUID NOT NULL REFERENCES api_keys (id) ON DELETE CASCADE,
+	role_id UUID NOT NULL REFERENCES roles (id) ON DELETE CASCADE,
+	created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+	PRIMARY KEY (api_key_id, role_id)
+);
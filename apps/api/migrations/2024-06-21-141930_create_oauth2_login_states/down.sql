@@ -0,0 +1 @@
+DROP TABLE oauth2_login_states;
@@ -0,0 +1,8 @@
+CREATE TABLE oauth2_login_states (
+	state TEXT PRIMARY KEY,
+	authentication_method_id UUID NOT NULL REFERENCES authentication_methods (id) ON DELETE CASCADE,
+	pkce_verifier TEXT NOT NULL,
+	nonce TEXT NOT NULL,
+	expires_at TIMESTAMP NOT NULL,
+	created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
@@ -0,0 +1,3 @@
+ALTER TABLE sites_users_roles DROP COLUMN granted_by_group;
+ALTER TABLE users_roles DROP COLUMN granted_by_group;
+ALTER TABLE authentication_method_roles DROP COLUMN group_name;
@@ -0,0 +1,6 @@
+ALTER TABLE authentication_method_roles ADD COLUMN group_name TEXT;
+
+-- Roles granted through a group of the identity provider are taken away again once the group goes,
+-- roles that were assigned by hand are left alone
+ALTER TABLE users_roles ADD COLUMN granted_by_group BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE sites_users_roles ADD COLUMN granted_by_group BOOLEAN NOT NULL DEFAULT false;
@@ -0,0 +1,20 @@
+CREATE EXTENSION IF NOT EXISTS "uuid-ossp";
+
+CREATE TYPE user_token_kinds AS ENUM('PASSWORD_RESET', 'EMAIL_VERIFICATION');
+
+CREATE TABLE user_tokens (
+	id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+	user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+	kind user_token_kinds NOT NULL,
+	token_hash TEXT NOT NULL UNIQUE,
+	expires_at TIMESTAMP NOT NULL,
+	used_at TIMESTAMP,
+	created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+CREATE INDEX user_tokens_user_id_idx ON user_tokens (user_id);
+
+ALTER TABLE users ADD COLUMN email_verified_at TIMESTAMP;
+
+-- Accounts that already exist were in use before verification was introduced
+UPDATE users SET email_verified_at = created_at;
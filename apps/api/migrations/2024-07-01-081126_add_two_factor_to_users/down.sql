@@ -0,0 +1,11 @@
+DROP TABLE user_recovery_codes;
+ALTER TABLE users DROP COLUMN totp_last_step;
+ALTER TABLE users DROP COLUMN totp_enabled_at;
+ALTER TABLE users DROP COLUMN totp_secret;
+
+DELETE FROM user_tokens WHERE kind = 'TWO_FACTOR_CHALLENGE';
+ALTER TABLE user_tokens DROP COLUMN login_identifier;
+ALTER TYPE user_token_kinds RENAME TO user_token_kinds_old;
+CREATE TYPE user_token_kinds AS ENUM('PASSWORD_RESET', 'EMAIL_VERIFICATION');
+ALTER TABLE user_tokens ALTER COLUMN kind TYPE user_token_kinds USING kind::text::user_token_kinds;
+DROP TYPE user_token_kinds_old;
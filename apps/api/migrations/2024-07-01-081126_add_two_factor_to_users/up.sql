@@ -0,0 +1,20 @@
+CREATE EXTENSION IF NOT EXISTS "uuid-ossp";
+
+ALTER TYPE user_token_kinds ADD VALUE 'TWO_FACTOR_CHALLENGE';
+-- The name the password step was throttled under, failed codes count against the same account
+ALTER TABLE user_tokens ADD COLUMN login_identifier TEXT;
+
+ALTER TABLE users ADD COLUMN totp_secret TEXT;
+ALTER TABLE users ADD COLUMN totp_enabled_at TIMESTAMP;
+-- Time step of the last accepted code, a code can't be used a second time
+ALTER TABLE users ADD COLUMN totp_last_step BIGINT;
+
+CREATE TABLE user_recovery_codes (
+	id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+	user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+	code_hash TEXT NOT NULL,
+	used_at TIMESTAMP,
+	created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+CREATE INDEX user_recovery_codes_user_id_idx ON user_recovery_codes (user_id);
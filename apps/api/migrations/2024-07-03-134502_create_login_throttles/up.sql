@@ -0,0 +1,26 @@
+CREATE EXTENSION IF NOT EXISTS "uuid-ossp";
+
+CREATE TYPE login_throttle_scopes AS ENUM('ACCOUNT', 'IP_ADDRESS');
+
+CREATE TABLE login_throttles (
+	id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+	scope login_throttle_scopes NOT NULL,
+	identifier TEXT NOT NULL,
+	failed_attempts INT NOT NULL DEFAULT 0,
+	locked_until TIMESTAMP,
+	last_failed_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	UNIQUE (scope, identifier)
+);
+
+CREATE TABLE login_lockouts (
+	id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+	scope login_throttle_scopes NOT NULL,
+	identifier TEXT NOT NULL,
+	failed_attempts INT NOT NULL,
+	ip_address TEXT,
+	user_agent TEXT,
+	locked_until TIMESTAMP NOT NULL,
+	created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+CREATE INDEX login_lockouts_created_at_idx ON login_lockouts (created_at);
@@ -0,0 +1,2 @@
+DROP TABLE site_invitations_roles;
+DROP TABLE site_invitations;
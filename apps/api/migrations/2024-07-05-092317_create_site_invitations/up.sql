@@ -0,0 +1,23 @@
+CREATE EXTENSION IF NOT EXISTS "uuid-ossp";
+
+CREATE TABLE site_invitations (
+	id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+	site_id UUID NOT NULL REFERENCES sites (id) ON DELETE CASCADE,
+	invited_by_id UUID REFERENCES users (id) ON DELETE SET NULL,
+	email TEXT NOT NULL,
+	token_hash TEXT NOT NULL UNIQUE,
+	expires_at TIMESTAMP NOT NULL,
+	accepted_at TIMESTAMP,
+	created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	updated_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX site_invitations_site_id_idx ON site_invitations (site_id);
+
+CREATE TABLE site_invitations_roles (
+	site_invitation_id UUID NOT NULL REFERENCES site_invitations (id) ON DELETE CASCADE,
+	role_id UUID NOT NULL REFERENCES roles (id) ON DELETE CASCADE,
+	created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+	PRIMARY KEY (site_invitation_id, role_id)
+);
@@ -0,0 +1,3 @@
+ALTER TABLE users DROP COLUMN language_key;
+DROP TABLE labels;
+DROP TYPE label_entity_types;
@@ -0,0 +1,17 @@
+CREATE EXTENSION IF NOT EXISTS "uuid-ossp";
+
+CREATE TYPE label_entity_types AS ENUM('CONTENT_TYPE', 'FIELD', 'COMPARTMENT', 'WORKFLOW_STATE');
+
+CREATE TABLE labels (
+	id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+	entity_type label_entity_types NOT NULL,
+	entity_id UUID NOT NULL,
+	language_key TEXT NOT NULL,
+	name TEXT NOT NULL,
+	description TEXT,
+	created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	UNIQUE (entity_id, language_key)
+);
+
+ALTER TABLE users ADD COLUMN language_key TEXT;
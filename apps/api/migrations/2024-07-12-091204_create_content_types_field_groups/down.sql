@@ -0,0 +1,2 @@
+DROP TABLE content_types_field_groups;
+-- Postgres can't drop a value from an enum, FIELD_GROUP stays in content_type_kinds
@@ -0,0 +1,11 @@
+ALTER TYPE content_type_kinds ADD VALUE IF NOT EXISTS 'FIELD_GROUP' AFTER 'CONTENT_BLOCK';
+
+CREATE TABLE content_types_field_groups (
+	content_type_id UUID NOT NULL REFERENCES content_types (id) ON DELETE CASCADE,
+	field_group_id UUID NOT NULL REFERENCES content_types (id) ON DELETE CASCADE,
+	sequence_number INTEGER NOT NULL DEFAULT 0,
+	PRIMARY KEY (content_type_id, field_group_id),
+	created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+CREATE INDEX content_types_field_groups_field_group_id_idx ON content_types_field_groups (field_group_id);
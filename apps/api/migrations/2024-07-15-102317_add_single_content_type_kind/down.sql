@@ -0,0 +1,4 @@
+DROP INDEX content_singleton_idx;
+ALTER TABLE content DROP COLUMN singleton;
+
+-- Postgres can't drop a value from an enum, SINGLE stays in content_type_kinds
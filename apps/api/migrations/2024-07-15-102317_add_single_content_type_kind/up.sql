@@ -0,0 +1,5 @@
+ALTER TYPE content_type_kinds ADD VALUE IF NOT EXISTS 'SINGLE' AFTER 'CONTENT_BLOCK';
+
+-- The kind lives on the content type, so the items of SINGLE content types are flagged to keep them unique
+ALTER TABLE content ADD COLUMN singleton BOOLEAN NOT NULL DEFAULT false;
+CREATE UNIQUE INDEX content_singleton_idx ON content (site_id, content_type_id, language_id) WHERE singleton AND NOT deleted;
@@ -0,0 +1,6 @@
+DROP INDEX content_published_path_idx;
+DROP INDEX content_site_id_language_id_path_idx;
+DROP INDEX content_parent_id_idx;
+
+ALTER TABLE content DROP COLUMN path;
+ALTER TABLE content DROP COLUMN parent_id;
@@ -0,0 +1,14 @@
+-- Deferred so a parent and its children can be inserted in any order within one transaction
+ALTER TABLE content ADD COLUMN parent_id UUID REFERENCES content (id) ON DELETE SET NULL DEFERRABLE INITIALLY DEFERRED;
+ALTER TABLE content ADD COLUMN path TEXT;
+
+CREATE INDEX content_parent_id_idx ON content (parent_id);
+CREATE INDEX content_site_id_language_id_path_idx ON content (site_id, language_id, path);
+
+UPDATE content
+SET path = '/' || content.slug
+FROM content_types
+WHERE content_types.id = content.content_type_id AND content_types.kind = 'PAGE';
+
+-- Two published pages of a language can't be served at the same path
+CREATE UNIQUE INDEX content_published_path_idx ON content (site_id, language_id, path) WHERE published AND NOT deleted;
@@ -0,0 +1,13 @@
+CREATE EXTENSION IF NOT EXISTS "uuid-ossp";
+
+CREATE TABLE redirects (
+	id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+	site_id UUID NOT NULL REFERENCES sites (id) ON DELETE CASCADE,
+	language_id UUID REFERENCES languages (id) ON DELETE CASCADE,
+	source_path TEXT NOT NULL,
+	content_id UUID REFERENCES content (id) ON DELETE CASCADE,
+	created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,
+	updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL
+);
+
+CREATE INDEX redirects_site_id_source_path_idx ON redirects (site_id, source_path);
@@ -0,0 +1,9 @@
+ALTER TABLE redirects DROP CONSTRAINT redirects_status_code_check;
+
+ALTER TABLE redirects DROP COLUMN last_hit_at;
+ALTER TABLE redirects DROP COLUMN hits;
+ALTER TABLE redirects DROP COLUMN status_code;
+ALTER TABLE redirects DROP COLUMN target_path;
+ALTER TABLE redirects DROP COLUMN match_type;
+
+DROP TYPE redirect_match_types;
@@ -0,0 +1,9 @@
+CREATE TYPE redirect_match_types AS ENUM('EXACT', 'PATTERN');
+
+ALTER TABLE redirects ADD COLUMN match_type redirect_match_types NOT NULL DEFAULT 'EXACT';
+ALTER TABLE redirects ADD COLUMN target_path TEXT;
+ALTER TABLE redirects ADD COLUMN status_code INTEGER NOT NULL DEFAULT 301;
+ALTER TABLE redirects ADD COLUMN hits BIGINT NOT NULL DEFAULT 0;
+ALTER TABLE redirects ADD COLUMN last_hit_at TIMESTAMP;
+
+ALTER TABLE redirects ADD CONSTRAINT redirects_status_code_check CHECK (status_code IN (301, 302, 410));
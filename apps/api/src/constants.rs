@@ -1,5 +1,6 @@
 pub const AUTHORIZATION: &str = "Authorization";
 pub const BIND: &str = "0.0.0.0:8000";
+pub const TOTP_ISSUER: &str = "DCM";
 
 pub mod env_key {
 	pub const DATABASE_URL: &str = "DATABASE_URL";
@@ -8,4 +9,28 @@ pub mod env_key {
 	pub const OTEL_ENDPOINT: &str = "OTEL_ENDPOINT";
 	pub const FRONTEND_URL: &str = "FRONTEND_URL";
 	pub const VERSION: &str = "VERSION";
+	pub const MAIL_TRANSPORT: &str = "MAIL_TRANSPORT";
+	pub const MAIL_FROM: &str = "MAIL_FROM";
+	pub const MAIL_FILE_DIR: &str = "MAIL_FILE_DIR";
+	pub const SMTP_URL: &str = "SMTP_URL";
+	pub const REQUIRE_EMAIL_VERIFICATION: &str = "REQUIRE_EMAIL_VERIFICATION";
+	pub const LOGIN_MAX_ATTEMPTS: &str = "LOGIN_MAX_ATTEMPTS";
+	pub const LOGIN_MAX_ATTEMPTS_PER_IP: &str = "LOGIN_MAX_ATTEMPTS_PER_IP";
+	pub const LOGIN_LOCKOUT_SECONDS: &str = "LOGIN_LOCKOUT_SECONDS";
+	pub const TRUSTED_PROXIES: &str = "TRUSTED_PROXIES";
+	pub const PASSWORD_MIN_LENGTH: &str = "PASSWORD_MIN_LENGTH";
+	pub const PASSWORD_REQUIRE_COMPLEXITY: &str = "PASSWORD_REQUIRE_COMPLEXITY";
+	pub const PASSWORD_BREACHED_LIST: &str = "PASSWORD_BREACHED_LIST";
+	pub const SCIM_TOKEN: &str = "SCIM_TOKEN";
+	pub const SCIM_AUTHENTICATION_METHOD_ID: &str = "SCIM_AUTHENTICATION_METHOD_ID";
+}
+
+pub mod scim_schema {
+	pub const USER: &str = "urn:ietf:params:scim:schemas:core:2.0:User";
+	pub const GROUP: &str = "urn:ietf:params:scim:schemas:core:2.0:Group";
+	pub const LIST_RESPONSE: &str = "urn:ietf:params:scim:api:messages:2.0:ListResponse";
+	pub const PATCH_OP: &str = "urn:ietf:params:scim:api:messages:2.0:PatchOp";
+	pub const ERROR: &str = "urn:ietf:params:scim:api:messages:2.0:Error";
+	pub const SERVICE_PROVIDER_CONFIG: &str =
+		"urn:ietf:params:scim:schemas:core:2.0:ServiceProviderConfig";
 }
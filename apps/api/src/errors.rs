@@ -1,9 +1,11 @@
-use actix_web::{http::StatusCode, HttpResponse};
+use actix_web::{error::BlockingError, http::StatusCode, HttpResponse};
+use aws_sdk_s3::error::{DisplayErrorContext, SdkError};
 use bcrypt::BcryptError;
 use core::fmt;
 use diesel::r2d2::{Error as R2D2Error, PoolError};
 use diesel::result::{DatabaseErrorKind, Error as DieselError};
 use jsonwebtoken::errors::{Error as JwtError, ErrorKind as JwtErrorKind};
+use ldap3::LdapError;
 use oauth2::RequestTokenError;
 use serde::Serialize;
 use std::convert::From;
@@ -13,6 +15,7 @@ use suppaftp::FtpError;
 use thiserror::Error;
 use utoipa::ToSchema;
 use uuid::{Error as UuidError, Uuid};
+use zip::result::ZipError;
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct AppErrorValue {
@@ -61,11 +64,29 @@ pub enum AppError {
 	#[error("Bad Request: {:#?}", _0)]
 	BadRequest(AppErrorValue),
 
+	// 429
+	#[error("Too Many Requests: {:#?}", _0)]
+	TooManyRequests(AppErrorValue),
+
 	// 500
 	#[error("Internal Server Error: {:#?}", _0)]
 	InternalServerError(AppErrorValue),
 }
 
+impl AppError {
+	pub fn value(&self) -> &AppErrorValue {
+		match self {
+			AppError::Unauthorized(value)
+			| AppError::Forbidden(value)
+			| AppError::NotFound(value)
+			| AppError::UnprocessableEntity(value)
+			| AppError::BadRequest(value)
+			| AppError::TooManyRequests(value)
+			| AppError::InternalServerError(value) => value,
+		}
+	}
+}
+
 impl actix_web::error::ResponseError for AppError {
 	fn error_response(&self) -> HttpResponse {
 		println!("ERROR_RESPONSE: {:?}", self);
@@ -75,6 +96,7 @@ impl actix_web::error::ResponseError for AppError {
 			AppError::NotFound(ref msg) => HttpResponse::NotFound().json(msg),
 			AppError::BadRequest(ref msg) => HttpResponse::BadRequest().json(msg),
 			AppError::UnprocessableEntity(ref msg) => HttpResponse::UnprocessableEntity().json(msg),
+			AppError::TooManyRequests(ref msg) => HttpResponse::TooManyRequests().json(msg),
 			AppError::InternalServerError(ref msg) => HttpResponse::InternalServerError().json(msg),
 		}
 	}
@@ -86,6 +108,7 @@ impl actix_web::error::ResponseError for AppError {
 			AppError::NotFound(_) => StatusCode::NOT_FOUND,
 			AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
 			AppError::UnprocessableEntity(_) => StatusCode::UNPROCESSABLE_ENTITY,
+			AppError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
 			AppError::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
 		}
 	}
@@ -247,17 +270,22 @@ impl From<&str> for AppError {
 	}
 }
 
-// impl<T> From<SdkError<T>> for AppError {
-// 	fn from(err: SdkError<T>) -> Self {
-// 		println!("{}", &err);
-// 		AppError::InternalServerError(AppErrorValue {
-// 			message: err.to_string(),
-// 			status: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
-// 			code: "S3_ERROR".to_owned(),
-// 			..Default::default()
-// 		})
-// 	}
-// }
+impl<E, R> From<SdkError<E, R>> for AppError
+where
+	E: std::error::Error + 'static,
+	R: std::fmt::Debug,
+{
+	fn from(err: SdkError<E, R>) -> Self {
+		let message = DisplayErrorContext(&err).to_string();
+		tracing::error!("S3 request failed: {}", message);
+		AppError::InternalServerError(AppErrorValue {
+			message,
+			status: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+			code: "S3_ERROR".to_owned(),
+			..Default::default()
+		})
+	}
+}
 
 impl From<TryFromIntError> for AppError {
 	fn from(_err: TryFromIntError) -> Self {
@@ -292,6 +320,17 @@ impl From<std::time::SystemTimeError> for AppError {
 	}
 }
 
+impl From<BlockingError> for AppError {
+	fn from(err: BlockingError) -> Self {
+		AppError::InternalServerError(AppErrorValue {
+			message: err.to_string(),
+			status: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+			code: "BLOCKING_ERROR".to_owned(),
+			..Default::default()
+		})
+	}
+}
+
 impl From<FtpError> for AppError {
 	fn from(err: FtpError) -> Self {
 		dbg!(&err);
@@ -304,6 +343,17 @@ impl From<FtpError> for AppError {
 	}
 }
 
+impl From<LdapError> for AppError {
+	fn from(err: LdapError) -> Self {
+		AppError::InternalServerError(AppErrorValue {
+			message: err.to_string(),
+			status: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+			code: "LDAP_ERROR".to_owned(),
+			..Default::default()
+		})
+	}
+}
+
 impl From<VarError> for AppError {
 	fn from(_err: VarError) -> Self {
 		AppError::InternalServerError(AppErrorValue {
@@ -350,3 +400,14 @@ impl From<serde_json::Error> for AppError {
 		})
 	}
 }
+
+impl From<ZipError> for AppError {
+	fn from(err: ZipError) -> Self {
+		AppError::InternalServerError(AppErrorValue {
+			message: err.to_string(),
+			status: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+			code: "ZIP_ERROR".to_owned(),
+			..Default::default()
+		})
+	}
+}
@@ -25,7 +25,9 @@ use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
 use crate::modules::core::actors::hook::HookActor;
+use crate::modules::core::actors::mail::MailActor;
 use crate::modules::iam_actions::models::iam_action::IAMAction;
+use crate::modules::resources::actors::storage_migration::StorageMigrationActor;
 use crate::openapi::ApiDoc;
 use serde_qs::actix::QsQueryConfig;
 use serde_qs::Config as QsConfig;
@@ -97,8 +99,15 @@ async fn main() -> std::io::Result<()> {
 	let state: modules::core::middleware::state::AppState = {
 		let pool = utils::db::establish_connection();
 		let hook_addr = SyncArbiter::start(2, || HookActor);
-
-		modules::core::middleware::state::AppState { pool, hook_addr }
+		let mail_addr = SyncArbiter::start(1, || MailActor);
+		let migration_addr = SyncArbiter::start(1, || StorageMigrationActor);
+
+		modules::core::middleware::state::AppState {
+			pool,
+			hook_addr,
+			mail_addr,
+			migration_addr,
+		}
 	};
 	println!("Database connected");
 
@@ -107,6 +116,10 @@ async fn main() -> std::io::Result<()> {
 	let _ = IAMAction::upsert(conn);
 	println!("Migrations ok");
 
+	if let Err(err) = modules::auth::services::password_policy::load_breached_passwords().await {
+		tracing::error!("Breached password list could not be loaded: {}", err);
+	}
+
 	HttpServer::new(move || {
 		App::new()
 			.wrap(TracingLogger::default())
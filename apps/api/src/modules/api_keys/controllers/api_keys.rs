@@ -0,0 +1,221 @@
+use super::super::dto::api_keys::{request, response};
+use crate::errors::AppErrorValue;
+use crate::modules::api_keys::models::api_key::{ApiKey, UpdateApiKey};
+use crate::modules::auth::helpers::permissions::{ensure_assignable_roles, ensure_permission};
+use crate::modules::core::middleware::state::AppState;
+use crate::modules::core::models::hal::HALPage;
+use crate::utils::api::ApiResponse;
+use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse};
+use chrono::Utc;
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindOnePathParams {
+	site_id: Uuid,
+	api_key_id: Uuid,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindPathParams {
+	site_id: Uuid,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindAllQueryParams {
+	page: Option<i64>,
+	pagesize: Option<i64>,
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/api-keys",
+    request_body = CreateApiKeyDTO,
+	responses(
+		(status = 200, body = CreatedApiKeyDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    )
+)]
+#[post("")]
+pub async fn create(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	form: web::Json<request::CreateApiKeyDTO>,
+	params: web::Path<FindPathParams>,
+) -> ApiResponse {
+	let user_id = ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:api-keys:*"),
+		"sites::api-keys:create",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let role_ids = ensure_assignable_roles(&req, conn, params.site_id, &form.role_ids, false)?;
+
+	let created_api_key = ApiKey::create(
+		conn,
+		params.site_id,
+		user_id,
+		form.name.clone(),
+		form.expires_at,
+		role_ids,
+	)?;
+
+	// The plain key is only ever returned in this response
+	let res = response::CreatedApiKeyDTO::from(created_api_key);
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/api-keys",
+	responses(
+		(status = 200, body = ApiKeysDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindAllQueryParams)
+)]
+#[get("")]
+pub async fn find_all(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	query: web::Query<FindAllQueryParams>,
+	params: web::Path<FindPathParams>,
+) -> ApiResponse {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:api-keys:*"),
+		"sites::api-keys:read",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let page = query.page.unwrap_or(1);
+	let pagesize = query.pagesize.unwrap_or(20);
+
+	let (api_keys, total_elements) = ApiKey::find(conn, params.site_id, page, pagesize)?;
+
+	let res = response::ApiKeysDTO::from((
+		api_keys,
+		HALPage {
+			number: page,
+			size: pagesize,
+			total_elements,
+			total_pages: (total_elements / pagesize + (total_elements % pagesize).signum()).max(1),
+		},
+		params.site_id,
+	));
+
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/api-keys",
+	responses(
+		(status = 200, body = ApiKeyDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindOnePathParams)
+)]
+#[get("/{api_key_id}")]
+pub async fn find_one(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindOnePathParams>,
+) -> ApiResponse {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:api-keys:{}", params.api_key_id),
+		"sites::api-keys:read",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let api_key = ApiKey::find_one(conn, params.site_id, params.api_key_id)?;
+
+	let res = response::ApiKeyDTO::from(api_key);
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/api-keys",
+    request_body = UpdateApiKeyDTO,
+	responses(
+		(status = 200, body = ApiKeyDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindOnePathParams)
+)]
+#[put("/{api_key_id}")]
+pub async fn update(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindOnePathParams>,
+	form: web::Json<request::UpdateApiKeyDTO>,
+) -> ApiResponse {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:api-keys:{}", params.api_key_id),
+		"sites::api-keys:update",
+	)?;
+	let conn = &mut state.get_conn()?;
+
+	let role_ids = form
+		.role_ids
+		.as_ref()
+		.map(|role_ids| ensure_assignable_roles(&req, conn, params.site_id, role_ids, false))
+		.transpose()?;
+
+	let api_key = ApiKey::update(
+		conn,
+		params.site_id,
+		params.api_key_id,
+		UpdateApiKey {
+			name: form.name.clone(),
+			expires_at: form.expires_at,
+			updated_at: Utc::now().naive_utc(),
+		},
+		role_ids,
+	)?;
+
+	let res = response::ApiKeyDTO::from(api_key);
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/api-keys",
+	responses(
+		(status = 204),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindOnePathParams)
+)]
+#[delete("/{api_key_id}")]
+pub async fn remove(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindOnePathParams>,
+) -> ApiResponse {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:api-keys:{}", params.api_key_id),
+		"sites::api-keys:remove",
+	)?;
+	let conn = &mut state.get_conn()?;
+	ApiKey::remove(conn, params.site_id, params.api_key_id)?;
+	Ok(HttpResponse::NoContent().body(()))
+}
@@ -0,0 +1,27 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateApiKeyDTO {
+	pub name: String,
+	pub role_ids: Vec<Uuid>,
+	pub expires_at: Option<NaiveDateTime>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateApiKeyDTO {
+	pub name: Option<String>,
+	pub role_ids: Option<Vec<Uuid>>,
+	// Sending `null` explicitly removes the expiry date
+	#[serde(
+		default,
+		skip_serializing_if = "Option::is_none",
+		with = "::serde_with::rust::double_option"
+	)]
+	#[schema(value_type = Option<NaiveDateTime>)]
+	pub expires_at: Option<Option<NaiveDateTime>>,
+}
@@ -0,0 +1,80 @@
+use crate::modules::{
+	api_keys::models::api_key::ApiKey,
+	core::models::hal::{HALLinkList, HALPage},
+	roles::{dto::response::RoleDTO, models::role::Role},
+};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::convert::From;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyDTO {
+	pub id: Uuid,
+	pub name: String,
+	pub prefix: String,
+	pub roles: Vec<RoleDTO>,
+	pub last_used_at: Option<NaiveDateTime>,
+	pub expires_at: Option<NaiveDateTime>,
+	pub created_at: NaiveDateTime,
+	pub updated_at: NaiveDateTime,
+}
+
+impl From<(ApiKey, Vec<Role>)> for ApiKeyDTO {
+	fn from((api_key, roles): (ApiKey, Vec<Role>)) -> Self {
+		Self {
+			id: api_key.id,
+			name: api_key.name,
+			prefix: api_key.prefix,
+			roles: roles.into_iter().map(RoleDTO::from).collect(),
+			last_used_at: api_key.last_used_at,
+			expires_at: api_key.expires_at,
+			created_at: api_key.created_at,
+			updated_at: api_key.updated_at,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedApiKeyDTO {
+	#[serde(flatten)]
+	pub api_key: ApiKeyDTO,
+	pub key: String,
+}
+
+impl From<((ApiKey, Vec<Role>), String)> for CreatedApiKeyDTO {
+	fn from((api_key, key): ((ApiKey, Vec<Role>), String)) -> Self {
+		Self {
+			api_key: ApiKeyDTO::from(api_key),
+			key,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeysEmbeddedDTO {
+	pub api_keys: Vec<ApiKeyDTO>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct ApiKeysDTO {
+	pub _links: HALLinkList,
+	pub _page: HALPage,
+	pub _embedded: ApiKeysEmbeddedDTO,
+}
+
+impl From<(Vec<(ApiKey, Vec<Role>)>, HALPage, Uuid)> for ApiKeysDTO {
+	fn from((api_keys, page, site_id): (Vec<(ApiKey, Vec<Role>)>, HALPage, Uuid)) -> Self {
+		Self {
+			_links: HALLinkList::from((format!("/api/v1/sites/{}/api-keys", site_id), &page)),
+			_embedded: ApiKeysEmbeddedDTO {
+				api_keys: api_keys.into_iter().map(ApiKeyDTO::from).collect(),
+			},
+			_page: page,
+		}
+	}
+}
@@ -0,0 +1,246 @@
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::modules::api_keys::models::api_key_role::ApiKeyRole;
+use crate::modules::roles::models::role::Role;
+use crate::schema::{api_keys, api_keys_roles, roles, users};
+use crate::utils::hasher;
+use crate::utils::string::generate_random_string;
+
+pub const API_KEY_PREFIX: &str = "dcm_";
+const API_KEY_SECRET_LENGTH: usize = 48;
+const API_KEY_DISPLAY_PREFIX_LENGTH: usize = 12;
+
+#[derive(Identifiable, Selectable, Queryable, Debug, Clone)]
+#[diesel(table_name = api_keys)]
+#[diesel(primary_key(id))]
+pub struct ApiKey {
+	pub id: Uuid,
+	pub site_id: Uuid,
+	pub user_id: Uuid,
+	pub name: String,
+	pub prefix: String,
+	pub key_hash: String,
+	pub last_used_at: Option<NaiveDateTime>,
+	pub expires_at: Option<NaiveDateTime>,
+	pub created_at: NaiveDateTime,
+	pub updated_at: NaiveDateTime,
+}
+
+pub fn generate_api_key() -> String {
+	format!(
+		"{}{}",
+		API_KEY_PREFIX,
+		generate_random_string(API_KEY_SECRET_LENGTH)
+	)
+}
+
+impl ApiKey {
+	// Returns the created key together with its plain value, which is never stored
+	#[instrument(skip(conn))]
+	pub fn create(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		user_id: Uuid,
+		name: String,
+		expires_at: Option<NaiveDateTime>,
+		role_ids: Vec<Uuid>,
+	) -> Result<((Self, Vec<Role>), String), AppError> {
+		let key = generate_api_key();
+
+		let api_key = diesel::insert_into(api_keys::table)
+			.values(CreateApiKey {
+				site_id,
+				user_id,
+				name,
+				prefix: key[..API_KEY_DISPLAY_PREFIX_LENGTH].to_owned(),
+				key_hash: hasher::hash_token(&key),
+				expires_at,
+			})
+			.returning(ApiKey::as_returning())
+			.get_result::<ApiKey>(conn)?;
+
+		ApiKeyRole::upsert_many(conn, api_key.id, role_ids)?;
+		let roles = api_key.get_roles(conn)?;
+
+		Ok(((api_key, roles), key))
+	}
+
+	#[instrument(skip(conn))]
+	pub fn find_one(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		id: Uuid,
+	) -> Result<(Self, Vec<Role>), AppError> {
+		let api_key = api_keys::table
+			.find(id)
+			.filter(api_keys::site_id.eq(site_id))
+			.first::<ApiKey>(conn)?;
+		let roles = api_key.get_roles(conn)?;
+
+		Ok((api_key, roles))
+	}
+
+	#[instrument(skip(conn))]
+	pub fn find(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		page: i64,
+		pagesize: i64,
+	) -> Result<(Vec<(Self, Vec<Role>)>, i64), AppError> {
+		let query = {
+			let mut query = api_keys::table
+				.filter(api_keys::site_id.eq(site_id))
+				.order(api_keys::created_at.desc())
+				.into_boxed();
+
+			if pagesize != -1 {
+				query = query.offset((page - 1) * pagesize).limit(pagesize);
+			};
+
+			query
+		};
+
+		let api_keys = query.select(ApiKey::as_select()).load::<ApiKey>(conn)?;
+		let api_key_roles = ApiKeyRole::belonging_to(&api_keys)
+			.inner_join(roles::table)
+			.select((ApiKeyRole::as_select(), Role::as_select()))
+			.load::<(ApiKeyRole, Role)>(conn)?;
+
+		let api_keys_with_roles = api_key_roles
+			.grouped_by(&api_keys)
+			.into_iter()
+			.zip(api_keys)
+			.map(|(roles, api_key)| (api_key, roles.into_iter().map(|(_, role)| role).collect()))
+			.collect::<Vec<(ApiKey, Vec<Role>)>>();
+
+		let total_elements = api_keys::table
+			.filter(api_keys::site_id.eq(site_id))
+			.count()
+			.get_result::<i64>(conn)?;
+
+		Ok((api_keys_with_roles, total_elements))
+	}
+
+	#[instrument(skip(conn, key))]
+	pub fn find_by_key(conn: &mut PgConnection, key: &str) -> Result<Option<Self>, AppError> {
+		// Keys stop working together with the user that owns them
+		let api_key = api_keys::table
+			.inner_join(users::table)
+			.filter(api_keys::key_hash.eq(hasher::hash_token(key)))
+			.filter(
+				api_keys::expires_at
+					.is_null()
+					.or(api_keys::expires_at.gt(Utc::now().naive_utc())),
+			)
+			.filter(users::deactivated_at.is_null())
+			.select(ApiKey::as_select())
+			.first::<ApiKey>(conn)
+			.optional()?;
+
+		Ok(api_key)
+	}
+
+	#[instrument(skip(conn))]
+	pub fn update(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		id: Uuid,
+		changeset: UpdateApiKey,
+		role_ids: Option<Vec<Uuid>>,
+	) -> Result<(Self, Vec<Role>), AppError> {
+		let target = api_keys::table
+			.find(id)
+			.filter(api_keys::site_id.eq(site_id));
+		let api_key = diesel::update(target)
+			.set(changeset)
+			.returning(ApiKey::as_returning())
+			.get_result::<ApiKey>(conn)?;
+
+		if let Some(role_ids) = role_ids {
+			ApiKeyRole::upsert_many(conn, api_key.id, role_ids)?;
+		}
+
+		let roles = api_key.get_roles(conn)?;
+		Ok((api_key, roles))
+	}
+
+	// Only records usage once a minute so authenticated requests don't all cause a write
+	#[instrument(skip(conn))]
+	pub fn touch(conn: &mut PgConnection, id: Uuid) -> Result<(), AppError> {
+		let now = Utc::now().naive_utc();
+		let target = api_keys::table.find(id).filter(
+			api_keys::last_used_at
+				.is_null()
+				.or(api_keys::last_used_at.lt(now - Duration::minutes(1))),
+		);
+		diesel::update(target)
+			.set(api_keys::last_used_at.eq(now))
+			.execute(conn)?;
+
+		Ok(())
+	}
+
+	#[instrument(skip(conn))]
+	pub fn remove(conn: &mut PgConnection, site_id: Uuid, id: Uuid) -> Result<(), AppError> {
+		diesel::delete(
+			api_keys::table
+				.filter(api_keys::site_id.eq(site_id))
+				.filter(api_keys::id.eq(id)),
+		)
+		.get_result::<ApiKey>(conn)?;
+
+		Ok(())
+	}
+
+	// Keys act on behalf of the user that created them, so they stop working once that user leaves the site
+	#[instrument(skip(conn))]
+	pub fn remove_for_user(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		user_id: Uuid,
+	) -> Result<(), AppError> {
+		diesel::delete(
+			api_keys::table
+				.filter(api_keys::site_id.eq(site_id))
+				.filter(api_keys::user_id.eq(user_id)),
+		)
+		.execute(conn)?;
+
+		Ok(())
+	}
+}
+
+impl ApiKey {
+	pub fn get_roles(&self, conn: &mut PgConnection) -> Result<Vec<Role>, AppError> {
+		let roles = api_keys_roles::table
+			.filter(api_keys_roles::api_key_id.eq(self.id))
+			.inner_join(roles::table)
+			.select(Role::as_select())
+			.load::<Role>(conn)?;
+
+		Ok(roles)
+	}
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = api_keys)]
+pub struct CreateApiKey {
+	pub site_id: Uuid,
+	pub user_id: Uuid,
+	pub name: String,
+	pub prefix: String,
+	pub key_hash: String,
+	pub expires_at: Option<NaiveDateTime>,
+}
+
+#[derive(AsChangeset, Debug, Clone)]
+#[diesel(table_name = api_keys)]
+pub struct UpdateApiKey {
+	pub name: Option<String>,
+	pub expires_at: Option<Option<NaiveDateTime>>,
+	pub updated_at: NaiveDateTime,
+}
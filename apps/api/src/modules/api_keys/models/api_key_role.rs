@@ -0,0 +1,55 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::modules::api_keys::models::api_key::ApiKey;
+use crate::modules::roles::models::role::Role;
+
+use crate::errors::AppError;
+use crate::schema::api_keys_roles;
+
+#[derive(Identifiable, Selectable, Queryable, Associations, Debug)]
+#[diesel(belongs_to(ApiKey))]
+#[diesel(belongs_to(Role))]
+#[diesel(table_name = api_keys_roles)]
+#[diesel(primary_key(api_key_id, role_id))]
+pub struct ApiKeyRole {
+	pub api_key_id: Uuid,
+	pub role_id: Uuid,
+	pub created_at: NaiveDateTime,
+	pub updated_at: NaiveDateTime,
+}
+
+impl ApiKeyRole {
+	pub fn upsert_many(
+		conn: &mut PgConnection,
+		api_key_id: Uuid,
+		role_ids: Vec<Uuid>,
+	) -> Result<Vec<Self>, AppError> {
+		let target = api_keys_roles::table.filter(api_keys_roles::api_key_id.eq(api_key_id));
+		diesel::delete(target).execute(conn)?;
+
+		let insert_items: Vec<CreateApiKeyRole> = role_ids
+			.into_iter()
+			.map(|role_id| CreateApiKeyRole {
+				api_key_id,
+				role_id,
+			})
+			.collect();
+
+		let api_key_roles = diesel::insert_into(api_keys_roles::table)
+			.values(insert_items)
+			.returning(ApiKeyRole::as_returning())
+			.get_results(conn)?;
+
+		Ok(api_key_roles)
+	}
+}
+
+#[derive(Insertable, Debug, Deserialize)]
+#[diesel(table_name = api_keys_roles)]
+pub struct CreateApiKeyRole {
+	pub api_key_id: Uuid,
+	pub role_id: Uuid,
+}
@@ -1,10 +1,29 @@
+use crate::errors::{AppError, AppErrorValue};
 use crate::modules::auth::dto::request;
 use crate::modules::auth::helpers::permissions::get_user_permissions;
+use crate::modules::auth::services::account::{send_email_verification, send_password_reset};
+use crate::modules::auth::services::login_protection;
+use crate::modules::auth::services::password_policy::validate_password;
+use crate::modules::auth::services::register::register_user;
+use crate::modules::auth::services::two_factor;
+use crate::modules::authentication_methods::models::authentication_method::AuthenticationMethod;
+use crate::modules::core::helpers::auth::get_session_metadata;
 use crate::modules::core::middleware::state::AppState;
+use crate::modules::languages::models::language::Language;
+use crate::modules::sites::models::site::Site;
+use crate::modules::sites::models::site_invitation::SiteInvitation;
+use crate::modules::sites::models::site_user::SiteUser;
+use crate::modules::sites::models::site_user_role::SiteUserRole;
 use crate::modules::users::models::user::{UpdateUser, User};
+use crate::modules::users::models::user_recovery_code::UserRecoveryCode;
+use crate::modules::users::models::user_session::UserSession;
+use crate::modules::users::models::user_token::{UserToken, UserTokenKindEnum};
 use crate::modules::{auth::dto::response, core::middleware::auth};
 use crate::utils::api::ApiResponse;
-use actix_web::{get, put, web, HttpRequest, HttpResponse};
+use crate::utils::hasher;
+use actix_web::http::StatusCode;
+use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse};
+use diesel::{Connection, PgConnection};
 use serde::Deserialize;
 use utoipa::IntoParams;
 use uuid::Uuid;
@@ -15,6 +34,16 @@ pub struct MeQueryParams {
 	site_id: Option<Uuid>,
 }
 
+#[derive(Deserialize, IntoParams)]
+pub struct SessionPathParams {
+	session_id: Uuid,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct SiteInvitationQueryParams {
+	token: String,
+}
+
 #[utoipa::path(
 	context_path = "/api/v1/auth",
 	responses(
@@ -33,9 +62,14 @@ pub async fn me(
 ) -> ApiResponse {
 	let conn = &mut state.get_conn()?;
 	let user = auth::get_current_user(&req)?;
-	let token = user.generate_token(None)?;
+	let session = auth::get_current_session(&req)?;
+	let token = session.generate_access_token(None)?;
 	let permissions = get_user_permissions(conn, user.id, query.site_id)?;
-	let res = response::MeDTO::from((user, token, permissions));
+	let sessions = UserSession::find_active(conn, user.id)?
+		.into_iter()
+		.map(|active_session| response::SessionDTO::from((active_session, session.id)))
+		.collect();
+	let res = response::MeDTO::from((user, token, permissions, sessions));
 	Ok(HttpResponse::Ok().json(res))
 }
 
@@ -58,20 +92,470 @@ pub async fn update(
 ) -> ApiResponse {
 	let conn = &mut state.get_conn()?;
 	let current_user = auth::get_current_user(&req)?;
-	let user = User::update(
-		conn,
-		current_user.id,
-		UpdateUser {
-			email: form.email.clone(),
-			name: form.name.clone(),
-			password: form.password.clone(),
-			avatar: form.avatar.clone(),
-			bio: form.bio.clone(),
-		},
-	)?;
-	let token = user.generate_token(None)?;
+	let session = auth::get_current_session(&req)?;
+	if let Some(password) = &form.password {
+		validate_password(password)?;
+	}
+
+	if let Some(language_key) = &form.language_key {
+		Language::find_by_key(conn, language_key)?;
+	}
+
+	let email_changed = form
+		.email
+		.as_ref()
+		.is_some_and(|email| *email != current_user.email);
+
+	// Passwords are hashed separately, a changeset without any other field has nothing to save
+	let mut user = match (
+		&form.email,
+		&form.name,
+		&form.avatar,
+		&form.bio,
+		&form.language_key,
+	) {
+		(None, None, None, None, None) => current_user,
+		_ => User::update(
+			conn,
+			current_user.id,
+			UpdateUser {
+				email: form.email.clone(),
+				name: form.name.clone(),
+				password: None,
+				avatar: form.avatar.clone(),
+				bio: form.bio.clone(),
+				language_key: form.language_key.clone(),
+			},
+		)?,
+	};
+
+	// A new address has to be verified again, the link goes to the new address
+	if email_changed {
+		user = User::clear_email_verification(conn, user.id)?;
+		send_email_verification(conn, &state.mail_addr, &user)?;
+	}
+
+	// Changing the password signs out every other session
+	if let Some(password) = &form.password {
+		user = User::set_password(conn, user.id, password)?;
+		UserSession::revoke_all(conn, user.id, Some(session.id))?;
+	}
+
+	let token = session.generate_access_token(None)?;
 	let sites = user.get_sites(conn)?;
 	let roles = user.get_roles(conn)?;
-	let res = response::AuthDTO::from((user, sites, roles, token));
+	let res = response::AuthDTO::from((user, sites, roles, token, None));
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/auth",
+    request_body = RefreshTokenDTO,
+	responses(
+		(status = 200, body = TokensDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	)
+)]
+#[post("/refresh")]
+pub async fn refresh(
+	state: web::Data<AppState>,
+	form: web::Json<request::RefreshTokenDTO>,
+) -> ApiResponse {
+	let conn = &mut state.get_conn()?;
+	let (_session, tokens) = UserSession::refresh(conn, &form.refresh_token)?;
+	let res = response::TokensDTO::from(tokens);
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/auth",
+	responses(
+		(status = 204),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    )
+)]
+#[post("/logout")]
+pub async fn logout(state: web::Data<AppState>, req: HttpRequest) -> ApiResponse {
+	let conn = &mut state.get_conn()?;
+	let session = auth::get_current_session(&req)?;
+	UserSession::revoke(conn, session.user_id, session.id)?;
+	Ok(HttpResponse::NoContent().body(()))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/auth",
+	responses(
+		(status = 204),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    )
+)]
+#[post("/logout-all")]
+pub async fn logout_all(state: web::Data<AppState>, req: HttpRequest) -> ApiResponse {
+	let conn = &mut state.get_conn()?;
+	let user = auth::get_current_user(&req)?;
+	UserSession::revoke_all(conn, user.id, None)?;
+	Ok(HttpResponse::NoContent().body(()))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/auth",
+	responses(
+		(status = 204),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(SessionPathParams)
+)]
+#[delete("/sessions/{session_id}")]
+pub async fn revoke_session(
+	state: web::Data<AppState>,
+	req: HttpRequest,
+	params: web::Path<SessionPathParams>,
+) -> ApiResponse {
+	let conn = &mut state.get_conn()?;
+	let user = auth::get_current_user(&req)?;
+	UserSession::revoke(conn, user.id, params.session_id)?;
+	Ok(HttpResponse::NoContent().body(()))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/auth",
+    request_body = RequestPasswordResetDTO,
+	responses(
+		(status = 204)
+	)
+)]
+#[post("/password-reset")]
+pub async fn request_password_reset(
+	state: web::Data<AppState>,
+	form: web::Json<request::RequestPasswordResetDTO>,
+) -> ApiResponse {
+	let conn = &mut state.get_conn()?;
+
+	// Always answer the same way so the endpoint can't be used to find out which accounts exist
+	if let Some(user) = User::find_local_by_email(conn, &form.email)? {
+		send_password_reset(conn, &state.mail_addr, &user)?;
+	}
+
+	Ok(HttpResponse::NoContent().body(()))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/auth",
+    request_body = ResetPasswordDTO,
+	responses(
+		(status = 204),
+		(status = 400, body = AppErrorValue, description = "Invalid or expired token")
+	)
+)]
+#[post("/password-reset/confirm")]
+pub async fn reset_password(
+	state: web::Data<AppState>,
+	form: web::Json<request::ResetPasswordDTO>,
+) -> ApiResponse {
+	let conn = &mut state.get_conn()?;
+	validate_password(&form.password)?;
+	let user_token = UserToken::consume(conn, UserTokenKindEnum::PASSWORD_RESET, &form.token)?;
+	let user = User::set_password(conn, user_token.user_id, &form.password)?;
+
+	// The reset link was received by mail, which proves the address as well
+	if user.email_verified_at.is_none() {
+		User::mark_email_verified(conn, user.id)?;
+	}
+
+	UserSession::revoke_all(conn, user.id, None)?;
+	Ok(HttpResponse::NoContent().body(()))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/auth",
+	responses(
+		(status = 204),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    )
+)]
+#[post("/verify-email")]
+pub async fn request_email_verification(
+	state: web::Data<AppState>,
+	req: HttpRequest,
+) -> ApiResponse {
+	let conn = &mut state.get_conn()?;
+	let user = auth::get_current_user(&req)?;
+
+	if user.email_verified_at.is_none() {
+		send_email_verification(conn, &state.mail_addr, &user)?;
+	}
+
+	Ok(HttpResponse::NoContent().body(()))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/auth",
+    request_body = VerifyEmailDTO,
+	responses(
+		(status = 204),
+		(status = 400, body = AppErrorValue, description = "Invalid or expired token")
+	)
+)]
+#[post("/verify-email/confirm")]
+pub async fn verify_email(
+	state: web::Data<AppState>,
+	form: web::Json<request::VerifyEmailDTO>,
+) -> ApiResponse {
+	let conn = &mut state.get_conn()?;
+	let user_token = UserToken::consume(conn, UserTokenKindEnum::EMAIL_VERIFICATION, &form.token)?;
+	User::mark_email_verified(conn, user_token.user_id)?;
+	Ok(HttpResponse::NoContent().body(()))
+}
+
+// Two-factor authentication is only offered for passwords we manage, other providers bring their own
+fn ensure_local_user(conn: &mut PgConnection, user: &User) -> Result<(), AppError> {
+	let local_auth_method = AuthenticationMethod::find_local(conn)?;
+
+	if user.authentication_method_id != local_auth_method.id {
+		return Err(AppError::BadRequest(AppErrorValue {
+			message: "Two-factor authentication is only available for local accounts".to_owned(),
+			status: StatusCode::BAD_REQUEST.as_u16(),
+			code: "TWO_FACTOR_UNSUPPORTED".to_owned(),
+			..Default::default()
+		}));
+	}
+
+	Ok(())
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/auth",
+	responses(
+		(status = 200, body = TwoFactorEnrolmentDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    )
+)]
+#[post("/two-factor/enrol")]
+pub async fn enrol_two_factor(state: web::Data<AppState>, req: HttpRequest) -> ApiResponse {
+	let conn = &mut state.get_conn()?;
+	let user = auth::get_current_user(&req)?;
+	ensure_local_user(conn, &user)?;
+
+	if user.totp_enabled_at.is_some() {
+		return Err(AppError::BadRequest(AppErrorValue {
+			message: "Two-factor authentication is already enabled".to_owned(),
+			status: StatusCode::BAD_REQUEST.as_u16(),
+			code: "TWO_FACTOR_ALREADY_ENABLED".to_owned(),
+			..Default::default()
+		}));
+	}
+
+	let res = response::TwoFactorEnrolmentDTO::from(two_factor::start_enrolment(conn, &user)?);
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/auth",
+    request_body = TwoFactorCodeDTO,
+	responses(
+		(status = 200, body = RecoveryCodesDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    )
+)]
+#[post("/two-factor/confirm")]
+pub async fn confirm_two_factor(
+	state: web::Data<AppState>,
+	req: HttpRequest,
+	form: web::Json<request::TwoFactorCodeDTO>,
+) -> ApiResponse {
+	let conn = &mut state.get_conn()?;
+	let user = auth::get_current_user(&req)?;
+	ensure_local_user(conn, &user)?;
+
+	let recovery_codes = two_factor::confirm_enrolment(conn, &user, &form.code)?;
+	let res = response::RecoveryCodesDTO::from(recovery_codes);
 	Ok(HttpResponse::Ok().json(res))
 }
+
+#[utoipa::path(
+	context_path = "/api/v1/auth",
+    request_body = TwoFactorCodeDTO,
+	responses(
+		(status = 200, body = RecoveryCodesDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    )
+)]
+#[post("/two-factor/recovery-codes")]
+pub async fn regenerate_recovery_codes(
+	state: web::Data<AppState>,
+	req: HttpRequest,
+	form: web::Json<request::TwoFactorCodeDTO>,
+) -> ApiResponse {
+	let conn = &mut state.get_conn()?;
+	let user = auth::get_current_user(&req)?;
+	two_factor::verify_second_factor(conn, &user, &form.code)?;
+
+	let recovery_codes = UserRecoveryCode::regenerate(conn, user.id)?;
+	let res = response::RecoveryCodesDTO::from(recovery_codes);
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/auth",
+    request_body = TwoFactorCodeDTO,
+	responses(
+		(status = 204),
+		(status = 401, body = AppErrorValue, description = "Unauthorized"),
+		(status = 403, body = AppErrorValue, description = "Two-factor authentication is required for one of the roles")
+	),
+    security(
+        ("jwt_token" = [])
+    )
+)]
+#[post("/two-factor/disable")]
+pub async fn disable_two_factor(
+	state: web::Data<AppState>,
+	req: HttpRequest,
+	form: web::Json<request::TwoFactorCodeDTO>,
+) -> ApiResponse {
+	let conn = &mut state.get_conn()?;
+	let user = auth::get_current_user(&req)?;
+	two_factor::verify_second_factor(conn, &user, &form.code)?;
+
+	if two_factor::two_factor_required(conn, &user)? {
+		return Err(AppError::Forbidden(AppErrorValue {
+			message: "Two-factor authentication is required for one of your roles".to_owned(),
+			status: StatusCode::FORBIDDEN.as_u16(),
+			code: "TWO_FACTOR_REQUIRED".to_owned(),
+			..Default::default()
+		}));
+	}
+
+	two_factor::disable(conn, user.id)?;
+	Ok(HttpResponse::NoContent().body(()))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/auth",
+	responses(
+		(status = 200, body = SiteInvitationPreviewDTO),
+		(status = 400, body = AppErrorValue, description = "Invalid or expired invitation")
+	),
+	params(SiteInvitationQueryParams)
+)]
+#[get("/invitations")]
+pub async fn find_site_invitation(
+	state: web::Data<AppState>,
+	query: web::Query<SiteInvitationQueryParams>,
+) -> ApiResponse {
+	let conn = &mut state.get_conn()?;
+	let (site_invitation, _) = SiteInvitation::find_pending_by_token(conn, &query.token)?;
+	let (site, _) = Site::find_one(conn, site_invitation.site_id)?;
+	let existing_account = User::find_local_by_email(conn, &site_invitation.email)?.is_some();
+
+	let res = response::SiteInvitationPreviewDTO::from((site_invitation, site, existing_account));
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/auth",
+    request_body = AcceptSiteInvitationDTO,
+	responses(
+		(status = 204),
+		(status = 400, body = AppErrorValue, description = "Invalid or expired invitation")
+	)
+)]
+#[post("/invitations/accept")]
+pub async fn accept_site_invitation(
+	state: web::Data<AppState>,
+	req: HttpRequest,
+	form: web::Json<request::AcceptSiteInvitationDTO>,
+) -> ApiResponse {
+	let conn = &mut state.get_conn()?;
+	let (site_invitation, roles) = SiteInvitation::find_pending_by_token(conn, &form.token)?;
+	let local_auth_method = AuthenticationMethod::find_local(conn)?;
+	let local_user =
+		User::find_by_email_and_source(conn, &site_invitation.email, local_auth_method.id)?;
+
+	// Checking the password of an existing account is a login attempt and throttled like one. It happens
+	// outside of the transaction, a rejected password has to stay counted.
+	if let (Some(user), Some(password)) = (&local_user, &form.password) {
+		let metadata = get_session_metadata(&req);
+		let login_attempt = login_protection::reserve_login_attempt(
+			conn,
+			local_auth_method.id,
+			&user.email,
+			&metadata,
+		)?;
+		let result = hasher::verify(password, &user.password)
+			.map_err(AppError::from)
+			.and_then(|password_correct| match password_correct {
+				true => Ok(()),
+				false => Err(AppError::Unauthorized(AppErrorValue {
+					message: "Username or password incorrect".to_owned(),
+					status: StatusCode::UNAUTHORIZED.as_u16(),
+					code: "LOGIN_FAILED".to_owned(),
+					..Default::default()
+				})),
+			});
+		login_protection::track_login_attempt(conn, login_attempt, &metadata, result)?;
+		login_protection::clear_account_lockout(conn, local_auth_method.id, &user.email)?;
+	}
+
+	conn.transaction::<_, AppError, _>(|conn| {
+		SiteInvitation::accept(conn, site_invitation.id)?;
+
+		// Only the local account that proves it owns the invited address joins the site, one is
+		// created when there is none yet
+		let user = match (local_user, &form.name, &form.password) {
+			(Some(user), _, Some(_)) => user,
+			(None, Some(name), Some(password)) if !name.trim().is_empty() => {
+				validate_password(password)?;
+				register_user(
+					conn,
+					&site_invitation.email,
+					name.trim(),
+					password,
+					None,
+					None,
+				)?
+			}
+			_ => return Err(AppError::UnprocessableEntity(AppErrorValue {
+				message:
+					"A password, and a name for new accounts, is needed to accept the invitation"
+						.to_owned(),
+				status: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+				code: "ACCOUNT_DETAILS_MISSING".to_owned(),
+				..Default::default()
+			})),
+		};
+
+		// The invitation link was received by mail, which proves the address as well
+		User::mark_email_verified(conn, user.id)?;
+
+		SiteUser::upsert(conn, site_invitation.site_id, user.id)?;
+		for role in &roles {
+			SiteUserRole::upsert(conn, user.id, site_invitation.site_id, role.id)?;
+		}
+
+		Ok(())
+	})?;
+
+	Ok(HttpResponse::NoContent().body(()))
+}
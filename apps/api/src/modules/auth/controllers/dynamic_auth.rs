@@ -1,9 +1,13 @@
 use super::super::dto::request;
 use crate::errors::AppError;
 use crate::modules::auth::services::dynamic_login::get_auth_provider;
+use crate::modules::authentication_methods::models::oauth2_login_state::{
+	OAuth2LoginState, LOGIN_STATE_COOKIE,
+};
+use crate::modules::core::helpers::auth::get_session_metadata;
 use crate::modules::core::middleware::state::AppState;
 use crate::utils::api::ApiResponse;
-use actix_web::{post, web, HttpResponse};
+use actix_web::{post, web, HttpRequest, HttpResponse};
 use serde::Deserialize;
 use utoipa::IntoParams;
 use uuid::Uuid;
@@ -16,18 +20,21 @@ pub struct LoginPathParams {
 #[derive(Deserialize, IntoParams, Debug)]
 pub struct LoginQueryParams {
 	pub code: String,
+	pub state: Option<String>,
 }
 
 #[utoipa::path(
 	context_path = "/api/v1/auth/{auth_id}",
     request_body = LoginUserDTO,
 	responses(
-		(status = 200, body = AuthDTO),
-		(status = 401, body = AppErrorValue, description = "Unauthorized")
+		(status = 200, body = AuthDTO, description = "Logged in, local and LDAP accounts that need a second factor receive a TwoFactorChallengeDTO instead"),
+		(status = 401, body = AppErrorValue, description = "Unauthorized"),
+		(status = 403, body = AppErrorValue, description = "Two-factor authentication is required but was not set up yet")
 	)
 )]
 #[post("/login")]
 pub async fn login(
+	req: HttpRequest,
 	state: web::Data<AppState>,
 	form: web::Json<request::LoginUserDTO>,
 	params: web::Path<LoginPathParams>,
@@ -35,7 +42,9 @@ pub async fn login(
 	let conn = &mut state.get_conn()?;
 	let auth_provider = get_auth_provider(conn, params.auth_id)?;
 
-	Ok(auth_provider.login(conn, form.0).await?)
+	Ok(auth_provider
+		.login(conn, form.0, get_session_metadata(&req))
+		.await?)
 }
 
 #[utoipa::path(
@@ -48,12 +57,30 @@ pub async fn login(
 )]
 #[post("/callback")]
 pub async fn callback(
+	req: HttpRequest,
 	state: web::Data<AppState>,
 	params: web::Path<LoginPathParams>,
 	query: web::Query<LoginQueryParams>,
 ) -> ApiResponse {
+	// Logins that carry a state only finish in the browser that holds its cookie
+	if let Some(login_state) = &query.state {
+		OAuth2LoginState::ensure_same_browser(
+			login_state,
+			req.cookie(LOGIN_STATE_COOKIE)
+				.as_ref()
+				.map(|cookie| cookie.value()),
+		)?;
+	}
+
 	let conn = &mut state.get_conn()?;
 	let auth_provider = get_auth_provider(conn, params.auth_id)?;
 
-	Ok(auth_provider.callback(conn, query.code.clone()).await?)
+	Ok(auth_provider
+		.callback(
+			conn,
+			query.code.clone(),
+			query.state.clone(),
+			get_session_metadata(&req),
+		)
+		.await?)
 }
@@ -12,6 +12,8 @@ pub struct RegisterUserDTO {
 pub struct LoginUserDTO {
 	pub email: Option<String>,
 	pub password: Option<String>,
+	pub challenge: Option<String>,
+	pub code: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
@@ -21,4 +23,40 @@ pub struct UpdateUserDTO {
 	pub password: Option<String>,
 	pub avatar: Option<String>,
 	pub bio: Option<String>,
+	pub language_key: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshTokenDTO {
+	pub refresh_token: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct RequestPasswordResetDTO {
+	pub email: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct ResetPasswordDTO {
+	pub token: String,
+	pub password: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct VerifyEmailDTO {
+	pub token: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct TwoFactorCodeDTO {
+	pub code: String,
+}
+
+// The password of the local account of the invited address, or a name and password to create it
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct AcceptSiteInvitationDTO {
+	pub token: String,
+	pub name: Option<String>,
+	pub password: Option<String>,
 }
@@ -5,19 +5,28 @@ use crate::modules::{
 	},
 	languages::models::language::Language,
 	roles::{dto::response::RoleWithPoliciesWithPermissionsDTO, models::role::Role},
-	sites::{dto::response::SiteWithRolesDTO, models::site::Site},
-	users::models::user::User,
+	sites::{
+		dto::response::SiteWithRolesDTO,
+		models::{site::Site, site_invitation::SiteInvitation},
+	},
+	users::models::{user::User, user_session::UserSession},
 };
+use crate::utils::token::AuthTokens;
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use std::convert::From;
 use utoipa::ToSchema;
+use uuid::Uuid;
 
 #[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct UserDTO {
 	pub email: String,
 	pub name: String,
 	pub bio: Option<String>,
 	pub avatar: Option<String>,
+	pub two_factor_enabled: bool,
+	pub language_key: Option<String>,
 }
 
 impl From<User> for UserDTO {
@@ -27,16 +36,21 @@ impl From<User> for UserDTO {
 			name: user.name,
 			bio: user.bio,
 			avatar: user.avatar,
+			two_factor_enabled: user.totp_enabled_at.is_some(),
+			language_key: user.language_key,
 		}
 	}
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct AuthDTO {
 	pub sites: Vec<SiteWithRolesDTO>,
 	pub roles: Vec<RoleWithPoliciesWithPermissionsDTO>,
 	pub user: UserDTO,
 	pub token: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub refresh_token: Option<String>,
 }
 
 impl
@@ -49,10 +63,11 @@ impl
 		)>,
 		Vec<(Role, Vec<(IAMPolicy, Vec<(Permission, Vec<String>)>)>)>,
 		String,
+		Option<String>,
 	)> for AuthDTO
 {
 	fn from(
-		(user, sites, roles, token): (
+		(user, sites, roles, token, refresh_token): (
 			User,
 			Vec<(
 				Site,
@@ -61,10 +76,12 @@ impl
 			)>,
 			Vec<(Role, Vec<(IAMPolicy, Vec<(Permission, Vec<String>)>)>)>,
 			String,
+			Option<String>,
 		),
 	) -> Self {
 		Self {
 			token,
+			refresh_token,
 			user: UserDTO::from(user),
 			sites: sites
 				.into_iter()
@@ -79,21 +96,157 @@ impl
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct MeDTO {
 	pub user: UserDTO,
 	pub token: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub refresh_token: Option<String>,
 	pub permissions: Vec<PermissionDTO>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub sessions: Option<Vec<SessionDTO>>,
 }
 
-impl From<(User, String, Vec<(Permission, Vec<String>)>)> for MeDTO {
-	fn from((user, token, permissions): (User, String, Vec<(Permission, Vec<String>)>)) -> Self {
+impl From<(User, AuthTokens, Vec<(Permission, Vec<String>)>)> for MeDTO {
+	fn from(
+		(user, tokens, permissions): (User, AuthTokens, Vec<(Permission, Vec<String>)>),
+	) -> Self {
+		Self {
+			token: tokens.access_token,
+			refresh_token: Some(tokens.refresh_token),
+			user: UserDTO::from(user),
+			permissions: permissions
+				.into_iter()
+				.map(|permission| PermissionDTO::from(permission))
+				.collect(),
+			sessions: None,
+		}
+	}
+}
+
+impl
+	From<(
+		User,
+		String,
+		Vec<(Permission, Vec<String>)>,
+		Vec<SessionDTO>,
+	)> for MeDTO
+{
+	fn from(
+		(user, token, permissions, sessions): (
+			User,
+			String,
+			Vec<(Permission, Vec<String>)>,
+			Vec<SessionDTO>,
+		),
+	) -> Self {
 		Self {
 			token,
+			refresh_token: None,
 			user: UserDTO::from(user),
 			permissions: permissions
 				.into_iter()
 				.map(|permission| PermissionDTO::from(permission))
 				.collect(),
+			sessions: Some(sessions),
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDTO {
+	pub id: Uuid,
+	pub user_agent: Option<String>,
+	pub ip_address: Option<String>,
+	pub current: bool,
+	pub expires_at: NaiveDateTime,
+	pub last_used_at: NaiveDateTime,
+	pub created_at: NaiveDateTime,
+}
+
+impl From<(UserSession, Uuid)> for SessionDTO {
+	fn from((session, current_session_id): (UserSession, Uuid)) -> Self {
+		Self {
+			id: session.id,
+			current: session.id == current_session_id,
+			user_agent: session.user_agent,
+			ip_address: session.ip_address,
+			expires_at: session.expires_at,
+			last_used_at: session.last_used_at,
+			created_at: session.created_at,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TokensDTO {
+	pub token: String,
+	pub refresh_token: String,
+}
+
+impl From<AuthTokens> for TokensDTO {
+	fn from(tokens: AuthTokens) -> Self {
+		Self {
+			token: tokens.access_token,
+			refresh_token: tokens.refresh_token,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoFactorEnrolmentDTO {
+	pub secret: String,
+	pub provisioning_uri: String,
+}
+
+impl From<(String, String)> for TwoFactorEnrolmentDTO {
+	fn from((secret, provisioning_uri): (String, String)) -> Self {
+		Self {
+			secret,
+			provisioning_uri,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TwoFactorChallengeDTO {
+	pub challenge: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryCodesDTO {
+	pub recovery_codes: Vec<String>,
+}
+
+impl From<Vec<String>> for RecoveryCodesDTO {
+	fn from(recovery_codes: Vec<String>) -> Self {
+		Self { recovery_codes }
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SiteInvitationPreviewDTO {
+	pub email: String,
+	pub site_id: Uuid,
+	pub site_name: String,
+	pub existing_account: bool,
+	pub expires_at: NaiveDateTime,
+}
+
+impl From<(SiteInvitation, Site, bool)> for SiteInvitationPreviewDTO {
+	fn from((site_invitation, site, existing_account): (SiteInvitation, Site, bool)) -> Self {
+		Self {
+			email: site_invitation.email,
+			site_id: site.id,
+			site_name: site.name,
+			existing_account,
+			expires_at: site_invitation.expires_at,
 		}
 	}
 }
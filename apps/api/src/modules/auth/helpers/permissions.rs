@@ -8,11 +8,12 @@ use uuid::Uuid;
 use crate::{
 	errors::{AppError, AppErrorValue},
 	modules::{
-		core::middleware::state::AppState, iam_policies::models::permission::Permission,
-		users::models::user::User,
+		api_keys::models::api_key::ApiKey, core::middleware::state::AppState,
+		iam_policies::models::permission::Permission, users::models::user::User,
 	},
 	schema::{
-		permissions, permissions_iam_actions, roles_iam_policies, sites_users_roles, users_roles,
+		api_keys_roles, permissions, permissions_iam_actions, roles, roles_iam_policies,
+		sites_users_roles, users_roles,
 	},
 };
 
@@ -31,16 +32,36 @@ pub fn ensure_permission<'a>(
 			..Default::default()
 		}))?;
 	let binding = req.extensions();
-	let user = binding
-		.get::<User>()
-		.ok_or(AppError::InternalServerError(AppErrorValue {
-			message: "Could not fetch user from request".to_owned(),
-			status: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
-			code: "USER_MISSING".to_owned(),
-			..Default::default()
-		}))?;
 	let conn = &mut app_state.get_conn()?;
-	let permissions = get_user_permissions(conn, user.id, site_id)?;
+
+	// API keys act on behalf of the user that created them, but only with their own roles
+	let (actor_id, permissions) = match binding.get::<ApiKey>() {
+		Some(api_key) => {
+			if site_id != Some(api_key.site_id) {
+				return Err(AppError::Forbidden(AppErrorValue {
+					message: "API key is not valid for this site".to_owned(),
+					status: StatusCode::FORBIDDEN.as_u16(),
+					code: "API_KEY_SITE_MISMATCH".to_owned(),
+					..Default::default()
+				}));
+			}
+
+			(api_key.user_id, get_api_key_permissions(conn, api_key.id)?)
+		}
+		None => {
+			let user =
+				binding
+					.get::<User>()
+					.ok_or(AppError::InternalServerError(AppErrorValue {
+						message: "Could not fetch user from request".to_owned(),
+						status: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+						code: "USER_MISSING".to_owned(),
+						..Default::default()
+					}))?;
+
+			(user.id, get_user_permissions(conn, user.id, site_id)?)
+		}
+	};
 
 	let result =
 		permissions
@@ -81,7 +102,68 @@ pub fn ensure_permission<'a>(
 		}));
 	}
 
-	Ok(user.id)
+	Ok(actor_id)
+}
+
+/// Checks that every role belongs to the site and is held by the actor of the request, so
+/// nobody can hand out more access than they have. `any_site_role` skips the second part for
+/// actors that were authorized through a root permission. Returns the deduplicated role ids.
+pub fn ensure_assignable_roles(
+	req: &HttpRequest,
+	conn: &mut PgConnection,
+	site_id: Uuid,
+	role_ids: &[Uuid],
+	any_site_role: bool,
+) -> Result<Vec<Uuid>, AppError> {
+	let mut role_ids = role_ids.to_vec();
+	role_ids.sort();
+	role_ids.dedup();
+
+	let mut query = roles::table
+		.filter(roles::id.eq_any(&role_ids))
+		.filter(roles::site_id.eq(site_id))
+		.into_boxed();
+
+	if !any_site_role {
+		let binding = req.extensions();
+		query = match (binding.get::<ApiKey>(), binding.get::<User>()) {
+			(Some(api_key), _) => query.filter(
+				roles::id.eq_any(
+					api_keys_roles::table
+						.filter(api_keys_roles::api_key_id.eq(api_key.id))
+						.select(api_keys_roles::role_id),
+				),
+			),
+			(None, Some(user)) => query.filter(
+				roles::id.eq_any(
+					sites_users_roles::table
+						.filter(sites_users_roles::site_id.eq(site_id))
+						.filter(sites_users_roles::user_id.eq(user.id))
+						.select(sites_users_roles::role_id),
+				),
+			),
+			(None, None) => {
+				return Err(AppError::InternalServerError(AppErrorValue {
+					message: "Could not fetch user from request".to_owned(),
+					status: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+					code: "USER_MISSING".to_owned(),
+					..Default::default()
+				}))
+			}
+		};
+	}
+
+	let assignable_roles = query.count().get_result::<i64>(conn)?;
+	if assignable_roles != role_ids.len() as i64 {
+		return Err(AppError::UnprocessableEntity(AppErrorValue {
+			message: "Only roles of this site that you hold yourself can be assigned".to_owned(),
+			status: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+			code: "ROLES_NOT_ASSIGNABLE".to_owned(),
+			..Default::default()
+		}));
+	}
+
+	Ok(role_ids)
 }
 
 // TODO: Dedupe
@@ -141,3 +223,31 @@ pub fn get_user_permissions(
 
 	Ok(permissions)
 }
+
+pub fn get_api_key_permissions(
+	conn: &mut PgConnection,
+	api_key_id: Uuid,
+) -> Result<Vec<(Permission, Vec<String>)>, AppError> {
+	let permissions = api_keys_roles::table
+		.filter(api_keys_roles::api_key_id.eq(api_key_id))
+		.inner_join(
+			roles_iam_policies::table.on(roles_iam_policies::role_id.eq(api_keys_roles::role_id)),
+		)
+		.inner_join(
+			permissions::table.on(permissions::iam_policy_id.eq(roles_iam_policies::iam_policy_id)),
+		)
+		.inner_join(
+			permissions_iam_actions::table
+				.on(permissions_iam_actions::permission_id.eq(permissions::id)),
+		)
+		.group_by(permissions::id)
+		.select((
+			Permission::as_select(),
+			diesel::dsl::sql::<Array<diesel::sql_types::Text>>(
+				"array_agg(permissions_iam_actions.iam_action_key) actions",
+			),
+		))
+		.load::<(Permission, Vec<String>)>(conn)?;
+
+	Ok(permissions)
+}
@@ -0,0 +1,744 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::errors::{AppError, AppErrorValue};
+use crate::modules::auth::dto::request::LoginUserDTO;
+use crate::modules::auth::dto::response;
+use crate::modules::auth::services::dynamic_login::AuthProvider;
+use crate::modules::auth::services::login_protection;
+use crate::modules::auth::services::register::{persist_role_assignments, register_user};
+use crate::modules::auth::services::two_factor;
+use crate::modules::authentication_methods::models::authentication_method::AuthenticationMethod;
+use crate::modules::users::models::user::User;
+use crate::modules::users::models::user_session::SessionMetadata;
+use crate::utils::string::generate_random_string;
+use actix_web::HttpResponse;
+use async_trait::async_trait;
+use diesel::PgConnection;
+use ldap3::{dn_escape, ldap_escape, Ldap, LdapConnAsync, LdapConnSettings, Scope, SearchEntry};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const INVALID_CREDENTIALS_RESULT_CODE: u32 = 49;
+
+fn default_email_attribute() -> String {
+	"mail".to_owned()
+}
+
+fn default_name_attribute() -> String {
+	"cn".to_owned()
+}
+
+fn default_group_attribute() -> String {
+	"memberOf".to_owned()
+}
+
+fn default_timeout() -> u64 {
+	10
+}
+
+// Users are either bound directly through `bind_dn_template`, e.g. `uid={username},ou=people,dc=example,dc=com`,
+// or looked up first with `search_filter`, e.g. `(uid={username})`, using the optional service account
+#[derive(Serialize, Deserialize)]
+struct LdapConfig {
+	url: String,
+	#[serde(default)]
+	starttls: bool,
+	bind_dn_template: Option<String>,
+	search_base: Option<String>,
+	search_filter: Option<String>,
+	service_bind_dn: Option<String>,
+	service_bind_password: Option<String>,
+	#[serde(default = "default_email_attribute")]
+	email_attribute: String,
+	#[serde(default = "default_name_attribute")]
+	name_attribute: String,
+	#[serde(default = "default_group_attribute")]
+	group_attribute: String,
+	#[serde(default = "default_timeout")]
+	timeout: u64,
+}
+
+fn login_failed_error() -> AppError {
+	AppError::Unauthorized(AppErrorValue {
+		message: "Username or password incorrect".to_owned(),
+		status: StatusCode::UNAUTHORIZED.as_u16(),
+		code: "LOGIN_FAILED".to_owned(),
+		..Default::default()
+	})
+}
+
+fn invalid_configuration_error(message: &str) -> AppError {
+	AppError::UnprocessableEntity(AppErrorValue {
+		message: message.to_owned(),
+		status: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+		code: "INVALID_LDAP_CONFIGURATION".to_owned(),
+		..Default::default()
+	})
+}
+
+fn get_config(authentication_method: &AuthenticationMethod) -> Result<LdapConfig, AppError> {
+	let mut config: LdapConfig = serde_json::from_str(
+		&authentication_method
+			.configuration
+			.as_ref()
+			.unwrap_or(&Value::Null)
+			.to_string(),
+	)?;
+
+	// The configuration form stores fields that were left open as empty strings
+	config.bind_dn_template = config.bind_dn_template.filter(|value| !value.is_empty());
+	config.search_base = config.search_base.filter(|value| !value.is_empty());
+	config.search_filter = config.search_filter.filter(|value| !value.is_empty());
+	config.service_bind_dn = config.service_bind_dn.filter(|value| !value.is_empty());
+	if config.email_attribute.is_empty() {
+		config.email_attribute = default_email_attribute();
+	}
+	if config.name_attribute.is_empty() {
+		config.name_attribute = default_name_attribute();
+	}
+	if config.group_attribute.is_empty() {
+		config.group_attribute = default_group_attribute();
+	}
+
+	Ok(config)
+}
+
+async fn connect(config: &LdapConfig) -> Result<Ldap, AppError> {
+	let settings = LdapConnSettings::new()
+		.set_conn_timeout(Duration::from_secs(config.timeout))
+		.set_starttls(config.starttls);
+	let (conn, ldap) = LdapConnAsync::with_settings(settings, &config.url).await?;
+	ldap3::drive!(conn);
+
+	Ok(ldap)
+}
+
+async fn find_user_dn(
+	config: &LdapConfig,
+	ldap: &mut Ldap,
+	username: &str,
+) -> Result<String, AppError> {
+	if let Some(bind_dn_template) = &config.bind_dn_template {
+		return Ok(bind_dn_template.replace("{username}", &dn_escape(username)));
+	}
+
+	let (search_base, search_filter) = match (&config.search_base, &config.search_filter) {
+		(Some(search_base), Some(search_filter)) => (search_base, search_filter),
+		_ => {
+			return Err(invalid_configuration_error(
+				"Either a bind DN template or a search base and filter have to be configured",
+			))
+		}
+	};
+
+	if let Some(service_bind_dn) = &config.service_bind_dn {
+		ldap.simple_bind(
+			service_bind_dn,
+			config.service_bind_password.as_deref().unwrap_or_default(),
+		)
+		.await?
+		.success()?;
+	}
+
+	let (entries, _) = ldap
+		.search(
+			search_base,
+			Scope::Subtree,
+			&search_filter.replace("{username}", &ldap_escape(username)),
+			vec!["1.1"],
+		)
+		.await?
+		.success()?;
+
+	// Ambiguous filters should never pick an account at random
+	match entries.len() {
+		1 => Ok(SearchEntry::construct(entries.into_iter().next().unwrap()).dn),
+		_ => Err(login_failed_error()),
+	}
+}
+
+fn get_attribute<'a>(
+	attrs: &'a HashMap<String, Vec<String>>,
+	name: &str,
+) -> Option<&'a Vec<String>> {
+	attrs
+		.iter()
+		.find(|(key, _)| key.eq_ignore_ascii_case(name))
+		.map(|(_, values)| values)
+}
+
+// Group bound role assignments can use either the full DN of a group or only its name
+fn get_group_names(values: &[String]) -> Vec<String> {
+	values
+		.iter()
+		.flat_map(|value| {
+			let name = value
+				.split(',')
+				.next()
+				.and_then(|rdn| rdn.split_once('='))
+				.map(|(_, name)| name.trim().to_owned());
+
+			std::iter::once(value.to_owned()).chain(name)
+		})
+		.collect()
+}
+
+struct LdapUser {
+	email: String,
+	name: String,
+	groups: Vec<String>,
+}
+
+async fn authenticate(
+	config: &LdapConfig,
+	username: &str,
+	password: &str,
+) -> Result<LdapUser, AppError> {
+	let mut ldap = connect(config).await?;
+	let user_dn = find_user_dn(config, &mut ldap, username).await?;
+
+	// The result code tells invalid credentials apart from connection or configuration problems
+	let bind_result = ldap.simple_bind(&user_dn, password).await?;
+	if bind_result.rc == INVALID_CREDENTIALS_RESULT_CODE {
+		return Err(login_failed_error());
+	}
+	bind_result.success()?;
+
+	let (entries, _) = ldap
+		.search(
+			&user_dn,
+			Scope::Base,
+			"(objectClass=*)",
+			vec![
+				config.email_attribute.as_str(),
+				config.name_attribute.as_str(),
+				config.group_attribute.as_str(),
+			],
+		)
+		.await?
+		.success()?;
+	let _ = ldap.unbind().await;
+
+	let entry = entries
+		.into_iter()
+		.next()
+		.map(SearchEntry::construct)
+		.ok_or_else(login_failed_error)?;
+
+	let email = get_attribute(&entry.attrs, &config.email_attribute)
+		.and_then(|values| values.first())
+		.ok_or_else(|| {
+			AppError::Unauthorized(AppErrorValue {
+				message: format!(
+					"Directory entry has no `{}` attribute",
+					config.email_attribute
+				),
+				status: StatusCode::UNAUTHORIZED.as_u16(),
+				code: "LDAP_ATTRIBUTE_MISSING".to_owned(),
+				..Default::default()
+			})
+		})?
+		.to_owned();
+	let name = get_attribute(&entry.attrs, &config.name_attribute)
+		.and_then(|values| values.first())
+		.map(|name| name.to_owned())
+		.unwrap_or_else(|| username.to_owned());
+	let groups = get_attribute(&entry.attrs, &config.group_attribute)
+		.map(|values| get_group_names(values))
+		.unwrap_or_default();
+
+	Ok(LdapUser {
+		email,
+		name,
+		groups,
+	})
+}
+
+#[derive(Debug, Clone)]
+pub struct LdapAuthProvider {
+	pub authentication_method: AuthenticationMethod,
+}
+
+#[async_trait]
+impl AuthProvider for LdapAuthProvider {
+	async fn login(
+		&self,
+		conn: &mut PgConnection,
+		body: LoginUserDTO,
+		metadata: SessionMetadata,
+	) -> Result<HttpResponse, AppError> {
+		// The second step answers the challenge of the first one, the directory was asked in the first step
+		if let Some(challenge) = &body.challenge {
+			let code = body.code.as_ref().ok_or("Code missing")?;
+			let user = two_factor::answer_challenge(
+				conn,
+				self.authentication_method.id,
+				challenge,
+				code,
+				&metadata,
+			)?;
+			let tokens = user.start_session(conn, None, &metadata)?;
+			let sites = user.get_sites(conn)?;
+			let roles = user.get_roles(conn)?;
+			let res = response::AuthDTO::from((
+				user,
+				sites,
+				roles,
+				tokens.access_token,
+				Some(tokens.refresh_token),
+			));
+			return Ok(HttpResponse::Ok().json(res));
+		}
+
+		let username = body.email.as_ref().ok_or("Email missing")?;
+		let password = body.password.as_ref().ok_or("Password missing")?;
+
+		// Most directories accept a bind without password as an anonymous bind
+		if username.is_empty() || password.is_empty() {
+			return Err(login_failed_error());
+		}
+
+		let login_attempt = login_protection::reserve_login_attempt(
+			conn,
+			self.authentication_method.id,
+			username,
+			&metadata,
+		)?;
+
+		let config = get_config(&self.authentication_method)?;
+		let result = authenticate(&config, username, password).await;
+		let ldap_user =
+			login_protection::track_login_attempt(conn, login_attempt, &metadata, result)?;
+
+		// Only sign up when there is no account yet, a deactivated account must not get a second one
+		let user = match User::find_by_email_and_source(
+			conn,
+			&ldap_user.email,
+			self.authentication_method.id,
+		)? {
+			Some(user) => user,
+			None => register_user(
+				conn,
+				&ldap_user.email,
+				&ldap_user.name,
+				&generate_random_string(20),
+				None,
+				Some(self.authentication_method.id),
+			)?,
+		};
+		user.ensure_active()?;
+
+		persist_role_assignments(
+			conn,
+			user.id,
+			Some(self.authentication_method.id),
+			Some(ldap_user.groups.as_slice()),
+		)?;
+
+		// The roles that come with the directory groups can require a second factor as well
+		if let Some(challenge) = two_factor::start_challenge(conn, &user, username)? {
+			return Ok(HttpResponse::Ok().json(response::TwoFactorChallengeDTO { challenge }));
+		}
+
+		// The directory name is cleared once the second factor checked out as well, like the address of local accounts
+		login_protection::clear_account_lockout(conn, self.authentication_method.id, username)?;
+
+		let tokens = user.start_session(conn, None, &metadata)?;
+		let sites = user.get_sites(conn)?;
+		let roles = user.get_roles(conn)?;
+		let res = response::AuthDTO::from((
+			user,
+			sites,
+			roles,
+			tokens.access_token,
+			Some(tokens.refresh_token),
+		));
+		Ok(HttpResponse::Ok().json(res))
+	}
+
+	async fn callback(
+		&self,
+		_conn: &mut PgConnection,
+		_code: String,
+		_state: Option<String>,
+		_metadata: SessionMetadata,
+	) -> Result<HttpResponse, AppError> {
+		Err(AppError::BadRequest(AppErrorValue {
+			message: "Callback not implemented for LDAP authentication methods".to_string(),
+			status: StatusCode::BAD_REQUEST.as_u16(),
+			code: "NOT_IMPLEMENTED".to_owned(),
+			..Default::default()
+		}))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io::{Read, Write};
+	use std::net::{TcpListener, TcpStream};
+	use std::sync::Arc;
+	use std::thread;
+
+	use ldap3::asn1::{parse_tag, StructureTag, TagClass, PL};
+	use serde_json::json;
+
+	use super::{authenticate, get_group_names, LdapConfig, INVALID_CREDENTIALS_RESULT_CODE};
+	use crate::errors::AppError;
+
+	const INSUFFICIENT_ACCESS_RESULT_CODE: u64 = 50;
+
+	struct FakeEntry {
+		dn: &'static str,
+		password: &'static str,
+		attributes: Vec<(&'static str, Vec<&'static str>)>,
+	}
+
+	fn directory() -> Vec<FakeEntry> {
+		vec![
+			FakeEntry {
+				dn: "cn=service,dc=example,dc=com",
+				password: "service-secret",
+				attributes: vec![("cn", vec!["service"])],
+			},
+			FakeEntry {
+				dn: "uid=jane,ou=people,dc=example,dc=com",
+				password: "secret",
+				attributes: vec![
+					("uid", vec!["jane"]),
+					("sn", vec!["Doe"]),
+					("cn", vec!["Jane Doe"]),
+					("mail", vec!["jane@example.com"]),
+					("memberOf", vec!["cn=editors,ou=groups,dc=example,dc=com"]),
+				],
+			},
+			FakeEntry {
+				dn: "uid=john,ou=people,dc=example,dc=com",
+				password: "hunter2",
+				attributes: vec![
+					("uid", vec!["john"]),
+					("sn", vec!["Doe"]),
+					("cn", vec!["John Doe"]),
+				],
+			},
+		]
+	}
+
+	fn primitive(class: TagClass, id: u64, payload: Vec<u8>) -> StructureTag {
+		StructureTag {
+			class,
+			id,
+			payload: PL::P(payload),
+		}
+	}
+
+	fn constructed(class: TagClass, id: u64, tags: Vec<StructureTag>) -> StructureTag {
+		StructureTag {
+			class,
+			id,
+			payload: PL::C(tags),
+		}
+	}
+
+	fn octet_string(value: &str) -> StructureTag {
+		primitive(TagClass::Universal, 4, value.as_bytes().to_vec())
+	}
+
+	fn integer(id: u64, value: u64) -> StructureTag {
+		let mut bytes = value.to_be_bytes().to_vec();
+		while bytes.len() > 1 && bytes[0] == 0 && bytes[1] & 0x80 == 0 {
+			bytes.remove(0);
+		}
+		primitive(TagClass::Universal, id, bytes)
+	}
+
+	fn into_string(tag: StructureTag) -> String {
+		String::from_utf8(tag.expect_primitive().unwrap_or_default()).unwrap_or_default()
+	}
+
+	fn into_u64(tag: StructureTag) -> u64 {
+		tag.expect_primitive()
+			.unwrap_or_default()
+			.iter()
+			.fold(0, |value, byte| (value << 8) | u64::from(*byte))
+	}
+
+	// Only low tag numbers are used by LDAP, so the identifier always fits in one byte
+	fn encode(tag: StructureTag) -> Vec<u8> {
+		let (constructed, content) = match tag.payload {
+			PL::P(content) => (0, content),
+			PL::C(tags) => (0x20, tags.into_iter().flat_map(encode).collect()),
+		};
+
+		let mut bytes = vec![((tag.class as u8) << 6) | constructed | tag.id as u8];
+		if content.len() < 0x80 {
+			bytes.push(content.len() as u8);
+		} else {
+			bytes.push(0x84);
+			bytes.extend((content.len() as u32).to_be_bytes());
+		}
+		bytes.extend(content);
+		bytes
+	}
+
+	fn ldap_result(operation: u64, result_code: u64) -> StructureTag {
+		constructed(
+			TagClass::Application,
+			operation,
+			vec![integer(10, result_code), octet_string(""), octet_string("")],
+		)
+	}
+
+	fn bind(directory: &[FakeEntry], request: StructureTag) -> (bool, StructureTag) {
+		let mut parts = request
+			.expect_constructed()
+			.unwrap_or_default()
+			.into_iter()
+			.skip(1);
+		let dn = parts.next().map(into_string).unwrap_or_default();
+		let password = parts.next().map(into_string).unwrap_or_default();
+		let authenticated = directory
+			.iter()
+			.any(|entry| entry.dn.eq_ignore_ascii_case(&dn) && entry.password == password);
+
+		let result_code = match authenticated {
+			true => 0,
+			false => u64::from(INVALID_CREDENTIALS_RESULT_CODE),
+		};
+		(authenticated, ldap_result(1, result_code))
+	}
+
+	// Understands the presence and equality filters the provider sends, anything else matches nothing
+	fn matches_filter(entry: &FakeEntry, filter: &StructureTag) -> bool {
+		match (filter.class, filter.id) {
+			(TagClass::Context, 7) => true,
+			(TagClass::Context, 3) => {
+				let mut parts = filter
+					.clone()
+					.expect_constructed()
+					.unwrap_or_default()
+					.into_iter()
+					.map(into_string);
+				let (attribute, value) = (
+					parts.next().unwrap_or_default(),
+					parts.next().unwrap_or_default(),
+				);
+
+				entry.attributes.iter().any(|(name, values)| {
+					name.eq_ignore_ascii_case(&attribute)
+						&& values
+							.iter()
+							.any(|entry_value| entry_value.eq_ignore_ascii_case(&value))
+				})
+			}
+			_ => false,
+		}
+	}
+
+	fn search_entry(entry: &FakeEntry, attributes: &[String]) -> StructureTag {
+		let attributes = entry
+			.attributes
+			.iter()
+			.filter(|(name, _)| {
+				attributes
+					.iter()
+					.any(|attribute| attribute.eq_ignore_ascii_case(name))
+			})
+			.map(|(name, values)| {
+				constructed(
+					TagClass::Universal,
+					16,
+					vec![
+						octet_string(name),
+						constructed(
+							TagClass::Universal,
+							17,
+							values.iter().map(|value| octet_string(value)).collect(),
+						),
+					],
+				)
+			})
+			.collect();
+
+		constructed(
+			TagClass::Application,
+			4,
+			vec![
+				octet_string(entry.dn),
+				constructed(TagClass::Universal, 16, attributes),
+			],
+		)
+	}
+
+	// Like most directories, searching needs a bound connection
+	fn search(directory: &[FakeEntry], bound: bool, request: StructureTag) -> Vec<StructureTag> {
+		if !bound {
+			return vec![ldap_result(5, INSUFFICIENT_ACCESS_RESULT_CODE)];
+		}
+
+		let parts = request.expect_constructed().unwrap_or_default();
+		let base = into_string(parts[0].clone()).to_lowercase();
+		let scope = into_u64(parts[1].clone());
+		let filter = &parts[6];
+		let attributes = parts[7]
+			.clone()
+			.expect_constructed()
+			.unwrap_or_default()
+			.into_iter()
+			.map(into_string)
+			.collect::<Vec<String>>();
+
+		directory
+			.iter()
+			.filter(|entry| match scope {
+				0 => entry.dn.eq_ignore_ascii_case(&base),
+				_ => entry.dn.to_lowercase().ends_with(&base),
+			})
+			.filter(|entry| matches_filter(entry, filter))
+			.map(|entry| search_entry(entry, &attributes))
+			.chain(std::iter::once(ldap_result(5, 0)))
+			.collect()
+	}
+
+	fn handle_connection(mut stream: TcpStream, directory: Arc<Vec<FakeEntry>>) {
+		let mut buffer = vec![];
+		let mut bound = false;
+
+		loop {
+			let (remaining, message) = match parse_tag(&buffer) {
+				Ok((remaining, message)) => (remaining.len(), message),
+				Err(_) => {
+					let mut chunk = [0; 4096];
+					match stream.read(&mut chunk) {
+						Ok(0) | Err(_) => return,
+						Ok(length) => buffer.extend_from_slice(&chunk[..length]),
+					}
+					continue;
+				}
+			};
+			buffer.drain(..buffer.len() - remaining);
+
+			let mut parts = message.expect_constructed().unwrap_or_default().into_iter();
+			let (Some(message_id), Some(operation)) = (parts.next(), parts.next()) else {
+				return;
+			};
+			let message_id = into_u64(message_id);
+
+			// Unbinding, and every operation the fake doesn't know, closes the connection
+			let responses = match (operation.class, operation.id) {
+				(TagClass::Application, 0) => {
+					let (authenticated, response) = bind(&directory, operation);
+					bound = authenticated;
+					vec![response]
+				}
+				(TagClass::Application, 3) => search(&directory, bound, operation),
+				_ => return,
+			};
+
+			for response in responses {
+				let message = constructed(
+					TagClass::Universal,
+					16,
+					vec![integer(2, message_id), response],
+				);
+				if stream.write_all(&encode(message)).is_err() {
+					return;
+				}
+			}
+		}
+	}
+
+	// Serves the fake directory on a random port, every connection gets a thread of its own
+	fn start_directory() -> String {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let address = listener.local_addr().unwrap();
+		let directory = Arc::new(directory());
+
+		thread::spawn(move || {
+			for stream in listener.incoming().flatten() {
+				let directory = directory.clone();
+				thread::spawn(move || handle_connection(stream, directory));
+			}
+		});
+
+		format!("ldap://{address}")
+	}
+
+	fn bind_dn_config() -> LdapConfig {
+		serde_json::from_value(json!({
+			"url": start_directory(),
+			"bind_dn_template": "uid={username},ou=people,dc=example,dc=com",
+		}))
+		.unwrap()
+	}
+
+	fn search_config(search_filter: &str) -> LdapConfig {
+		serde_json::from_value(json!({
+			"url": start_directory(),
+			"search_base": "ou=people,dc=example,dc=com",
+			"search_filter": search_filter,
+			"service_bind_dn": "cn=service,dc=example,dc=com",
+			"service_bind_password": "service-secret",
+		}))
+		.unwrap()
+	}
+
+	fn is_error(result: Result<super::LdapUser, AppError>, code: &str) -> bool {
+		matches!(result, Err(AppError::Unauthorized(error)) if error.code == code)
+	}
+
+	#[actix_web::test]
+	async fn user_is_bound_through_the_dn_template() {
+		let user = authenticate(&bind_dn_config(), "jane", "secret")
+			.await
+			.unwrap();
+
+		assert_eq!(user.email, "jane@example.com");
+		assert_eq!(user.name, "Jane Doe");
+		assert_eq!(
+			user.groups,
+			vec!["cn=editors,ou=groups,dc=example,dc=com", "editors"]
+		);
+	}
+
+	#[actix_web::test]
+	async fn wrong_password_is_rejected() {
+		let result = authenticate(&bind_dn_config(), "jane", "wrong").await;
+		assert!(is_error(result, "LOGIN_FAILED"));
+	}
+
+	#[actix_web::test]
+	async fn user_is_found_with_the_service_account() {
+		let user = authenticate(&search_config("(uid={username})"), "jane", "secret")
+			.await
+			.unwrap();
+		assert_eq!(user.email, "jane@example.com");
+	}
+
+	#[actix_web::test]
+	async fn ambiguous_search_is_rejected() {
+		let result = authenticate(&search_config("(sn={username})"), "Doe", "secret").await;
+		assert!(is_error(result, "LOGIN_FAILED"));
+	}
+
+	#[actix_web::test]
+	async fn username_can_not_widen_the_filter() {
+		let result = authenticate(&search_config("(uid={username})"), "*", "secret").await;
+		assert!(is_error(result, "LOGIN_FAILED"));
+	}
+
+	#[actix_web::test]
+	async fn entry_without_email_is_rejected() {
+		let result = authenticate(&bind_dn_config(), "john", "hunter2").await;
+		assert!(is_error(result, "LDAP_ATTRIBUTE_MISSING"));
+	}
+
+	#[test]
+	fn groups_match_on_dn_and_name() {
+		assert_eq!(
+			get_group_names(&["cn=admins,ou=groups,dc=example,dc=com".to_owned()]),
+			vec!["cn=admins,ou=groups,dc=example,dc=com", "admins"]
+		);
+	}
+}
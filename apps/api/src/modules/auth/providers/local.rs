@@ -2,8 +2,11 @@ use crate::errors::{AppError, AppErrorValue};
 use crate::modules::auth::dto::request::LoginUserDTO;
 use crate::modules::auth::dto::response;
 use crate::modules::auth::services::dynamic_login::AuthProvider;
+use crate::modules::auth::services::login_protection;
+use crate::modules::auth::services::two_factor;
 use crate::modules::authentication_methods::models::authentication_method::AuthenticationMethod;
 use crate::modules::users::models::user::User;
+use crate::modules::users::models::user_session::SessionMetadata;
 use actix_web::HttpResponse;
 use async_trait::async_trait;
 use diesel::PgConnection;
@@ -25,29 +28,76 @@ pub struct LocalAuthProvider {
 	pub authentication_method: AuthenticationMethod,
 }
 
+impl LocalAuthProvider {
+	fn start_session(
+		&self,
+		conn: &mut PgConnection,
+		user: User,
+		metadata: SessionMetadata,
+	) -> Result<HttpResponse, AppError> {
+		let tokens = user.start_session(conn, None, &metadata)?;
+		let sites = user.get_sites(conn)?;
+		let roles = user.get_roles(conn)?;
+		let res = response::AuthDTO::from((
+			user,
+			sites,
+			roles,
+			tokens.access_token,
+			Some(tokens.refresh_token),
+		));
+		Ok(HttpResponse::Ok().json(res))
+	}
+}
+
 #[async_trait]
 impl AuthProvider for LocalAuthProvider {
 	async fn login(
 		&self,
 		conn: &mut PgConnection,
 		body: LoginUserDTO,
+		metadata: SessionMetadata,
 	) -> Result<HttpResponse, AppError> {
-		let (user, token) = User::signin_local(
+		// The second step answers the challenge of the first one with a code of the authenticator app
+		if let Some(challenge) = &body.challenge {
+			let code = body.code.as_ref().ok_or("Code missing")?;
+			let user = two_factor::answer_challenge(
+				conn,
+				self.authentication_method.id,
+				challenge,
+				code,
+				&metadata,
+			)?;
+			return self.start_session(conn, user, metadata);
+		}
+
+		let email = body.email.as_ref().ok_or("Email missing")?;
+		let password = body.password.as_ref().ok_or("Password missing")?;
+
+		let login_attempt = login_protection::reserve_login_attempt(
 			conn,
-			body.email.as_ref().ok_or("Email missing")?,
-			body.password.as_ref().ok_or("Password missing")?,
 			self.authentication_method.id,
+			email,
+			&metadata,
 		)?;
-		let sites = user.get_sites(conn)?;
-		let roles = user.get_roles(conn)?;
-		let res = response::AuthDTO::from((user, sites, roles, token));
-		Ok(HttpResponse::Ok().json(res))
+		let result = User::authenticate_local(conn, email, password, self.authentication_method.id);
+		let user = login_protection::track_login_attempt(conn, login_attempt, &metadata, result)?;
+
+		if let Some(challenge) = two_factor::start_challenge(conn, &user, email)? {
+			return Ok(HttpResponse::Ok().json(response::TwoFactorChallengeDTO { challenge }));
+		}
+
+		// The account is only cleared once the second factor checked out as well
+		login_protection::clear_account_lockout(conn, self.authentication_method.id, email)?;
+
+		self.start_session(conn, user, metadata)
 	}
 
 	async fn callback(
 		&self,
 		_conn: &mut PgConnection,
 		_code: String,
+		_state: Option<String>,
+		_metadata: SessionMetadata,
 	) -> Result<HttpResponse, AppError> {
 		Err(AppError::BadRequest(AppErrorValue {
 			message: "Callback not implemented for local authentication methods".to_string(),
@@ -1,2 +1,4 @@
+pub mod ldap;
 pub mod local;
 pub mod oauth2;
+pub mod oidc;
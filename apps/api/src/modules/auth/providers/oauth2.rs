@@ -1,29 +1,38 @@
-use std::borrow::Borrow;
 use std::env;
 
 use crate::constants;
-use crate::errors::AppError;
+use crate::errors::{AppError, AppErrorValue};
 use crate::modules::auth::dto::request::LoginUserDTO;
 use crate::modules::auth::dto::response;
 use crate::modules::auth::helpers::permissions::get_user_permissions;
 use crate::modules::auth::services::dynamic_login::AuthProvider;
 use crate::modules::auth::services::register::{persist_role_assignments, register_user};
 use crate::modules::authentication_methods::models::authentication_method::AuthenticationMethod;
+use crate::modules::authentication_methods::models::oauth2_login_state::OAuth2LoginState;
 use crate::modules::users::models::user::User;
+use crate::modules::users::models::user_session::SessionMetadata;
 use crate::utils::string::generate_random_string;
+use actix_web::http::StatusCode;
 use actix_web::HttpResponse;
 use async_trait::async_trait;
 use diesel::PgConnection;
-use oauth2::basic::BasicClient;
+use jsonwebtoken::{DecodingKey, Validation};
+use oauth2::basic::{
+	BasicErrorResponse, BasicRevocationErrorResponse, BasicTokenIntrospectionResponse,
+	BasicTokenType,
+};
 use oauth2::reqwest::async_http_client;
+use oauth2::url::Url;
 use oauth2::{
-	AuthUrl, AuthorizationCode, ClientId, ClientSecret, CsrfToken, RedirectUrl, Scope,
-	TokenResponse, TokenUrl,
+	AuthUrl, AuthorizationCode, Client, ClientId, ClientSecret, CsrfToken, ExtraTokenFields,
+	PkceCodeChallenge, PkceCodeVerifier, RedirectUrl, Scope, StandardRevocableToken,
+	StandardTokenResponse, TokenResponse, TokenUrl,
 };
 use reqwest::header::AUTHORIZATION;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use utoipa::IntoParams;
+use uuid::Uuid;
 
 #[derive(Deserialize, IntoParams, Debug)]
 pub struct UserInfoResponse {
@@ -35,32 +44,132 @@ pub struct UserInfoResponse {
 #[derive(Serialize, Deserialize)]
 struct OAuth2Config {
 	client_id: String,
-	client_secret: String,
+	// Public clients have no secret and rely on PKCE alone
+	client_secret: Option<String>,
 	auth_url: String,
 	token_url: String,
 	userinfo_url: String,
 	scopes: Vec<String>,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct IdTokenFields {
+	pub id_token: Option<String>,
+}
+
+impl ExtraTokenFields for IdTokenFields {}
+
+pub type OAuth2Client = Client<
+	BasicErrorResponse,
+	StandardTokenResponse<IdTokenFields, BasicTokenType>,
+	BasicTokenType,
+	BasicTokenIntrospectionResponse,
+	StandardRevocableToken,
+	BasicRevocationErrorResponse,
+>;
+
+#[derive(Deserialize)]
+struct IdTokenClaims {
+	nonce: Option<String>,
+}
+
 fn get_client(
 	config: &OAuth2Config,
-	authentication_method: &AuthenticationMethod,
-) -> Result<BasicClient, AppError> {
+	authentication_method_id: Uuid,
+) -> Result<OAuth2Client, AppError> {
 	let frontend_url = env::var(constants::env_key::FRONTEND_URL)?;
-	let client = BasicClient::new(
+	let client = OAuth2Client::new(
 		ClientId::new(config.client_id.clone()),
-		Some(ClientSecret::new(config.client_secret.clone())),
+		config.client_secret.clone().map(ClientSecret::new),
 		AuthUrl::new(config.auth_url.clone())?,
 		Some(TokenUrl::new(config.token_url.clone())?),
 	)
 	.set_redirect_uri(RedirectUrl::new(format!(
 		"{}/auth/{}/callback",
-		frontend_url, authentication_method.id
+		frontend_url, authentication_method_id
 	))?);
 
 	Ok(client)
 }
 
+// The ID token is received directly from the token endpoint over TLS, so its signature is not
+// checked here, only that it was issued for the login we started
+fn verify_id_token_nonce(id_token: &str, nonce: &str) -> Result<(), AppError> {
+	let mut validation = Validation::default();
+	validation.insecure_disable_signature_validation();
+	validation.validate_exp = false;
+	validation.required_spec_claims.clear();
+
+	let claims = jsonwebtoken::decode::<IdTokenClaims>(
+		id_token,
+		&DecodingKey::from_secret(&[]),
+		&validation,
+	)?
+	.claims;
+
+	if claims.nonce.as_deref() != Some(nonce) {
+		return Err(AppError::Unauthorized(AppErrorValue {
+			message: "ID token nonce does not match the login request".to_owned(),
+			status: StatusCode::UNAUTHORIZED.as_u16(),
+			code: "INVALID_OAUTH2_NONCE".to_owned(),
+			..Default::default()
+		}));
+	}
+
+	Ok(())
+}
+
+// Returns the URL to send the browser to, along with what the callback needs to verify the login
+fn authorize(
+	config: &OAuth2Config,
+	authentication_method_id: Uuid,
+) -> Result<(Url, CsrfToken, PkceCodeVerifier, String), AppError> {
+	let scopes = config
+		.scopes
+		.iter()
+		.map(|scope| Scope::new(scope.clone()))
+		.collect::<Vec<Scope>>();
+	let (pkce_challenge, pkce_verifier) = PkceCodeChallenge::new_random_sha256();
+	let nonce = generate_random_string(32);
+	let (auth_url, csrf_token) = get_client(config, authentication_method_id)?
+		.authorize_url(CsrfToken::new_random)
+		.add_scopes(scopes)
+		.set_pkce_challenge(pkce_challenge)
+		.add_extra_param("nonce", &nonce)
+		.url();
+
+	Ok((auth_url, csrf_token, pkce_verifier, nonce))
+}
+
+// Trades the code for an access token and fetches the user it belongs to
+async fn exchange_code(
+	config: &OAuth2Config,
+	code: String,
+	login_state: &OAuth2LoginState,
+) -> Result<(String, UserInfoResponse), AppError> {
+	let token_result = get_client(config, login_state.authentication_method_id)?
+		.exchange_code(AuthorizationCode::new(code))
+		.set_pkce_verifier(PkceCodeVerifier::new(login_state.pkce_verifier.clone()))
+		.request_async(async_http_client)
+		.await?;
+
+	if let Some(id_token) = &token_result.extra_fields().id_token {
+		verify_id_token_nonce(id_token, &login_state.nonce)?;
+	}
+
+	let access_token = token_result.access_token().secret().to_owned();
+	let userinfo = reqwest::Client::new()
+		.get(&config.userinfo_url)
+		.header(AUTHORIZATION, format!("Bearer {}", access_token))
+		.send()
+		.await?
+		.error_for_status()?
+		.json::<UserInfoResponse>()
+		.await?;
+
+	Ok((access_token, userinfo))
+}
+
 #[derive(Debug, Clone)]
 pub struct OAuth2AuthProvider {
 	pub authentication_method: AuthenticationMethod,
@@ -70,8 +179,9 @@ pub struct OAuth2AuthProvider {
 impl AuthProvider for OAuth2AuthProvider {
 	async fn login(
 		&self,
-		_conn: &mut PgConnection,
+		conn: &mut PgConnection,
 		_body: LoginUserDTO,
+		_metadata: SessionMetadata,
 	) -> Result<HttpResponse, AppError> {
 		let config: OAuth2Config = serde_json::from_str(
 			&self
@@ -82,27 +192,35 @@ impl AuthProvider for OAuth2AuthProvider {
 				.to_string(),
 		)?;
 
-		// TODO: Verify CSRF
-		let oauth2_client = get_client(&config, &self.authentication_method)?;
+		let (auth_url, csrf_token, pkce_verifier, nonce) =
+			authorize(&config, self.authentication_method.id)?;
 
-		let scopes = config
-			.scopes
-			.into_iter()
-			.map(|scope| Scope::new(scope))
-			.collect::<Vec<Scope>>();
-		let (auth_url, _csrf_token) = oauth2_client
-			.authorize_url(CsrfToken::new_random)
-			.add_scopes(scopes)
-			.url();
+		let login_state = OAuth2LoginState::create(
+			conn,
+			self.authentication_method.id,
+			csrf_token.secret().to_owned(),
+			pkce_verifier.secret().to_owned(),
+			nonce,
+		)?;
 
-		Ok(HttpResponse::Ok().json(json!({ "redirect": auth_url.as_str() })))
+		Ok(HttpResponse::Ok()
+			.cookie(login_state.cookie()?)
+			.json(json!({ "redirect": auth_url.as_str() })))
 	}
 
 	async fn callback(
 		&self,
 		conn: &mut PgConnection,
 		code: String,
+		state: Option<String>,
+		metadata: SessionMetadata,
 	) -> Result<HttpResponse, AppError> {
+		let login_state = OAuth2LoginState::consume(
+			conn,
+			self.authentication_method.id,
+			state.as_deref().unwrap_or_default(),
+		)?;
+
 		let config: OAuth2Config = serde_json::from_str(
 			&self
 				.authentication_method
@@ -112,55 +230,260 @@ impl AuthProvider for OAuth2AuthProvider {
 				.to_string(),
 		)?;
 
-		let token_result = get_client(&config, &self.authentication_method)?
-			.exchange_code(AuthorizationCode::new(code))
-			.request_async(async_http_client)
-			.await?;
-
-		let client = reqwest::Client::new();
-		let userinfo_result = client
-			.get(config.userinfo_url)
-			.header(
-				AUTHORIZATION,
-				format!("Bearer {}", token_result.access_token().secret()),
-			)
-			.send()
-			.await?
-			.text()
-			.await?;
-		let userinfo: UserInfoResponse = serde_json::from_str(userinfo_result.borrow())?;
+		let (access_token, userinfo) = exchange_code(&config, code, &login_state).await?;
 
 		// Try to find existing user
 		let existing_user = User::signin_social(
 			conn,
 			&userinfo.email,
 			self.authentication_method.id,
-			Some(token_result.access_token().secret().to_owned()),
+			Some(access_token.clone()),
+			&metadata,
 		);
 
 		match existing_user {
-			Ok((user, token)) => {
-				persist_role_assignments(conn, user.id, Some(self.authentication_method.id))?;
+			Ok((user, tokens)) => {
+				persist_role_assignments(conn, user.id, Some(self.authentication_method.id), None)?;
 				let permissions = get_user_permissions(conn, user.id, None)?;
-				let res = response::MeDTO::from((user, token, permissions));
+				let res = response::MeDTO::from((user, tokens, permissions));
 				Ok(HttpResponse::Ok().json(res))
 			}
-			Err(_) => {
-				let (user, token) = register_user(
+			// Only sign up when there is no account yet, a deactivated account must not get a second one
+			Err(AppError::NotFound(_)) => {
+				let user = register_user(
 					conn,
 					&userinfo.email,
 					&userinfo.name,
 					&generate_random_string(20),
 					Some(&userinfo.picture),
 					Some(self.authentication_method.id),
-				)
-				.await?;
-				persist_role_assignments(conn, user.id, Some(self.authentication_method.id))?;
+				)?;
+				persist_role_assignments(conn, user.id, Some(self.authentication_method.id), None)?;
+				let tokens = user.start_session(conn, Some(access_token.clone()), &metadata)?;
 
 				let permissions = get_user_permissions(conn, user.id, None)?;
-				let res = response::MeDTO::from((user, token, permissions));
+				let res = response::MeDTO::from((user, tokens, permissions));
 				Ok(HttpResponse::Ok().json(res))
 			}
+			Err(err) => Err(err),
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::collections::HashMap;
+	use std::env;
+	use std::sync::Mutex;
+
+	use actix_web::http::header;
+	use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
+	use chrono::Utc;
+	use jsonwebtoken::{EncodingKey, Header};
+	use oauth2::url::Url;
+	use oauth2::{PkceCodeChallenge, PkceCodeVerifier};
+	use serde_json::json;
+	use uuid::Uuid;
+
+	use super::{authorize, exchange_code, OAuth2Config};
+	use crate::constants;
+	use crate::errors::AppError;
+	use crate::modules::authentication_methods::models::oauth2_login_state::OAuth2LoginState;
+
+	const ACCESS_TOKEN: &str = "mock-access-token";
+
+	// What the authorization endpoint handed out, the token endpoint checks the exchange against it
+	#[derive(Default)]
+	struct Authorization {
+		code: String,
+		code_challenge: String,
+		nonce: String,
+	}
+
+	struct MockAuthorizationServer {
+		authorization: Mutex<Authorization>,
+		// Signs the ID token with this nonce instead of the one of the login when set
+		id_token_nonce: Option<String>,
+	}
+
+	async fn authorize_endpoint(
+		server: web::Data<MockAuthorizationServer>,
+		query: web::Query<HashMap<String, String>>,
+	) -> HttpResponse {
+		let code = Uuid::new_v4().to_string();
+		*server.authorization.lock().unwrap() = Authorization {
+			code: code.clone(),
+			code_challenge: query.get("code_challenge").cloned().unwrap_or_default(),
+			nonce: query.get("nonce").cloned().unwrap_or_default(),
+		};
+
+		let mut location = Url::parse(&query["redirect_uri"]).unwrap();
+		location
+			.query_pairs_mut()
+			.append_pair("code", &code)
+			.append_pair("state", &query["state"]);
+		HttpResponse::Found()
+			.insert_header((header::LOCATION, location.as_str()))
+			.finish()
+	}
+
+	async fn token_endpoint(
+		server: web::Data<MockAuthorizationServer>,
+		form: web::Form<HashMap<String, String>>,
+	) -> HttpResponse {
+		let authorization = server.authorization.lock().unwrap();
+		let code_verifier =
+			PkceCodeVerifier::new(form.get("code_verifier").cloned().unwrap_or_default());
+		let code_challenge = PkceCodeChallenge::from_code_verifier_sha256(&code_verifier);
+		if form.get("code") != Some(&authorization.code)
+			|| code_challenge.as_str() != authorization.code_challenge
+		{
+			return HttpResponse::BadRequest().json(json!({ "error": "invalid_grant" }));
+		}
+
+		let nonce = server
+			.id_token_nonce
+			.clone()
+			.unwrap_or_else(|| authorization.nonce.clone());
+		let id_token = jsonwebtoken::encode(
+			&Header::default(),
+			&json!({ "sub": "mock-user", "nonce": nonce }),
+			&EncodingKey::from_secret(b"mock"),
+		)
+		.unwrap();
+
+		HttpResponse::Ok().json(json!({
+			"access_token": ACCESS_TOKEN,
+			"token_type": "bearer",
+			"expires_in": 3600,
+			"id_token": id_token,
+		}))
+	}
+
+	async fn userinfo_endpoint(req: HttpRequest) -> HttpResponse {
+		let authorization = req
+			.headers()
+			.get(header::AUTHORIZATION)
+			.and_then(|value| value.to_str().ok());
+		if authorization != Some(format!("Bearer {ACCESS_TOKEN}").as_str()) {
+			return HttpResponse::Unauthorized().finish();
+		}
+
+		HttpResponse::Ok().json(json!({
+			"email": "jane@example.com",
+			"name": "Jane",
+			"picture": "https://example.com/jane.png",
+		}))
+	}
+
+	fn start_server(id_token_nonce: Option<String>) -> OAuth2Config {
+		env::set_var(constants::env_key::FRONTEND_URL, "http://localhost:4200");
+
+		let server = web::Data::new(MockAuthorizationServer {
+			authorization: Mutex::default(),
+			id_token_nonce,
+		});
+		let http_server = HttpServer::new(move || {
+			App::new()
+				.app_data(server.clone())
+				.route("/authorize", web::get().to(authorize_endpoint))
+				.route("/token", web::post().to(token_endpoint))
+				.route("/userinfo", web::get().to(userinfo_endpoint))
+		})
+		.workers(1)
+		.bind(("127.0.0.1", 0))
+		.unwrap();
+		let address = http_server.addrs()[0];
+		actix_web::rt::spawn(http_server.run());
+
+		OAuth2Config {
+			client_id: "dcm".to_owned(),
+			client_secret: None,
+			auth_url: format!("http://{address}/authorize"),
+			token_url: format!("http://{address}/token"),
+			userinfo_url: format!("http://{address}/userinfo"),
+			scopes: vec!["openid".to_owned(), "email".to_owned()],
+		}
+	}
+
+	// Follows the login the way the browser would, up to the redirect back to the frontend
+	async fn start_login(config: &OAuth2Config) -> (String, OAuth2LoginState) {
+		let authentication_method_id = Uuid::new_v4();
+		let (auth_url, csrf_token, pkce_verifier, nonce) =
+			authorize(config, authentication_method_id).unwrap();
+		let auth_params = auth_url
+			.query_pairs()
+			.into_owned()
+			.collect::<HashMap<String, String>>();
+		assert_eq!(auth_params["code_challenge_method"], "S256");
+		assert_eq!(auth_params["nonce"], nonce);
+
+		let response = reqwest::Client::builder()
+			.redirect(reqwest::redirect::Policy::none())
+			.build()
+			.unwrap()
+			.get(auth_url.as_str())
+			.send()
+			.await
+			.unwrap();
+		let location = Url::parse(
+			response.headers()[reqwest::header::LOCATION]
+				.to_str()
+				.unwrap(),
+		)
+		.unwrap();
+		let callback_params = location
+			.query_pairs()
+			.into_owned()
+			.collect::<HashMap<String, String>>();
+		assert_eq!(callback_params["state"], *csrf_token.secret());
+
+		let login_state = OAuth2LoginState {
+			state: csrf_token.secret().to_owned(),
+			authentication_method_id,
+			pkce_verifier: pkce_verifier.secret().to_owned(),
+			nonce,
+			expires_at: Utc::now().naive_utc(),
+			created_at: Utc::now().naive_utc(),
+		};
+		(callback_params["code"].clone(), login_state)
+	}
+
+	#[actix_web::test]
+	async fn code_is_exchanged_with_the_pkce_verifier() {
+		let config = start_server(None);
+		let (code, login_state) = start_login(&config).await;
+
+		let (access_token, userinfo) = exchange_code(&config, code, &login_state).await.unwrap();
+		assert_eq!(access_token, ACCESS_TOKEN);
+		assert_eq!(userinfo.email, "jane@example.com");
+	}
+
+	#[actix_web::test]
+	async fn code_without_the_matching_verifier_is_rejected() {
+		let config = start_server(None);
+		let (code, mut login_state) = start_login(&config).await;
+		let (_, other_verifier) = PkceCodeChallenge::new_random_sha256();
+		login_state.pkce_verifier = other_verifier.secret().to_owned();
+
+		assert!(exchange_code(&config, code, &login_state).await.is_err());
+	}
+
+	#[actix_web::test]
+	async fn id_token_of_another_login_is_rejected() {
+		let config = start_server(Some("another-login".to_owned()));
+		let (code, login_state) = start_login(&config).await;
+
+		let result = exchange_code(&config, code, &login_state).await;
+		assert!(
+			matches!(result, Err(AppError::Unauthorized(ref error)) if error.code == "INVALID_OAUTH2_NONCE")
+		);
+	}
+
+	#[test]
+	fn state_only_counts_in_the_browser_that_started_the_login() {
+		assert!(OAuth2LoginState::ensure_same_browser("state", Some("state")).is_ok());
+		assert!(OAuth2LoginState::ensure_same_browser("state", Some("other")).is_err());
+		assert!(OAuth2LoginState::ensure_same_browser("state", None).is_err());
+	}
+}
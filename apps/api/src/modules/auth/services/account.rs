@@ -0,0 +1,80 @@
+use std::env;
+
+use crate::constants;
+use crate::errors::AppError;
+use crate::modules::core::actors::mail::{MailActor, MailMessage};
+use crate::modules::core::mail::lib::Mail;
+use crate::modules::users::models::user::User;
+use crate::modules::users::models::user_token::{UserToken, UserTokenKindEnum};
+use actix::Addr;
+use diesel::PgConnection;
+use tracing::instrument;
+
+#[instrument(skip(conn, mail_addr))]
+pub fn send_password_reset(
+	conn: &mut PgConnection,
+	mail_addr: &Addr<MailActor>,
+	user: &User,
+) -> Result<(), AppError> {
+	let token = UserToken::issue(conn, user.id, UserTokenKindEnum::PASSWORD_RESET)?;
+	let frontend_url = env::var(constants::env_key::FRONTEND_URL)?;
+
+	mail_addr.do_send(MailMessage {
+		mail: Mail {
+			to: user.email.clone(),
+			subject: "Reset your password".to_owned(),
+			body: format!(
+				"Hi {},\n\nSomeone requested a password reset for your account. Use the link below to choose a new password, it is valid for one hour.\n\n{}/auth/reset-password?token={}\n\nIf you did not request this, you can safely ignore this mail.",
+				user.name, frontend_url, token
+			),
+		},
+	});
+
+	Ok(())
+}
+
+#[instrument(skip(conn, mail_addr))]
+pub fn send_email_verification(
+	conn: &mut PgConnection,
+	mail_addr: &Addr<MailActor>,
+	user: &User,
+) -> Result<(), AppError> {
+	let token = UserToken::issue(conn, user.id, UserTokenKindEnum::EMAIL_VERIFICATION)?;
+	let frontend_url = env::var(constants::env_key::FRONTEND_URL)?;
+
+	mail_addr.do_send(MailMessage {
+		mail: Mail {
+			to: user.email.clone(),
+			subject: "Verify your email address".to_owned(),
+			body: format!(
+				"Hi {},\n\nPlease confirm your email address by opening the link below, it is valid for two days.\n\n{}/auth/verify-email?token={}",
+				user.name, frontend_url, token
+			),
+		},
+	});
+
+	Ok(())
+}
+
+#[instrument(skip(mail_addr, token))]
+pub fn send_site_invitation(
+	mail_addr: &Addr<MailActor>,
+	email: &str,
+	site_name: &str,
+	token: &str,
+) -> Result<(), AppError> {
+	let frontend_url = env::var(constants::env_key::FRONTEND_URL)?;
+
+	mail_addr.do_send(MailMessage {
+		mail: Mail {
+			to: email.to_owned(),
+			subject: format!("You have been invited to {}", site_name),
+			body: format!(
+				"Hi,\n\nYou have been invited to join the site {}. Use the link below to accept the invitation, it is valid for seven days.\n\n{}/auth/accept-invitation?token={}\n\nIf you were not expecting this invitation, you can safely ignore this mail.",
+				site_name, frontend_url, token
+			),
+		},
+	});
+
+	Ok(())
+}
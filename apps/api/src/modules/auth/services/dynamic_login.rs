@@ -3,9 +3,13 @@ use crate::{
 	modules::{
 		auth::{
 			dto::request::LoginUserDTO,
-			providers::{local::LocalAuthProvider, oauth2::OAuth2AuthProvider},
+			providers::{
+				ldap::LdapAuthProvider, local::LocalAuthProvider, oauth2::OAuth2AuthProvider,
+				oidc::OidcAuthProvider,
+			},
 		},
 		authentication_methods::models::authentication_method::AuthenticationMethod,
+		users::models::user_session::SessionMetadata,
 	},
 };
 use actix_web::HttpResponse;
@@ -20,11 +24,14 @@ pub trait AuthProvider {
 		&self,
 		conn: &mut PgConnection,
 		body: LoginUserDTO,
+		metadata: SessionMetadata,
 	) -> Result<HttpResponse, AppError>;
 	async fn callback(
 		&self,
 		conn: &mut PgConnection,
 		code: String,
+		state: Option<String>,
+		metadata: SessionMetadata,
 	) -> Result<HttpResponse, AppError>;
 }
 
@@ -45,6 +52,16 @@ pub fn get_auth_provider(
 				authentication_method,
 			}
 		})),
+		"LDAP" => Ok(Box::new({
+			LdapAuthProvider {
+				authentication_method,
+			}
+		})),
+		"OIDC" => Ok(Box::new({
+			OidcAuthProvider {
+				authentication_method,
+			}
+		})),
 		_ => Err(AppError::UnprocessableEntity(AppErrorValue {
 			message: "Auth provider is not implemented".to_owned(),
 			status: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
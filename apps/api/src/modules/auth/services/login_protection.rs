@@ -0,0 +1,233 @@
+use std::env;
+use std::str::FromStr;
+
+use crate::constants;
+use crate::errors::{AppError, AppErrorValue};
+use crate::modules::users::models::login_lockout::{CreateLoginLockout, LoginLockout};
+use crate::modules::users::models::login_throttle::{LoginThrottle, LoginThrottleScopeEnum};
+use crate::modules::users::models::user_session::SessionMetadata;
+use actix_web::http::StatusCode;
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::PgConnection;
+use tracing::{instrument, warn};
+use uuid::Uuid;
+
+const DEFAULT_MAX_ATTEMPTS: i32 = 5;
+const DEFAULT_MAX_ATTEMPTS_PER_IP: i32 = 20;
+const DEFAULT_LOCKOUT_SECONDS: i64 = 60;
+const MAX_LOCKOUT_SECONDS: i64 = 60 * 60 * 24;
+const FAILED_ATTEMPT_WINDOW_HOURS: i64 = 1;
+const REJECTED_CREDENTIALS_CODES: [&str; 2] = ["LOGIN_FAILED", "TWO_FACTOR_FAILED"];
+
+fn env_or<T: FromStr>(key: &str, default: T) -> T {
+	env::var(key)
+		.ok()
+		.and_then(|value| value.parse::<T>().ok())
+		.unwrap_or(default)
+}
+
+fn max_attempts(scope: LoginThrottleScopeEnum) -> i32 {
+	match scope {
+		LoginThrottleScopeEnum::ACCOUNT => {
+			env_or(constants::env_key::LOGIN_MAX_ATTEMPTS, DEFAULT_MAX_ATTEMPTS)
+		}
+		LoginThrottleScopeEnum::IP_ADDRESS => env_or(
+			constants::env_key::LOGIN_MAX_ATTEMPTS_PER_IP,
+			DEFAULT_MAX_ATTEMPTS_PER_IP,
+		),
+	}
+}
+
+// Every failure past the limit doubles the lockout, up to a day
+fn lockout_duration(failed_attempts: i32, max_attempts: i32) -> Duration {
+	let base_seconds = env_or(
+		constants::env_key::LOGIN_LOCKOUT_SECONDS,
+		DEFAULT_LOCKOUT_SECONDS,
+	);
+	let exponent = (failed_attempts - max_attempts).clamp(0, 16) as u32;
+
+	Duration::seconds((base_seconds * 2_i64.pow(exponent)).min(MAX_LOCKOUT_SECONDS))
+}
+
+// Accounts are tracked by the address that was typed in, so unknown accounts are locked the same way as existing ones
+fn get_account_identifier(authentication_method_id: Uuid, email: &str) -> String {
+	format!(
+		"{}:{}",
+		authentication_method_id,
+		email.trim().to_lowercase()
+	)
+}
+
+fn get_targets(
+	authentication_method_id: Uuid,
+	email: &str,
+	metadata: &SessionMetadata,
+) -> Vec<(LoginThrottleScopeEnum, String)> {
+	let account = (
+		LoginThrottleScopeEnum::ACCOUNT,
+		get_account_identifier(authentication_method_id, email),
+	);
+	let ip_address = metadata
+		.ip_address
+		.as_ref()
+		.map(|ip_address| (LoginThrottleScopeEnum::IP_ADDRESS, ip_address.to_owned()));
+
+	std::iter::once(account).chain(ip_address).collect()
+}
+
+fn login_locked_error(locked_until: NaiveDateTime) -> AppError {
+	AppError::TooManyRequests(AppErrorValue {
+		message: format!(
+			"Too many failed login attempts, try again after {} UTC",
+			locked_until.format("%Y-%m-%d %H:%M:%S")
+		),
+		status: StatusCode::TOO_MANY_REQUESTS.as_u16(),
+		code: "LOGIN_LOCKED".to_owned(),
+		..Default::default()
+	})
+}
+
+// A reserved attempt counts as failed until it is released, so parallel requests cannot verify
+// more passwords than the limit allows
+struct ReservedAttempt {
+	scope: LoginThrottleScopeEnum,
+	identifier: String,
+	failed_attempts: i32,
+	locked_until: Option<NaiveDateTime>,
+}
+
+pub struct LoginAttempt {
+	reserved: Vec<ReservedAttempt>,
+}
+
+// Reserves the attempt before any credentials are checked. An attempt that would reach the limit
+// locks right away, the lock is lifted again when it turns out to be valid
+#[instrument(skip(conn, metadata))]
+pub fn reserve_login_attempt(
+	conn: &mut PgConnection,
+	authentication_method_id: Uuid,
+	email: &str,
+	metadata: &SessionMetadata,
+) -> Result<LoginAttempt, AppError> {
+	let now = Utc::now().naive_utc();
+	let mut login_attempt = LoginAttempt { reserved: vec![] };
+
+	for (scope, identifier) in get_targets(authentication_method_id, email, metadata) {
+		// Failures are forgotten after a quiet hour, lockouts themselves always run their course
+		LoginThrottle::remove_stale(
+			conn,
+			scope,
+			&identifier,
+			now - Duration::hours(FAILED_ATTEMPT_WINDOW_HOURS),
+		)?;
+		let login_throttle = LoginThrottle::increment(conn, scope, &identifier)?;
+
+		if let (true, Some(locked_until)) =
+			(login_throttle.is_locked(), login_throttle.locked_until)
+		{
+			LoginThrottle::decrement(conn, scope, &identifier)?;
+			release_login_attempt(conn, login_attempt)?;
+			return Err(login_locked_error(locked_until));
+		}
+
+		let max_attempts = max_attempts(scope);
+		let failed_attempts = login_throttle.failed_attempts;
+		let locked_until = match failed_attempts >= max_attempts {
+			true => Some(now + lockout_duration(failed_attempts, max_attempts)),
+			false => None,
+		};
+		if locked_until.is_some() {
+			LoginThrottle::set_locked_until(conn, scope, &identifier, locked_until)?;
+		}
+
+		login_attempt.reserved.push(ReservedAttempt {
+			scope,
+			identifier,
+			failed_attempts,
+			locked_until,
+		});
+	}
+
+	Ok(login_attempt)
+}
+
+fn release_login_attempt(
+	conn: &mut PgConnection,
+	login_attempt: LoginAttempt,
+) -> Result<(), AppError> {
+	for reserved in login_attempt.reserved {
+		LoginThrottle::decrement(conn, reserved.scope, &reserved.identifier)?;
+		if reserved.locked_until.is_some() {
+			LoginThrottle::set_locked_until(conn, reserved.scope, &reserved.identifier, None)?;
+		}
+	}
+
+	Ok(())
+}
+
+fn register_failed_login(
+	conn: &mut PgConnection,
+	login_attempt: LoginAttempt,
+	metadata: &SessionMetadata,
+) -> Result<(), AppError> {
+	for reserved in login_attempt.reserved {
+		let locked_until = match reserved.locked_until {
+			Some(locked_until) => locked_until,
+			None => continue,
+		};
+
+		warn!(
+			scope = ?reserved.scope,
+			identifier = %reserved.identifier,
+			failed_attempts = reserved.failed_attempts,
+			%locked_until,
+			"login locked"
+		);
+		LoginLockout::create(
+			conn,
+			CreateLoginLockout {
+				scope: reserved.scope,
+				identifier: reserved.identifier,
+				failed_attempts: reserved.failed_attempts,
+				ip_address: metadata.ip_address.clone(),
+				user_agent: metadata.user_agent.clone(),
+				locked_until,
+			},
+		)?;
+	}
+
+	Ok(())
+}
+
+// Used after a successful login and by administrators. Only the account is cleared, a single valid login should not lift the limit of an address that is guessing
+#[instrument(skip(conn))]
+pub fn clear_account_lockout(
+	conn: &mut PgConnection,
+	authentication_method_id: Uuid,
+	email: &str,
+) -> Result<(), AppError> {
+	LoginThrottle::remove(
+		conn,
+		LoginThrottleScopeEnum::ACCOUNT,
+		&get_account_identifier(authentication_method_id, email),
+	)
+}
+
+// Keeps a reserved attempt counted when the credentials were rejected, any other outcome releases it
+pub fn track_login_attempt<T>(
+	conn: &mut PgConnection,
+	login_attempt: LoginAttempt,
+	metadata: &SessionMetadata,
+	result: Result<T, AppError>,
+) -> Result<T, AppError> {
+	match &result {
+		Err(AppError::Unauthorized(value))
+			if REJECTED_CREDENTIALS_CODES.contains(&value.code.as_str()) =>
+		{
+			register_failed_login(conn, login_attempt, metadata)?
+		}
+		_ => release_login_attempt(conn, login_attempt)?,
+	}
+
+	result
+}
@@ -1,2 +1,6 @@
+pub mod account;
 pub mod dynamic_login;
+pub mod login_protection;
+pub mod password_policy;
 pub mod register;
+pub mod two_factor;
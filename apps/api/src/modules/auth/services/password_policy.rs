@@ -0,0 +1,123 @@
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::sync::RwLock;
+
+use crate::constants;
+use crate::errors::{AppError, AppErrorValue};
+use actix_web::http::StatusCode;
+use actix_web::web;
+use sha1::{Digest, Sha1};
+use tracing::warn;
+
+const DEFAULT_MIN_LENGTH: usize = 8;
+const SHA1_HEX_LENGTH: usize = 40;
+
+// Lines are either a plain password or an uppercase SHA-1 hash as found in the Have I Been Pwned downloads, optionally followed by `:count`
+fn read_breached_passwords() -> HashSet<String> {
+	let Ok(path) = env::var(constants::env_key::PASSWORD_BREACHED_LIST) else {
+		return HashSet::new();
+	};
+
+	match fs::read_to_string(&path) {
+		Ok(contents) => contents
+			.lines()
+			.map(|line| line.trim())
+			.filter(|line| !line.is_empty())
+			.map(|line| match line.split_once(':') {
+				Some((hash, count))
+					if is_sha1_hex(hash) && count.chars().all(|c| c.is_ascii_digit()) =>
+				{
+					hash.to_uppercase()
+				}
+				_ if is_sha1_hex(line) => line.to_uppercase(),
+				_ => line.to_lowercase(),
+			})
+			.collect(),
+		Err(err) => {
+			warn!(%path, %err, "breached password list could not be read");
+			HashSet::new()
+		}
+	}
+}
+
+lazy_static! {
+	// Filled once at startup, stays empty when no list is configured
+	static ref BREACHED_PASSWORDS: RwLock<HashSet<String>> = RwLock::new(HashSet::new());
+}
+
+// The list can be large, it is read on a blocking thread before the server accepts requests
+pub async fn load_breached_passwords() -> Result<(), AppError> {
+	let breached_passwords = web::block(read_breached_passwords).await?;
+	*BREACHED_PASSWORDS
+		.write()
+		.map_err(|_| AppError::from("Could not lock the breached password list"))? = breached_passwords;
+
+	Ok(())
+}
+
+fn is_sha1_hex(value: &str) -> bool {
+	value.len() == SHA1_HEX_LENGTH && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn is_breached(password: &str) -> Result<bool, AppError> {
+	let breached_passwords = BREACHED_PASSWORDS
+		.read()
+		.map_err(|_| AppError::from("Could not lock the breached password list"))?;
+	if breached_passwords.is_empty() {
+		return Ok(false);
+	}
+
+	let hash = format!("{:X}", Sha1::digest(password.as_bytes()));
+	Ok(breached_passwords.contains(&hash) || breached_passwords.contains(&password.to_lowercase()))
+}
+
+// Complex passwords mix at least three of lowercase, uppercase, digits and symbols
+fn is_complex(password: &str) -> bool {
+	let classes = [
+		password.chars().any(|c| c.is_lowercase()),
+		password.chars().any(|c| c.is_uppercase()),
+		password.chars().any(|c| c.is_ascii_digit()),
+		password.chars().any(|c| !c.is_alphanumeric()),
+	];
+
+	classes.iter().filter(|class| **class).count() >= 3
+}
+
+pub fn validate_password(password: &str) -> Result<(), AppError> {
+	let min_length = env::var(constants::env_key::PASSWORD_MIN_LENGTH)
+		.ok()
+		.and_then(|value| value.parse::<usize>().ok())
+		.unwrap_or(DEFAULT_MIN_LENGTH);
+	let require_complexity = env::var(constants::env_key::PASSWORD_REQUIRE_COMPLEXITY)
+		.map(|value| value == "true")
+		.unwrap_or(false);
+
+	let mut violations: Vec<String> = vec![];
+
+	if password.chars().count() < min_length {
+		violations.push(format!("be at least {} characters long", min_length));
+	}
+
+	if require_complexity && !is_complex(password) {
+		violations.push(
+			"contain at least three of lowercase letters, uppercase letters, digits and symbols"
+				.to_owned(),
+		);
+	}
+
+	if is_breached(password)? {
+		violations.push("not appear in a list of breached passwords".to_owned());
+	}
+
+	if violations.is_empty() {
+		return Ok(());
+	}
+
+	Err(AppError::UnprocessableEntity(AppErrorValue {
+		message: format!("Password should {}", violations.join(", ")),
+		status: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+		code: "PASSWORD_POLICY_VIOLATION".to_owned(),
+		..Default::default()
+	}))
+}
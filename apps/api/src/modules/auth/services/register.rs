@@ -8,18 +8,19 @@ use crate::modules::users::models::user_role::UserRole;
 use crate::schema::authentication_method_roles;
 use diesel::prelude::*;
 use diesel::PgConnection;
+use std::collections::{BTreeMap, BTreeSet};
 use tracing::instrument;
 use uuid::Uuid;
 
 #[instrument(skip(conn, password))]
-pub async fn register_user(
+pub fn register_user(
 	conn: &mut PgConnection,
 	email: &str,
 	username: &str,
 	password: &str,
 	image: Option<&str>,
 	authentication_method_id: Option<Uuid>,
-) -> Result<(User, String), AppError> {
+) -> Result<User, AppError> {
 	// TODO: move this logic somewhere seperatly? Try to implement the `service` pattern perhaps?
 	// Create the user account
 	let auth_method_id = if authentication_method_id.is_some() {
@@ -29,16 +30,56 @@ pub async fn register_user(
 		local_auth_method.id
 	};
 
-	let (user, token) = User::signup(conn, email, username, password, image, auth_method_id)?;
+	let user = User::signup(conn, email, username, password, image, auth_method_id)?;
 
-	Ok((user, token))
+	Ok(user)
 }
 
+// Which (site, role) pairs the assignments grant, and whether only groups grant them, plus the
+// group bound pairs that no assignment grants anymore. Row order makes no difference.
+fn plan_role_assignments(
+	assignments: &[AuthenticationMethodRole],
+	groups: Option<&[String]>,
+) -> (
+	BTreeMap<(Option<Uuid>, Uuid), bool>,
+	Vec<(Option<Uuid>, Uuid)>,
+) {
+	let mut grants: BTreeMap<(Option<Uuid>, Uuid), bool> = BTreeMap::new();
+	let mut revocations = BTreeSet::new();
+
+	for assignment in assignments {
+		let key = (assignment.site_id, assignment.role_id);
+		match (&assignment.group_name, groups) {
+			(None, _) => {
+				grants.insert(key, false);
+			}
+			(Some(group_name), Some(groups)) if groups.contains(group_name) => {
+				grants.entry(key).or_insert(true);
+			}
+			(Some(_), Some(_)) => {
+				revocations.insert(key);
+			}
+			// Providers without group information never touch group bound assignments
+			(Some(_), None) => {}
+		}
+	}
+
+	let revocations = revocations
+		.into_iter()
+		.filter(|key| !grants.contains_key(key))
+		.collect();
+
+	(grants, revocations)
+}
+
+// Group bound assignments are only granted when the identity provider reports a matching group,
+// and are taken away again once it stops doing so. Roles assigned by hand are never taken away.
 #[instrument(skip(conn))]
 pub fn persist_role_assignments(
 	conn: &mut PgConnection,
 	user_id: Uuid,
 	authentication_method_id: Option<Uuid>,
+	groups: Option<&[String]>,
 ) -> Result<(), AppError> {
 	let auth_method_id = if authentication_method_id.is_some() {
 		authentication_method_id.unwrap()
@@ -53,25 +94,105 @@ pub fn persist_role_assignments(
 			.filter(authentication_method_roles::authentication_method_id.eq(auth_method_id))
 			.get_results::<AuthenticationMethodRole>(conn)?;
 
-	let _ = authentication_method_roles
-		.into_iter()
-		.map(|assignment| {
-			if assignment.site_id.is_none() {
-				UserRole::upsert(conn, user_id, assignment.role_id)?;
-				return Ok(());
-			}
+	let (grants, revocations) = plan_role_assignments(&authentication_method_roles, groups);
 
-			SiteUser::upsert(conn, assignment.site_id.unwrap(), user_id)?;
-			SiteUserRole::upsert(
-				conn,
-				user_id,
-				assignment.site_id.unwrap(),
-				assignment.role_id,
-			)?;
+	for ((site_id, role_id), granted_by_group) in grants {
+		match (site_id, granted_by_group) {
+			(None, false) => {
+				UserRole::upsert(conn, user_id, role_id)?;
+			}
+			(None, true) => UserRole::upsert_from_group(conn, user_id, role_id)?,
+			(Some(site_id), false) => {
+				SiteUser::upsert(conn, site_id, user_id)?;
+				SiteUserRole::upsert(conn, user_id, site_id, role_id)?;
+			}
+			(Some(site_id), true) => {
+				SiteUser::upsert(conn, site_id, user_id)?;
+				SiteUserRole::upsert_from_group(conn, user_id, site_id, role_id)?;
+			}
+		}
+	}
 
-			Ok(())
-		})
-		.collect::<Result<Vec<()>, AppError>>()?;
+	for (site_id, role_id) in revocations {
+		match site_id {
+			None => UserRole::remove_from_group(conn, user_id, role_id)?,
+			Some(site_id) => SiteUserRole::remove_from_group(conn, user_id, site_id, role_id)?,
+		}
+	}
 
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use uuid::Uuid;
+
+	use super::plan_role_assignments;
+	use crate::modules::authentication_methods::models::authentication_method_role::AuthenticationMethodRole;
+
+	fn assignment(
+		site_id: Option<Uuid>,
+		role_id: Uuid,
+		group_name: Option<&str>,
+	) -> AuthenticationMethodRole {
+		AuthenticationMethodRole {
+			id: Uuid::new_v4(),
+			authentication_method_id: Uuid::nil(),
+			site_id,
+			role_id,
+			group_name: group_name.map(|group_name| group_name.to_owned()),
+		}
+	}
+
+	#[test]
+	fn ungranted_groups_leave_granted_roles_alone() {
+		let site_id = Some(Uuid::new_v4());
+		let role_id = Uuid::new_v4();
+		let granted = assignment(site_id, role_id, Some("editors"));
+		let ungranted = assignment(site_id, role_id, Some("admins"));
+		let groups = vec!["editors".to_owned()];
+
+		for assignments in [
+			vec![granted.clone(), ungranted.clone()],
+			vec![ungranted, granted],
+		] {
+			let (grants, revocations) =
+				plan_role_assignments(&assignments, Some(groups.as_slice()));
+			assert_eq!(grants.get(&(site_id, role_id)), Some(&true));
+			assert!(revocations.is_empty());
+		}
+	}
+
+	#[test]
+	fn unconditional_assignments_are_not_group_grants() {
+		let role_id = Uuid::new_v4();
+		let assignments = vec![
+			assignment(None, role_id, Some("editors")),
+			assignment(None, role_id, None),
+		];
+		let groups = vec!["editors".to_owned()];
+
+		let (grants, _) = plan_role_assignments(&assignments, Some(groups.as_slice()));
+		assert_eq!(grants.get(&(None, role_id)), Some(&false));
+	}
+
+	#[test]
+	fn roles_of_missing_groups_are_revoked() {
+		let role_id = Uuid::new_v4();
+		let assignments = vec![assignment(None, role_id, Some("admins"))];
+
+		let (grants, revocations) = plan_role_assignments(&assignments, Some(&[]));
+		assert!(grants.is_empty());
+		assert_eq!(revocations, vec![(None, role_id)]);
+	}
+
+	#[test]
+	fn providers_without_groups_touch_no_group_assignments() {
+		let role_id = Uuid::new_v4();
+		let assignments = vec![assignment(None, role_id, Some("admins"))];
+
+		let (grants, revocations) = plan_role_assignments(&assignments, None);
+		assert!(grants.is_empty());
+		assert!(revocations.is_empty());
+	}
+}
@@ -0,0 +1,259 @@
+use std::collections::HashSet;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::constants;
+use crate::errors::{AppError, AppErrorValue};
+use crate::modules::auth::services::login_protection;
+use crate::modules::core::models::config_item::ConfigItem;
+use crate::modules::users::models::user::User;
+use crate::modules::users::models::user_recovery_code::UserRecoveryCode;
+use crate::modules::users::models::user_session::SessionMetadata;
+use crate::modules::users::models::user_token::{UserToken, UserTokenKindEnum};
+use crate::schema::{sites_users_roles, users_roles};
+use actix_web::http::StatusCode;
+use diesel::prelude::*;
+use serde_json::Value;
+use totp_rs::{Algorithm, Secret, TOTP};
+use tracing::instrument;
+use uuid::Uuid;
+
+// Holds a list of role ids. The global item applies to root roles, site items to the roles within that site.
+pub const TWO_FACTOR_REQUIRED_ROLES_KEY: &str = "two_factor_required_roles";
+
+const TOTP_STEP_SECONDS: u64 = 30;
+// One step of skew accounts for clocks of authenticator apps drifting slightly
+const TOTP_SKEW_STEPS: u64 = 1;
+
+fn totp_error(message: String) -> AppError {
+	AppError::InternalServerError(AppErrorValue {
+		message,
+		status: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+		code: "TOTP_ERROR".to_owned(),
+		..Default::default()
+	})
+}
+
+pub fn two_factor_failed_error() -> AppError {
+	AppError::Unauthorized(AppErrorValue {
+		message: "Verification code is invalid".to_owned(),
+		status: StatusCode::UNAUTHORIZED.as_u16(),
+		code: "TWO_FACTOR_FAILED".to_owned(),
+		..Default::default()
+	})
+}
+
+fn enrolment_required_error() -> AppError {
+	AppError::Forbidden(AppErrorValue {
+		message: "Two-factor authentication is required for this account, contact an administrator to set it up"
+			.to_owned(),
+		status: StatusCode::FORBIDDEN.as_u16(),
+		code: "TWO_FACTOR_ENROLMENT_REQUIRED".to_owned(),
+		..Default::default()
+	})
+}
+
+fn build_totp(user: &User, secret: &str) -> Result<TOTP, AppError> {
+	let secret = Secret::Encoded(secret.to_owned())
+		.to_bytes()
+		.map_err(|err| totp_error(err.to_string()))?;
+
+	// The skew is handled by `verify_totp`, which needs to know the step a code belongs to
+	TOTP::new(
+		Algorithm::SHA1,
+		6,
+		0,
+		TOTP_STEP_SECONDS,
+		secret,
+		Some(constants::TOTP_ISSUER.to_owned()),
+		user.email.clone(),
+	)
+	.map_err(|err| totp_error(err.to_string()))
+}
+
+pub fn generate_secret() -> String {
+	Secret::generate_secret().to_encoded().to_string()
+}
+
+pub fn get_provisioning_uri(user: &User, secret: &str) -> Result<String, AppError> {
+	Ok(build_totp(user, secret)?.get_url())
+}
+
+// Checks against the pending secret as well, which lets enrolment confirm the first code.
+// Every code is only accepted once, neither it nor the codes before it work after it was used.
+#[instrument(skip(conn, user, code))]
+pub fn verify_totp(conn: &mut PgConnection, user: &User, code: &str) -> Result<bool, AppError> {
+	let Some(secret) = &user.totp_secret else {
+		return Ok(false);
+	};
+
+	let totp = build_totp(user, secret)?;
+	let current_step = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() / TOTP_STEP_SECONDS;
+	let matching_step = (current_step.saturating_sub(TOTP_SKEW_STEPS)
+		..=current_step + TOTP_SKEW_STEPS)
+		.find(|step| totp.check(code.trim(), step * TOTP_STEP_SECONDS));
+
+	match matching_step {
+		Some(step) => User::use_totp_step(conn, user.id, i64::try_from(step)?),
+		None => Ok(false),
+	}
+}
+
+// Accepts either a code of the authenticator app or one of the single-use recovery codes
+#[instrument(skip(conn, user, code))]
+pub fn verify_second_factor(
+	conn: &mut PgConnection,
+	user: &User,
+	code: &str,
+) -> Result<(), AppError> {
+	if user.totp_enabled_at.is_none() {
+		return Err(two_factor_failed_error());
+	}
+
+	if verify_totp(conn, user, code)? || UserRecoveryCode::consume(conn, user.id, code)? {
+		return Ok(());
+	}
+
+	Err(two_factor_failed_error())
+}
+
+fn get_role_ids(value: &Option<Value>) -> HashSet<Uuid> {
+	value
+		.as_ref()
+		.and_then(|value| value.as_array())
+		.map(|values| {
+			values
+				.iter()
+				.filter_map(|value| value.as_str())
+				.filter_map(|value| Uuid::parse_str(value).ok())
+				.collect()
+		})
+		.unwrap_or_default()
+}
+
+#[instrument(skip(conn, user))]
+pub fn two_factor_required(conn: &mut PgConnection, user: &User) -> Result<bool, AppError> {
+	let policies = ConfigItem::find_by_key(conn, TWO_FACTOR_REQUIRED_ROLES_KEY)?;
+	if policies.is_empty() {
+		return Ok(false);
+	}
+
+	let root_role_ids = users_roles::table
+		.filter(users_roles::user_id.eq(user.id))
+		.select(users_roles::role_id)
+		.load::<Uuid>(conn)?;
+	let site_role_ids = sites_users_roles::table
+		.filter(sites_users_roles::user_id.eq(user.id))
+		.select((sites_users_roles::site_id, sites_users_roles::role_id))
+		.load::<(Uuid, Uuid)>(conn)?;
+
+	let required = policies.iter().any(|policy| {
+		let required_role_ids = get_role_ids(&policy.value);
+
+		match policy.site_id {
+			None => root_role_ids
+				.iter()
+				.any(|role_id| required_role_ids.contains(role_id)),
+			Some(policy_site_id) => site_role_ids.iter().any(|(site_id, role_id)| {
+				*site_id == policy_site_id && required_role_ids.contains(role_id)
+			}),
+		}
+	});
+
+	Ok(required)
+}
+
+// Issues the challenge of the second login step when the account uses two-factor authentication.
+// Enrolling takes an authenticated session, otherwise the password alone would be enough to add an authenticator
+// to an account whose roles require one, so those accounts can't log in until they are enrolled.
+// The login identifier is what the password step was throttled under, the second step is throttled the same way.
+#[instrument(skip(conn, user))]
+pub fn start_challenge(
+	conn: &mut PgConnection,
+	user: &User,
+	login_identifier: &str,
+) -> Result<Option<String>, AppError> {
+	if user.totp_enabled_at.is_none() {
+		return match two_factor_required(conn, user)? {
+			true => Err(enrolment_required_error()),
+			false => Ok(None),
+		};
+	}
+
+	let challenge = UserToken::issue_for_login(
+		conn,
+		user.id,
+		UserTokenKindEnum::TWO_FACTOR_CHALLENGE,
+		Some(login_identifier),
+	)?;
+
+	Ok(Some(challenge))
+}
+
+// A challenge is consumed by every attempt, a wrong code means starting over with the password
+#[instrument(skip(conn, challenge, code))]
+pub fn answer_challenge(
+	conn: &mut PgConnection,
+	authentication_method_id: Uuid,
+	challenge: &str,
+	code: &str,
+	metadata: &SessionMetadata,
+) -> Result<User, AppError> {
+	let user_token = UserToken::consume(conn, UserTokenKindEnum::TWO_FACTOR_CHALLENGE, challenge)?;
+	let user = User::find_one(conn, user_token.user_id)?;
+
+	if user.authentication_method_id != authentication_method_id {
+		return Err(two_factor_failed_error());
+	}
+
+	let login_identifier = user_token
+		.login_identifier
+		.unwrap_or_else(|| user.email.clone());
+	let login_attempt = login_protection::reserve_login_attempt(
+		conn,
+		authentication_method_id,
+		&login_identifier,
+		metadata,
+	)?;
+	let result = verify_second_factor(conn, &user, code);
+	login_protection::track_login_attempt(conn, login_attempt, metadata, result)?;
+	login_protection::clear_account_lockout(conn, authentication_method_id, &login_identifier)?;
+
+	Ok(user)
+}
+
+// Keeps a pending secret around so a QR code that was already scanned stays valid until enrolment is confirmed
+#[instrument(skip(conn, user))]
+pub fn start_enrolment(conn: &mut PgConnection, user: &User) -> Result<(String, String), AppError> {
+	let secret = match (&user.totp_secret, user.totp_enabled_at) {
+		(Some(secret), None) => secret.to_owned(),
+		_ => {
+			let secret = generate_secret();
+			User::set_totp_secret(conn, user.id, Some(secret.clone()))?;
+			secret
+		}
+	};
+	let provisioning_uri = get_provisioning_uri(user, &secret)?;
+
+	Ok((secret, provisioning_uri))
+}
+
+// Returns a fresh set of recovery codes once the first code of the authenticator app checks out
+#[instrument(skip(conn, user, code))]
+pub fn confirm_enrolment(
+	conn: &mut PgConnection,
+	user: &User,
+	code: &str,
+) -> Result<Vec<String>, AppError> {
+	if user.totp_enabled_at.is_some() || !verify_totp(conn, user, code)? {
+		return Err(two_factor_failed_error());
+	}
+
+	User::enable_totp(conn, user.id)?;
+	UserRecoveryCode::regenerate(conn, user.id)
+}
+
+#[instrument(skip(conn))]
+pub fn disable(conn: &mut PgConnection, user_id: Uuid) -> Result<(), AppError> {
+	User::set_totp_secret(conn, user_id, None)?;
+	UserRecoveryCode::remove_all(conn, user_id)
+}
@@ -66,6 +66,7 @@ pub async fn create(
 			authentication_method_id: params.authentication_method_id,
 			site_id: form.site_id,
 			role_id: form.role_id,
+			group_name: form.group_name.clone(),
 		},
 	)?;
 	let res = response::AuthenticationMethodRoleDTO::from(authentication_method_role);
@@ -187,6 +188,7 @@ pub async fn update(
 		params.authentication_method_role_id,
 		UpdateAuthenticationMethodRole {
 			role_id: form.role_id,
+			group_name: form.group_name.clone(),
 		},
 	)?;
 	let res = response::AuthenticationMethodRoleDTO::from(authentication_method_role);
@@ -7,10 +7,19 @@ use uuid::Uuid;
 pub struct CreateAuthenticationMethodRoleDTO {
 	pub role_id: Uuid,
 	pub site_id: Option<Uuid>,
+	pub group_name: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateAuthenticationMethodRoleDTO {
 	pub role_id: Uuid,
+	// Sending `null` explicitly makes the assignment apply to every user again
+	#[serde(
+		default,
+		skip_serializing_if = "Option::is_none",
+		with = "::serde_with::rust::double_option"
+	)]
+	#[schema(value_type = Option<String>)]
+	pub group_name: Option<Option<String>>,
 }
@@ -15,6 +15,7 @@ pub struct AuthenticationMethodRoleDTO {
 	pub id: Uuid,
 	pub role_id: Uuid,
 	pub site_id: Option<Uuid>,
+	pub group_name: Option<String>,
 	pub role: RoleDTO,
 	pub site: Option<SiteDTO>,
 }
@@ -27,6 +28,7 @@ impl From<(AuthenticationMethodRole, Role, Option<Site>)> for AuthenticationMeth
 			id: authentication_method.id,
 			site_id: authentication_method.site_id,
 			role_id: authentication_method.role_id,
+			group_name: authentication_method.group_name,
 			role: RoleDTO::from(role),
 			site: if site.is_some() {
 				Some(SiteDTO::from(site.unwrap()))
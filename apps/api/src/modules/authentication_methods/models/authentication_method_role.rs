@@ -16,6 +16,8 @@ pub struct AuthenticationMethodRole {
 	pub authentication_method_id: Uuid,
 	pub site_id: Option<Uuid>,
 	pub role_id: Uuid,
+	// Only assigned to users whose identity provider reports this group, always when empty
+	pub group_name: Option<String>,
 }
 
 impl AuthenticationMethodRole {
@@ -128,10 +130,12 @@ pub struct CreateAuthenticationMethodRole {
 	pub authentication_method_id: Uuid,
 	pub role_id: Uuid,
 	pub site_id: Option<Uuid>,
+	pub group_name: Option<String>,
 }
 
 #[derive(AsChangeset, Debug, Deserialize, Clone)]
 #[diesel(table_name = authentication_method_roles)]
 pub struct UpdateAuthenticationMethodRole {
 	pub role_id: Uuid,
+	pub group_name: Option<Option<String>>,
 }
@@ -1,2 +1,3 @@
 pub mod authentication_method;
 pub mod authentication_method_role;
+pub mod oauth2_login_state;
@@ -0,0 +1,121 @@
+use std::env;
+
+use actix_web::cookie::{time, Cookie, SameSite};
+use actix_web::http::StatusCode;
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::constants;
+use crate::errors::{AppError, AppErrorValue};
+use crate::schema::oauth2_login_states;
+
+const LOGIN_STATE_LIFETIME_MINUTES: i64 = 10;
+pub const LOGIN_STATE_COOKIE: &str = "oauth2_login_state";
+
+#[derive(Identifiable, Selectable, Queryable, Debug, Clone)]
+#[diesel(table_name = oauth2_login_states)]
+#[diesel(primary_key(state))]
+pub struct OAuth2LoginState {
+	pub state: String,
+	pub authentication_method_id: Uuid,
+	pub pkce_verifier: String,
+	pub nonce: String,
+	pub expires_at: NaiveDateTime,
+	pub created_at: NaiveDateTime,
+}
+
+fn invalid_state_error() -> AppError {
+	AppError::Unauthorized(AppErrorValue {
+		message: "Login state is invalid or has expired, please try again".to_owned(),
+		status: StatusCode::UNAUTHORIZED.as_u16(),
+		code: "INVALID_OAUTH2_STATE".to_owned(),
+		..Default::default()
+	})
+}
+
+impl OAuth2LoginState {
+	#[instrument(skip(conn, pkce_verifier, nonce))]
+	pub fn create(
+		conn: &mut PgConnection,
+		authentication_method_id: Uuid,
+		state: String,
+		pkce_verifier: String,
+		nonce: String,
+	) -> Result<Self, AppError> {
+		let now = Utc::now().naive_utc();
+
+		// Logins that were started but never finished are cleaned up here
+		diesel::delete(oauth2_login_states::table.filter(oauth2_login_states::expires_at.lt(now)))
+			.execute(conn)?;
+
+		let login_state = diesel::insert_into(oauth2_login_states::table)
+			.values(CreateOAuth2LoginState {
+				state,
+				authentication_method_id,
+				pkce_verifier,
+				nonce,
+				expires_at: now + Duration::minutes(LOGIN_STATE_LIFETIME_MINUTES),
+			})
+			.returning(OAuth2LoginState::as_returning())
+			.get_result(conn)?;
+
+		Ok(login_state)
+	}
+
+	// States can only be used once, they are removed as soon as a callback presents them
+	#[instrument(skip(conn, state))]
+	pub fn consume(
+		conn: &mut PgConnection,
+		authentication_method_id: Uuid,
+		state: &str,
+	) -> Result<Self, AppError> {
+		let login_state =
+			diesel::delete(oauth2_login_states::table.find(state).filter(
+				oauth2_login_states::authentication_method_id.eq(authentication_method_id),
+			))
+			.returning(OAuth2LoginState::as_returning())
+			.get_result::<OAuth2LoginState>(conn)
+			.optional()?
+			.ok_or_else(invalid_state_error)?;
+
+		if login_state.expires_at < Utc::now().naive_utc() {
+			return Err(invalid_state_error());
+		}
+
+		Ok(login_state)
+	}
+
+	// The state is handed to the browser that started the login as well, so a callback link taken from
+	// someone else's login can't be completed in another browser
+	pub fn cookie(&self) -> Result<Cookie<'static>, AppError> {
+		let frontend_url = env::var(constants::env_key::FRONTEND_URL)?;
+
+		Ok(Cookie::build(LOGIN_STATE_COOKIE, self.state.clone())
+			.path("/")
+			.http_only(true)
+			.secure(frontend_url.starts_with("https://"))
+			.same_site(SameSite::Lax)
+			.max_age(time::Duration::minutes(LOGIN_STATE_LIFETIME_MINUTES))
+			.finish())
+	}
+
+	pub fn ensure_same_browser(state: &str, cookie: Option<&str>) -> Result<(), AppError> {
+		if cookie != Some(state) {
+			return Err(invalid_state_error());
+		}
+
+		Ok(())
+	}
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = oauth2_login_states)]
+pub struct CreateOAuth2LoginState {
+	pub state: String,
+	pub authentication_method_id: Uuid,
+	pub pkce_verifier: String,
+	pub nonce: String,
+	pub expires_at: NaiveDateTime,
+}
@@ -109,6 +109,7 @@ pub async fn create(
 			language_id: form.language_id,
 			translation_id,
 			site_id: params.site_id,
+			parent_id: form.parent_id,
 		},
 		form.fields.clone(),
 	)?;
@@ -283,6 +284,7 @@ pub async fn update(
 			workflow_state_id: form.workflow_state_id.clone(),
 			updated_at: Utc::now().naive_utc(),
 			published,
+			parent_id: form.parent_id,
 		},
 		form.fields.clone(),
 	)?;
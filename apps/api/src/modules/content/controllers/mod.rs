@@ -1,3 +1,4 @@
 pub mod content;
 pub mod content_revisions;
 pub mod public_content;
+pub mod public_pages;
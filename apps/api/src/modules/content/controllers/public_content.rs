@@ -14,6 +14,12 @@ pub struct FindOnePathParams {
 	content_id: String,
 }
 
+#[derive(Deserialize, IntoParams)]
+pub struct FindSingletonPathParams {
+	site_id: Uuid,
+	content_type_slug: String,
+}
+
 #[derive(Deserialize, IntoParams)]
 pub struct FindPathParams {
 	site_id: Uuid,
@@ -119,3 +125,37 @@ pub async fn find(
 	));
 	Ok(HttpResponse::Ok().json(res))
 }
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/singletons",
+	responses(
+		(status = 200, body = PublicContentDTO),
+		(status = 404, body = AppErrorValue, description = "Not Found")
+	),
+	params(FindSingletonPathParams, FindOneQueryParams)
+)]
+#[get("/{content_type_slug}")]
+pub async fn find_singleton(
+	state: web::Data<AppState>,
+	params: web::Path<FindSingletonPathParams>,
+	query: web::Query<FindOneQueryParams>,
+) -> Result<HttpResponse, AppError> {
+	let conn = &mut state.get_conn()?;
+	let (content, revision, fields, languages, translations) = Content::find_singleton_public(
+		conn,
+		params.site_id,
+		params.content_type_slug.clone(),
+		query.populate,
+		&query.lang,
+	)?;
+
+	let res = response::PublicContentDTO::from((
+		content,
+		revision,
+		fields,
+		languages,
+		translations,
+		query.populate.unwrap_or(false),
+	));
+	Ok(HttpResponse::Ok().json(res))
+}
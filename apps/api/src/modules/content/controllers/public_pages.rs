@@ -0,0 +1,113 @@
+use super::super::dto::content::response::PublicContentDTO;
+use super::super::dto::pages::response;
+use super::super::models::_content::content_tree::normalize_path;
+use crate::modules::core::middleware::state::AppState;
+use crate::modules::languages::models::language::Language;
+use crate::modules::redirects::models::redirect::Redirect;
+use crate::{
+	errors::{AppError, AppErrorValue},
+	modules::content::models::content::Content,
+};
+use actix_web::{get, web, HttpResponse};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindPathParams {
+	site_id: Uuid,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolveQueryParams {
+	path: String,
+	lang: String,
+	populate: Option<bool>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct NavigationQueryParams {
+	lang: String,
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/pages",
+	responses(
+		(status = 200, body = ResolvedPageDTO),
+		(status = 404, body = AppErrorValue, description = "Not Found")
+	),
+	params(FindPathParams, ResolveQueryParams)
+)]
+#[get("/resolve")]
+pub async fn resolve(
+	state: web::Data<AppState>,
+	params: web::Path<FindPathParams>,
+	query: web::Query<ResolveQueryParams>,
+) -> Result<HttpResponse, AppError> {
+	let conn = &mut state.get_conn()?;
+	let path = normalize_path(&query.path);
+	let language = Language::find_by_key(conn, &query.lang)?;
+
+	if let Some((content, revision, fields, translations)) =
+		Content::find_by_path_public(conn, params.site_id, &language, &path, query.populate)?
+	{
+		let breadcrumbs = Content::find_ancestors(conn, content.id)?
+			.into_iter()
+			.filter(|ancestor| ancestor.published && !ancestor.deleted)
+			.chain(std::iter::once(content.clone()))
+			.collect::<Vec<Content>>();
+		let page = PublicContentDTO::from((
+			content,
+			revision,
+			fields,
+			language,
+			translations,
+			query.populate.unwrap_or(false),
+		));
+
+		let res = response::ResolvedPageDTO::from((page, breadcrumbs));
+		return Ok(HttpResponse::Ok().json(res));
+	}
+
+	match Redirect::find_match(conn, params.site_id, Some(language.id), &path)? {
+		Some(redirect_match) => {
+			Redirect::register_hit(conn, redirect_match.redirect.id)?;
+			let res = response::ResolvedPageDTO::from(response::PageRedirectDTO {
+				path: redirect_match.target,
+				status_code: redirect_match.redirect.status_code as u16,
+			});
+			Ok(HttpResponse::Ok().json(res))
+		}
+		None => Err(AppError::NotFound(AppErrorValue {
+			message: format!("No page found for {}", path),
+			status: StatusCode::NOT_FOUND.as_u16(),
+			code: "PAGE_NOT_FOUND".to_owned(),
+			..Default::default()
+		})),
+	}
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/pages",
+	responses(
+		(status = 200, body = NavigationDTO),
+		(status = 404, body = AppErrorValue, description = "Not Found")
+	),
+	params(FindPathParams, NavigationQueryParams)
+)]
+#[get("/navigation")]
+pub async fn navigation(
+	state: web::Data<AppState>,
+	params: web::Path<FindPathParams>,
+	query: web::Query<NavigationQueryParams>,
+) -> Result<HttpResponse, AppError> {
+	let conn = &mut state.get_conn()?;
+	let language = Language::find_by_key(conn, &query.lang)?;
+	let pages = Content::find_navigation_public(conn, params.site_id, language.id)?;
+
+	let res = response::NavigationDTO::from(pages);
+	Ok(HttpResponse::Ok().json(res))
+}
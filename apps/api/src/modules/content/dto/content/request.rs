@@ -12,6 +12,7 @@ pub struct CreateContentDTO {
 	pub language_id: Uuid,
 	pub slug: String,
 	pub translation_id: Option<Uuid>,
+	pub parent_id: Option<Uuid>,
 	pub fields: Value,
 }
 
@@ -23,5 +24,13 @@ pub struct UpdateContentDTO {
 	pub content_type_id: Uuid,
 	pub workflow_state_id: Uuid,
 	pub translation_id: Uuid,
+	// Left out keeps the current parent, `null` moves the page to the root
+	#[serde(
+		default,
+		skip_serializing_if = "Option::is_none",
+		with = "::serde_with::rust::double_option"
+	)]
+	#[schema(value_type = Option<Uuid>)]
+	pub parent_id: Option<Option<Uuid>>,
 	pub fields: Value,
 }
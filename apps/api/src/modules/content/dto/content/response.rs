@@ -38,6 +38,8 @@ pub struct ContentWithFieldsDTO {
 	pub language: LanguageDTO,
 	pub current_workflow_state: WorkflowStateDTO,
 	pub revision_id: Uuid,
+	pub parent_id: Option<Uuid>,
+	pub path: Option<String>,
 }
 
 // TODO: dedupe
@@ -220,6 +222,8 @@ impl
 			language: LanguageDTO::from(language),
 			current_workflow_state: WorkflowStateDTO::from(workflow_state),
 			revision_id: revision.id,
+			parent_id: content.parent_id,
+			path: content.path,
 		}
 	}
 }
@@ -229,6 +233,7 @@ impl
 pub struct PublicContentTranslationsDTO {
 	pub id: Uuid,
 	pub slug: String,
+	pub path: Option<String>,
 	pub language: String,
 }
 
@@ -237,6 +242,7 @@ impl From<(Content, Language)> for PublicContentTranslationsDTO {
 		Self {
 			id: content.id,
 			slug: content.slug,
+			path: content.path,
 			language: language.key,
 		}
 	}
@@ -253,6 +259,8 @@ pub struct PublicContentDTO {
 	pub fields: HashMap<String, Option<Value>>,
 	pub language: String,
 	pub translations: Vec<PublicContentTranslationsDTO>,
+	pub parent_id: Option<Uuid>,
+	pub path: Option<String>,
 }
 
 impl
@@ -293,6 +301,8 @@ impl
 				.into_iter()
 				.map(PublicContentTranslationsDTO::from)
 				.collect(),
+			parent_id: content.parent_id,
+			path: content.path,
 		}
 	}
 }
@@ -334,6 +344,8 @@ pub struct ContentDTO {
 	pub language: LanguageDTO,
 	pub content_type: ContentTypeDTO,
 	pub current_workflow_state: WorkflowStateDTO,
+	pub parent_id: Option<Uuid>,
+	pub path: Option<String>,
 }
 
 impl From<(Content, Language, ContentType, WorkflowState)> for ContentDTO {
@@ -359,6 +371,8 @@ impl From<(Content, Language, ContentType, WorkflowState)> for ContentDTO {
 			language: LanguageDTO::from(language),
 			content_type: ContentTypeDTO::from(content_type),
 			current_workflow_state: WorkflowStateDTO::from(workflow_state),
+			parent_id: content.parent_id,
+			path: content.path,
 		}
 	}
 }
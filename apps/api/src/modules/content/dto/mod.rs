@@ -1,2 +1,3 @@
 pub mod content;
+pub mod pages;
 pub mod revisions;
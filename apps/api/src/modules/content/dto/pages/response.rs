@@ -0,0 +1,115 @@
+use crate::modules::content::{dto::content::response::PublicContentDTO, models::content::Content};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, convert::From};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BreadcrumbDTO {
+	pub id: Uuid,
+	pub name: String,
+	pub slug: String,
+	pub path: Option<String>,
+}
+
+impl From<Content> for BreadcrumbDTO {
+	fn from(content: Content) -> Self {
+		Self {
+			id: content.id,
+			name: content.name,
+			slug: content.slug,
+			path: content.path,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PageRedirectDTO {
+	// Empty when the page is gone for good
+	pub path: Option<String>,
+	pub status_code: u16,
+}
+
+// Either the page with its breadcrumbs, or where the path moved to
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedPageDTO {
+	pub page: Option<PublicContentDTO>,
+	pub breadcrumbs: Vec<BreadcrumbDTO>,
+	pub redirect: Option<PageRedirectDTO>,
+}
+
+impl From<(PublicContentDTO, Vec<Content>)> for ResolvedPageDTO {
+	fn from((page, breadcrumbs): (PublicContentDTO, Vec<Content>)) -> Self {
+		Self {
+			page: Some(page),
+			breadcrumbs: breadcrumbs.into_iter().map(BreadcrumbDTO::from).collect(),
+			redirect: None,
+		}
+	}
+}
+
+impl From<PageRedirectDTO> for ResolvedPageDTO {
+	fn from(redirect: PageRedirectDTO) -> Self {
+		Self {
+			page: None,
+			breadcrumbs: vec![],
+			redirect: Some(redirect),
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NavigationItemDTO {
+	pub id: Uuid,
+	pub translation_id: Uuid,
+	pub name: String,
+	pub slug: String,
+	pub path: Option<String>,
+	pub children: Vec<NavigationItemDTO>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NavigationDTO {
+	pub items: Vec<NavigationItemDTO>,
+}
+
+fn build_navigation_items(
+	parent_id: Option<Uuid>,
+	pages_by_parent: &mut HashMap<Option<Uuid>, Vec<Content>>,
+) -> Vec<NavigationItemDTO> {
+	pages_by_parent
+		.remove(&parent_id)
+		.unwrap_or_default()
+		.into_iter()
+		.map(|page| NavigationItemDTO {
+			id: page.id,
+			translation_id: page.translation_id,
+			children: build_navigation_items(Some(page.id), pages_by_parent),
+			name: page.name,
+			slug: page.slug,
+			path: page.path,
+		})
+		.collect()
+}
+
+// Pages below an unpublished page can't be reached through the navigation, so they are left out
+impl From<Vec<Content>> for NavigationDTO {
+	fn from(pages: Vec<Content>) -> Self {
+		let mut pages_by_parent: HashMap<Option<Uuid>, Vec<Content>> = HashMap::new();
+		for page in pages {
+			pages_by_parent
+				.entry(page.parent_id)
+				.or_default()
+				.push(page);
+		}
+
+		Self {
+			items: build_navigation_items(None, &mut pages_by_parent),
+		}
+	}
+}
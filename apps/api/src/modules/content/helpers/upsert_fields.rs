@@ -89,7 +89,23 @@ fn get_field_insert(
 			data_type: populated_cc.content_component.data_type,
 			value: Some(value),
 		}],
-		DataTypeEnum::ARRAY => todo!(),
+		DataTypeEnum::ARRAY => {
+			// A lone value is kept as a list of one, a missing value as an empty list
+			let items = match value {
+				Value::Array(items) => items,
+				Value::Null => vec![],
+				value => vec![value],
+			};
+
+			get_value_inserts(
+				revision_id,
+				parent_id,
+				index_key,
+				sequence_number,
+				Some(populated_cc.content_component.id),
+				Value::Array(items),
+			)
+		}
 		DataTypeEnum::OBJECT => {
 			let uuid = Uuid::new_v4();
 
@@ -198,6 +214,74 @@ fn get_field_insert(
 	}
 }
 
+// Free-form values have no content component to describe them, so the shape of the JSON is stored instead.
+// Arrays keep their order through `sequence_number`, object keys become the field names.
+fn get_value_inserts(
+	revision_id: Uuid,
+	parent_id: Option<Uuid>,
+	name: String,
+	sequence_number: Option<i32>,
+	content_component_id: Option<Uuid>,
+	value: Value,
+) -> Vec<CreateContentField> {
+	let data_type = match &value {
+		Value::Array(_) => DataTypeEnum::ARRAY,
+		Value::Object(_) => DataTypeEnum::OBJECT,
+		Value::Number(_) => DataTypeEnum::NUMBER,
+		Value::Bool(_) => DataTypeEnum::BOOLEAN,
+		Value::String(_) | Value::Null => DataTypeEnum::TEXT,
+	};
+
+	let children: Vec<(String, Option<i32>, Value)> = match value {
+		Value::Array(items) => items
+			.into_iter()
+			.enumerate()
+			.map(|(i, item)| (i.to_string(), Some(i as i32), item))
+			.collect(),
+		Value::Object(entries) => entries
+			.into_iter()
+			.map(|(key, item)| (key, None, item))
+			.collect(),
+		value => {
+			return vec![CreateContentField {
+				id: None,
+				parent_id,
+				source_id: revision_id,
+				name,
+				sequence_number,
+				content_component_id,
+				data_type,
+				value: Some(value),
+			}]
+		}
+	};
+
+	let parent_field = CreateContentField {
+		id: Some(Uuid::new_v4()),
+		parent_id,
+		source_id: revision_id,
+		name,
+		sequence_number,
+		content_component_id,
+		data_type,
+		value: None,
+	};
+	let mut fields = vec![parent_field.clone()];
+
+	for (name, sequence_number, item) in children {
+		fields.append(&mut get_value_inserts(
+			revision_id,
+			parent_field.id,
+			name,
+			sequence_number,
+			None,
+			item,
+		));
+	}
+
+	fields
+}
+
 pub fn get_field_inserts(
 	revision_id: Uuid,
 	translation_id: Uuid,
@@ -277,3 +361,215 @@ pub fn get_field_inserts(
 
 	values_to_insert
 }
+
+#[cfg(test)]
+mod tests {
+	use std::collections::HashMap;
+
+	use chrono::Utc;
+	use proptest::prelude::*;
+	use serde_json::{json, Map, Value};
+	use uuid::Uuid;
+
+	use super::get_field_inserts;
+	use crate::modules::content::dto::content::response::{
+		parse_object_fields, ContentDefaultValuesDTO,
+	};
+	use crate::modules::content::models::content_field::ContentField;
+	use crate::modules::content_components::enums::data_type::DataTypeEnum;
+	use crate::modules::content_components::models::content_component::{
+		ContentComponent, PopulatedContentComponent,
+	};
+	use crate::modules::content_types::models::content_type::PopulatedContentTypeField;
+	use crate::modules::content_types::models::field::{FieldModel, FieldTypeEnum};
+
+	fn array_field(slug: &str, min: i32, max: i32) -> PopulatedContentTypeField {
+		let content_component = ContentComponent {
+			id: Uuid::new_v4(),
+			name: "List".to_owned(),
+			slug: "list".to_owned(),
+			description: None,
+			data_type: DataTypeEnum::ARRAY,
+			hidden: false,
+			internal: false,
+			deleted: false,
+			removeable: true,
+			component_name: "list".to_owned(),
+			created_at: Utc::now().naive_utc(),
+			updated_at: Utc::now().naive_utc(),
+		};
+		let field = FieldModel {
+			id: Uuid::new_v4(),
+			name: slug.to_owned(),
+			slug: slug.to_owned(),
+			description: None,
+			min,
+			max,
+			hidden: false,
+			multi_language: false,
+			field_type: FieldTypeEnum::ContentTypeField,
+			parent_id: Uuid::new_v4(),
+			content_component_id: content_component.id,
+			sequence_number: Some(0),
+			compartment_id: None,
+			validation: None,
+		};
+
+		(
+			field,
+			PopulatedContentComponent {
+				content_component,
+				configuration_fields: vec![],
+				fields: vec![],
+			},
+			HashMap::new(),
+			vec![],
+		)
+	}
+
+	// The rows `upsert_fields` writes, reversed so the order can only come from `sequence_number`
+	fn content_fields(
+		source_id: Uuid,
+		translation_id: Uuid,
+		fields: Vec<PopulatedContentTypeField>,
+		values: Value,
+	) -> Vec<ContentField> {
+		let mut content_fields = get_field_inserts(
+			source_id,
+			translation_id,
+			None,
+			fields,
+			values,
+			&vec![],
+			false,
+		)
+		.into_iter()
+		.map(|field| ContentField {
+			id: field.id.unwrap_or_else(Uuid::new_v4),
+			name: field.name,
+			value: field.value,
+			content_component_id: field.content_component_id,
+			parent_id: field.parent_id,
+			source_id: field.source_id,
+			sequence_number: field.sequence_number,
+			data_type: field.data_type,
+		})
+		.collect::<Vec<ContentField>>();
+		content_fields.reverse();
+
+		content_fields
+	}
+
+	// Writes the values the way `upsert_fields` does and reads them back like the API responses do
+	fn round_trip(fields: Vec<PopulatedContentTypeField>, values: Value) -> Value {
+		let revision_id = Uuid::new_v4();
+		let translation_id = Uuid::new_v4();
+		let content_fields = content_fields(revision_id, translation_id, fields, values);
+
+		let parsed = parse_object_fields(
+			Some(revision_id),
+			translation_id,
+			None,
+			content_fields,
+			false,
+		);
+		serde_json::to_value(parsed).unwrap()
+	}
+
+	// Floats are kept to quarters so their text form parses back to the exact same number
+	fn json_value() -> impl Strategy<Value = Value> {
+		let leaf = prop_oneof![
+			Just(Value::Null),
+			any::<bool>().prop_map(Value::from),
+			any::<i64>().prop_map(Value::from),
+			any::<i32>().prop_map(|number| Value::from(f64::from(number) / 4.0)),
+			".*".prop_map(Value::from),
+		];
+
+		leaf.prop_recursive(4, 64, 8, |inner| {
+			prop_oneof![
+				prop::collection::vec(inner.clone(), 0..8).prop_map(Value::Array),
+				prop::collection::hash_map(".*", inner, 0..8)
+					.prop_map(|entries| Value::Object(entries.into_iter().collect::<Map<_, _>>())),
+			]
+		})
+	}
+
+	proptest! {
+		#[test]
+		fn array_values_round_trip(items in prop::collection::vec(json_value(), 0..8)) {
+			let values = json!({ "items": items });
+			prop_assert_eq!(round_trip(vec![array_field("items", 1, 1)], values.clone()), values);
+		}
+
+		#[test]
+		fn multiple_array_values_round_trip(
+			lists in prop::collection::vec(prop::collection::vec(json_value(), 0..4), 0..4)
+		) {
+			let values = json!({ "lists": lists });
+			prop_assert_eq!(round_trip(vec![array_field("lists", 0, 4)], values.clone()), values);
+		}
+	}
+
+	// `Content::default_values` and `ContentRevision::default_values` load the rows stored under the translation,
+	// the rows of an array share the source of their parent so the nested values come along
+	#[test]
+	fn default_values_keep_arrays() {
+		let translation_id = Uuid::new_v4();
+		let values = json!({ "items": [[1, 2], { "label": "three" }, null] });
+		let content_fields = content_fields(
+			translation_id,
+			translation_id,
+			vec![array_field("items", 1, 1)],
+			values.clone(),
+		);
+
+		let default_values =
+			ContentDefaultValuesDTO::from((None, translation_id, content_fields, false));
+		assert_eq!(serde_json::to_value(default_values.fields).unwrap(), values);
+	}
+
+	// `compare` reads every revision like `find_one` does, the arrays of one revision don't leak into the other
+	#[test]
+	fn compared_revisions_keep_their_own_arrays() {
+		let translation_id = Uuid::new_v4();
+		let first_revision_id = Uuid::new_v4();
+		let second_revision_id = Uuid::new_v4();
+		let first_values = json!({ "items": ["a", ["b", "c"]] });
+		let second_values = json!({ "items": ["c", "b"] });
+		let mut all_fields = content_fields(
+			first_revision_id,
+			translation_id,
+			vec![array_field("items", 1, 1)],
+			first_values.clone(),
+		);
+		all_fields.append(&mut content_fields(
+			second_revision_id,
+			translation_id,
+			vec![array_field("items", 1, 1)],
+			second_values.clone(),
+		));
+
+		for (revision_id, values) in [
+			(first_revision_id, first_values),
+			(second_revision_id, second_values),
+		] {
+			let parsed = parse_object_fields(
+				Some(revision_id),
+				translation_id,
+				None,
+				all_fields.clone(),
+				false,
+			);
+			assert_eq!(serde_json::to_value(parsed).unwrap(), values);
+		}
+	}
+
+	#[test]
+	fn missing_array_value_is_empty() {
+		assert_eq!(
+			round_trip(vec![array_field("items", 1, 1)], json!({})),
+			json!({ "items": [] })
+		);
+	}
+}
@@ -1,14 +1,15 @@
 use diesel::prelude::*;
+use reqwest::StatusCode;
 use serde_json::Value;
 use tracing::instrument;
 use uuid::Uuid;
 
-use crate::errors::AppError;
+use crate::errors::{AppError, AppErrorValue};
 use crate::modules::content::helpers::upsert_fields::upsert_fields;
 use crate::modules::content::models::content::CreateContent;
 use crate::modules::content::models::content_revision::ContentRevision;
 use crate::modules::content::models::content_revision::CreateContentRevision;
-use crate::modules::content_types::models::content_type::ContentType;
+use crate::modules::content_types::models::content_type::{ContentType, ContentTypeKindEnum};
 use crate::modules::languages::models::language::Language;
 use crate::modules::workflows::models::workflow_state::WorkflowState;
 use crate::schema::content;
@@ -34,14 +35,69 @@ impl Content {
 		),
 		AppError,
 	> {
+		let (content_type, fields, _compartments) =
+			ContentType::find_one(conn, site_id, content_item.content_type_id)?;
+		if content_type.kind == ContentTypeKindEnum::FIELD_GROUP {
+			return Err(AppError::BadRequest(AppErrorValue {
+				message: "Field groups can only be included in other content types".to_owned(),
+				status: StatusCode::BAD_REQUEST.as_u16(),
+				code: "FIELD_GROUP_NOT_CREATABLE".to_owned(),
+				..Default::default()
+			}));
+		}
+
+		if content_type.kind == ContentTypeKindEnum::SINGLE {
+			let existing_items = content::table
+				.filter(content::site_id.eq(site_id))
+				.filter(content::content_type_id.eq(content_type.id))
+				.filter(content::language_id.eq(content_item.language_id))
+				.filter(content::deleted.eq(false))
+				.count()
+				.get_result::<i64>(conn)?;
+			if existing_items > 0 {
+				return Err(AppError::UnprocessableEntity(AppErrorValue {
+					message: format!(
+						"{} already has a content item in this language",
+						content_type.slug
+					),
+					status: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+					code: "SINGLE_CONTENT_EXISTS".to_owned(),
+					..Default::default()
+				}));
+			}
+		}
+
+		if let Some(parent_id) = content_item.parent_id {
+			Self::ensure_valid_parent(
+				conn,
+				site_id,
+				&content_type,
+				content_item.language_id,
+				None,
+				parent_id,
+			)?;
+		}
+
+		if content_type.kind == ContentTypeKindEnum::PAGE {
+			Self::ensure_path_available(
+				conn,
+				site_id,
+				content_item.language_id,
+				None,
+				content_item.parent_id,
+				content_item.slug,
+			)?;
+		}
+
+		// The flag backs the unique index that keeps concurrent requests from creating a second item
 		let created_content_item = diesel::insert_into(content::table)
-			.values(&content_item)
+			.values((
+				&content_item,
+				content::singleton.eq(content_type.kind == ContentTypeKindEnum::SINGLE),
+			))
 			.returning(Content::as_returning())
 			.get_result(conn)?;
 
-		let (_content_type, fields, _compartments) =
-			ContentType::find_one(conn, site_id, created_content_item.content_type_id)?;
-
 		let revision = ContentRevision::create(
 			conn,
 			site_id,
@@ -66,6 +122,10 @@ impl Content {
 			&vec![],
 		)?;
 
+		if content_type.kind == ContentTypeKindEnum::PAGE {
+			Self::update_paths(conn, created_content_item.id)?;
+		}
+
 		let content_item = Self::find_one(conn, site_id, created_content_item.id)?;
 
 		Ok(content_item)
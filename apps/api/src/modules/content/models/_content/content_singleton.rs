@@ -0,0 +1,108 @@
+use diesel::prelude::*;
+use serde_json::json;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::modules::content::models::content::CreateContent;
+use crate::modules::content_types::models::content_type::ContentTypeKindEnum;
+use crate::modules::sites::models::site::Site;
+use crate::schema::{content, content_types, sites_content_types, workflows};
+
+use super::super::content::Content;
+
+impl Content {
+	// Creates the item of a `SINGLE` content type for every site language that doesn't have one yet,
+	// all languages share one translation so the items show up as translations of each other
+	#[instrument(skip(conn))]
+	pub fn create_singletons(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+		site_id: Uuid,
+		content_type_id: Uuid,
+	) -> Result<Vec<Self>, AppError> {
+		let (kind, name, slug, default_workflow_state_id) = content_types::table
+			.inner_join(workflows::table.on(workflows::id.eq(content_types::workflow_id)))
+			.filter(content_types::id.eq(content_type_id))
+			.select((
+				content_types::kind,
+				content_types::name,
+				content_types::slug,
+				workflows::default_workflow_state_id,
+			))
+			.first::<(ContentTypeKindEnum, String, String, Uuid)>(conn)?;
+		if kind != ContentTypeKindEnum::SINGLE {
+			return Ok(vec![]);
+		}
+
+		let existing_items = content::table
+			.filter(content::site_id.eq(site_id))
+			.filter(content::content_type_id.eq(content_type_id))
+			.filter(content::deleted.eq(false))
+			.select((content::language_id, content::translation_id))
+			.load::<(Uuid, Uuid)>(conn)?;
+		let translation_id = existing_items
+			.first()
+			.map(|(_, translation_id)| *translation_id)
+			.unwrap_or_else(Uuid::new_v4);
+
+		// The slug of the content type is the obvious choice, unless regular content already took it
+		let slug = if Self::slug_in_use(conn, site_id, Some(translation_id), &slug)? {
+			format!("{}-{}", slug, &translation_id.to_string()[..8])
+		} else {
+			slug
+		};
+
+		let (_, languages) = Site::find_one(conn, site_id)?;
+		languages
+			.into_iter()
+			.filter(|language| {
+				!existing_items
+					.iter()
+					.any(|(language_id, _)| *language_id == language.id)
+			})
+			.map(|language| {
+				let (content_item, _, _, _, _) = Self::create(
+					conn,
+					user_id,
+					site_id,
+					CreateContent {
+						name: &name,
+						slug: &slug,
+						workflow_state_id: default_workflow_state_id,
+						translation_id,
+						content_type_id,
+						language_id: language.id,
+						site_id,
+						parent_id: None,
+					},
+					json!({}),
+				)?;
+
+				Ok(content_item)
+			})
+			.collect()
+	}
+
+	// Languages that are enabled later on get the items of every `SINGLE` content type of the site
+	#[instrument(skip(conn))]
+	pub fn create_site_singletons(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+		site_id: Uuid,
+	) -> Result<(), AppError> {
+		let content_type_ids = sites_content_types::table
+			.inner_join(content_types::table)
+			.filter(sites_content_types::site_id.eq(site_id))
+			.filter(content_types::kind.eq(ContentTypeKindEnum::SINGLE))
+			.filter(content_types::deleted.eq(false))
+			.select(content_types::id)
+			.load::<Uuid>(conn)?;
+
+		for content_type_id in content_type_ids {
+			Self::create_singletons(conn, user_id, site_id, content_type_id)?;
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,328 @@
+use diesel::prelude::*;
+use diesel::sql_query;
+use diesel::sql_types;
+use reqwest::StatusCode;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::errors::{AppError, AppErrorValue};
+use crate::modules::content_types::models::content_type::{ContentType, ContentTypeKindEnum};
+use crate::modules::languages::models::language::Language;
+use crate::modules::redirects::models::redirect::Redirect;
+use crate::schema::{content, content_types};
+
+use super::super::content::Content;
+
+// Pages nest at most this many levels deep, which also keeps the recursive queries from following a loop forever
+const MAX_DEPTH: i32 = 32;
+
+#[derive(QueryableByName, Debug)]
+struct ContentPath {
+	#[diesel(sql_type = sql_types::Uuid)]
+	id: Uuid,
+	#[diesel(sql_type = sql_types::Uuid)]
+	site_id: Uuid,
+	#[diesel(sql_type = sql_types::Uuid)]
+	language_id: Uuid,
+	#[diesel(sql_type = sql_types::Bool)]
+	published: bool,
+	#[diesel(sql_type = sql_types::Nullable<sql_types::Text>)]
+	old_path: Option<String>,
+	#[diesel(sql_type = sql_types::Text)]
+	new_path: String,
+}
+
+#[derive(QueryableByName, Debug)]
+struct SubtreeHeight {
+	#[diesel(sql_type = sql_types::Integer)]
+	height: i32,
+}
+
+// `/about//team/` and `about/team` both end up as `/about/team`
+pub fn normalize_path(path: &str) -> String {
+	let segments = path
+		.split('/')
+		.filter(|segment| !segment.is_empty())
+		.collect::<Vec<&str>>();
+
+	format!("/{}", segments.join("/"))
+}
+
+impl Content {
+	// Parents are pages of the same site and language, and a page can't end up below itself
+	#[instrument(skip(conn))]
+	pub fn ensure_valid_parent(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		content_type: &ContentType,
+		language_id: Uuid,
+		content_id: Option<Uuid>,
+		parent_id: Uuid,
+	) -> Result<(), AppError> {
+		if content_type.kind != ContentTypeKindEnum::PAGE {
+			return Err(AppError::BadRequest(AppErrorValue {
+				message: "Only pages can have a parent".to_owned(),
+				status: StatusCode::BAD_REQUEST.as_u16(),
+				code: "PARENT_NOT_SUPPORTED".to_owned(),
+				..Default::default()
+			}));
+		}
+
+		let parent = content::table
+			.filter(content::site_id.eq(site_id))
+			.filter(content::id.eq(parent_id))
+			.filter(content::deleted.eq(false))
+			.inner_join(content_types::table.on(content_types::id.eq(content::content_type_id)))
+			.select((Content::as_select(), content_types::kind))
+			.first::<(Self, ContentTypeKindEnum)>(conn)
+			.optional()?;
+		let Some((parent, parent_kind)) = parent else {
+			return Err(AppError::NotFound(AppErrorValue {
+				message: format!("Parent {} could not be found", parent_id),
+				status: StatusCode::NOT_FOUND.as_u16(),
+				code: "PARENT_NOT_FOUND".to_owned(),
+				..Default::default()
+			}));
+		};
+
+		if parent_kind != ContentTypeKindEnum::PAGE || parent.language_id != language_id {
+			return Err(AppError::BadRequest(AppErrorValue {
+				message: "The parent has to be a page in the same language".to_owned(),
+				status: StatusCode::BAD_REQUEST.as_u16(),
+				code: "INVALID_PARENT".to_owned(),
+				..Default::default()
+			}));
+		}
+
+		let ancestors = Self::find_ancestors(conn, parent.id)?;
+		if let Some(content_id) = content_id {
+			if parent.id == content_id || ancestors.iter().any(|ancestor| ancestor.id == content_id)
+			{
+				return Err(AppError::BadRequest(AppErrorValue {
+					message: "A page can't be moved below itself".to_owned(),
+					status: StatusCode::BAD_REQUEST.as_u16(),
+					code: "PARENT_CYCLE".to_owned(),
+					..Default::default()
+				}));
+			}
+		}
+
+		// The pages below the page move along, the deepest of them has to stay within the limit as well
+		let subtree_height = match content_id {
+			Some(content_id) => Self::find_subtree_height(conn, content_id)?,
+			None => 0,
+		};
+		let levels = i32::try_from(ancestors.len())? + 2 + subtree_height;
+		if levels > MAX_DEPTH {
+			return Err(AppError::BadRequest(AppErrorValue {
+				message: format!("Pages can be nested at most {} levels deep", MAX_DEPTH),
+				status: StatusCode::BAD_REQUEST.as_u16(),
+				code: "PARENT_TOO_DEEP".to_owned(),
+				..Default::default()
+			}));
+		}
+
+		Ok(())
+	}
+
+	// Levels below the page, 0 when it has no children
+	#[instrument(skip(conn))]
+	fn find_subtree_height(conn: &mut PgConnection, content_id: Uuid) -> Result<i32, AppError> {
+		let subtree_height = sql_query(
+			"
+			WITH RECURSIVE tree AS (
+				SELECT c.id, 0 AS depth
+				FROM content c
+				WHERE c.id = $1
+				UNION ALL
+				SELECT child.id, tree.depth + 1
+				FROM tree
+				INNER JOIN content child ON child.parent_id = tree.id
+				WHERE tree.depth < $2
+			)
+			SELECT COALESCE(MAX(depth), 0) AS height
+			FROM tree",
+		)
+		.bind::<sql_types::Uuid, _>(content_id)
+		.bind::<sql_types::Integer, _>(MAX_DEPTH)
+		.get_result::<SubtreeHeight>(conn)?;
+
+		Ok(subtree_height.height)
+	}
+
+	// Siblings can't share a slug, so no two pages of a language end up with the same path
+	#[instrument(skip(conn))]
+	pub fn ensure_path_available(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		language_id: Uuid,
+		content_id: Option<Uuid>,
+		parent_id: Option<Uuid>,
+		slug: &str,
+	) -> Result<(), AppError> {
+		let parent_path = match parent_id {
+			Some(parent_id) => content::table
+				.find(parent_id)
+				.select(content::path)
+				.first::<Option<String>>(conn)?,
+			None => None,
+		};
+		let path = format!("{}/{}", parent_path.unwrap_or_default(), slug);
+
+		let mut query = content::table
+			.filter(content::site_id.eq(site_id))
+			.filter(content::language_id.eq(language_id))
+			.filter(content::path.eq(&path))
+			.filter(content::deleted.eq(false))
+			.into_boxed();
+		if let Some(content_id) = content_id {
+			query = query.filter(content::id.ne(content_id));
+		}
+
+		if query.count().get_result::<i64>(conn)? > 0 {
+			return Err(AppError::UnprocessableEntity(AppErrorValue {
+				message: format!("A page with path {path} already exists"),
+				status: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+				code: "PATH_IN_USE".to_owned(),
+				..Default::default()
+			}));
+		}
+
+		Ok(())
+	}
+
+	// Root first, the item itself is left out
+	#[instrument(skip(conn))]
+	pub fn find_ancestors(
+		conn: &mut PgConnection,
+		content_id: Uuid,
+	) -> Result<Vec<Self>, AppError> {
+		let ancestors = sql_query(
+			"
+			WITH RECURSIVE ancestors AS (
+				SELECT p.*, 0 AS depth
+				FROM content c
+				INNER JOIN content p ON p.id = c.parent_id
+				WHERE c.id = $1
+				UNION ALL
+				SELECT p.*, a.depth + 1
+				FROM ancestors a
+				INNER JOIN content p ON p.id = a.parent_id
+				WHERE a.depth < $2
+			)
+			SELECT
+				id, name, slug, workflow_state_id, translation_id, language_id, site_id,
+				content_type_id, published, deleted, created_at, updated_at, parent_id, path,
+				singleton
+			FROM ancestors
+			ORDER BY depth DESC",
+		)
+		.bind::<sql_types::Uuid, _>(content_id)
+		.bind::<sql_types::Integer, _>(MAX_DEPTH)
+		.load::<Self>(conn)?;
+
+		Ok(ancestors)
+	}
+
+	// Recomputes the path of a page and everything below it. Published pages that move keep their
+	// old path as a redirect, a page that takes over a path removes the redirect that was there.
+	#[instrument(skip(conn))]
+	pub fn update_paths(conn: &mut PgConnection, content_id: Uuid) -> Result<(), AppError> {
+		let paths = sql_query(
+			"
+			WITH RECURSIVE tree AS (
+				SELECT c.id, COALESCE(p.path, '') || '/' || c.slug AS path, 0 AS depth
+				FROM content c
+				LEFT JOIN content p ON p.id = c.parent_id
+				WHERE c.id = $1
+				UNION ALL
+				SELECT child.id, tree.path || '/' || child.slug, tree.depth + 1
+				FROM tree
+				INNER JOIN content child ON child.parent_id = tree.id
+				WHERE tree.depth < $2
+			)
+			SELECT
+				c.id, c.site_id, c.language_id, c.published,
+				c.path AS old_path, tree.path AS new_path
+			FROM tree
+			INNER JOIN content c ON c.id = tree.id",
+		)
+		.bind::<sql_types::Uuid, _>(content_id)
+		.bind::<sql_types::Integer, _>(MAX_DEPTH)
+		.load::<ContentPath>(conn)?;
+
+		for path in paths {
+			if path.old_path.as_deref() == Some(path.new_path.as_str()) {
+				continue;
+			}
+
+			diesel::update(content::table.find(path.id))
+				.set(content::path.eq(&path.new_path))
+				.execute(conn)?;
+
+			Redirect::remove_by_path(conn, path.site_id, path.language_id, &path.new_path)?;
+			if let (true, Some(old_path)) = (path.published, &path.old_path) {
+				Redirect::upsert_for_content(
+					conn,
+					path.site_id,
+					path.language_id,
+					old_path,
+					path.id,
+				)?;
+			}
+		}
+
+		Ok(())
+	}
+
+	#[instrument(skip(conn))]
+	pub fn find_navigation_public(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		language_id: Uuid,
+	) -> Result<Vec<Self>, AppError> {
+		let pages = content::table
+			.filter(content::site_id.eq(site_id))
+			.filter(content::language_id.eq(language_id))
+			.filter(content::published.eq(true))
+			.filter(content::deleted.eq(false))
+			.filter(content::path.is_not_null())
+			.order(content::name.asc())
+			.select(Content::as_select())
+			.load::<Self>(conn)?;
+
+		Ok(pages)
+	}
+
+	// Every language at once, each page comes with the translations it links to as alternates
+	#[instrument(skip(conn))]
+	pub fn find_sitemap_public(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		limit: i64,
+	) -> Result<Vec<(Self, Vec<(Self, Language)>)>, AppError> {
+		let pages = content::table
+			.filter(content::site_id.eq(site_id))
+			.filter(content::published.eq(true))
+			.filter(content::deleted.eq(false))
+			.filter(content::path.is_not_null())
+			.order(content::path.asc())
+			.limit(limit)
+			.select(Content::as_select())
+			.load::<Self>(conn)?;
+		let translations = Self::find_translations_by_translation_id(conn, &pages)?;
+
+		let pages = pages
+			.into_iter()
+			.map(|page| {
+				let page_translations = translations
+					.get(&page.translation_id)
+					.cloned()
+					.unwrap_or_default();
+				(page, page_translations)
+			})
+			.collect();
+
+		Ok(pages)
+	}
+}
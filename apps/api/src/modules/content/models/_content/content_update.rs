@@ -6,7 +6,7 @@ use uuid::Uuid;
 use crate::errors::AppError;
 use crate::modules::content::helpers::upsert_fields::upsert_fields;
 use crate::modules::content::models::content_revision::{ContentRevision, CreateContentRevision};
-use crate::modules::content_types::models::content_type::ContentType;
+use crate::modules::content_types::models::content_type::{ContentType, ContentTypeKindEnum};
 use crate::modules::languages::models::language::Language;
 use crate::modules::workflows::models::workflow_state::WorkflowState;
 use crate::schema::content;
@@ -34,14 +34,37 @@ impl Content {
 		),
 		AppError,
 	> {
+		let (content_type, fields, _compartments) =
+			ContentType::find_one(conn, site_id, content_type_id)?;
+		let content_item = content::table.find(content_id).first::<Self>(conn)?;
+		if let Some(Some(parent_id)) = changeset.parent_id {
+			Self::ensure_valid_parent(
+				conn,
+				site_id,
+				&content_type,
+				content_item.language_id,
+				Some(content_id),
+				parent_id,
+			)?;
+		}
+
+		if content_type.kind == ContentTypeKindEnum::PAGE {
+			Self::ensure_path_available(
+				conn,
+				site_id,
+				content_item.language_id,
+				Some(content_id),
+				changeset.parent_id.unwrap_or(content_item.parent_id),
+				changeset.slug.as_deref().unwrap_or(&content_item.slug),
+			)?;
+		}
+
 		let target = content::table.find(content_id);
 		let _updated_content_item = diesel::update(target)
 			.set(&changeset)
 			.returning(Content::as_returning())
 			.get_result::<Self>(conn)?;
 
-		let (_content_type, fields, _compartments) =
-			ContentType::find_one(conn, site_id, content_type_id)?;
 		// TODO: check this
 		// let content_fields = content_fields::table
 		// 	.filter(content_fields::source_id.eq_any(vec![content_id, updated_content_item.translation_id]))
@@ -72,6 +95,10 @@ impl Content {
 			&vec![],
 		)?;
 
+		if content_type.kind == ContentTypeKindEnum::PAGE {
+			Self::update_paths(conn, content_id)?;
+		}
+
 		let content_item = Self::find_one(conn, site_id, content_id)?;
 
 		Ok(content_item)
@@ -1,2 +1,4 @@
 pub mod content_create;
+pub mod content_singleton;
+pub mod content_tree;
 pub mod content_update;
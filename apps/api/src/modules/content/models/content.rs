@@ -16,7 +16,7 @@ use crate::schema::{content, content_fields, content_types, languages, workflow_
 use super::content_field::ContentField;
 use super::content_revision::ContentRevision;
 
-#[derive(Identifiable, Selectable, Queryable, Debug, Associations, Clone)]
+#[derive(Identifiable, Selectable, Queryable, QueryableByName, Debug, Associations, Clone)]
 #[diesel(table_name = content)]
 #[diesel(belongs_to(ContentType))]
 #[diesel(primary_key(id))]
@@ -33,6 +33,11 @@ pub struct Content {
 	pub deleted: bool,
 	pub created_at: NaiveDateTime,
 	pub updated_at: NaiveDateTime,
+	pub parent_id: Option<Uuid>,
+	// Only pages have a path, it's kept in sync with the slugs of the page and its parents
+	pub path: Option<String>,
+	// Set on the items of `SINGLE` content types, there is one per site and language
+	pub singleton: bool,
 }
 
 impl Content {
@@ -80,6 +85,48 @@ impl Content {
 		Ok((content_item, revision, fields, language, workflow_state))
 	}
 
+	// Published items that share the translation id, the item itself included
+	#[instrument(skip(conn))]
+	pub fn find_translations(
+		conn: &mut PgConnection,
+		content_item: &Self,
+	) -> Result<Vec<(Self, Language)>, AppError> {
+		let translations = content::table
+			.filter(content::translation_id.eq(content_item.translation_id))
+			.filter(content::published.eq(true))
+			.inner_join(languages::table.on(languages::id.eq(content::language_id)))
+			.get_results::<(Self, Language)>(conn)?;
+
+		Ok(translations)
+	}
+
+	// `find_translations` for many items at once, keyed by translation id
+	#[instrument(skip(conn, content_items))]
+	pub fn find_translations_by_translation_id(
+		conn: &mut PgConnection,
+		content_items: &[Self],
+	) -> Result<HashMap<Uuid, Vec<(Self, Language)>>, AppError> {
+		let translation_ids = content_items
+			.iter()
+			.map(|content_item| content_item.translation_id)
+			.collect::<Vec<Uuid>>();
+		let translations = content::table
+			.filter(content::translation_id.eq_any(translation_ids))
+			.filter(content::published.eq(true))
+			.inner_join(languages::table.on(languages::id.eq(content::language_id)))
+			.get_results::<(Self, Language)>(conn)?;
+
+		let mut grouped_translations: HashMap<Uuid, Vec<(Self, Language)>> = HashMap::new();
+		for (translation, language) in translations {
+			grouped_translations
+				.entry(translation.translation_id)
+				.or_default()
+				.push((translation, language));
+		}
+
+		Ok(grouped_translations)
+	}
+
 	fn find_field_content(
 		conn: &mut PgConnection,
 		site_id: Uuid,
@@ -93,11 +140,7 @@ impl Content {
 			.order(content_revisions::created_at.desc())
 			.first::<ContentRevision>(conn)?;
 
-		let translations = content::table
-			.filter(content::translation_id.eq(content_item.translation_id))
-			.filter(content::published.eq(true))
-			.inner_join(languages::table.on(languages::id.eq(content::language_id)))
-			.get_results::<(Self, Language)>(conn)?;
+		let translations = Self::find_translations(conn, content_item)?;
 
 		let fields = match populate {
 			Some(true) => {
@@ -200,6 +243,77 @@ impl Content {
 		Ok((content_item, revision, fields, language, translations))
 	}
 
+	// Content types of kind `SINGLE` only have one item per language, so the slug of the content type is enough
+	#[instrument(skip(conn))]
+	pub fn find_singleton_public<'a>(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		content_type_slug: String,
+		populate: Option<bool>,
+		lang: &'a str,
+	) -> Result<
+		(
+			Self,
+			ContentRevision,
+			Vec<ContentField>,
+			Language,
+			Vec<(Self, Language)>,
+		),
+		AppError,
+	> {
+		let (content_item, language) = content::table
+			.filter(content::published.eq(true))
+			.filter(content::deleted.eq(false))
+			.filter(content::site_id.eq(site_id))
+			.inner_join(content_types::table.on(content_types::id.eq(content::content_type_id)))
+			.filter(content_types::slug.eq(content_type_slug))
+			.filter(content_types::kind.eq(ContentTypeKindEnum::SINGLE))
+			.filter(content_types::deleted.eq(false))
+			.inner_join(languages::table.on(languages::id.eq(content::language_id)))
+			.filter(languages::key.eq(lang))
+			.select((Content::as_select(), Language::as_select()))
+			.first::<(Self, Language)>(conn)?;
+		let (revision, fields, translations) =
+			Self::find_field_content(conn, site_id, &content_item, &populate)?;
+
+		Ok((content_item, revision, fields, language, translations))
+	}
+
+	#[instrument(skip(conn))]
+	pub fn find_by_path_public(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		language: &Language,
+		path: &str,
+		populate: Option<bool>,
+	) -> Result<
+		Option<(
+			Self,
+			ContentRevision,
+			Vec<ContentField>,
+			Vec<(Self, Language)>,
+		)>,
+		AppError,
+	> {
+		let content_item = content::table
+			.filter(content::published.eq(true))
+			.filter(content::deleted.eq(false))
+			.filter(content::site_id.eq(site_id))
+			.filter(content::language_id.eq(language.id))
+			.filter(content::path.eq(path))
+			.select(Content::as_select())
+			.first::<Self>(conn)
+			.optional()?;
+		let Some(content_item) = content_item else {
+			return Ok(None);
+		};
+
+		let (revision, fields, translations) =
+			Self::find_field_content(conn, site_id, &content_item, &populate)?;
+
+		Ok(Some((content_item, revision, fields, translations)))
+	}
+
 	#[instrument(skip(conn))]
 	pub fn find_public<'a>(
 		conn: &mut PgConnection,
@@ -284,6 +398,90 @@ impl Content {
 		Ok((mapped_content, total_elements))
 	}
 
+	// Newest items first, revisions, fields and translations are loaded for every item at once.
+	// Items without a published revision are left out.
+	#[instrument(skip(conn))]
+	pub fn find_feed_public(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		language_id: Uuid,
+		content_type_ids: &[Uuid],
+		limit: i64,
+	) -> Result<
+		Vec<(
+			Self,
+			ContentRevision,
+			Vec<ContentField>,
+			Vec<(Self, Language)>,
+		)>,
+		AppError,
+	> {
+		let content_items = content::table
+			.filter(content::published.eq(true))
+			.filter(content::deleted.eq(false))
+			.filter(content::site_id.eq(site_id))
+			.filter(content::language_id.eq(language_id))
+			.filter(content::content_type_id.eq_any(content_type_ids))
+			.order(content::created_at.desc())
+			.limit(limit)
+			.select(Content::as_select())
+			.load::<Self>(conn)?;
+
+		let content_ids = content_items
+			.iter()
+			.map(|content_item| content_item.id)
+			.collect::<Vec<Uuid>>();
+		let mut revisions = content_revisions::table
+			.filter(content_revisions::site_id.eq(site_id))
+			.filter(content_revisions::content_id.eq_any(content_ids))
+			.filter(content_revisions::published.eq(true))
+			.distinct_on(content_revisions::content_id)
+			.order((
+				content_revisions::content_id,
+				content_revisions::created_at.desc(),
+			))
+			.select(ContentRevision::as_select())
+			.load::<ContentRevision>(conn)?
+			.into_iter()
+			.map(|revision| (revision.content_id, revision))
+			.collect::<HashMap<Uuid, ContentRevision>>();
+
+		let source_ids = revisions
+			.values()
+			.flat_map(|revision| [revision.id, revision.revision_translation_id])
+			.collect::<Vec<Uuid>>();
+		let mut fields: HashMap<Uuid, Vec<ContentField>> = HashMap::new();
+		for field in content_fields::table
+			.filter(content_fields::source_id.eq_any(source_ids))
+			.select(ContentField::as_select())
+			.load::<ContentField>(conn)?
+		{
+			fields.entry(field.source_id).or_default().push(field);
+		}
+
+		let translations = Self::find_translations_by_translation_id(conn, &content_items)?;
+
+		let feed_content = content_items
+			.into_iter()
+			.filter_map(|content_item| {
+				let revision = revisions.remove(&content_item.id)?;
+				// Fields are stored on the revision and on its revision translation
+				let revision_fields = [revision.id, revision.revision_translation_id]
+					.iter()
+					.flat_map(|source_id| fields.get(source_id).cloned().unwrap_or_default())
+					.collect();
+				let item_translations = translations
+					.get(&content_item.translation_id)
+					.cloned()
+					.unwrap_or_default();
+
+				Some((content_item, revision, revision_fields, item_translations))
+			})
+			.collect();
+
+		Ok(feed_content)
+	}
+
 	#[instrument(skip(conn))]
 	pub fn default_values(
 		conn: &mut PgConnection,
@@ -448,6 +646,7 @@ pub struct CreateContent<'a> {
 	pub content_type_id: Uuid,
 	pub language_id: Uuid,
 	pub site_id: Uuid,
+	pub parent_id: Option<Uuid>,
 }
 
 #[derive(AsChangeset, Debug, Deserialize, Clone)]
@@ -458,4 +657,5 @@ pub struct UpdateContent {
 	pub published: Option<bool>,
 	pub workflow_state_id: Uuid,
 	pub updated_at: NaiveDateTime,
+	pub parent_id: Option<Option<Uuid>>,
 }
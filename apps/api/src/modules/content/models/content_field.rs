@@ -1,8 +1,11 @@
 use diesel::prelude::*;
+use diesel::sql_query;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tracing::instrument;
 use uuid::Uuid;
 
+use crate::errors::AppError;
 use crate::{modules::content_components::enums::data_type::DataTypeEnum, schema::content_fields};
 
 #[derive(
@@ -21,6 +24,37 @@ pub struct ContentField {
 	pub data_type: DataTypeEnum,
 }
 
+impl ContentField {
+	// Media fields store a `{ storageRepositoryId, path }` object, paths stay the same when a tree is copied
+	#[instrument(skip(conn))]
+	pub fn rewrite_storage_repository_references(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		source_storage_repository_id: Uuid,
+		target_storage_repository_id: Uuid,
+	) -> Result<usize, AppError> {
+		let updated_rows = sql_query(
+			"
+			UPDATE content_fields
+			SET value = jsonb_set(value, '{storageRepositoryId}', to_jsonb($1::text))
+			WHERE
+				jsonb_typeof(value) = 'object'
+				AND value->>'storageRepositoryId' = $2
+				AND source_id IN (
+					SELECT id FROM content_revisions WHERE site_id = $3
+					UNION
+					SELECT revision_translation_id FROM content_revisions WHERE site_id = $3
+				)",
+		)
+		.bind::<diesel::sql_types::Text, _>(target_storage_repository_id.to_string())
+		.bind::<diesel::sql_types::Text, _>(source_storage_repository_id.to_string())
+		.bind::<diesel::sql_types::Uuid, _>(site_id)
+		.execute(conn)?;
+
+		Ok(updated_rows)
+	}
+}
+
 #[derive(Insertable, Debug, Deserialize, Clone)]
 #[diesel(table_name = content_fields)]
 pub struct CreateContentField {
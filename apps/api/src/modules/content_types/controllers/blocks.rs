@@ -5,6 +5,7 @@ use crate::modules::content_types::models::field::{FieldTypeEnum, UpdateField};
 use crate::modules::content_types::models::field_config::FieldConfig;
 use crate::modules::core::middleware::state::AppState;
 use crate::modules::core::models::hal::HALPage;
+use crate::modules::labels::helpers::localize::{get_language_keys, localize};
 use crate::utils::api::ApiResponse;
 use crate::{errors::AppError, modules::content_types::models::field::FieldModel};
 use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse};
@@ -100,8 +101,13 @@ pub async fn find_all(
 	let page = query.page.unwrap_or(1);
 	let pagesize = query.pagesize.unwrap_or(20);
 
-	let (fields, total_elements) =
+	let (mut fields, total_elements) =
 		FieldModel::find(conn, params.site_id, params.field_id, page, pagesize)?;
+	localize(
+		conn,
+		&get_language_keys(&req),
+		fields.iter_mut().map(|(field, _, _)| field),
+	)?;
 
 	let res = response::BlockFieldsDTO::from((
 		fields,
@@ -143,7 +149,8 @@ pub async fn find_one(
 		"sites::content-types:update",
 	)?;
 	let conn = &mut state.get_conn()?;
-	let field = FieldModel::find_one(conn, params.site_id, params.block_field_id)?;
+	let mut field = FieldModel::find_one(conn, params.site_id, params.block_field_id)?;
+	localize(conn, &get_language_keys(&req), [&mut field.0])?;
 
 	let res = FieldWithContentComponentDTO::from(field);
 	Ok(HttpResponse::Ok().json(res))
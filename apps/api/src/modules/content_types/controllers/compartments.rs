@@ -4,6 +4,7 @@ use crate::modules::content_types::dto::compartments::response::CompartmentDTO;
 use crate::modules::content_types::models::compartment::UpdateCompartment;
 use crate::modules::core::middleware::state::AppState;
 use crate::modules::core::models::hal::HALPage;
+use crate::modules::labels::helpers::localize::{get_language_keys, localize};
 use crate::utils::api::ApiResponse;
 use crate::{errors::AppError, modules::content_types::models::compartment::CompartmentModel};
 use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse};
@@ -91,8 +92,9 @@ pub async fn find_all(
 	let page = query.page.unwrap_or(1);
 	let pagesize = query.pagesize.unwrap_or(20);
 
-	let (compartments, total_elements) =
+	let (mut compartments, total_elements) =
 		CompartmentModel::find(conn, params.site_id, params.content_type_id, page, pagesize)?;
+	localize(conn, &get_language_keys(&req), compartments.iter_mut())?;
 
 	let res = response::CompartmentsDTO::from((
 		compartments,
@@ -133,7 +135,8 @@ pub async fn find_one(
 		"sites::content-types:update",
 	)?;
 	let conn = &mut state.get_conn()?;
-	let compartment = CompartmentModel::find_one(conn, params.site_id, params.compartment_id)?;
+	let mut compartment = CompartmentModel::find_one(conn, params.site_id, params.compartment_id)?;
+	localize(conn, &get_language_keys(&req), [&mut compartment])?;
 
 	let res = CompartmentDTO::from(compartment);
 	Ok(HttpResponse::Ok().json(res))
@@ -0,0 +1,144 @@
+use super::super::dto::content_model::response::ContentModelPlanDTO;
+use super::super::services::content_model::{
+	apply_content_model, export_content_model, parse_content_model, plan_content_model,
+	render_content_model,
+};
+use crate::modules::auth::helpers::permissions::ensure_permission;
+use crate::modules::core::middleware::state::AppState;
+use crate::utils::api::ApiResponse;
+use actix_web::http::header;
+use actix_web::{get, post, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindPathParams {
+	site_id: Uuid,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindOneQueryParams {
+	format: Option<String>,
+}
+
+// Documents are JSON unless the request says otherwise
+fn is_yaml_request(req: &HttpRequest) -> bool {
+	req.headers()
+		.get(header::CONTENT_TYPE)
+		.and_then(|value| value.to_str().ok())
+		.map(|value| value.contains("yaml"))
+		.unwrap_or(false)
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/content-model",
+	responses(
+		(status = 200, body = ContentModelDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindPathParams, FindOneQueryParams)
+)]
+#[get("")]
+pub async fn find_one(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindPathParams>,
+	query: web::Query<FindOneQueryParams>,
+) -> ApiResponse {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:content-types:*"),
+		"sites::content-types:read",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let document = export_content_model(conn, params.site_id)?;
+
+	if query.format.as_deref() == Some("yaml") {
+		return Ok(HttpResponse::Ok()
+			.content_type("application/yaml")
+			.body(render_content_model(&document)?));
+	}
+
+	Ok(HttpResponse::Ok().json(document))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/content-model",
+    request_body(content = ContentModelDTO, description = "JSON, or YAML when sent as application/yaml"),
+	responses(
+		(status = 200, body = ContentModelPlanDTO),
+		(status = 400, body = AppErrorValue, description = "The content model is invalid"),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindPathParams)
+)]
+#[post("/plan")]
+pub async fn plan(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindPathParams>,
+	body: web::Bytes,
+) -> ApiResponse {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:content-types:*"),
+		"sites::content-types:read",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let document = parse_content_model(&body, is_yaml_request(&req))?;
+	let plan = plan_content_model(conn, params.site_id, &document)?;
+
+	let res = ContentModelPlanDTO::from((&plan, false));
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/content-model",
+    request_body(content = ContentModelDTO, description = "JSON, or YAML when sent as application/yaml"),
+	responses(
+		(status = 200, body = ContentModelPlanDTO),
+		(status = 400, body = AppErrorValue, description = "The content model is invalid"),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindPathParams)
+)]
+#[post("/apply")]
+pub async fn apply(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindPathParams>,
+	body: web::Bytes,
+) -> ApiResponse {
+	let mut user_id = Uuid::nil();
+	for action in [
+		"sites::content-types:create",
+		"sites::content-types:update",
+		"sites::content-types:remove",
+	] {
+		user_id = ensure_permission(
+			&req,
+			Some(params.site_id),
+			format!("urn:dcm:content-types:*"),
+			action,
+		)?;
+	}
+	let conn = &mut state.get_conn()?;
+	let document = parse_content_model(&body, is_yaml_request(&req))?;
+	let plan = plan_content_model(conn, params.site_id, &document)?;
+	apply_content_model(conn, params.site_id, user_id, &plan)?;
+
+	let res = ContentModelPlanDTO::from((&plan, true));
+	Ok(HttpResponse::Ok().json(res))
+}
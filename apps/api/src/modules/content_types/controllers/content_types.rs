@@ -1,15 +1,18 @@
 use super::super::dto::content_types::{request, response};
 use crate::modules::auth::helpers::permissions::ensure_permission;
+use crate::modules::content::models::content::Content;
 use crate::modules::content_types::models::content_type::{
 	ContentType, ContentTypeKindEnum, CreateContentType,
 };
 use crate::modules::core::middleware::state::AppState;
 use crate::modules::core::models::hal::HALPage;
+use crate::modules::labels::helpers::localize::{get_language_keys, localize};
 use crate::utils::api::ApiResponse;
 use crate::{errors::AppError, modules::content_types::models::content_type::UpdateContentType};
 use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse};
 use serde::Deserialize;
 use slug::slugify;
+use std::iter;
 use utoipa::IntoParams;
 use uuid::Uuid;
 
@@ -53,7 +56,7 @@ pub async fn create(
 	params: web::Path<FindPathParams>,
 ) -> Result<HttpResponse, AppError> {
 	// TODO: fix this so it keeps the "kind" in mind.
-	ensure_permission(
+	let user_id = ensure_permission(
 		&req,
 		Some(params.site_id),
 		format!("urn:dcm:content-types:*"),
@@ -71,6 +74,7 @@ pub async fn create(
 			kind: form.kind.clone(),
 		},
 	)?;
+	Content::create_singletons(conn, user_id, params.site_id, content_type.id)?;
 	let res = response::ContentTypeWithFieldsDTO::from((content_type, Vec::new(), Vec::new()));
 	Ok(HttpResponse::Ok().json(res))
 }
@@ -111,7 +115,7 @@ pub async fn find_all(
 	let page = query.page.unwrap_or(1);
 	let pagesize = query.pagesize.unwrap_or(20);
 
-	let (content_types, total_elements) = ContentType::find(
+	let (mut content_types, total_elements) = ContentType::find(
 		conn,
 		params.site_id,
 		page,
@@ -119,6 +123,13 @@ pub async fn find_all(
 		query.kind,
 		query.include_occurrences,
 	)?;
+	localize(
+		conn,
+		&get_language_keys(&req),
+		content_types
+			.iter_mut()
+			.map(|(content_type, _)| content_type),
+	)?;
 
 	let res = response::ContentTypesDTO::from((
 		content_types,
@@ -166,8 +177,19 @@ pub async fn find_one(
 		)
 	})?;
 	let conn = &mut state.get_conn()?;
-	let content_type = ContentType::find_one(conn, params.site_id, params.content_type_id)?;
-	let res = response::ContentTypeWithFieldsDTO::from(content_type);
+	let (mut content_type, mut fields, mut compartments) =
+		ContentType::find_one(conn, params.site_id, params.content_type_id)?;
+	let language_keys = get_language_keys(&req);
+	localize(conn, &language_keys, [&mut content_type])?;
+	localize(
+		conn,
+		&language_keys,
+		fields.iter_mut().flat_map(|(field, _, _, blocks)| {
+			iter::once(field).chain(blocks.iter_mut().map(|(block, _, _)| block))
+		}),
+	)?;
+	localize(conn, &language_keys, compartments.iter_mut())?;
+	let res = response::ContentTypeWithFieldsDTO::from((content_type, fields, compartments));
 	Ok(HttpResponse::Ok().json(res))
 }
 
@@ -206,7 +228,7 @@ pub async fn update(
 			description: form.description.clone(),
 		},
 	)?;
-	let res = response::ContentTypeWithFieldsDTO::from(content_type);
+	let res = response::ContentTypeWithFieldsDTO::from((content_type, fields, compartments));
 	Ok(HttpResponse::Ok().json(res))
 }
 
@@ -0,0 +1,131 @@
+use super::super::dto::field_groups::{request, response};
+use crate::modules::auth::helpers::permissions::ensure_permission;
+use crate::modules::content_types::models::content_type::ContentType;
+use crate::modules::content_types::models::content_type_field_group::ContentTypeFieldGroup;
+use crate::modules::core::middleware::state::AppState;
+use crate::modules::labels::helpers::localize::{get_language_keys, localize};
+use crate::utils::api::ApiResponse;
+use actix_web::{delete, get, put, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindPathParams {
+	site_id: Uuid,
+	content_type_id: Uuid,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindOnePathParams {
+	site_id: Uuid,
+	content_type_id: Uuid,
+	field_group_id: Uuid,
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/content-types/{content_type_id}/field-groups",
+	responses(
+		(status = 200, body = FieldGroupsDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindPathParams)
+)]
+#[get("")]
+pub async fn find_all(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindPathParams>,
+) -> ApiResponse {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:content-types:{}", params.content_type_id),
+		"sites::content-types:read",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let mut field_groups = ContentTypeFieldGroup::find(conn, vec![params.content_type_id])?;
+	localize(
+		conn,
+		&get_language_keys(&req),
+		field_groups.iter_mut().map(|(_, field_group)| field_group),
+	)?;
+
+	let res = response::FieldGroupsDTO::from(field_groups);
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/content-types/{content_type_id}/field-groups",
+    request_body = UpsertFieldGroupDTO,
+	responses(
+		(status = 200, body = FieldGroupsDTO),
+		(status = 400, body = AppErrorValue, description = "The field group can't be included"),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindOnePathParams)
+)]
+#[put("/{field_group_id}")]
+pub async fn update(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindOnePathParams>,
+	form: web::Json<request::UpsertFieldGroupDTO>,
+) -> ApiResponse {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:content-types:{}", params.content_type_id),
+		"sites::content-types:update",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let (content_type, _, _) = ContentType::find_one(conn, params.site_id, params.content_type_id)?;
+	let (field_group, _, _) = ContentType::find_one(conn, params.site_id, params.field_group_id)?;
+
+	ContentTypeFieldGroup::ensure_includable(conn, params.site_id, &content_type, &field_group)?;
+	ContentTypeFieldGroup::upsert(
+		conn,
+		params.content_type_id,
+		params.field_group_id,
+		form.sequence_number,
+	)?;
+
+	let field_groups = ContentTypeFieldGroup::find(conn, vec![params.content_type_id])?;
+	let res = response::FieldGroupsDTO::from(field_groups);
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/content-types/{content_type_id}/field-groups",
+	responses(
+		(status = 204),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindOnePathParams)
+)]
+#[delete("/{field_group_id}")]
+pub async fn remove(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindOnePathParams>,
+) -> ApiResponse {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:content-types:{}", params.content_type_id),
+		"sites::content-types:update",
+	)?;
+	let conn = &mut state.get_conn()?;
+	ContentTypeFieldGroup::remove(conn, params.content_type_id, params.field_group_id)?;
+
+	Ok(HttpResponse::NoContent().body(()))
+}
@@ -1,10 +1,19 @@
+use super::super::dto::field_migrations::{
+	request::MigrateFieldDTO,
+	response::{FieldImpactDTO, FieldMigrationReportDTO},
+};
 use super::super::dto::fields::{request, response};
+use super::super::services::field_migration::{
+	apply_field_migration, find_field_impact, plan_field_migration, purge_field_content,
+	FieldMigration,
+};
 use crate::modules::auth::helpers::permissions::ensure_permission;
 use crate::modules::content_components::dto::content_components::response::FieldWithContentComponentDTO;
 use crate::modules::content_types::models::field::{FieldTypeEnum, UpdateField};
 use crate::modules::content_types::models::field_config::FieldConfig;
 use crate::modules::core::middleware::state::AppState;
 use crate::modules::core::models::hal::HALPage;
+use crate::modules::labels::helpers::localize::{get_language_keys, localize};
 use crate::utils::api::ApiResponse;
 use crate::{errors::AppError, modules::content_types::models::field::FieldModel};
 use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse};
@@ -31,6 +40,11 @@ pub struct FindAllQueryParams {
 	pagesize: Option<i64>,
 }
 
+#[derive(Deserialize, IntoParams)]
+pub struct RemoveQueryParams {
+	purge: Option<bool>,
+}
+
 #[utoipa::path(
 	context_path = "/api/v1/sites/{site_id}/content-types/{content_type_id}/fields",
     request_body = CreateFieldDTO,
@@ -99,8 +113,13 @@ pub async fn find_all(
 	let page = query.page.unwrap_or(1);
 	let pagesize = query.pagesize.unwrap_or(20);
 
-	let (fields, total_elements) =
+	let (mut fields, total_elements) =
 		FieldModel::find(conn, params.site_id, params.content_type_id, page, pagesize)?;
+	localize(
+		conn,
+		&get_language_keys(&req),
+		fields.iter_mut().map(|(field, _, _)| field),
+	)?;
 
 	let res = response::FieldsDTO::from((
 		fields,
@@ -141,7 +160,8 @@ pub async fn find_one(
 		"sites::content-types:update",
 	)?;
 	let conn = &mut state.get_conn()?;
-	let field = FieldModel::find_one(conn, params.site_id, params.field_id)?;
+	let mut field = FieldModel::find_one(conn, params.site_id, params.field_id)?;
+	localize(conn, &get_language_keys(&req), [&mut field.0])?;
 
 	let res = FieldWithContentComponentDTO::from(field);
 	Ok(HttpResponse::Ok().json(res))
@@ -174,6 +194,22 @@ pub async fn update(
 	)?;
 	let conn = &mut state.get_conn()?;
 
+	// Changing between a single value and a list, or between shared and translated values, reshapes the stored content
+	let plan = plan_field_migration(
+		conn,
+		params.site_id,
+		params.field_id,
+		FieldMigration {
+			min: form.min,
+			max: form.max,
+			multi_language: form.multi_language,
+			..Default::default()
+		},
+	)?;
+	if !plan.steps.is_empty() {
+		apply_field_migration(conn, params.site_id, &plan)?;
+	}
+
 	FieldConfig::upsert(conn, params.field_id, form.config.clone())?;
 	let field = FieldModel::update(
 		conn,
@@ -206,13 +242,14 @@ pub async fn update(
     security(
         ("jwt_token" = [])
     ),
-	params(FindPathParams)
+	params(FindPathParams, RemoveQueryParams)
 )]
 #[delete("/{field_id}")]
 pub async fn remove(
 	req: HttpRequest,
 	state: web::Data<AppState>,
 	params: web::Path<FindOnePathParams>,
+	query: web::Query<RemoveQueryParams>,
 ) -> ApiResponse {
 	ensure_permission(
 		&req,
@@ -221,6 +258,96 @@ pub async fn remove(
 		"sites::content-types:update",
 	)?;
 	let conn = &mut state.get_conn()?;
+
+	if query.purge.unwrap_or(false) {
+		let (field, _, _) = FieldModel::find_one(conn, params.site_id, params.field_id)?;
+		purge_field_content(conn, &field)?;
+	}
+
 	FieldModel::remove(conn, params.field_id)?;
 	Ok(HttpResponse::NoContent().body(()))
 }
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/content-types/{content_type_id}/fields",
+	responses(
+		(status = 200, body = FieldImpactDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindPathParams)
+)]
+#[get("/{field_id}/impact")]
+pub async fn impact(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindOnePathParams>,
+) -> ApiResponse {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:content-types:{}", params.content_type_id),
+		"sites::content-types:read",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let (field, _, _) = FieldModel::find_one(conn, params.site_id, params.field_id)?;
+	let impact = find_field_impact(conn, &field)?;
+
+	let res = FieldImpactDTO::from(impact);
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/content-types/{content_type_id}/fields",
+    request_body = MigrateFieldDTO,
+	responses(
+		(status = 200, body = FieldMigrationReportDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized"),
+		(status = 422, body = FieldMigrationReportDTO, description = "Stored values would be lost without purge")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindPathParams)
+)]
+#[post("/{field_id}/migrate")]
+pub async fn migrate(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindOnePathParams>,
+	form: web::Json<MigrateFieldDTO>,
+) -> ApiResponse {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:content-types:{}", params.content_type_id),
+		"sites::content-types:update",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let form = form.into_inner();
+	let dry_run = form.dry_run.unwrap_or(false);
+
+	let plan = plan_field_migration(
+		conn,
+		params.site_id,
+		params.field_id,
+		FieldMigration::from(form),
+	)?;
+
+	if dry_run {
+		let res = FieldMigrationReportDTO::from((plan, false));
+		return Ok(HttpResponse::Ok().json(res));
+	}
+
+	if plan.is_blocked() {
+		let res = FieldMigrationReportDTO::from((plan, false));
+		return Ok(HttpResponse::UnprocessableEntity().json(res));
+	}
+
+	apply_field_migration(conn, params.site_id, &plan)?;
+
+	let res = FieldMigrationReportDTO::from((plan, true));
+	Ok(HttpResponse::Ok().json(res))
+}
@@ -1,6 +1,8 @@
 pub mod blocks;
 pub mod compartments;
+pub mod content_model;
 pub mod content_types;
+pub mod field_groups;
 pub mod field_order;
 pub mod fields;
 pub mod root_content_types;
@@ -1,6 +1,7 @@
 use super::super::dto::content_types::response;
 use crate::errors::AppError;
 use crate::modules::auth::helpers::permissions::ensure_permission;
+use crate::modules::content::models::content::Content;
 use crate::modules::content_types::models::content_type::{ContentType, ContentTypeKindEnum};
 use crate::modules::core::middleware::state::AppState;
 use crate::modules::core::models::hal::HALPage;
@@ -91,7 +92,7 @@ pub async fn enable(
 	state: web::Data<AppState>,
 	params: web::Path<UpdatePathParams>,
 ) -> Result<HttpResponse, AppError> {
-	ensure_permission(
+	let user_id = ensure_permission(
 		&req,
 		None,
 		format!("urn:dcm:content-types:*"),
@@ -100,6 +101,7 @@ pub async fn enable(
 	let conn = &mut state.get_conn()?;
 
 	ContentType::enable_site(conn, params.content_type_id, params.site_id)?;
+	Content::create_singletons(conn, user_id, params.site_id, params.content_type_id)?;
 
 	Ok(HttpResponse::NoContent().body(()))
 }
@@ -0,0 +1,67 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use crate::modules::content_types::models::content_type::ContentTypeKindEnum;
+
+fn default_occurrences() -> i32 {
+	1
+}
+
+// Everything is referenced by slug (or name for compartments) so the document can live next to the
+// frontend and be applied to any environment. The order of fields and blocks is their sequence.
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentModelDTO {
+	#[serde(default)]
+	pub content_types: Vec<ContentModelContentTypeDTO>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentModelContentTypeDTO {
+	pub slug: String,
+	pub name: String,
+	pub description: Option<String>,
+	pub kind: ContentTypeKindEnum,
+	// Slug of the workflow
+	pub workflow: String,
+	#[serde(default)]
+	pub compartments: Vec<ContentModelCompartmentDTO>,
+	#[serde(default)]
+	pub fields: Vec<ContentModelFieldDTO>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentModelCompartmentDTO {
+	pub name: String,
+	pub description: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentModelFieldDTO {
+	pub slug: String,
+	pub name: String,
+	// Slug of the content component
+	pub component: String,
+	pub description: Option<String>,
+	// Name of the compartment, not available on blocks
+	pub compartment: Option<String>,
+	#[serde(default = "default_occurrences")]
+	pub min: i32,
+	#[serde(default = "default_occurrences")]
+	pub max: i32,
+	#[serde(default)]
+	pub hidden: bool,
+	#[serde(default)]
+	pub multi_language: bool,
+	pub validation: Option<Value>,
+	#[serde(default)]
+	pub config: BTreeMap<String, Value>,
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub blocks: Vec<ContentModelFieldDTO>,
+}
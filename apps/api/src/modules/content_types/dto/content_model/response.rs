@@ -0,0 +1,46 @@
+use crate::modules::content_types::services::content_model::{
+	ContentModelChange, ContentModelChangeActionEnum, ContentModelPlan, ContentModelResourceEnum,
+};
+use serde::{Deserialize, Serialize};
+use std::convert::From;
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentModelChangeDTO {
+	pub action: ContentModelChangeActionEnum,
+	pub resource: ContentModelResourceEnum,
+	pub path: String,
+	pub attributes: Vec<String>,
+}
+
+impl From<&ContentModelChange> for ContentModelChangeDTO {
+	fn from(change: &ContentModelChange) -> Self {
+		Self {
+			action: change.action.clone(),
+			resource: change.resource.clone(),
+			path: change.path.clone(),
+			attributes: change.attributes.clone(),
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentModelPlanDTO {
+	pub applied: bool,
+	pub changes: Vec<ContentModelChangeDTO>,
+}
+
+impl From<(&ContentModelPlan, bool)> for ContentModelPlanDTO {
+	fn from((plan, applied): (&ContentModelPlan, bool)) -> Self {
+		Self {
+			applied,
+			changes: plan
+				.changes
+				.iter()
+				.map(ContentModelChangeDTO::from)
+				.collect(),
+		}
+	}
+}
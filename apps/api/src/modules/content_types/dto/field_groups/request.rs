@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertFieldGroupDTO {
+	// Position of the group's fields among the fields of the content type
+	pub sequence_number: i32,
+}
@@ -0,0 +1,43 @@
+use crate::modules::content_types::models::{
+	content_type::ContentType, content_type_field_group::ContentTypeFieldGroup,
+};
+use serde::{Deserialize, Serialize};
+use std::convert::From;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldGroupDTO {
+	pub field_group_id: Uuid,
+	pub name: String,
+	pub slug: String,
+	pub description: Option<String>,
+	pub sequence_number: i32,
+}
+
+impl From<(ContentTypeFieldGroup, ContentType)> for FieldGroupDTO {
+	fn from((included, field_group): (ContentTypeFieldGroup, ContentType)) -> Self {
+		Self {
+			field_group_id: included.field_group_id,
+			name: field_group.name,
+			slug: field_group.slug,
+			description: field_group.description,
+			sequence_number: included.sequence_number,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldGroupsDTO {
+	pub field_groups: Vec<FieldGroupDTO>,
+}
+
+impl From<Vec<(ContentTypeFieldGroup, ContentType)>> for FieldGroupsDTO {
+	fn from(field_groups: Vec<(ContentTypeFieldGroup, ContentType)>) -> Self {
+		Self {
+			field_groups: field_groups.into_iter().map(FieldGroupDTO::from).collect(),
+		}
+	}
+}
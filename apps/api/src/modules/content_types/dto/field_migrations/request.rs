@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::modules::content_types::services::field_migration::FieldMigration;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrateFieldDTO {
+	pub slug: Option<String>,
+	pub content_component_id: Option<Uuid>,
+	pub min: Option<i32>,
+	pub max: Option<i32>,
+	pub multi_language: Option<bool>,
+	// Removes stored values that can't be converted instead of refusing the migration
+	pub purge: Option<bool>,
+	// Only reports the impact without changing anything
+	pub dry_run: Option<bool>,
+}
+
+impl From<MigrateFieldDTO> for FieldMigration {
+	fn from(dto: MigrateFieldDTO) -> Self {
+		Self {
+			slug: dto.slug,
+			content_component_id: dto.content_component_id,
+			min: dto.min,
+			max: dto.max,
+			multi_language: dto.multi_language,
+			purge: dto.purge.unwrap_or(false),
+		}
+	}
+}
@@ -0,0 +1,66 @@
+use crate::modules::content_types::services::field_migration::{
+	FieldImpact, FieldMigrationPlan, FieldMigrationStep, FieldMigrationStepKindEnum,
+};
+use serde::{Deserialize, Serialize};
+use std::convert::From;
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldImpactDTO {
+	pub content_items: i64,
+	pub revisions: i64,
+	pub content_fields: i64,
+}
+
+impl From<FieldImpact> for FieldImpactDTO {
+	fn from(impact: FieldImpact) -> Self {
+		Self {
+			content_items: impact.content_items,
+			revisions: impact.revisions,
+			content_fields: impact.content_fields,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldMigrationStepDTO {
+	pub kind: FieldMigrationStepKindEnum,
+	pub message: String,
+	pub data_loss: bool,
+	pub blocking: bool,
+}
+
+impl From<FieldMigrationStep> for FieldMigrationStepDTO {
+	fn from(step: FieldMigrationStep) -> Self {
+		Self {
+			kind: step.kind,
+			message: step.message,
+			data_loss: step.data_loss,
+			blocking: step.blocking,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldMigrationReportDTO {
+	pub migrated: bool,
+	pub impact: FieldImpactDTO,
+	pub steps: Vec<FieldMigrationStepDTO>,
+}
+
+impl From<(FieldMigrationPlan, bool)> for FieldMigrationReportDTO {
+	fn from((plan, migrated): (FieldMigrationPlan, bool)) -> Self {
+		Self {
+			migrated,
+			impact: FieldImpactDTO::from(plan.impact),
+			steps: plan
+				.steps
+				.into_iter()
+				.map(FieldMigrationStepDTO::from)
+				.collect(),
+		}
+	}
+}
@@ -1,3 +1,4 @@
 pub mod controllers;
 pub mod dto;
 pub mod models;
+pub mod services;
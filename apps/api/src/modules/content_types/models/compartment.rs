@@ -5,6 +5,7 @@ use tracing::instrument;
 use uuid::Uuid;
 
 use crate::errors::AppError;
+use crate::modules::labels::models::label::Label;
 use crate::schema::compartments;
 
 #[derive(
@@ -97,6 +98,7 @@ impl CompartmentModel {
 	pub fn remove(conn: &mut PgConnection, compartment_id: Uuid) -> Result<(), AppError> {
 		let target = compartments::table.filter(compartments::id.eq(compartment_id));
 		diesel::delete(target).execute(conn)?;
+		Label::remove(conn, vec![compartment_id])?;
 
 		Ok(())
 	}
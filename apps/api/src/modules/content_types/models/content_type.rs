@@ -16,12 +16,14 @@ use crate::modules::content::models::content::Content;
 use crate::modules::content_components::models::content_component::{
 	ContentComponent, PopulatedContentComponent,
 };
-use crate::schema::{content, fields};
+use crate::modules::labels::models::label::Label;
+use crate::schema::{compartments, content, fields};
 use crate::schema::{
 	content_components, content_types, sites_content_types, sql_types::ContentTypeKinds,
 };
 
 use super::compartment::CompartmentModel;
+use super::content_type_field_group::ContentTypeFieldGroup;
 use super::field::FieldModel;
 use super::field_config::{FieldConfig, FieldConfigContent};
 use super::site_content_type::SiteContentType;
@@ -48,6 +50,10 @@ pub enum ContentTypeKindEnum {
 	CONTENT,
 	PAGE,
 	CONTENT_BLOCK,
+	// Exactly one content item per language, created as soon as the content type is enabled for a site
+	SINGLE,
+	// Only holds fields for other content types to include, see `content_type_field_group`
+	FIELD_GROUP,
 }
 
 impl ToSql<ContentTypeKinds, Pg> for ContentTypeKindEnum {
@@ -56,6 +62,8 @@ impl ToSql<ContentTypeKinds, Pg> for ContentTypeKindEnum {
 			ContentTypeKindEnum::CONTENT => out.write_all(b"CONTENT")?,
 			ContentTypeKindEnum::PAGE => out.write_all(b"PAGE")?,
 			ContentTypeKindEnum::CONTENT_BLOCK => out.write_all(b"CONTENT_BLOCK")?,
+			ContentTypeKindEnum::SINGLE => out.write_all(b"SINGLE")?,
+			ContentTypeKindEnum::FIELD_GROUP => out.write_all(b"FIELD_GROUP")?,
 		}
 		Ok(IsNull::No)
 	}
@@ -67,6 +75,8 @@ impl FromSql<ContentTypeKinds, Pg> for ContentTypeKindEnum {
 			b"CONTENT" => Ok(ContentTypeKindEnum::CONTENT),
 			b"PAGE" => Ok(ContentTypeKindEnum::PAGE),
 			b"CONTENT_BLOCK" => Ok(ContentTypeKindEnum::CONTENT_BLOCK),
+			b"SINGLE" => Ok(ContentTypeKindEnum::SINGLE),
+			b"FIELD_GROUP" => Ok(ContentTypeKindEnum::FIELD_GROUP),
 			_ => Err("Unrecognized enum variant".into()),
 		}
 	}
@@ -267,6 +277,13 @@ impl ContentType {
 
 	#[instrument(skip(conn))]
 	pub fn remove(conn: &mut PgConnection, content_type_id: Uuid) -> Result<(), AppError> {
+		// Compartments are removed along with the content type, their labels have to go as well
+		let compartment_ids = compartments::table
+			.filter(compartments::content_type_id.eq(content_type_id))
+			.select(compartments::id)
+			.load::<Uuid>(conn)?;
+		Label::remove(conn, [vec![content_type_id], compartment_ids].concat())?;
+
 		let target = sites_content_types::table
 			.filter(sites_content_types::content_type_id.eq(content_type_id));
 		diesel::delete(target).get_result::<SiteContentType>(conn)?;
@@ -285,10 +302,40 @@ impl ContentType {
 			.select(ContentComponent::as_select())
 			.load::<ContentComponent>(conn)?;
 
-		let fields = FieldModel::belonging_to(content_types)
+		// Fields of included field groups take the place of the group, in the order of the group
+		let field_groups = ContentTypeFieldGroup::find(
+			conn,
+			content_types
+				.iter()
+				.map(|content_type| content_type.id)
+				.collect(),
+		)?;
+		let mut parent_ids = content_types
+			.iter()
+			.map(|content_type| content_type.id)
+			.collect::<Vec<Uuid>>();
+		parent_ids.extend(
+			field_groups
+				.iter()
+				.map(|(field_group, _)| field_group.field_group_id),
+		);
+
+		let mut fields = fields::table
+			.filter(fields::parent_id.eq_any(parent_ids))
 			.order(fields::sequence_number)
 			.select(FieldModel::as_select())
 			.load::<FieldModel>(conn)?;
+		fields.sort_by_key(|field| {
+			match field_groups
+				.iter()
+				.find(|(field_group, _)| field_group.field_group_id == field.parent_id)
+			{
+				Some((field_group, _)) => {
+					(field_group.sequence_number, 1, field_group.field_group_id)
+				}
+				None => (field.sequence_number.unwrap_or(i32::MAX), 0, Uuid::nil()),
+			}
+		});
 
 		let field_config = FieldConfig::belonging_to(&fields)
 			.select(FieldConfig::as_select())
@@ -0,0 +1,218 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::errors::{AppError, AppErrorValue};
+use crate::modules::content_types::models::content_type::{ContentType, ContentTypeKindEnum};
+use crate::schema::{content_types, content_types_field_groups, fields, sites_content_types};
+
+// A field group is a content type of kind `FIELD_GROUP`, its fields are merged into every content type that includes it
+#[derive(Identifiable, Selectable, Queryable, Associations, Debug, Clone)]
+#[diesel(belongs_to(ContentType))]
+#[diesel(table_name = content_types_field_groups)]
+#[diesel(primary_key(content_type_id, field_group_id))]
+pub struct ContentTypeFieldGroup {
+	pub content_type_id: Uuid,
+	pub field_group_id: Uuid,
+	pub sequence_number: i32,
+	pub created_at: NaiveDateTime,
+}
+
+impl ContentTypeFieldGroup {
+	// Field groups that were removed are skipped
+	#[instrument(skip(conn))]
+	pub fn find(
+		conn: &mut PgConnection,
+		content_type_ids: Vec<Uuid>,
+	) -> Result<Vec<(Self, ContentType)>, AppError> {
+		let field_groups = content_types_field_groups::table
+			.filter(content_types_field_groups::content_type_id.eq_any(content_type_ids))
+			.inner_join(
+				content_types::table
+					.on(content_types::id.eq(content_types_field_groups::field_group_id)),
+			)
+			.filter(content_types::deleted.eq(false))
+			.order(content_types_field_groups::sequence_number.asc())
+			.select((ContentTypeFieldGroup::as_select(), ContentType::as_select()))
+			.load::<(Self, ContentType)>(conn)?;
+
+		Ok(field_groups)
+	}
+
+	#[instrument(skip(conn))]
+	pub fn find_consumers(
+		conn: &mut PgConnection,
+		field_group_id: Uuid,
+	) -> Result<Vec<Uuid>, AppError> {
+		let content_type_ids = content_types_field_groups::table
+			.filter(content_types_field_groups::field_group_id.eq(field_group_id))
+			.select(content_types_field_groups::content_type_id)
+			.load::<Uuid>(conn)?;
+
+		Ok(content_type_ids)
+	}
+
+	// Fields of a content type and of the groups it includes end up side by side, so a slug has to be free
+	// in all of them. For a group that means in every content type including it as well.
+	#[instrument(skip(conn))]
+	pub fn ensure_field_slug_available(
+		conn: &mut PgConnection,
+		parent_id: Uuid,
+		field_id: Option<Uuid>,
+		slug: &str,
+	) -> Result<(), AppError> {
+		let mut content_type_ids = vec![parent_id];
+		content_type_ids.extend(Self::find_consumers(conn, parent_id)?);
+
+		let mut parent_ids = content_type_ids.clone();
+		parent_ids.extend(
+			Self::find(conn, content_type_ids)?
+				.into_iter()
+				.map(|(included, _)| included.field_group_id),
+		);
+
+		let mut query = fields::table
+			.filter(fields::parent_id.eq_any(parent_ids))
+			.filter(fields::slug.eq(slug))
+			.into_boxed();
+		if let Some(field_id) = field_id {
+			query = query.filter(fields::id.ne(field_id));
+		}
+
+		if query.count().get_result::<i64>(conn)? > 0 {
+			return Err(AppError::BadRequest(AppErrorValue {
+				message: format!("A field with slug {slug} already exists"),
+				status: StatusCode::BAD_REQUEST.as_u16(),
+				code: "FIELD_SLUG_TAKEN".to_owned(),
+				..Default::default()
+			}));
+		}
+
+		Ok(())
+	}
+
+	// Groups can't be nested, and their field slugs can't clash with the fields the content type already has
+	#[instrument(skip(conn))]
+	pub fn ensure_includable(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		content_type: &ContentType,
+		field_group: &ContentType,
+	) -> Result<(), AppError> {
+		if field_group.kind != ContentTypeKindEnum::FIELD_GROUP {
+			return Err(AppError::BadRequest(AppErrorValue {
+				message: format!("{} is not a field group", field_group.slug),
+				status: StatusCode::BAD_REQUEST.as_u16(),
+				code: "NOT_A_FIELD_GROUP".to_owned(),
+				..Default::default()
+			}));
+		}
+
+		if content_type.kind == ContentTypeKindEnum::FIELD_GROUP {
+			return Err(AppError::BadRequest(AppErrorValue {
+				message: "Field groups can't include other field groups".to_owned(),
+				status: StatusCode::BAD_REQUEST.as_u16(),
+				code: "NESTED_FIELD_GROUP".to_owned(),
+				..Default::default()
+			}));
+		}
+
+		let enabled = sites_content_types::table
+			.filter(sites_content_types::site_id.eq(site_id))
+			.filter(sites_content_types::content_type_id.eq(field_group.id))
+			.count()
+			.get_result::<i64>(conn)?
+			> 0;
+		if !enabled {
+			return Err(AppError::NotFound(AppErrorValue {
+				message: format!(
+					"Field group {} is not enabled for this site",
+					field_group.slug
+				),
+				status: StatusCode::NOT_FOUND.as_u16(),
+				code: "FIELD_GROUP_NOT_FOUND".to_owned(),
+				..Default::default()
+			}));
+		}
+
+		let mut parent_ids = vec![content_type.id];
+		parent_ids.extend(
+			Self::find(conn, vec![content_type.id])?
+				.into_iter()
+				.map(|(included, _)| included.field_group_id)
+				.filter(|field_group_id| *field_group_id != field_group.id),
+		);
+		let existing_slugs = fields::table
+			.filter(fields::parent_id.eq_any(parent_ids))
+			.select(fields::slug)
+			.load::<String>(conn)?;
+		let clashing_slug = fields::table
+			.filter(fields::parent_id.eq(field_group.id))
+			.filter(fields::slug.eq_any(existing_slugs))
+			.select(fields::slug)
+			.first::<String>(conn)
+			.optional()?;
+		if let Some(slug) = clashing_slug {
+			return Err(AppError::BadRequest(AppErrorValue {
+				message: format!("{} already has a field with slug {slug}", content_type.slug),
+				status: StatusCode::BAD_REQUEST.as_u16(),
+				code: "FIELD_SLUG_TAKEN".to_owned(),
+				..Default::default()
+			}));
+		}
+
+		Ok(())
+	}
+
+	#[instrument(skip(conn))]
+	pub fn upsert(
+		conn: &mut PgConnection,
+		content_type_id: Uuid,
+		field_group_id: Uuid,
+		sequence_number: i32,
+	) -> Result<Self, AppError> {
+		let field_group = diesel::insert_into(content_types_field_groups::table)
+			.values(CreateContentTypeFieldGroup {
+				content_type_id,
+				field_group_id,
+				sequence_number,
+			})
+			.on_conflict((
+				content_types_field_groups::content_type_id,
+				content_types_field_groups::field_group_id,
+			))
+			.do_update()
+			.set(content_types_field_groups::sequence_number.eq(sequence_number))
+			.returning(ContentTypeFieldGroup::as_returning())
+			.get_result(conn)?;
+
+		Ok(field_group)
+	}
+
+	#[instrument(skip(conn))]
+	pub fn remove(
+		conn: &mut PgConnection,
+		content_type_id: Uuid,
+		field_group_id: Uuid,
+	) -> Result<(), AppError> {
+		let target = content_types_field_groups::table.filter(
+			content_types_field_groups::content_type_id
+				.eq(content_type_id)
+				.and(content_types_field_groups::field_group_id.eq(field_group_id)),
+		);
+		diesel::delete(target).execute(conn)?;
+
+		Ok(())
+	}
+}
+
+#[derive(Insertable, Debug, Deserialize)]
+#[diesel(table_name = content_types_field_groups)]
+pub struct CreateContentTypeFieldGroup {
+	pub content_type_id: Uuid,
+	pub field_group_id: Uuid,
+	pub sequence_number: i32,
+}
@@ -5,6 +5,8 @@ use crate::modules::content_components::models::content_component::{
 	ContentComponent, PopulatedContentComponent,
 };
 use crate::modules::content_types::models::content_type::ContentType;
+use crate::modules::content_types::models::content_type_field_group::ContentTypeFieldGroup;
+use crate::modules::labels::models::label::Label;
 use crate::schema::field_config;
 use diesel::deserialize::{self, FromSql};
 use diesel::pg::{Pg, PgValue};
@@ -99,10 +101,13 @@ impl FieldModel {
 		),
 		AppError,
 	> {
+		let slug = slugify(name);
+		ContentTypeFieldGroup::ensure_field_slug_available(conn, parent_id, None, &slug)?;
+
 		let field = diesel::insert_into(fields::table)
 			.values(CreateField {
 				name: name.to_owned(),
-				slug: slugify(name),
+				slug,
 				parent_id,
 				content_component_id,
 				field_type,
@@ -240,6 +245,14 @@ impl FieldModel {
 		),
 		AppError,
 	> {
+		if let Some(slug) = &changeset.slug {
+			let parent_id = fields::table
+				.find(id)
+				.select(fields::parent_id)
+				.first::<Uuid>(conn)?;
+			ContentTypeFieldGroup::ensure_field_slug_available(conn, parent_id, Some(id), slug)?;
+		}
+
 		let target = fields::table.find(id);
 		diesel::update(target)
 			.set(changeset)
@@ -258,6 +271,38 @@ impl FieldModel {
 		let target = field_config::table.filter(field_config::field_id.eq(field_id));
 		diesel::delete(target).execute(conn)?;
 
+		Label::remove(conn, vec![field_id])?;
+
+		Ok(())
+	}
+
+	// Unlike `update`, this can also move a field out of its compartment
+	#[instrument(skip(conn))]
+	pub fn update_compartment(
+		conn: &mut PgConnection,
+		id: Uuid,
+		compartment_id: Option<Uuid>,
+	) -> Result<(), AppError> {
+		let target = fields::table.find(id);
+		diesel::update(target)
+			.set(fields::compartment_id.eq(compartment_id))
+			.execute(conn)?;
+
+		Ok(())
+	}
+
+	// The content component is only changed through a field migration, see `services::field_migration`
+	#[instrument(skip(conn))]
+	pub fn update_content_component(
+		conn: &mut PgConnection,
+		id: Uuid,
+		content_component_id: Uuid,
+	) -> Result<(), AppError> {
+		let target = fields::table.find(id);
+		diesel::update(target)
+			.set(fields::content_component_id.eq(content_component_id))
+			.execute(conn)?;
+
 		Ok(())
 	}
 
@@ -30,7 +30,7 @@ pub struct FieldConfig {
 	pub updated_at: NaiveDateTime,
 }
 
-#[derive(Debug, PartialEq, FromSqlRow, AsExpression, Eq, Clone, Deserialize)]
+#[derive(Debug, PartialEq, FromSqlRow, AsExpression, Eq, Clone, Deserialize, Serialize)]
 #[diesel(sql_type = FieldConfigTypes)]
 pub enum FieldConfigTypeEnum {
 	Text,
@@ -1,5 +1,6 @@
 pub mod compartment;
 pub mod content_type;
+pub mod content_type_field_group;
 pub mod field;
 pub mod field_config;
 pub mod site_content_type;
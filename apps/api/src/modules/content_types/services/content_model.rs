@@ -0,0 +1,1037 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use diesel::prelude::*;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::instrument;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+	errors::{AppError, AppErrorValue},
+	modules::content::models::content::Content,
+	modules::content_types::{
+		dto::content_model::request::{
+			ContentModelCompartmentDTO, ContentModelContentTypeDTO, ContentModelDTO,
+			ContentModelFieldDTO,
+		},
+		models::{
+			compartment::{CompartmentModel, UpdateCompartment},
+			content_type::{ContentType, CreateContentType, UpdateContentType},
+			field::{FieldModel, FieldTypeEnum, UpdateField},
+			field_config::{FieldConfig, FieldConfigTypeEnum},
+		},
+		services::field_migration::{apply_field_migration, plan_field_migration, FieldMigration},
+	},
+	schema::{content_components, content_types, fields, sites_content_types, workflows},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+pub enum ContentModelChangeActionEnum {
+	CREATE,
+	UPDATE,
+	REMOVE,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[allow(non_camel_case_types)]
+pub enum ContentModelResourceEnum {
+	CONTENT_TYPE,
+	COMPARTMENT,
+	FIELD,
+	BLOCK,
+}
+
+#[derive(Debug, Clone)]
+pub enum ContentModelOperation {
+	CreateContentType {
+		content_type: ContentModelContentTypeDTO,
+		workflow_id: Uuid,
+	},
+	UpdateContentType {
+		id: Uuid,
+		name: String,
+		description: Option<String>,
+	},
+	RemoveContentType {
+		id: Uuid,
+	},
+	CreateCompartment {
+		content_type: String,
+		compartment: ContentModelCompartmentDTO,
+	},
+	UpdateCompartment {
+		id: Uuid,
+		description: Option<String>,
+	},
+	RemoveCompartment {
+		id: Uuid,
+	},
+	CreateField {
+		content_type: String,
+		parent_field: Option<String>,
+		field: ContentModelFieldDTO,
+		content_component_id: Uuid,
+		sequence_number: i32,
+	},
+	UpdateField {
+		id: Uuid,
+		content_type: String,
+		field: ContentModelFieldDTO,
+		sequence_number: i32,
+	},
+	RemoveField {
+		id: Uuid,
+	},
+}
+
+#[derive(Debug, Clone)]
+pub struct ContentModelChange {
+	pub action: ContentModelChangeActionEnum,
+	pub resource: ContentModelResourceEnum,
+	// Dotted path of slugs, e.g. `page.fields.body.blocks.hero`
+	pub path: String,
+	// The attributes that differ for updates
+	pub attributes: Vec<String>,
+	pub operation: ContentModelOperation,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ContentModelPlan {
+	pub changes: Vec<ContentModelChange>,
+	// Ids of what already exists, so operations can reference their parents by slug
+	content_type_ids: HashMap<String, Uuid>,
+	compartment_ids: HashMap<(String, String), Uuid>,
+	field_ids: HashMap<(String, String), Uuid>,
+}
+
+impl ContentModelPlan {
+	fn push(
+		&mut self,
+		action: ContentModelChangeActionEnum,
+		resource: ContentModelResourceEnum,
+		path: String,
+		attributes: Vec<String>,
+		operation: ContentModelOperation,
+	) {
+		self.changes.push(ContentModelChange {
+			action,
+			resource,
+			path,
+			attributes,
+			operation,
+		});
+	}
+}
+
+struct CurrentField {
+	field: FieldModel,
+	config: BTreeMap<String, Value>,
+	blocks: Vec<CurrentField>,
+}
+
+struct CurrentContentType {
+	content_type: ContentType,
+	compartments: Vec<CompartmentModel>,
+	fields: Vec<CurrentField>,
+}
+
+fn invalid_content_model_error(message: String) -> AppError {
+	AppError::BadRequest(AppErrorValue {
+		message,
+		status: StatusCode::BAD_REQUEST.as_u16(),
+		code: "INVALID_CONTENT_MODEL".to_owned(),
+		..Default::default()
+	})
+}
+
+// Empty descriptions and null validation are stored in a few different ways, they all mean "nothing"
+fn normalize_text(value: &Option<String>) -> Option<&str> {
+	value.as_deref().filter(|text| !text.is_empty())
+}
+
+fn normalize_value(value: &Option<Value>) -> Option<&Value> {
+	value.as_ref().filter(|value| !value.is_null())
+}
+
+pub fn parse_content_model(body: &[u8], yaml: bool) -> Result<ContentModelDTO, AppError> {
+	if yaml {
+		return serde_yaml::from_slice(body)
+			.map_err(|err| invalid_content_model_error(err.to_string()));
+	}
+
+	serde_json::from_slice(body).map_err(|err| invalid_content_model_error(err.to_string()))
+}
+
+pub fn render_content_model(document: &ContentModelDTO) -> Result<String, AppError> {
+	serde_yaml::to_string(document).map_err(|err| {
+		AppError::InternalServerError(AppErrorValue {
+			message: err.to_string(),
+			status: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+			code: "CONTENT_MODEL_SERIALIZATION_FAILED".to_owned(),
+			..Default::default()
+		})
+	})
+}
+
+fn find_config(
+	conn: &mut PgConnection,
+	fields: &[FieldModel],
+) -> Result<HashMap<Uuid, BTreeMap<String, Value>>, AppError> {
+	let field_config = FieldConfig::belonging_to(fields)
+		.select(FieldConfig::as_select())
+		.load::<FieldConfig>(conn)?;
+
+	let mut config: HashMap<Uuid, BTreeMap<String, Value>> = HashMap::new();
+	for item in field_config {
+		// Nested field definitions belong to content components, not to the content model
+		let value = match (item.config_type, item.content) {
+			(FieldConfigTypeEnum::Json, Some(content)) => {
+				serde_json::from_str(&content).unwrap_or(Value::String(content))
+			}
+			(FieldConfigTypeEnum::Text, Some(content)) => Value::String(content),
+			_ => continue,
+		};
+
+		config
+			.entry(item.field_id)
+			.or_default()
+			.insert(item.config_key, value);
+	}
+
+	Ok(config)
+}
+
+fn find_current(
+	conn: &mut PgConnection,
+	site_id: Uuid,
+) -> Result<Vec<CurrentContentType>, AppError> {
+	let content_types = sites_content_types::table
+		.filter(sites_content_types::site_id.eq(site_id))
+		.inner_join(
+			content_types::table.on(content_types::id.eq(sites_content_types::content_type_id)),
+		)
+		.filter(content_types::deleted.eq(false))
+		.order(content_types::slug.asc())
+		.select(ContentType::as_select())
+		.load::<ContentType>(conn)?;
+
+	let compartments = CompartmentModel::belonging_to(&content_types)
+		.select(CompartmentModel::as_select())
+		.load::<CompartmentModel>(conn)?;
+	let fields = FieldModel::belonging_to(&content_types)
+		.order(fields::sequence_number.asc())
+		.select(FieldModel::as_select())
+		.load::<FieldModel>(conn)?;
+	let blocks = FieldModel::belonging_to(&fields)
+		.order(fields::sequence_number.asc())
+		.select(FieldModel::as_select())
+		.load::<FieldModel>(conn)?;
+	let mut config = find_config(
+		conn,
+		&fields
+			.iter()
+			.chain(blocks.iter())
+			.cloned()
+			.collect::<Vec<FieldModel>>(),
+	)?;
+
+	let grouped_blocks = blocks.grouped_by(&fields);
+	let fields_with_blocks = fields
+		.into_iter()
+		.zip(grouped_blocks)
+		.map(|(field, blocks)| CurrentField {
+			config: config.remove(&field.id).unwrap_or_default(),
+			blocks: blocks
+				.into_iter()
+				.map(|block| CurrentField {
+					config: config.remove(&block.id).unwrap_or_default(),
+					field: block,
+					blocks: vec![],
+				})
+				.collect(),
+			field,
+		})
+		.collect::<Vec<CurrentField>>();
+
+	let mut grouped_fields: HashMap<Uuid, Vec<CurrentField>> = HashMap::new();
+	for field in fields_with_blocks {
+		grouped_fields
+			.entry(field.field.parent_id)
+			.or_default()
+			.push(field);
+	}
+	let grouped_compartments = compartments.grouped_by(&content_types);
+
+	Ok(content_types
+		.into_iter()
+		.zip(grouped_compartments)
+		.map(|(content_type, compartments)| CurrentContentType {
+			fields: grouped_fields.remove(&content_type.id).unwrap_or_default(),
+			compartments,
+			content_type,
+		})
+		.collect())
+}
+
+fn find_slugs(
+	conn: &mut PgConnection,
+) -> Result<(HashMap<String, Uuid>, HashMap<String, Uuid>), AppError> {
+	let components = content_components::table
+		.filter(content_components::deleted.eq(false))
+		.select((content_components::slug, content_components::id))
+		.load::<(String, Uuid)>(conn)?
+		.into_iter()
+		.collect::<HashMap<String, Uuid>>();
+	let workflows = workflows::table
+		.filter(workflows::deleted.eq(false))
+		.select((workflows::slug, workflows::id))
+		.load::<(String, Uuid)>(conn)?
+		.into_iter()
+		.collect::<HashMap<String, Uuid>>();
+
+	Ok((components, workflows))
+}
+
+fn find_slug(slugs: &HashMap<String, Uuid>, id: Uuid) -> String {
+	slugs
+		.iter()
+		.find(|(_, slug_id)| **slug_id == id)
+		.map(|(slug, _)| slug.clone())
+		.unwrap_or_else(|| id.to_string())
+}
+
+// Describes the content model of a site in the same format that plan and apply accept
+#[instrument(skip(conn))]
+pub fn export_content_model(
+	conn: &mut PgConnection,
+	site_id: Uuid,
+) -> Result<ContentModelDTO, AppError> {
+	let current = find_current(conn, site_id)?;
+	let (components, workflows) = find_slugs(conn)?;
+
+	let to_field_dto = |current_field: &CurrentField,
+	                    compartments: &Vec<CompartmentModel>|
+	 -> ContentModelFieldDTO {
+		let field = &current_field.field;
+
+		ContentModelFieldDTO {
+			slug: field.slug.clone(),
+			name: field.name.clone(),
+			component: find_slug(&components, field.content_component_id),
+			description: normalize_text(&field.description).map(str::to_owned),
+			compartment: field.compartment_id.and_then(|compartment_id| {
+				compartments
+					.iter()
+					.find(|compartment| compartment.id == compartment_id)
+					.map(|compartment| compartment.name.clone())
+			}),
+			min: field.min,
+			max: field.max,
+			hidden: field.hidden,
+			multi_language: field.multi_language,
+			validation: normalize_value(&field.validation).cloned(),
+			config: current_field.config.clone(),
+			blocks: vec![],
+		}
+	};
+
+	let content_types = current
+		.iter()
+		.map(|current_content_type| {
+			let content_type = &current_content_type.content_type;
+
+			ContentModelContentTypeDTO {
+				slug: content_type.slug.clone(),
+				name: content_type.name.clone(),
+				description: normalize_text(&content_type.description).map(str::to_owned),
+				kind: content_type.kind,
+				workflow: find_slug(&workflows, content_type.workflow_id),
+				compartments: current_content_type
+					.compartments
+					.iter()
+					.map(|compartment| ContentModelCompartmentDTO {
+						name: compartment.name.clone(),
+						description: normalize_text(&compartment.description).map(str::to_owned),
+					})
+					.collect(),
+				fields: current_content_type
+					.fields
+					.iter()
+					.map(|current_field| ContentModelFieldDTO {
+						blocks: current_field
+							.blocks
+							.iter()
+							.map(|block| to_field_dto(block, &current_content_type.compartments))
+							.collect(),
+						..to_field_dto(current_field, &current_content_type.compartments)
+					})
+					.collect(),
+			}
+		})
+		.collect();
+
+	Ok(ContentModelDTO { content_types })
+}
+
+fn ensure_unique<'a>(
+	values: impl Iterator<Item = &'a String>,
+	description: &str,
+) -> Result<(), AppError> {
+	let mut seen = HashSet::new();
+	for value in values {
+		if value.trim().is_empty() {
+			return Err(invalid_content_model_error(format!(
+				"{description} can't be empty"
+			)));
+		}
+
+		if !seen.insert(value) {
+			return Err(invalid_content_model_error(format!(
+				"{description} {value} is used more than once"
+			)));
+		}
+	}
+
+	Ok(())
+}
+
+fn validate_content_model(document: &ContentModelDTO) -> Result<(), AppError> {
+	ensure_unique(
+		document
+			.content_types
+			.iter()
+			.map(|content_type| &content_type.slug),
+		"Content type slug",
+	)?;
+
+	for content_type in &document.content_types {
+		ensure_unique(
+			content_type
+				.compartments
+				.iter()
+				.map(|compartment| &compartment.name),
+			&format!("{}: compartment name", content_type.slug),
+		)?;
+		ensure_unique(
+			content_type.fields.iter().map(|field| &field.slug),
+			&format!("{}: field slug", content_type.slug),
+		)?;
+
+		for field in &content_type.fields {
+			if let Some(compartment) = &field.compartment {
+				if !content_type
+					.compartments
+					.iter()
+					.any(|declared| &declared.name == compartment)
+				{
+					return Err(invalid_content_model_error(format!(
+						"{}.fields.{}: compartment {compartment} is not declared",
+						content_type.slug, field.slug
+					)));
+				}
+			}
+
+			ensure_unique(
+				field.blocks.iter().map(|block| &block.slug),
+				&format!("{}.fields.{}: block slug", content_type.slug, field.slug),
+			)?;
+
+			for block in &field.blocks {
+				if block.compartment.is_some() || !block.blocks.is_empty() {
+					return Err(invalid_content_model_error(format!(
+						"{}.fields.{}.blocks.{}: blocks can't have a compartment or blocks of their own",
+						content_type.slug, field.slug, block.slug
+					)));
+				}
+			}
+		}
+	}
+
+	Ok(())
+}
+
+fn diff_field_attributes(
+	current: &CurrentField,
+	desired: &ContentModelFieldDTO,
+	current_compartment: Option<&String>,
+	sequence_number: i32,
+) -> Vec<String> {
+	let field = &current.field;
+	let mut attributes = vec![];
+
+	if field.name != desired.name {
+		attributes.push("name".to_owned());
+	}
+	if normalize_text(&field.description) != normalize_text(&desired.description) {
+		attributes.push("description".to_owned());
+	}
+	if current_compartment != desired.compartment.as_ref() {
+		attributes.push("compartment".to_owned());
+	}
+	if field.min != desired.min {
+		attributes.push("min".to_owned());
+	}
+	if field.max != desired.max {
+		attributes.push("max".to_owned());
+	}
+	if field.hidden != desired.hidden {
+		attributes.push("hidden".to_owned());
+	}
+	if field.multi_language != desired.multi_language {
+		attributes.push("multiLanguage".to_owned());
+	}
+	if normalize_value(&field.validation) != normalize_value(&desired.validation) {
+		attributes.push("validation".to_owned());
+	}
+	if field.sequence_number != Some(sequence_number) {
+		attributes.push("sequence".to_owned());
+	}
+
+	let config_keys = current
+		.config
+		.keys()
+		.chain(desired.config.keys())
+		.collect::<BTreeSet<&String>>();
+	for key in config_keys {
+		if current.config.get(key) != desired.config.get(key) {
+			attributes.push(format!("config.{key}"));
+		}
+	}
+
+	attributes
+}
+
+#[allow(clippy::too_many_arguments)]
+fn diff_fields(
+	plan: &mut ContentModelPlan,
+	components: &HashMap<String, Uuid>,
+	content_type: &str,
+	parent_field: Option<&str>,
+	path: &str,
+	desired_fields: &[ContentModelFieldDTO],
+	current_fields: &[CurrentField],
+	current_compartments: &[CompartmentModel],
+) -> Result<(), AppError> {
+	let (resource, segment) = match parent_field {
+		Some(_) => (ContentModelResourceEnum::BLOCK, "blocks"),
+		None => (ContentModelResourceEnum::FIELD, "fields"),
+	};
+
+	for (index, desired) in desired_fields.iter().enumerate() {
+		let field_path = format!("{path}.{segment}.{}", desired.slug);
+		let sequence_number = index as i32;
+		let content_component_id = *components.get(&desired.component).ok_or_else(|| {
+			invalid_content_model_error(format!(
+				"{field_path}: content component {} does not exist",
+				desired.component
+			))
+		})?;
+		let field = ContentModelFieldDTO {
+			blocks: vec![],
+			..desired.clone()
+		};
+
+		let Some(current) = current_fields
+			.iter()
+			.find(|current| current.field.slug == desired.slug)
+		else {
+			plan.push(
+				ContentModelChangeActionEnum::CREATE,
+				resource.clone(),
+				field_path.clone(),
+				vec![],
+				ContentModelOperation::CreateField {
+					content_type: content_type.to_owned(),
+					parent_field: parent_field.map(str::to_owned),
+					field,
+					content_component_id,
+					sequence_number,
+				},
+			);
+			diff_fields(
+				plan,
+				components,
+				content_type,
+				Some(&desired.slug),
+				&field_path,
+				&desired.blocks,
+				&[],
+				current_compartments,
+			)?;
+			continue;
+		};
+
+		if current.field.content_component_id != content_component_id {
+			return Err(invalid_content_model_error(format!(
+				"{field_path}: the content component of a field can't be changed, remove the field and add it under another slug"
+			)));
+		}
+
+		let current_compartment = current.field.compartment_id.and_then(|compartment_id| {
+			current_compartments
+				.iter()
+				.find(|compartment| compartment.id == compartment_id)
+				.map(|compartment| &compartment.name)
+		});
+		let attributes =
+			diff_field_attributes(current, desired, current_compartment, sequence_number);
+		if !attributes.is_empty() {
+			plan.push(
+				ContentModelChangeActionEnum::UPDATE,
+				resource.clone(),
+				field_path.clone(),
+				attributes,
+				ContentModelOperation::UpdateField {
+					id: current.field.id,
+					content_type: content_type.to_owned(),
+					field,
+					sequence_number,
+				},
+			);
+		}
+
+		diff_fields(
+			plan,
+			components,
+			content_type,
+			Some(&desired.slug),
+			&field_path,
+			&desired.blocks,
+			&current.blocks,
+			current_compartments,
+		)?;
+	}
+
+	for current in current_fields {
+		if desired_fields
+			.iter()
+			.any(|desired| desired.slug == current.field.slug)
+		{
+			continue;
+		}
+
+		let field_path = format!("{path}.{segment}.{}", current.field.slug);
+		// Blocks aren't removed together with their field, so they are listed on their own
+		for block in &current.blocks {
+			plan.push(
+				ContentModelChangeActionEnum::REMOVE,
+				ContentModelResourceEnum::BLOCK,
+				format!("{field_path}.blocks.{}", block.field.slug),
+				vec![],
+				ContentModelOperation::RemoveField { id: block.field.id },
+			);
+		}
+		plan.push(
+			ContentModelChangeActionEnum::REMOVE,
+			resource.clone(),
+			field_path,
+			vec![],
+			ContentModelOperation::RemoveField {
+				id: current.field.id,
+			},
+		);
+	}
+
+	Ok(())
+}
+
+// Compares the document with the content types enabled for the site. Content types are matched on slug,
+// compartments on name and fields on slug within their parent; anything the document doesn't mention is removed.
+#[instrument(skip(conn, document))]
+pub fn plan_content_model(
+	conn: &mut PgConnection,
+	site_id: Uuid,
+	document: &ContentModelDTO,
+) -> Result<ContentModelPlan, AppError> {
+	validate_content_model(document)?;
+
+	let current = find_current(conn, site_id)?;
+	let (components, workflows) = find_slugs(conn)?;
+	let mut plan = ContentModelPlan::default();
+
+	for current_content_type in &current {
+		let slug = &current_content_type.content_type.slug;
+		plan.content_type_ids
+			.insert(slug.clone(), current_content_type.content_type.id);
+
+		for compartment in &current_content_type.compartments {
+			plan.compartment_ids
+				.insert((slug.clone(), compartment.name.clone()), compartment.id);
+		}
+		for field in &current_content_type.fields {
+			plan.field_ids
+				.insert((slug.clone(), field.field.slug.clone()), field.field.id);
+		}
+	}
+
+	for desired in &document.content_types {
+		let path = desired.slug.clone();
+		let workflow_id = *workflows.get(&desired.workflow).ok_or_else(|| {
+			invalid_content_model_error(format!(
+				"{path}: workflow {} does not exist",
+				desired.workflow
+			))
+		})?;
+		let current_content_type = current
+			.iter()
+			.find(|current| current.content_type.slug == desired.slug);
+
+		match current_content_type {
+			None => plan.push(
+				ContentModelChangeActionEnum::CREATE,
+				ContentModelResourceEnum::CONTENT_TYPE,
+				path.clone(),
+				vec![],
+				ContentModelOperation::CreateContentType {
+					content_type: ContentModelContentTypeDTO {
+						compartments: vec![],
+						fields: vec![],
+						..desired.clone()
+					},
+					workflow_id,
+				},
+			),
+			Some(current) => {
+				let content_type = &current.content_type;
+				if content_type.kind != desired.kind {
+					return Err(invalid_content_model_error(format!(
+						"{path}: the kind of a content type can't be changed"
+					)));
+				}
+				if content_type.workflow_id != workflow_id {
+					return Err(invalid_content_model_error(format!(
+						"{path}: the workflow of a content type can't be changed"
+					)));
+				}
+
+				let mut attributes = vec![];
+				if content_type.name != desired.name {
+					attributes.push("name".to_owned());
+				}
+				if normalize_text(&content_type.description) != normalize_text(&desired.description)
+				{
+					attributes.push("description".to_owned());
+				}
+				if !attributes.is_empty() {
+					plan.push(
+						ContentModelChangeActionEnum::UPDATE,
+						ContentModelResourceEnum::CONTENT_TYPE,
+						path.clone(),
+						attributes,
+						ContentModelOperation::UpdateContentType {
+							id: content_type.id,
+							name: desired.name.clone(),
+							description: desired.description.clone(),
+						},
+					);
+				}
+			}
+		}
+
+		let current_compartments = current_content_type
+			.map(|current| current.compartments.as_slice())
+			.unwrap_or_default();
+		let current_fields = current_content_type
+			.map(|current| current.fields.as_slice())
+			.unwrap_or_default();
+
+		for compartment in &desired.compartments {
+			let compartment_path = format!("{path}.compartments.{}", compartment.name);
+
+			match current_compartments
+				.iter()
+				.find(|current| current.name == compartment.name)
+			{
+				None => plan.push(
+					ContentModelChangeActionEnum::CREATE,
+					ContentModelResourceEnum::COMPARTMENT,
+					compartment_path,
+					vec![],
+					ContentModelOperation::CreateCompartment {
+						content_type: desired.slug.clone(),
+						compartment: compartment.clone(),
+					},
+				),
+				Some(current) => {
+					if normalize_text(&current.description)
+						!= normalize_text(&compartment.description)
+					{
+						plan.push(
+							ContentModelChangeActionEnum::UPDATE,
+							ContentModelResourceEnum::COMPARTMENT,
+							compartment_path,
+							vec!["description".to_owned()],
+							ContentModelOperation::UpdateCompartment {
+								id: current.id,
+								description: compartment.description.clone(),
+							},
+						);
+					}
+				}
+			}
+		}
+
+		diff_fields(
+			&mut plan,
+			&components,
+			&desired.slug,
+			None,
+			&path,
+			&desired.fields,
+			current_fields,
+			current_compartments,
+		)?;
+
+		// Compartments go last so the fields have moved out of them by then
+		for current in current_compartments {
+			if desired
+				.compartments
+				.iter()
+				.any(|compartment| compartment.name == current.name)
+			{
+				continue;
+			}
+
+			plan.push(
+				ContentModelChangeActionEnum::REMOVE,
+				ContentModelResourceEnum::COMPARTMENT,
+				format!("{path}.compartments.{}", current.name),
+				vec![],
+				ContentModelOperation::RemoveCompartment { id: current.id },
+			);
+		}
+	}
+
+	for current_content_type in &current {
+		let content_type = &current_content_type.content_type;
+		if document
+			.content_types
+			.iter()
+			.any(|desired| desired.slug == content_type.slug)
+		{
+			continue;
+		}
+
+		plan.push(
+			ContentModelChangeActionEnum::REMOVE,
+			ContentModelResourceEnum::CONTENT_TYPE,
+			content_type.slug.clone(),
+			vec![],
+			ContentModelOperation::RemoveContentType {
+				id: content_type.id,
+			},
+		);
+	}
+
+	Ok(plan)
+}
+
+fn update_field(
+	conn: &mut PgConnection,
+	site_id: Uuid,
+	id: Uuid,
+	field: &ContentModelFieldDTO,
+	compartment_id: Option<Uuid>,
+	sequence_number: i32,
+) -> Result<(), AppError> {
+	// Stored content of content type fields follows when they switch between a single value and a list,
+	// or between shared and translated values
+	let field_type = fields::table
+		.find(id)
+		.select(fields::field_type)
+		.first::<FieldTypeEnum>(conn)?;
+	if field_type == FieldTypeEnum::ContentTypeField {
+		let migration_plan = plan_field_migration(
+			conn,
+			site_id,
+			id,
+			FieldMigration {
+				min: Some(field.min),
+				max: Some(field.max),
+				multi_language: Some(field.multi_language),
+				..Default::default()
+			},
+		)?;
+		if !migration_plan.steps.is_empty() {
+			apply_field_migration(conn, site_id, &migration_plan)?;
+		}
+	}
+
+	FieldConfig::upsert(conn, id, field.config.clone().into_iter().collect())?;
+	FieldModel::update(
+		conn,
+		site_id,
+		id,
+		UpdateField {
+			name: Some(field.name.clone()),
+			slug: Some(field.slug.clone()),
+			description: Some(field.description.clone().unwrap_or_default()),
+			min: Some(field.min),
+			max: Some(field.max),
+			hidden: Some(field.hidden),
+			multi_language: Some(field.multi_language),
+			compartment_id: None,
+			sequence_number: Some(sequence_number),
+			validation: Some(field.validation.clone().unwrap_or(Value::Null)),
+		},
+	)?;
+	FieldModel::update_compartment(conn, id, compartment_id)?;
+
+	Ok(())
+}
+
+// Executes the plan in order, in one transaction so a failing change leaves the content model untouched
+#[instrument(skip(conn, plan))]
+pub fn apply_content_model(
+	conn: &mut PgConnection,
+	site_id: Uuid,
+	user_id: Uuid,
+	plan: &ContentModelPlan,
+) -> Result<(), AppError> {
+	let mut content_type_ids = plan.content_type_ids.clone();
+	let mut compartment_ids = plan.compartment_ids.clone();
+	let mut field_ids = plan.field_ids.clone();
+	let mut created_content_type_ids = vec![];
+
+	conn.transaction::<_, AppError, _>(|conn| {
+		for change in &plan.changes {
+			match &change.operation {
+				ContentModelOperation::CreateContentType {
+					content_type,
+					workflow_id,
+				} => {
+					let created = ContentType::create(
+						conn,
+						site_id,
+						CreateContentType {
+							name: content_type.name.clone(),
+							description: content_type.description.clone().unwrap_or_default(),
+							slug: content_type.slug.clone(),
+							workflow_id: *workflow_id,
+							kind: content_type.kind,
+						},
+					)?;
+					content_type_ids.insert(content_type.slug.clone(), created.id);
+					created_content_type_ids.push(created.id);
+				}
+				ContentModelOperation::UpdateContentType {
+					id,
+					name,
+					description,
+				} => {
+					ContentType::update(
+						conn,
+						site_id,
+						*id,
+						UpdateContentType {
+							name: Some(name.clone()),
+							description: Some(description.clone().unwrap_or_default()),
+						},
+					)?;
+				}
+				ContentModelOperation::RemoveContentType { id } => {
+					ContentType::remove(conn, *id)?;
+				}
+				ContentModelOperation::CreateCompartment {
+					content_type,
+					compartment,
+				} => {
+					let created = CompartmentModel::create(
+						conn,
+						site_id,
+						content_type_ids[content_type],
+						&compartment.name,
+					)?;
+					if compartment.description.is_some() {
+						CompartmentModel::update(
+							conn,
+							site_id,
+							created.id,
+							UpdateCompartment {
+								name: None,
+								description: compartment.description.clone(),
+							},
+						)?;
+					}
+					compartment_ids
+						.insert((content_type.clone(), compartment.name.clone()), created.id);
+				}
+				ContentModelOperation::UpdateCompartment { id, description } => {
+					CompartmentModel::update(
+						conn,
+						site_id,
+						*id,
+						UpdateCompartment {
+							name: None,
+							description: Some(description.clone().unwrap_or_default()),
+						},
+					)?;
+				}
+				ContentModelOperation::RemoveCompartment { id } => {
+					CompartmentModel::remove(conn, *id)?;
+				}
+				ContentModelOperation::CreateField {
+					content_type,
+					parent_field,
+					field,
+					content_component_id,
+					sequence_number,
+				} => {
+					let parent_id = match parent_field {
+						Some(parent_field) => {
+							field_ids[&(content_type.clone(), parent_field.clone())]
+						}
+						None => content_type_ids[content_type],
+					};
+					let compartment_id = field.compartment.as_ref().map(|compartment| {
+						compartment_ids[&(content_type.clone(), compartment.clone())]
+					});
+					let (created, _, _) = FieldModel::create(
+						conn,
+						site_id,
+						parent_id,
+						*content_component_id,
+						compartment_id,
+						FieldTypeEnum::ContentTypeField,
+						&field.name,
+					)?;
+					update_field(
+						conn,
+						site_id,
+						created.id,
+						field,
+						compartment_id,
+						*sequence_number,
+					)?;
+
+					if parent_field.is_none() {
+						field_ids.insert((content_type.clone(), field.slug.clone()), created.id);
+					}
+				}
+				ContentModelOperation::UpdateField {
+					id,
+					content_type,
+					field,
+					sequence_number,
+				} => {
+					let compartment_id = field.compartment.as_ref().map(|compartment| {
+						compartment_ids[&(content_type.clone(), compartment.clone())]
+					});
+					update_field(conn, site_id, *id, field, compartment_id, *sequence_number)?;
+				}
+				ContentModelOperation::RemoveField { id } => {
+					FieldModel::remove(conn, *id)?;
+				}
+			}
+		}
+
+		// Singletons are created once the fields of their content type are in place
+		for content_type_id in &created_content_type_ids {
+			Content::create_singletons(conn, user_id, site_id, *content_type_id)?;
+		}
+
+		Ok(())
+	})
+}
@@ -0,0 +1,592 @@
+use diesel::prelude::*;
+use diesel::sql_query;
+use diesel::sql_types::{Array, BigInt, Nullable, Text, Uuid as SqlUuid};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+use tracing::instrument;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+	errors::{AppError, AppErrorValue},
+	modules::{
+		content::models::content_field::ContentField,
+		content_components::{
+			enums::data_type::DataTypeEnum, models::content_component::ContentComponent,
+		},
+		content_types::models::{
+			content_type_field_group::ContentTypeFieldGroup,
+			field::{FieldModel, FieldTypeEnum, UpdateField},
+		},
+	},
+	schema::content_fields,
+};
+
+// Stored content of a field is found through its root rows: `content_fields` without a parent, named after the field slug
+// and belonging to a revision of a content item of the field's content type, or of a content type including its field group
+const ROOT_CONTENT_FIELDS_QUERY: &str = "
+	SELECT DISTINCT f.*
+	FROM content_fields f
+	JOIN content_revisions r ON f.source_id IN (r.id, r.revision_translation_id)
+	JOIN content c ON c.id = r.content_id
+	WHERE f.parent_id IS NULL AND f.name = $1 AND c.content_type_id = ANY($2)";
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[allow(non_camel_case_types)]
+pub enum FieldMigrationStepKindEnum {
+	RENAME,
+	CONVERT,
+	WRAP_MULTIPLE,
+	UNWRAP_MULTIPLE,
+	SHARE_TRANSLATIONS,
+	SPLIT_TRANSLATIONS,
+	PURGE,
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldMigrationStep {
+	pub kind: FieldMigrationStepKindEnum,
+	pub message: String,
+	// Whether stored values can't be fully carried over
+	pub data_loss: bool,
+	// Blocking steps need `purge` before the migration can be applied
+	pub blocking: bool,
+}
+
+#[derive(Debug, Clone, Default, QueryableByName)]
+pub struct FieldImpact {
+	#[diesel(sql_type = BigInt)]
+	pub content_items: i64,
+	#[diesel(sql_type = BigInt)]
+	pub revisions: i64,
+	#[diesel(sql_type = BigInt)]
+	pub content_fields: i64,
+}
+
+// The latest revision of a translation, with the root of the field in the latest revision of the
+// original language that it takes over
+#[derive(Debug, Clone, QueryableByName)]
+struct SharedTranslation {
+	#[diesel(sql_type = SqlUuid)]
+	revision_id: Uuid,
+	#[diesel(sql_type = SqlUuid)]
+	revision_translation_id: Uuid,
+	#[diesel(sql_type = Nullable<SqlUuid>)]
+	original_root_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FieldMigration {
+	pub slug: Option<String>,
+	pub content_component_id: Option<Uuid>,
+	pub min: Option<i32>,
+	pub max: Option<i32>,
+	pub multi_language: Option<bool>,
+	// Drops stored values that can't be migrated instead of refusing the migration
+	pub purge: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldMigrationPlan {
+	pub field: FieldModel,
+	pub migration: FieldMigration,
+	pub impact: FieldImpact,
+	pub steps: Vec<FieldMigrationStep>,
+	from_data_type: DataTypeEnum,
+	to_data_type: DataTypeEnum,
+}
+
+impl FieldMigrationPlan {
+	pub fn is_blocked(&self) -> bool {
+		self.steps.iter().any(|step| step.blocking)
+	}
+
+	fn has_step(&self, kind: FieldMigrationStepKindEnum) -> bool {
+		self.steps.iter().any(|step| step.kind == kind)
+	}
+}
+
+fn is_scalar(data_type: &DataTypeEnum) -> bool {
+	matches!(
+		data_type,
+		DataTypeEnum::TEXT | DataTypeEnum::NUMBER | DataTypeEnum::BOOLEAN | DataTypeEnum::REFERENCE
+	)
+}
+
+// Mirrors how `upsert_fields` decides to store a list of values, blocks always have their own structure
+fn is_multiple(min: i32, max: i32, data_type: &DataTypeEnum) -> bool {
+	(min != 1 || max != 1) && *data_type != DataTypeEnum::BLOCK
+}
+
+fn is_lossless_conversion(from: &DataTypeEnum, to: &DataTypeEnum) -> bool {
+	from == to
+		|| matches!(to, DataTypeEnum::TEXT)
+		|| matches!((from, to), (DataTypeEnum::TEXT, DataTypeEnum::REFERENCE))
+}
+
+// Values that don't make sense in the new data type become null
+fn convert_value(value: &Value, to: &DataTypeEnum) -> Value {
+	match (to, value) {
+		(_, Value::Null) => Value::Null,
+		(DataTypeEnum::TEXT | DataTypeEnum::REFERENCE, Value::String(_)) => value.clone(),
+		(DataTypeEnum::TEXT | DataTypeEnum::REFERENCE, Value::Number(number)) => {
+			Value::String(number.to_string())
+		}
+		(DataTypeEnum::TEXT | DataTypeEnum::REFERENCE, Value::Bool(boolean)) => {
+			Value::String(boolean.to_string())
+		}
+		(DataTypeEnum::NUMBER, Value::Number(_)) => value.clone(),
+		(DataTypeEnum::NUMBER, Value::String(text)) => {
+			let text = text.trim();
+			text.parse::<i64>()
+				.map(Number::from)
+				.ok()
+				.or_else(|| text.parse::<f64>().ok().and_then(Number::from_f64))
+				.map(Value::Number)
+				.unwrap_or(Value::Null)
+		}
+		(DataTypeEnum::NUMBER, Value::Bool(boolean)) => {
+			Value::Number(Number::from(*boolean as i64))
+		}
+		(DataTypeEnum::BOOLEAN, Value::Bool(_)) => value.clone(),
+		(DataTypeEnum::BOOLEAN, Value::String(text)) => match text.trim().to_lowercase().as_str() {
+			"true" | "1" | "yes" => Value::Bool(true),
+			"false" | "0" | "no" | "" => Value::Bool(false),
+			_ => Value::Null,
+		},
+		(DataTypeEnum::BOOLEAN, Value::Number(number)) => {
+			Value::Bool(number.as_f64().map(|number| number != 0.0).unwrap_or(false))
+		}
+		_ => Value::Null,
+	}
+}
+
+// Content is never stored for a field group itself, only for the content types including it
+fn find_content_type_ids(
+	conn: &mut PgConnection,
+	field: &FieldModel,
+) -> Result<Vec<Uuid>, AppError> {
+	let mut content_type_ids = vec![field.parent_id];
+	content_type_ids.extend(ContentTypeFieldGroup::find_consumers(
+		conn,
+		field.parent_id,
+	)?);
+
+	Ok(content_type_ids)
+}
+
+fn find_roots(conn: &mut PgConnection, field: &FieldModel) -> Result<Vec<ContentField>, AppError> {
+	let roots = sql_query(ROOT_CONTENT_FIELDS_QUERY)
+		.bind::<Text, _>(&field.slug)
+		.bind::<Array<SqlUuid>, _>(find_content_type_ids(conn, field)?)
+		.load::<ContentField>(conn)?;
+
+	Ok(roots)
+}
+
+// The item created first is the original of its translations
+fn find_shared_translations(
+	conn: &mut PgConnection,
+	field: &FieldModel,
+) -> Result<Vec<SharedTranslation>, AppError> {
+	let shared_translations = sql_query(
+		"
+		WITH latest AS (
+			SELECT DISTINCT ON (c.id)
+				c.id AS content_id, c.translation_id, c.created_at,
+				r.id AS revision_id, r.revision_translation_id
+			FROM content c
+			JOIN content_revisions r ON r.content_id = c.id
+			WHERE c.content_type_id = ANY($2)
+			ORDER BY c.id, r.created_at DESC
+		),
+		originals AS (
+			SELECT DISTINCT ON (translation_id) *
+			FROM latest
+			ORDER BY translation_id, created_at, content_id
+		)
+		SELECT l.revision_id, l.revision_translation_id, f.id AS original_root_id
+		FROM latest l
+		JOIN originals o ON o.translation_id = l.translation_id AND o.content_id <> l.content_id
+		LEFT JOIN content_fields f ON f.parent_id IS NULL AND f.name = $1
+			AND f.source_id IN (o.revision_id, o.revision_translation_id)",
+	)
+	.bind::<Text, _>(&field.slug)
+	.bind::<Array<SqlUuid>, _>(find_content_type_ids(conn, field)?)
+	.load::<SharedTranslation>(conn)?;
+
+	Ok(shared_translations)
+}
+
+// Copies a stored value with all of its nested rows to another revision
+fn copy_tree(conn: &mut PgConnection, root_id: Uuid, source_id: Uuid) -> Result<(), AppError> {
+	sql_query(
+		"
+		WITH RECURSIVE tree AS (
+			SELECT id FROM content_fields WHERE id = $1
+			UNION ALL
+			SELECT f.id FROM content_fields f JOIN tree ON f.parent_id = tree.id
+		),
+		copies AS (
+			SELECT id, uuid_generate_v4() AS copy_id FROM tree
+		)
+		INSERT INTO content_fields (id, name, value, parent_id, source_id, content_component_id, sequence_number, data_type)
+		SELECT c.copy_id, f.name, f.value, p.copy_id, $2, f.content_component_id, f.sequence_number, f.data_type
+		FROM content_fields f
+		JOIN copies c ON c.id = f.id
+		LEFT JOIN copies p ON p.id = f.parent_id",
+	)
+	.bind::<SqlUuid, _>(root_id)
+	.bind::<SqlUuid, _>(source_id)
+	.execute(conn)?;
+
+	Ok(())
+}
+
+fn delete_trees(conn: &mut PgConnection, ids: Vec<Uuid>) -> Result<usize, AppError> {
+	let deleted_rows = sql_query(
+		"
+		WITH RECURSIVE tree AS (
+			SELECT id FROM content_fields WHERE id = ANY($1)
+			UNION ALL
+			SELECT f.id FROM content_fields f JOIN tree ON f.parent_id = tree.id
+		)
+		DELETE FROM content_fields WHERE id IN (SELECT id FROM tree)",
+	)
+	.bind::<Array<SqlUuid>, _>(ids)
+	.execute(conn)?;
+
+	Ok(deleted_rows)
+}
+
+// Only content type fields are stored by slug at the root of a revision, block and component fields are nested in them
+fn ensure_migratable(field: &FieldModel) -> Result<(), AppError> {
+	if field.field_type == FieldTypeEnum::ContentTypeField {
+		return Ok(());
+	}
+
+	Err(AppError::UnprocessableEntity(AppErrorValue {
+		message: "Only content type fields can be migrated".to_owned(),
+		status: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+		code: "FIELD_MIGRATION_NOT_SUPPORTED".to_owned(),
+		..Default::default()
+	}))
+}
+
+#[instrument(skip(conn))]
+pub fn find_field_impact(
+	conn: &mut PgConnection,
+	field: &FieldModel,
+) -> Result<FieldImpact, AppError> {
+	let impact = sql_query(
+		"
+		SELECT
+			COUNT(DISTINCT c.id) AS content_items,
+			COUNT(DISTINCT r.id) AS revisions,
+			COUNT(DISTINCT f.id) AS content_fields
+		FROM content_fields f
+		JOIN content_revisions r ON f.source_id IN (r.id, r.revision_translation_id)
+		JOIN content c ON c.id = r.content_id
+		WHERE f.parent_id IS NULL AND f.name = $1 AND c.content_type_id = ANY($2)",
+	)
+	.bind::<Text, _>(&field.slug)
+	.bind::<Array<SqlUuid>, _>(find_content_type_ids(conn, field)?)
+	.get_result::<FieldImpact>(conn)?;
+
+	Ok(impact)
+}
+
+// Works out what happens to the stored content when the field changes, nothing is written yet
+#[instrument(skip(conn))]
+pub fn plan_field_migration(
+	conn: &mut PgConnection,
+	site_id: Uuid,
+	field_id: Uuid,
+	migration: FieldMigration,
+) -> Result<FieldMigrationPlan, AppError> {
+	let (field, populated_content_component, _) = FieldModel::find_one(conn, site_id, field_id)?;
+	ensure_migratable(&field)?;
+
+	let content_component_id = migration
+		.content_component_id
+		.unwrap_or(field.content_component_id);
+	let from_data_type = populated_content_component.content_component.data_type;
+	let to_data_type = if content_component_id != field.content_component_id {
+		ContentComponent::find_one(conn, Some(site_id), content_component_id)?.data_type
+	} else {
+		from_data_type.clone()
+	};
+	let impact = find_field_impact(conn, &field)?;
+	let has_content = impact.content_fields > 0;
+	let mut steps = vec![];
+
+	if let Some(slug) = migration.slug.as_ref().filter(|slug| **slug != field.slug) {
+		ContentTypeFieldGroup::ensure_field_slug_available(
+			conn,
+			field.parent_id,
+			Some(field.id),
+			slug,
+		)?;
+
+		steps.push(FieldMigrationStep {
+			kind: FieldMigrationStepKindEnum::RENAME,
+			message: format!("Stored values move from {} to {slug}", field.slug),
+			data_loss: false,
+			blocking: false,
+		});
+	}
+
+	let component_changed = content_component_id != field.content_component_id;
+	let convertible = is_scalar(&from_data_type) && is_scalar(&to_data_type);
+	if component_changed && convertible {
+		steps.push(FieldMigrationStep {
+			kind: FieldMigrationStepKindEnum::CONVERT,
+			message: format!(
+				"Stored values are converted from {from_data_type:?} to {to_data_type:?}"
+			),
+			data_loss: !is_lossless_conversion(&from_data_type, &to_data_type),
+			blocking: false,
+		});
+	}
+
+	if component_changed && !convertible && has_content {
+		steps.push(FieldMigrationStep {
+			kind: FieldMigrationStepKindEnum::PURGE,
+			message: format!(
+				"Stored values can't be converted from {from_data_type:?} to {to_data_type:?} and are removed"
+			),
+			data_loss: true,
+			blocking: !migration.purge,
+		});
+	}
+
+	// Values that are purged don't need to change shape
+	let was_multiple = is_multiple(field.min, field.max, &from_data_type);
+	let will_be_multiple = is_multiple(
+		migration.min.unwrap_or(field.min),
+		migration.max.unwrap_or(field.max),
+		&to_data_type,
+	);
+	if !component_changed || convertible {
+		if !was_multiple && will_be_multiple {
+			steps.push(FieldMigrationStep {
+				kind: FieldMigrationStepKindEnum::WRAP_MULTIPLE,
+				message: "Stored values become the first item of a list".to_owned(),
+				data_loss: false,
+				blocking: false,
+			});
+		}
+
+		if was_multiple && !will_be_multiple {
+			steps.push(FieldMigrationStep {
+				kind: FieldMigrationStepKindEnum::UNWRAP_MULTIPLE,
+				message: "Only the first item of stored lists is kept".to_owned(),
+				data_loss: true,
+				blocking: has_content && !migration.purge,
+			});
+		}
+	}
+
+	match migration.multi_language {
+		Some(false) if field.multi_language && has_content => {
+			steps.push(FieldMigrationStep {
+				kind: FieldMigrationStepKindEnum::SHARE_TRANSLATIONS,
+				message: "Translations take over the stored values of their original language"
+					.to_owned(),
+				data_loss: true,
+				blocking: !migration.purge,
+			});
+		}
+		Some(true) if !field.multi_language && has_content => {
+			steps.push(FieldMigrationStep {
+				kind: FieldMigrationStepKindEnum::SPLIT_TRANSLATIONS,
+				message: "Translations keep their current values and can be changed separately"
+					.to_owned(),
+				data_loss: false,
+				blocking: false,
+			});
+		}
+		_ => {}
+	}
+
+	Ok(FieldMigrationPlan {
+		field,
+		migration,
+		impact,
+		steps,
+		from_data_type,
+		to_data_type,
+	})
+}
+
+// Runs the data migrations before updating the field so the stored content matches the new definition.
+// Content is converted first, then reshaped and shared between translations, and renamed last since the earlier
+// steps look it up by the old slug.
+#[instrument(skip(conn, plan))]
+pub fn apply_field_migration(
+	conn: &mut PgConnection,
+	site_id: Uuid,
+	plan: &FieldMigrationPlan,
+) -> Result<(), AppError> {
+	if plan.is_blocked() {
+		return Err(AppError::UnprocessableEntity(AppErrorValue {
+			message: "Stored values would be lost, set purge to remove them".to_owned(),
+			status: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+			code: "FIELD_MIGRATION_BLOCKED".to_owned(),
+			..Default::default()
+		}));
+	}
+
+	let field = &plan.field;
+	let content_component_id = plan
+		.migration
+		.content_component_id
+		.unwrap_or(field.content_component_id);
+
+	conn.transaction::<_, AppError, _>(|conn| {
+		let roots = find_roots(conn, field)?;
+		let root_ids = roots.iter().map(|root| root.id).collect::<Vec<Uuid>>();
+
+		if plan.has_step(FieldMigrationStepKindEnum::PURGE) {
+			delete_trees(conn, root_ids.clone())?;
+		}
+
+		if plan.has_step(FieldMigrationStepKindEnum::CONVERT) {
+			// Lists keep their values one level down
+			let items = if is_multiple(field.min, field.max, &plan.from_data_type) {
+				content_fields::table
+					.filter(content_fields::parent_id.eq_any(&root_ids))
+					.select(ContentField::as_select())
+					.load::<ContentField>(conn)?
+			} else {
+				roots.clone()
+			};
+
+			for item in items {
+				let value = item
+					.value
+					.as_ref()
+					.map(|value| convert_value(value, &plan.to_data_type));
+				diesel::update(content_fields::table.find(item.id))
+					.set((
+						content_fields::value.eq(value),
+						content_fields::data_type.eq(plan.to_data_type.clone()),
+						content_fields::content_component_id.eq(Some(content_component_id)),
+					))
+					.execute(conn)?;
+			}
+			diesel::update(content_fields::table.filter(content_fields::id.eq_any(&root_ids)))
+				.set(content_fields::content_component_id.eq(Some(content_component_id)))
+				.execute(conn)?;
+		}
+
+		if plan.has_step(FieldMigrationStepKindEnum::WRAP_MULTIPLE) {
+			sql_query(
+				"
+				WITH roots AS (
+					SELECT id, source_id FROM content_fields WHERE id = ANY($1)
+				),
+				parents AS (
+					INSERT INTO content_fields (name, value, parent_id, source_id, content_component_id, sequence_number, data_type)
+					SELECT $2, NULL, NULL, roots.source_id, $3, NULL, 'ARRAY'::data_types FROM roots
+					RETURNING id, source_id
+				)
+				UPDATE content_fields SET parent_id = parents.id, name = '0', sequence_number = 0
+				FROM roots JOIN parents ON parents.source_id = roots.source_id
+				WHERE content_fields.id = roots.id",
+			)
+			.bind::<Array<SqlUuid>, _>(&root_ids)
+			.bind::<Text, _>(&field.slug)
+			.bind::<SqlUuid, _>(content_component_id)
+			.execute(conn)?;
+		}
+
+		if plan.has_step(FieldMigrationStepKindEnum::UNWRAP_MULTIPLE) {
+			sql_query(
+				"
+				WITH kept AS (
+					SELECT DISTINCT ON (parent_id) id
+					FROM content_fields
+					WHERE parent_id = ANY($1)
+					ORDER BY parent_id, sequence_number NULLS LAST, name
+				)
+				UPDATE content_fields SET parent_id = NULL, name = $2, sequence_number = NULL
+				FROM kept
+				WHERE content_fields.id = kept.id",
+			)
+			.bind::<Array<SqlUuid>, _>(&root_ids)
+			.bind::<Text, _>(&field.slug)
+			.execute(conn)?;
+			delete_trees(conn, root_ids.clone())?;
+		}
+
+		// Every revision already stores its own values, so splitting needs no changes
+		if plan.has_step(FieldMigrationStepKindEnum::SHARE_TRANSLATIONS) {
+			let shared_translations = find_shared_translations(conn, field)?;
+			let translated_roots = find_roots(conn, field)?
+				.into_iter()
+				.filter(|root| {
+					shared_translations.iter().any(|translation| {
+						root.source_id == translation.revision_id
+							|| root.source_id == translation.revision_translation_id
+					})
+				})
+				.map(|root| root.id)
+				.collect::<Vec<Uuid>>();
+			delete_trees(conn, translated_roots)?;
+
+			for translation in shared_translations {
+				if let Some(original_root_id) = translation.original_root_id {
+					copy_tree(conn, original_root_id, translation.revision_id)?;
+				}
+			}
+		}
+
+		let slug = plan
+			.migration
+			.slug
+			.clone()
+			.unwrap_or_else(|| field.slug.clone());
+		if plan.has_step(FieldMigrationStepKindEnum::RENAME) {
+			// Unwrapped lists have new root rows, so these are looked up again
+			let roots = find_roots(conn, field)?;
+			diesel::update(
+				content_fields::table
+					.filter(content_fields::id.eq_any(roots.iter().map(|root| root.id))),
+			)
+			.set(content_fields::name.eq(&slug))
+			.execute(conn)?;
+		}
+
+		FieldModel::update_content_component(conn, field.id, content_component_id)?;
+		FieldModel::update(
+			conn,
+			site_id,
+			field.id,
+			UpdateField {
+				name: None,
+				slug: Some(slug),
+				description: None,
+				min: plan.migration.min,
+				max: plan.migration.max,
+				hidden: None,
+				multi_language: plan.migration.multi_language,
+				compartment_id: None,
+				sequence_number: None,
+				validation: None,
+			},
+		)?;
+
+		Ok(())
+	})
+}
+
+// Removes everything stored for the field, used when a field is removed together with its data
+#[instrument(skip(conn))]
+pub fn purge_field_content(conn: &mut PgConnection, field: &FieldModel) -> Result<usize, AppError> {
+	ensure_migratable(field)?;
+
+	let roots = find_roots(conn, field)?;
+	delete_trees(conn, roots.into_iter().map(|root| root.id).collect())
+}
@@ -0,0 +1,2 @@
+pub mod content_model;
+pub mod field_migration;
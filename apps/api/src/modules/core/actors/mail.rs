@@ -0,0 +1,34 @@
+use actix::prelude::*;
+use tracing::error;
+
+use crate::{
+	errors::AppError,
+	modules::core::mail::lib::{get_mail_transport, Mail},
+};
+
+pub struct MailActor;
+
+#[derive(Message)]
+#[rtype(result = "Result<(), AppError>")]
+pub struct MailMessage {
+	pub mail: Mail,
+}
+
+impl Actor for MailActor {
+	type Context = SyncContext<Self>;
+}
+
+impl Handler<MailMessage> for MailActor {
+	type Result = Result<(), AppError>;
+
+	fn handle(&mut self, msg: MailMessage, _sctx: &mut SyncContext<Self>) -> Self::Result {
+		let result = get_mail_transport().and_then(|transport| transport.send(&msg.mail));
+
+		// Mails are sent with `do_send`, so failures would otherwise go unnoticed
+		if let Err(err) = &result {
+			error!(to = %msg.mail.to, subject = %msg.mail.subject, "failed to send mail: {:?}", err);
+		}
+
+		result
+	}
+}
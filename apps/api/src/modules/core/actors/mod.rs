@@ -1 +1,2 @@
 pub mod hook;
+pub mod mail;
@@ -1,2 +1,3 @@
 pub mod config;
+pub mod site_config;
 pub mod status;
@@ -0,0 +1,90 @@
+use super::super::dto::config::{request, response};
+use crate::errors::AppError;
+use crate::modules::auth::helpers::permissions::ensure_permission;
+use crate::modules::core::middleware::state::AppState;
+use crate::modules::core::models::config_item::{ConfigItem, CreateConfigItem};
+use actix_web::{get, put, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindPathParams {
+	site_id: Uuid,
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/config",
+    request_body = UpdateConfigDTO,
+	responses(
+		(status = 200, body = ConfigDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindPathParams)
+)]
+#[put("")]
+pub async fn update(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	form: web::Json<request::UpdateConfigDTO>,
+	params: web::Path<FindPathParams>,
+) -> Result<HttpResponse, AppError> {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:config:*"),
+		"sites::config:update",
+	)?;
+
+	let conn = &mut state.get_conn()?;
+
+	let request::UpdateConfigDTO(hashmap) = form.0;
+	let create_orders: Vec<CreateConfigItem> = hashmap
+		.into_iter()
+		.map(|(key, value)| CreateConfigItem {
+			key,
+			value,
+			site_id: Some(params.site_id),
+			module_name: None,
+		})
+		.collect();
+
+	let config_items = ConfigItem::upsert(conn, Some(params.site_id), None, create_orders)?;
+
+	let res = response::ConfigDTO::from(config_items);
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/config",
+	responses(
+		(status = 200, body = ConfigDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindPathParams)
+)]
+#[get("")]
+pub async fn find_all(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindPathParams>,
+) -> Result<HttpResponse, AppError> {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:config:*"),
+		"sites::config:read",
+	)?;
+
+	let conn = &mut state.get_conn()?;
+	let config_items = ConfigItem::find(conn, Some(params.site_id), None)?;
+
+	let res = response::ConfigDTO::from(config_items);
+	Ok(HttpResponse::Ok().json(res))
+}
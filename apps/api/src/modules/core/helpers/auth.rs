@@ -1,12 +1,21 @@
-use actix_web::{dev::ServiceRequest, HttpRequest};
+use std::env;
+use std::net::{IpAddr, SocketAddr};
+
+use actix_web::{dev::ServiceRequest, http::header::USER_AGENT, HttpRequest};
 use uuid::Uuid;
 
-use crate::{constants, utils::token};
+use crate::{
+	constants,
+	modules::{
+		api_keys::models::api_key::API_KEY_PREFIX, users::models::user_session::SessionMetadata,
+	},
+	utils::token::{self, Claims},
+};
 
 const TOKEN_IDENTIFIER: &str = "Bearer";
 
 // TODO: dedupe
-pub fn get_user_id_from_header(req: &ServiceRequest) -> Result<Uuid, &str> {
+pub fn get_bearer_token(req: &ServiceRequest) -> Result<&str, &str> {
 	req.headers()
 		.get(constants::AUTHORIZATION)
 		.ok_or("Cannot find authorization value in headers")
@@ -23,8 +32,18 @@ pub fn get_user_id_from_header(req: &ServiceRequest) -> Result<Uuid, &str> {
 			}
 		})
 		.map(|auth_str| auth_str[6..auth_str.len()].trim())
+}
+
+pub fn get_claims_from_header(req: &ServiceRequest) -> Result<Claims, &str> {
+	get_bearer_token(req)
 		.and_then(|token| token::decode(token).map_err(|_err| "Cannot decode token."))
-		.map(|token| token.claims.sub)
+		.map(|token| token.claims)
+}
+
+pub fn get_api_key_from_header(req: &ServiceRequest) -> Option<&str> {
+	get_bearer_token(req)
+		.ok()
+		.filter(|token| token.starts_with(API_KEY_PREFIX))
 }
 
 pub fn get_user_id_from_req(req: &HttpRequest) -> Result<Uuid, &str> {
@@ -47,3 +66,37 @@ pub fn get_user_id_from_req(req: &HttpRequest) -> Result<Uuid, &str> {
 		.and_then(|token| token::decode(token).map_err(|_err| "Cannot decode token."))
 		.map(|token| token.claims.sub)
 }
+
+pub fn get_session_metadata(req: &HttpRequest) -> SessionMetadata {
+	SessionMetadata {
+		user_agent: req
+			.headers()
+			.get(USER_AGENT)
+			.and_then(|user_agent| user_agent.to_str().ok())
+			.map(|user_agent| user_agent.to_owned()),
+		ip_address: get_client_ip_address(req),
+	}
+}
+
+// Forwarded headers can be set by anyone, they only count when the request came through a trusted proxy
+fn get_client_ip_address(req: &HttpRequest) -> Option<String> {
+	let peer_ip_address = req.peer_addr()?.ip();
+	let trusted_proxy = env::var(constants::env_key::TRUSTED_PROXIES)
+		.unwrap_or_default()
+		.split(',')
+		.filter_map(|proxy| proxy.trim().parse::<IpAddr>().ok())
+		.any(|proxy| proxy == peer_ip_address);
+
+	if !trusted_proxy {
+		return Some(peer_ip_address.to_string());
+	}
+
+	// The proxy may put the port next to the address, which would count each connection separately
+	req.connection_info()
+		.realip_remote_addr()
+		.map(|ip_address| match ip_address.parse::<SocketAddr>() {
+			Ok(socket_address) => socket_address.ip().to_string(),
+			Err(_) => ip_address.to_owned(),
+		})
+		.or_else(|| Some(peer_ip_address.to_string()))
+}
@@ -0,0 +1,37 @@
+use std::env;
+use std::fs;
+
+use crate::constants;
+use crate::errors::AppError;
+use lettre::{FileTransport, Transport};
+
+use super::lib::{build_message, mail_error, Mail, MailTransport};
+
+const DEFAULT_MAIL_FILE_DIR: &str = "./mails";
+
+// Writes every mail as an `.eml` file, useful to inspect mails during development and tests
+pub struct FileMailTransport {
+	transport: FileTransport,
+}
+
+impl FileMailTransport {
+	pub fn new() -> Result<Self, AppError> {
+		let directory = env::var(constants::env_key::MAIL_FILE_DIR)
+			.unwrap_or_else(|_| DEFAULT_MAIL_FILE_DIR.to_owned());
+		fs::create_dir_all(&directory)?;
+
+		Ok(Self {
+			transport: FileTransport::new(directory),
+		})
+	}
+}
+
+impl MailTransport for FileMailTransport {
+	fn send(&self, mail: &Mail) -> Result<(), AppError> {
+		self.transport
+			.send(&build_message(mail)?)
+			.map_err(mail_error)?;
+
+		Ok(())
+	}
+}
@@ -0,0 +1,62 @@
+use std::env;
+
+use crate::constants;
+use crate::errors::{AppError, AppErrorValue};
+use lettre::message::header::ContentType;
+use lettre::Message;
+use reqwest::StatusCode;
+
+use super::{file::FileMailTransport, log::LogMailTransport, smtp::SmtpMailTransport};
+
+const DEFAULT_MAIL_FROM: &str = "DCM <no-reply@localhost>";
+
+#[derive(Debug, Clone)]
+pub struct Mail {
+	pub to: String,
+	pub subject: String,
+	pub body: String,
+}
+
+pub trait MailTransport {
+	fn send(&self, mail: &Mail) -> Result<(), AppError>;
+}
+
+pub fn mail_error(err: impl std::fmt::Display) -> AppError {
+	AppError::InternalServerError(AppErrorValue {
+		message: err.to_string(),
+		status: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+		code: "MAIL_ERROR".to_owned(),
+		..Default::default()
+	})
+}
+
+pub fn build_message(mail: &Mail) -> Result<Message, AppError> {
+	let from =
+		env::var(constants::env_key::MAIL_FROM).unwrap_or_else(|_| DEFAULT_MAIL_FROM.to_owned());
+
+	Message::builder()
+		.from(from.parse().map_err(mail_error)?)
+		.to(mail.to.parse().map_err(mail_error)?)
+		.subject(&mail.subject)
+		.header(ContentType::TEXT_PLAIN)
+		.body(mail.body.clone())
+		.map_err(mail_error)
+}
+
+// Defaults to logging mails so development setups work without a mail server
+pub fn get_mail_transport() -> Result<Box<dyn MailTransport>, AppError> {
+	let transport =
+		env::var(constants::env_key::MAIL_TRANSPORT).unwrap_or_else(|_| "log".to_owned());
+
+	match transport.to_lowercase().as_str() {
+		"smtp" => Ok(Box::new(SmtpMailTransport::new()?)),
+		"file" => Ok(Box::new(FileMailTransport::new()?)),
+		"log" => Ok(Box::new(LogMailTransport)),
+		_ => Err(AppError::UnprocessableEntity(AppErrorValue {
+			message: "Mail transport is not implemented".to_owned(),
+			status: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+			code: "MAIL_TRANSPORT_NOT_IMPLEMENTED".to_owned(),
+			..Default::default()
+		})),
+	}
+}
@@ -0,0 +1,14 @@
+use crate::errors::AppError;
+use tracing::info;
+
+use super::lib::{Mail, MailTransport};
+
+pub struct LogMailTransport;
+
+impl MailTransport for LogMailTransport {
+	fn send(&self, mail: &Mail) -> Result<(), AppError> {
+		info!(to = %mail.to, subject = %mail.subject, body = %mail.body, "mail sent");
+
+		Ok(())
+	}
+}
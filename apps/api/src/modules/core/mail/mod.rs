@@ -0,0 +1,4 @@
+pub mod file;
+pub mod lib;
+pub mod log;
+pub mod smtp;
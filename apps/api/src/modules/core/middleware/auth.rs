@@ -1,8 +1,14 @@
-use crate::modules::core::helpers::auth::get_user_id_from_header;
+use crate::constants;
+use crate::modules::api_keys::models::api_key::ApiKey;
+use crate::modules::core::helpers::auth::{
+	get_api_key_from_header, get_bearer_token, get_claims_from_header,
+};
 use crate::modules::users::models::user::User;
+use crate::modules::users::models::user_session::UserSession;
 
 use crate::errors::{AppError, AppErrorValue};
 use crate::modules::core::middleware::state::AppState;
+use crate::utils::hasher;
 
 use actix_web::http::StatusCode;
 use actix_web::HttpMessage;
@@ -18,9 +24,12 @@ use futures::future::{ok, Ready};
 use futures::Future;
 use regex::Regex;
 use serde_json::json;
+use std::env;
 use std::pin::Pin;
 use uuid::Uuid;
 
+const SCIM_PATH_PREFIX: &str = "/scim/v2/";
+
 // There are two steps in middleware processing.
 // 1. Middleware initialization, middleware factory gets called with
 //    next service in chain as parameter.
@@ -68,6 +77,8 @@ where
 	fn call(&self, mut req: ServiceRequest) -> Self::Future {
 		let is_verified = if should_skip_auth(&req) {
 			true
+		} else if is_scim_route(&req) {
+			verify_scim_token(&req)
 		} else {
 			set_auth_user(&mut req)
 		};
@@ -108,28 +119,97 @@ fn should_skip_auth(req: &ServiceRequest) -> bool {
 		.any(|route| route.matches_path_and_method(req.path(), req.method()))
 }
 
+fn is_scim_route(req: &ServiceRequest) -> bool {
+	req.path().starts_with(SCIM_PATH_PREFIX)
+}
+
+// Provisioning clients use a dedicated token instead of a user session, without one SCIM is disabled
+fn verify_scim_token(req: &ServiceRequest) -> bool {
+	match (
+		env::var(constants::env_key::SCIM_TOKEN),
+		get_bearer_token(req),
+	) {
+		(Ok(scim_token), Ok(token)) if !scim_token.is_empty() => {
+			hasher::hash_token(&scim_token) == hasher::hash_token(token)
+		}
+		_ => false,
+	}
+}
+
 fn set_auth_user(req: &mut ServiceRequest) -> bool {
+	if let Some(key) = get_api_key_from_header(req) {
+		return match fetch_api_key(req, key) {
+			Ok(api_key) => {
+				req.extensions_mut().insert(api_key);
+				true
+			}
+			Err(err_msg) => {
+				tracing::debug!("Cannot fetch API key {}", err_msg);
+				false
+			}
+		};
+	}
+
 	match fetch_user(req) {
-		Ok(user) => {
+		Ok((user, session)) => {
 			req.extensions_mut().insert(user);
+			req.extensions_mut().insert(session);
 			true
 		}
 		Err(err_msg) => {
-			println!("Cannot fetch user {}", err_msg);
+			tracing::debug!("Cannot fetch user {}", err_msg);
 			false
 		}
 	}
 }
 
-fn fetch_user(req: &ServiceRequest) -> Result<User, &str> {
-	let user_id = get_user_id_from_header(req)?;
+fn fetch_user(req: &ServiceRequest) -> Result<(User, UserSession), &str> {
+	let claims = get_claims_from_header(req)?;
+
+	let conn = &mut req
+		.app_data::<Data<AppState>>()
+		.ok_or("Cannot get state.")
+		.and_then(|state| state.get_conn().map_err(|_err| "Cannot get db connection."))?;
+
+	let session = find_auth_session(conn, claims.sid).map_err(|_err| "Cannot find session")?;
+	if session.user_id != claims.sub || !session.is_active() {
+		return Err("Session has been revoked or has expired");
+	}
+
+	let user = find_auth_user(conn, claims.sub).map_err(|_err| "Cannot find user")?;
+	if user.deactivated_at.is_some() {
+		return Err("User has been deactivated");
+	}
+
+	Ok((user, session))
+}
 
+fn fetch_api_key(req: &ServiceRequest, key: &str) -> Result<ApiKey, &'static str> {
 	let conn = &mut req
 		.app_data::<Data<AppState>>()
 		.ok_or("Cannot get state.")
 		.and_then(|state| state.get_conn().map_err(|_err| "Cannot get db connection."))?;
 
-	find_auth_user(conn, user_id).map_err(|_err| "Cannot find user")
+	let api_key = ApiKey::find_by_key(conn, key)
+		.map_err(|_err| "Cannot find API key")?
+		.ok_or("API key is invalid or has expired")?;
+	ApiKey::touch(conn, api_key.id).map_err(|_err| "Cannot update API key usage")?;
+
+	Ok(api_key)
+}
+
+pub fn get_current_session(req: &HttpRequest) -> Result<UserSession, AppError> {
+	req.extensions()
+		.get::<UserSession>()
+		.map(|session| session.to_owned())
+		.ok_or_else(|| {
+			AppError::Unauthorized(AppErrorValue {
+				message: "Unauthorized, please login".to_owned(),
+				status: StatusCode::UNAUTHORIZED.as_u16(),
+				identifier: Uuid::new_v4(),
+				code: "UNAUTHORIZED".to_owned(),
+			})
+		})
 }
 
 pub fn get_current_user(req: &HttpRequest) -> Result<User, AppError> {
@@ -168,11 +248,35 @@ impl SkipAuthRoute {
 }
 
 lazy_static! {
-	static ref SKIP_AUTH_ROUTES: [SkipAuthRoute; 9] = [
+	static ref SKIP_AUTH_ROUTES: [SkipAuthRoute; 15] = [
 		SkipAuthRoute {
 			path: Regex::new(r"/admin-api/v1/auth/(.*)/login").unwrap(),
 			method: Method::POST,
 		},
+		SkipAuthRoute {
+			path: Regex::new(r"/admin-api/v1/auth/refresh$").unwrap(),
+			method: Method::POST,
+		},
+		SkipAuthRoute {
+			path: Regex::new(r"/admin-api/v1/auth/password-reset$").unwrap(),
+			method: Method::POST,
+		},
+		SkipAuthRoute {
+			path: Regex::new(r"/admin-api/v1/auth/password-reset/confirm$").unwrap(),
+			method: Method::POST,
+		},
+		SkipAuthRoute {
+			path: Regex::new(r"/admin-api/v1/auth/verify-email/confirm$").unwrap(),
+			method: Method::POST,
+		},
+		SkipAuthRoute {
+			path: Regex::new(r"/admin-api/v1/auth/invitations$").unwrap(),
+			method: Method::GET,
+		},
+		SkipAuthRoute {
+			path: Regex::new(r"/admin-api/v1/auth/invitations/accept$").unwrap(),
+			method: Method::POST,
+		},
 		SkipAuthRoute {
 			path: Regex::new(r"/admin-api/v1/status").unwrap(),
 			method: Method::GET,
@@ -198,11 +302,12 @@ lazy_static! {
 			method: Method::GET,
 		},
 		SkipAuthRoute {
-			path: Regex::new(r"/docs/?(.*)").unwrap(),
+			path: Regex::new(r"^/docs(/|$)").unwrap(),
 			method: Method::GET
 		},
 		SkipAuthRoute {
-			path: Regex::new(r"/api/?(.*)").unwrap(),
+			// Anchored, admin routes such as `/admin-api/v1/sites/{site_id}/api-keys` contain `/api` too
+			path: Regex::new(r"^/api(/|$)").unwrap(),
 			method: Method::GET
 		}
 	];
@@ -214,3 +319,64 @@ fn find_auth_user(conn: &mut PgConnection, user_id: Uuid) -> Result<User, AppErr
 	let user = User::find_one(conn, user_id)?;
 	Ok(user)
 }
+
+fn find_auth_session(conn: &mut PgConnection, session_id: Uuid) -> Result<UserSession, AppError> {
+	let session = UserSession::find_one(conn, session_id)?;
+	Ok(session)
+}
+
+#[cfg(test)]
+mod tests {
+	use actix_web::http::StatusCode;
+	use actix_web::{test, web, App, HttpResponse};
+
+	use super::Authentication;
+
+	async fn ok() -> HttpResponse {
+		HttpResponse::Ok().finish()
+	}
+
+	#[actix_web::test]
+	async fn admin_routes_containing_api_are_authenticated() {
+		let app = test::init_service(
+			App::new()
+				.wrap(Authentication)
+				.route("/admin-api/v1/sites/{site_id}/api-keys", web::get().to(ok))
+				.route(
+					"/admin-api/v1/sites/{site_id}/api-keys/{id}",
+					web::get().to(ok),
+				),
+		)
+		.await;
+
+		for uri in [
+			"/admin-api/v1/sites/b1d2c3e4-0000-0000-0000-000000000000/api-keys",
+			"/admin-api/v1/sites/b1d2c3e4-0000-0000-0000-000000000000/api-keys/a1d2c3e4-0000-0000-0000-000000000000",
+		] {
+			let res = test::call_service(&app, test::TestRequest::get().uri(uri).to_request()).await;
+			assert_eq!(res.status(), StatusCode::UNAUTHORIZED, "{uri}");
+		}
+	}
+
+	#[actix_web::test]
+	async fn public_api_is_not_authenticated() {
+		let app = test::init_service(
+			App::new()
+				.wrap(Authentication)
+				.route("/api/v1/sites/{site_id}/content", web::get().to(ok))
+				.route("/apis", web::get().to(ok)),
+		)
+		.await;
+
+		let req = test::TestRequest::get()
+			.uri("/api/v1/sites/b1d2c3e4-0000-0000-0000-000000000000/content")
+			.to_request();
+		assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+
+		let req = test::TestRequest::get().uri("/apis").to_request();
+		assert_eq!(
+			test::call_service(&app, req).await.status(),
+			StatusCode::UNAUTHORIZED
+		);
+	}
+}
@@ -1,5 +1,7 @@
 use crate::errors::AppError;
 use crate::modules::core::actors::hook::HookActor;
+use crate::modules::core::actors::mail::MailActor;
+use crate::modules::resources::actors::storage_migration::StorageMigrationActor;
 use crate::utils;
 use actix::Addr;
 use diesel::pg::PgConnection;
@@ -11,6 +13,8 @@ pub type AppConn = PooledConnection<ConnectionManager<PgConnection>>;
 pub struct AppState {
 	pub pool: utils::db::DbPool,
 	pub hook_addr: Addr<HookActor>,
+	pub mail_addr: Addr<MailActor>,
+	pub migration_addr: Addr<StorageMigrationActor>,
 }
 
 impl AppState {
@@ -2,5 +2,6 @@ pub mod actors;
 pub mod controllers;
 pub mod dto;
 pub mod helpers;
+pub mod mail;
 pub mod middleware;
 pub mod models;
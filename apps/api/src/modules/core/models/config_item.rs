@@ -81,6 +81,18 @@ impl ConfigItem {
 		Ok(config_items)
 	}
 
+	// Returns the global item and every site specific item for a key
+	#[instrument(skip(conn))]
+	pub fn find_by_key(conn: &mut PgConnection, key: &str) -> Result<Vec<Self>, AppError> {
+		let config_items = config_items::table
+			.filter(config_items::key.eq(key))
+			.filter(config_items::module_name.is_null())
+			.select(ConfigItem::as_select())
+			.load::<ConfigItem>(conn)?;
+
+		Ok(config_items)
+	}
+
 	#[instrument(skip(conn))]
 	pub fn remove(conn: &mut PgConnection, config_item_id: Uuid) -> Result<(), AppError> {
 		diesel::delete(config_items::table.filter(config_items::id.eq(config_item_id)))
@@ -0,0 +1,86 @@
+use super::super::dto::feeds::{request, response};
+use crate::errors::AppError;
+use crate::modules::auth::helpers::permissions::ensure_permission;
+use crate::modules::core::middleware::state::AppState;
+use crate::modules::feeds::models::feed_config::FeedConfig;
+use actix_web::{get, put, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindPathParams {
+	site_id: Uuid,
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/feeds",
+	responses(
+		(status = 200, body = FeedsDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindPathParams)
+)]
+#[get("")]
+pub async fn find_all(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindPathParams>,
+) -> Result<HttpResponse, AppError> {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:config:*"),
+		"sites::config:read",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let feeds = FeedConfig::find(conn, params.site_id)?;
+
+	let res = response::FeedsDTO::from(feeds);
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/feeds",
+    request_body = UpdateFeedsDTO,
+	responses(
+		(status = 200, body = FeedsDTO),
+		(status = 400, body = AppErrorValue, description = "A feed is invalid"),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindPathParams)
+)]
+#[put("")]
+pub async fn update(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	form: web::Json<request::UpdateFeedsDTO>,
+	params: web::Path<FindPathParams>,
+) -> Result<HttpResponse, AppError> {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:config:*"),
+		"sites::config:update",
+	)?;
+	let conn = &mut state.get_conn()?;
+
+	let request::UpdateFeedsDTO(feeds) = form.into_inner();
+	let feeds = FeedConfig::upsert(
+		conn,
+		params.site_id,
+		feeds
+			.into_iter()
+			.map(|(key, feed)| (key, FeedConfig::from(feed)))
+			.collect(),
+	)?;
+
+	let res = response::FeedsDTO::from(feeds);
+	Ok(HttpResponse::Ok().json(res))
+}
@@ -0,0 +1,102 @@
+use crate::errors::AppError;
+use crate::modules::content::models::content::Content;
+use crate::modules::core::middleware::state::AppState;
+use crate::modules::feeds::helpers::xml::get_base_url;
+use crate::modules::feeds::services::feed::{build_atom, build_rss, find_feed};
+use crate::modules::feeds::services::sitemap::{build_sitemap, MAX_SITEMAP_URLS};
+use crate::modules::languages::models::language::Language;
+use crate::modules::sites::models::site::Site;
+use actix_web::{get, web, HttpResponse};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindPathParams {
+	site_id: Uuid,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindFeedPathParams {
+	site_id: Uuid,
+	feed_key: String,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedQueryParams {
+	lang: String,
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/sitemap.xml",
+	responses(
+		(status = 200, body = String, content_type = "application/xml"),
+		(status = 422, body = AppErrorValue, description = "The site has no url")
+	),
+	params(FindPathParams)
+)]
+#[get("")]
+pub async fn sitemap(
+	state: web::Data<AppState>,
+	params: web::Path<FindPathParams>,
+) -> Result<HttpResponse, AppError> {
+	let conn = &mut state.get_conn()?;
+	let (site, _) = Site::find_one(conn, params.site_id)?;
+	let base_url = get_base_url(&site)?;
+	let pages = Content::find_sitemap_public(conn, params.site_id, MAX_SITEMAP_URLS)?;
+
+	Ok(HttpResponse::Ok()
+		.content_type("application/xml; charset=utf-8")
+		.body(build_sitemap(&base_url, pages)))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/feeds",
+	responses(
+		(status = 200, body = String, content_type = "application/rss+xml"),
+		(status = 404, body = AppErrorValue, description = "Not Found"),
+		(status = 422, body = AppErrorValue, description = "The site has no url")
+	),
+	params(FindFeedPathParams, FeedQueryParams)
+)]
+#[get("/{feed_key}/rss")]
+pub async fn rss(
+	state: web::Data<AppState>,
+	params: web::Path<FindFeedPathParams>,
+	query: web::Query<FeedQueryParams>,
+) -> Result<HttpResponse, AppError> {
+	let conn = &mut state.get_conn()?;
+	let (site, _) = Site::find_one(conn, params.site_id)?;
+	let language = Language::find_by_key(conn, &query.lang)?;
+	let feed = find_feed(conn, &site, &params.feed_key, language)?;
+
+	Ok(HttpResponse::Ok()
+		.content_type("application/rss+xml; charset=utf-8")
+		.body(build_rss(&feed)))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/feeds",
+	responses(
+		(status = 200, body = String, content_type = "application/atom+xml"),
+		(status = 404, body = AppErrorValue, description = "Not Found"),
+		(status = 422, body = AppErrorValue, description = "The site has no url")
+	),
+	params(FindFeedPathParams, FeedQueryParams)
+)]
+#[get("/{feed_key}/atom")]
+pub async fn atom(
+	state: web::Data<AppState>,
+	params: web::Path<FindFeedPathParams>,
+	query: web::Query<FeedQueryParams>,
+) -> Result<HttpResponse, AppError> {
+	let conn = &mut state.get_conn()?;
+	let (site, _) = Site::find_one(conn, params.site_id)?;
+	let language = Language::find_by_key(conn, &query.lang)?;
+	let feed = find_feed(conn, &site, &params.feed_key, language)?;
+
+	Ok(HttpResponse::Ok()
+		.content_type("application/atom+xml; charset=utf-8")
+		.body(build_atom(&feed)))
+}
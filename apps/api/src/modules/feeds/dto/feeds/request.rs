@@ -0,0 +1,28 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::response::FeedConfigDTO;
+use crate::modules::feeds::models::feed_config::{FeedConfig, FeedFieldsConfig};
+
+// Feeds by key, the key is part of the public url of the feed
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct UpdateFeedsDTO(pub HashMap<String, FeedConfigDTO>);
+
+impl From<FeedConfigDTO> for FeedConfig {
+	fn from(feed: FeedConfigDTO) -> Self {
+		Self {
+			title: feed.title,
+			description: feed.description,
+			content_types: feed.content_types,
+			fields: FeedFieldsConfig {
+				title: feed.fields.title,
+				summary: feed.fields.summary,
+				date: feed.fields.date,
+			},
+			link_template: feed.link_template,
+			limit: feed.limit,
+		}
+	}
+}
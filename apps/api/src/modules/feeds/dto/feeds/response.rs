@@ -0,0 +1,63 @@
+use crate::modules::feeds::models::feed_config::{FeedConfig, FeedFieldsConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::convert::From;
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedFieldsDTO {
+	pub title: Option<String>,
+	pub summary: Option<String>,
+	pub date: Option<String>,
+}
+
+impl From<FeedFieldsConfig> for FeedFieldsDTO {
+	fn from(fields: FeedFieldsConfig) -> Self {
+		Self {
+			title: fields.title,
+			summary: fields.summary,
+			date: fields.date,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedConfigDTO {
+	pub title: String,
+	pub description: Option<String>,
+	// Slugs of the content types
+	pub content_types: Vec<String>,
+	#[serde(default)]
+	pub fields: FeedFieldsDTO,
+	pub link_template: Option<String>,
+	pub limit: Option<i64>,
+}
+
+impl From<FeedConfig> for FeedConfigDTO {
+	fn from(feed: FeedConfig) -> Self {
+		Self {
+			title: feed.title,
+			description: feed.description,
+			content_types: feed.content_types,
+			fields: FeedFieldsDTO::from(feed.fields),
+			link_template: feed.link_template,
+			limit: feed.limit,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct FeedsDTO(HashMap<String, FeedConfigDTO>);
+
+impl From<Vec<(String, FeedConfig)>> for FeedsDTO {
+	fn from(feeds: Vec<(String, FeedConfig)>) -> Self {
+		FeedsDTO(
+			feeds
+				.into_iter()
+				.map(|(key, feed)| (key, FeedConfigDTO::from(feed)))
+				.collect(),
+		)
+	}
+}
@@ -0,0 +1,35 @@
+use reqwest::StatusCode;
+
+use crate::errors::{AppError, AppErrorValue};
+use crate::modules::sites::models::site::Site;
+
+pub const XML_DECLARATION: &str = r#"<?xml version="1.0" encoding="UTF-8"?>"#;
+
+pub fn escape_xml(value: &str) -> String {
+	let mut escaped = String::with_capacity(value.len());
+	for character in value.chars() {
+		match character {
+			'&' => escaped.push_str("&amp;"),
+			'<' => escaped.push_str("&lt;"),
+			'>' => escaped.push_str("&gt;"),
+			'"' => escaped.push_str("&quot;"),
+			'\'' => escaped.push_str("&apos;"),
+			_ => escaped.push(character),
+		}
+	}
+
+	escaped
+}
+
+// Sitemaps and feeds need absolute links, those are built on top of the url of the site
+pub fn get_base_url(site: &Site) -> Result<String, AppError> {
+	match site.url.as_deref().map(str::trim) {
+		Some(url) if !url.is_empty() => Ok(url.trim_end_matches('/').to_owned()),
+		_ => Err(AppError::UnprocessableEntity(AppErrorValue {
+			message: format!("Site {} has no url to build links with", site.slug),
+			status: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+			code: "SITE_URL_MISSING".to_owned(),
+			..Default::default()
+		})),
+	}
+}
@@ -0,0 +1,5 @@
+pub mod controllers;
+pub mod dto;
+pub mod helpers;
+pub mod models;
+pub mod services;
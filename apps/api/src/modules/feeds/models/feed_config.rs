@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+
+use diesel::prelude::*;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use slug::slugify;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::errors::{AppError, AppErrorValue};
+use crate::modules::core::models::config_item::{ConfigItem, CreateConfigItem};
+use crate::schema::{content_types, sites_content_types};
+
+// Feeds are stored as site config items of their own module, so saving the site config leaves them alone
+pub const FEEDS_MODULE_NAME: &str = "feeds";
+
+pub const MAX_FEED_LIMIT: i64 = 100;
+
+// Slugs of the fields that hold the title, summary and date of an entry
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedFieldsConfig {
+	// Falls back to the name of the content
+	pub title: Option<String>,
+	pub summary: Option<String>,
+	// Falls back to when the content was created
+	pub date: Option<String>,
+}
+
+// Content types are referenced by slug, so the config survives a site export and import
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedConfig {
+	pub title: String,
+	pub description: Option<String>,
+	pub content_types: Vec<String>,
+	#[serde(default)]
+	pub fields: FeedFieldsConfig,
+	// Links content without a path, `{slug}`, `{id}` and `{lang}` are filled in
+	pub link_template: Option<String>,
+	pub limit: Option<i64>,
+}
+
+impl FeedConfig {
+	#[instrument(skip(conn))]
+	pub fn find(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+	) -> Result<Vec<(String, FeedConfig)>, AppError> {
+		let feeds = ConfigItem::find(conn, Some(site_id), Some(FEEDS_MODULE_NAME.to_owned()))?
+			.into_iter()
+			.filter_map(|config_item| {
+				let feed = serde_json::from_value::<FeedConfig>(config_item.value?).ok()?;
+				Some((config_item.key, feed))
+			})
+			.collect();
+
+		Ok(feeds)
+	}
+
+	#[instrument(skip(conn))]
+	pub fn find_one(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		key: &str,
+	) -> Result<FeedConfig, AppError> {
+		Self::find(conn, site_id)?
+			.into_iter()
+			.find(|(feed_key, _)| feed_key == key)
+			.map(|(_, feed)| feed)
+			.ok_or_else(|| {
+				AppError::NotFound(AppErrorValue {
+					message: format!("Feed {} does not exist", key),
+					status: StatusCode::NOT_FOUND.as_u16(),
+					code: "FEED_NOT_FOUND".to_owned(),
+					..Default::default()
+				})
+			})
+	}
+
+	// Replaces every feed of the site
+	#[instrument(skip(conn))]
+	pub fn upsert(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		feeds: HashMap<String, FeedConfig>,
+	) -> Result<Vec<(String, FeedConfig)>, AppError> {
+		for (key, feed) in &feeds {
+			feed.validate(conn, site_id, key)?;
+		}
+
+		let values = feeds
+			.into_iter()
+			.map(|(key, feed)| {
+				Ok(CreateConfigItem {
+					key,
+					value: Some(serde_json::to_value(feed)?),
+					site_id: Some(site_id),
+					module_name: Some(FEEDS_MODULE_NAME.to_owned()),
+				})
+			})
+			.collect::<Result<Vec<CreateConfigItem>, AppError>>()?;
+		ConfigItem::upsert(
+			conn,
+			Some(site_id),
+			Some(FEEDS_MODULE_NAME.to_owned()),
+			values,
+		)?;
+
+		Self::find(conn, site_id)
+	}
+
+	#[instrument(skip(conn))]
+	fn validate(&self, conn: &mut PgConnection, site_id: Uuid, key: &str) -> Result<(), AppError> {
+		if key.is_empty() || slugify(key) != key {
+			return Err(AppError::BadRequest(AppErrorValue {
+				message: format!("{} is not a valid feed key, use a slug", key),
+				status: StatusCode::BAD_REQUEST.as_u16(),
+				code: "INVALID_FEED_KEY".to_owned(),
+				..Default::default()
+			}));
+		}
+
+		if self
+			.limit
+			.is_some_and(|limit| !(1..=MAX_FEED_LIMIT).contains(&limit))
+		{
+			return Err(AppError::BadRequest(AppErrorValue {
+				message: format!(
+					"The limit of feed {} has to be between 1 and {}",
+					key, MAX_FEED_LIMIT
+				),
+				status: StatusCode::BAD_REQUEST.as_u16(),
+				code: "INVALID_FEED_LIMIT".to_owned(),
+				..Default::default()
+			}));
+		}
+
+		let content_type_ids = self.find_content_type_ids(conn, site_id)?;
+		if self.content_types.is_empty() || content_type_ids.len() != self.content_types.len() {
+			return Err(AppError::BadRequest(AppErrorValue {
+				message: format!(
+					"Feed {} needs content types that are enabled for this site",
+					key
+				),
+				status: StatusCode::BAD_REQUEST.as_u16(),
+				code: "INVALID_FEED_CONTENT_TYPES".to_owned(),
+				..Default::default()
+			}));
+		}
+
+		Ok(())
+	}
+
+	#[instrument(skip(conn))]
+	pub fn find_content_type_ids(
+		&self,
+		conn: &mut PgConnection,
+		site_id: Uuid,
+	) -> Result<Vec<Uuid>, AppError> {
+		let content_type_ids = content_types::table
+			.inner_join(sites_content_types::table)
+			.filter(sites_content_types::site_id.eq(site_id))
+			.filter(content_types::slug.eq_any(&self.content_types))
+			.filter(content_types::deleted.eq(false))
+			.select(content_types::id)
+			.distinct()
+			.load::<Uuid>(conn)?;
+
+		Ok(content_type_ids)
+	}
+}
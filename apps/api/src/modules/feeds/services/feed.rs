@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use diesel::PgConnection;
+use serde_json::Value;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::modules::content::dto::content::response::PublicContentDTO;
+use crate::modules::content::models::content::Content;
+use crate::modules::feeds::helpers::xml::{escape_xml, get_base_url, XML_DECLARATION};
+use crate::modules::feeds::models::feed_config::{FeedConfig, MAX_FEED_LIMIT};
+use crate::modules::languages::models::language::Language;
+use crate::modules::sites::models::site::Site;
+
+const DEFAULT_FEED_LIMIT: i64 = 20;
+// Feeds sorted by a date field pick their entries from this many of the most recently created items
+const DATE_SORTED_FEED_WINDOW: i64 = 500;
+
+#[derive(Debug)]
+pub struct FeedEntry {
+	pub id: Uuid,
+	pub title: String,
+	pub summary: Option<String>,
+	pub link: Option<String>,
+	pub published_at: DateTime<Utc>,
+	pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct Feed {
+	// Stable across builds, Atom readers use it to tell feeds apart
+	pub id: String,
+	pub title: String,
+	pub description: Option<String>,
+	pub author: String,
+	pub link: String,
+	pub language: String,
+	pub updated_at: DateTime<Utc>,
+	pub entries: Vec<FeedEntry>,
+}
+
+fn get_field_text(
+	fields: &HashMap<String, Option<Value>>,
+	slug: &Option<String>,
+) -> Option<String> {
+	let text = match fields.get(slug.as_ref()?)? {
+		Some(Value::String(text)) => text.clone(),
+		Some(Value::Number(number)) => number.to_string(),
+		Some(Value::Bool(boolean)) => boolean.to_string(),
+		_ => return None,
+	};
+
+	(!text.trim().is_empty()).then_some(text)
+}
+
+// Date fields hold dates or date times, with or without an offset
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+	if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+		return Some(date_time.with_timezone(&Utc));
+	}
+
+	if let Ok(date_time) = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f") {
+		return Some(Utc.from_utc_datetime(&date_time));
+	}
+
+	NaiveDate::parse_from_str(value, "%Y-%m-%d")
+		.ok()
+		.and_then(|date| date.and_hms_opt(0, 0, 0))
+		.map(|date_time| Utc.from_utc_datetime(&date_time))
+}
+
+fn get_link(
+	base_url: &str,
+	link_template: &Option<String>,
+	content: &PublicContentDTO,
+) -> Option<String> {
+	if let Some(path) = &content.path {
+		return Some(format!("{base_url}{path}"));
+	}
+
+	let link = link_template
+		.as_ref()?
+		.replace("{slug}", &content.slug)
+		.replace("{id}", &content.id.to_string())
+		.replace("{lang}", &content.language);
+	if link.starts_with("http://") || link.starts_with("https://") {
+		Some(link)
+	} else {
+		Some(format!("{base_url}/{}", link.trim_start_matches('/')))
+	}
+}
+
+#[instrument(skip(conn))]
+pub fn find_feed(
+	conn: &mut PgConnection,
+	site: &Site,
+	key: &str,
+	language: Language,
+) -> Result<Feed, AppError> {
+	let config = FeedConfig::find_one(conn, site.id, key)?;
+	let base_url = get_base_url(site)?;
+	let content_type_ids = config.find_content_type_ids(conn, site.id)?;
+	// Feeds imported with a site skip validation, so the limit is capped here as well
+	let limit = config
+		.limit
+		.unwrap_or(DEFAULT_FEED_LIMIT)
+		.clamp(1, MAX_FEED_LIMIT);
+
+	// Dates from a field can differ from when the content was created, so those feeds are sorted here
+	let content = Content::find_feed_public(
+		conn,
+		site.id,
+		language.id,
+		&content_type_ids,
+		match config.fields.date {
+			Some(_) => DATE_SORTED_FEED_WINDOW,
+			None => limit,
+		},
+	)?;
+
+	let mut entries = content
+		.into_iter()
+		.map(|(content_item, revision, fields, translations)| {
+			let created_at = Utc.from_utc_datetime(&content_item.created_at);
+			let updated_at = Utc.from_utc_datetime(&content_item.updated_at);
+			let content = PublicContentDTO::from((
+				content_item,
+				revision,
+				fields,
+				language.clone(),
+				translations,
+				false,
+			));
+
+			FeedEntry {
+				id: content.id,
+				title: get_field_text(&content.fields, &config.fields.title)
+					.unwrap_or_else(|| content.name.clone()),
+				summary: get_field_text(&content.fields, &config.fields.summary),
+				link: get_link(&base_url, &config.link_template, &content),
+				published_at: get_field_text(&content.fields, &config.fields.date)
+					.and_then(|date| parse_date(&date))
+					.unwrap_or(created_at),
+				updated_at,
+			}
+		})
+		.collect::<Vec<FeedEntry>>();
+	entries.sort_by(|a, b| b.published_at.cmp(&a.published_at));
+	entries.truncate(limit as usize);
+
+	Ok(Feed {
+		id: format!("urn:dcm:sites:{}:feeds:{}:{}", site.id, key, language.key),
+		title: config.title,
+		description: config.description,
+		author: site.name.clone(),
+		link: base_url,
+		language: language.key,
+		updated_at: entries
+			.iter()
+			.map(|entry| entry.updated_at)
+			.max()
+			.unwrap_or_else(Utc::now),
+		entries,
+	})
+}
+
+pub fn build_rss(feed: &Feed) -> String {
+	let mut rss = format!(
+		r#"{XML_DECLARATION}<rss version="2.0"><channel><title>{}</title><link>{}</link><description>{}</description><language>{}</language><lastBuildDate>{}</lastBuildDate>"#,
+		escape_xml(&feed.title),
+		escape_xml(&feed.link),
+		escape_xml(feed.description.as_deref().unwrap_or(&feed.title)),
+		escape_xml(&feed.language),
+		feed.updated_at.to_rfc2822()
+	);
+
+	for entry in &feed.entries {
+		let _ = write!(
+			rss,
+			r#"<item><title>{}</title><guid isPermaLink="false">{}</guid><pubDate>{}</pubDate>"#,
+			escape_xml(&entry.title),
+			entry.id,
+			entry.published_at.to_rfc2822()
+		);
+		if let Some(link) = &entry.link {
+			let _ = write!(rss, "<link>{}</link>", escape_xml(link));
+		}
+		if let Some(summary) = &entry.summary {
+			let _ = write!(rss, "<description>{}</description>", escape_xml(summary));
+		}
+		rss.push_str("</item>");
+	}
+
+	rss.push_str("</channel></rss>");
+	rss
+}
+
+pub fn build_atom(feed: &Feed) -> String {
+	let mut atom = format!(
+		r#"{XML_DECLARATION}<feed xmlns="http://www.w3.org/2005/Atom" xml:lang="{}"><id>{}</id><title>{}</title><updated>{}</updated><link href="{}"/><author><name>{}</name></author>"#,
+		escape_xml(&feed.language),
+		escape_xml(&feed.id),
+		escape_xml(&feed.title),
+		feed.updated_at.to_rfc3339(),
+		escape_xml(&feed.link),
+		escape_xml(&feed.author)
+	);
+	if let Some(description) = &feed.description {
+		let _ = write!(atom, "<subtitle>{}</subtitle>", escape_xml(description));
+	}
+
+	for entry in &feed.entries {
+		let _ = write!(
+			atom,
+			"<entry><id>urn:uuid:{}</id><title>{}</title><published>{}</published><updated>{}</updated>",
+			entry.id,
+			escape_xml(&entry.title),
+			entry.published_at.to_rfc3339(),
+			entry.updated_at.to_rfc3339()
+		);
+		if let Some(link) = &entry.link {
+			let _ = write!(atom, r#"<link href="{}"/>"#, escape_xml(link));
+		}
+		if let Some(summary) = &entry.summary {
+			let _ = write!(
+				atom,
+				r#"<summary type="html">{}</summary>"#,
+				escape_xml(summary)
+			);
+		}
+		atom.push_str("</entry>");
+	}
+
+	atom.push_str("</feed>");
+	atom
+}
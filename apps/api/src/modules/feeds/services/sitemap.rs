@@ -0,0 +1,54 @@
+use std::fmt::Write;
+
+use chrono::{TimeZone, Utc};
+
+use crate::modules::content::models::content::Content;
+use crate::modules::feeds::helpers::xml::{escape_xml, XML_DECLARATION};
+use crate::modules::languages::models::language::Language;
+
+// The sitemap protocol allows at most this many urls in one file
+pub const MAX_SITEMAP_URLS: i64 = 50_000;
+
+// Only content with a path has a url, translations are listed as hreflang alternates of each other
+pub fn build_sitemap(base_url: &str, pages: Vec<(Content, Vec<(Content, Language)>)>) -> String {
+	let mut sitemap = format!(
+		r#"{XML_DECLARATION}<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9" xmlns:xhtml="http://www.w3.org/1999/xhtml">"#
+	);
+
+	for (page, translations) in pages {
+		let Some(path) = &page.path else {
+			continue;
+		};
+		let lastmod = Utc.from_utc_datetime(&page.updated_at).to_rfc3339();
+		let _ = write!(
+			sitemap,
+			"<url><loc>{}</loc><lastmod>{}</lastmod>",
+			escape_xml(&format!("{base_url}{path}")),
+			lastmod
+		);
+
+		let alternates = translations
+			.iter()
+			.filter_map(|(translation, language)| {
+				let path = translation.path.as_ref()?;
+				(!translation.deleted).then(|| (language.key.as_str(), path))
+			})
+			.collect::<Vec<(&str, &String)>>();
+		// A page without translations has nothing to point to
+		if alternates.len() > 1 {
+			for (language_key, path) in alternates {
+				let _ = write!(
+					sitemap,
+					r#"<xhtml:link rel="alternate" hreflang="{}" href="{}"/>"#,
+					escape_xml(language_key),
+					escape_xml(&format!("{base_url}{path}"))
+				);
+			}
+		}
+
+		sitemap.push_str("</url>");
+	}
+
+	sitemap.push_str("</urlset>");
+	sitemap
+}
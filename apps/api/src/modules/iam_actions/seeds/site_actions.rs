@@ -1,6 +1,6 @@
 use crate::modules::iam_actions::models::iam_action::CreateIAMAction;
 
-pub const SITE_IAM_ACTION_SEEDS: [CreateIAMAction<'_>; 52] = [
+pub const SITE_IAM_ACTION_SEEDS: [CreateIAMAction<'_>; 75] = [
 	CreateIAMAction {
 		key: "sites::*",
 		description: None,
@@ -139,6 +139,18 @@ pub const SITE_IAM_ACTION_SEEDS: [CreateIAMAction<'_>; 52] = [
 		key: "sites::resources:remove-directory",
 		description: None,
 	},
+	CreateIAMAction {
+		key: "sites::resources:move-directory",
+		description: None,
+	},
+	CreateIAMAction {
+		key: "sites::resources:rename-directory",
+		description: None,
+	},
+	CreateIAMAction {
+		key: "sites::resources:copy-directory",
+		description: None,
+	},
 	CreateIAMAction {
 		key: "sites::resources:upload-file",
 		description: None,
@@ -147,6 +159,18 @@ pub const SITE_IAM_ACTION_SEEDS: [CreateIAMAction<'_>; 52] = [
 		key: "sites::resources:remove-file",
 		description: None,
 	},
+	CreateIAMAction {
+		key: "sites::resources:move-file",
+		description: None,
+	},
+	CreateIAMAction {
+		key: "sites::resources:rename-file",
+		description: None,
+	},
+	CreateIAMAction {
+		key: "sites::resources:copy-file",
+		description: None,
+	},
 	/*
 	 * Users
 	 */
@@ -239,4 +263,84 @@ pub const SITE_IAM_ACTION_SEEDS: [CreateIAMAction<'_>; 52] = [
 		key: "sites::storage-repositories:remove",
 		description: None,
 	},
+	/*
+	 * storage-migrations
+	 */
+	CreateIAMAction {
+		key: "sites::storage-migrations:*",
+		description: None,
+	},
+	CreateIAMAction {
+		key: "sites::storage-migrations:read",
+		description: None,
+	},
+	CreateIAMAction {
+		key: "sites::storage-migrations:create",
+		description: None,
+	},
+	CreateIAMAction {
+		key: "sites::storage-migrations:resume",
+		description: None,
+	},
+	/*
+	 * api-keys
+	 */
+	CreateIAMAction {
+		key: "sites::api-keys:*",
+		description: None,
+	},
+	CreateIAMAction {
+		key: "sites::api-keys:read",
+		description: None,
+	},
+	CreateIAMAction {
+		key: "sites::api-keys:create",
+		description: None,
+	},
+	CreateIAMAction {
+		key: "sites::api-keys:update",
+		description: None,
+	},
+	CreateIAMAction {
+		key: "sites::api-keys:remove",
+		description: None,
+	},
+	/*
+	 * config
+	 */
+	CreateIAMAction {
+		key: "sites::config:*",
+		description: None,
+	},
+	CreateIAMAction {
+		key: "sites::config:read",
+		description: None,
+	},
+	CreateIAMAction {
+		key: "sites::config:update",
+		description: None,
+	},
+	/*
+	 * redirects
+	 */
+	CreateIAMAction {
+		key: "sites::redirects:*",
+		description: None,
+	},
+	CreateIAMAction {
+		key: "sites::redirects:read",
+		description: None,
+	},
+	CreateIAMAction {
+		key: "sites::redirects:create",
+		description: None,
+	},
+	CreateIAMAction {
+		key: "sites::redirects:update",
+		description: None,
+	},
+	CreateIAMAction {
+		key: "sites::redirects:remove",
+		description: None,
+	},
 ];
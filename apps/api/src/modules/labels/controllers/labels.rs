@@ -0,0 +1,164 @@
+use super::super::dto::labels::{request, response};
+use super::super::models::label::{CreateLabel, Label, LabelEntityTypeEnum};
+use crate::errors::{AppError, AppErrorValue};
+use crate::modules::auth::helpers::permissions::ensure_permission;
+use crate::modules::content_types::models::{
+	compartment::CompartmentModel, content_type::ContentType, field::FieldModel,
+};
+use crate::modules::core::middleware::state::AppState;
+use crate::modules::sites::models::site::Site;
+use crate::modules::workflows::models::workflow_state::WorkflowState;
+use crate::utils::api::ApiResponse;
+use actix_web::{get, put, web, HttpRequest, HttpResponse};
+use diesel::PgConnection;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindOnePathParams {
+	site_id: Uuid,
+	entity_type: LabelEntityTypeEnum,
+	entity_id: Uuid,
+}
+
+// Labels are managed with the same permissions as the entity they belong to
+fn ensure_label_permission(
+	req: &HttpRequest,
+	params: &FindOnePathParams,
+	update: bool,
+) -> Result<(), AppError> {
+	let (resource, action) = match (params.entity_type, update) {
+		(LabelEntityTypeEnum::WORKFLOW_STATE, false) => (
+			format!("urn:dcm:workflow-states:{}", params.entity_id),
+			"sites::workflow-states:read",
+		),
+		(LabelEntityTypeEnum::WORKFLOW_STATE, true) => (
+			format!("urn:dcm:workflow-states:{}", params.entity_id),
+			"sites::workflow-states:update",
+		),
+		(_, false) => (
+			format!("urn:dcm:content-types:*"),
+			"sites::content-types:read",
+		),
+		(_, true) => (
+			format!("urn:dcm:content-types:*"),
+			"sites::content-types:update",
+		),
+	};
+
+	ensure_permission(req, Some(params.site_id), resource, action)?;
+	Ok(())
+}
+
+fn ensure_entity_exists(
+	conn: &mut PgConnection,
+	site_id: Uuid,
+	entity_type: LabelEntityTypeEnum,
+	entity_id: Uuid,
+) -> Result<(), AppError> {
+	match entity_type {
+		LabelEntityTypeEnum::CONTENT_TYPE => {
+			ContentType::find_one(conn, site_id, entity_id)?;
+		}
+		LabelEntityTypeEnum::FIELD => {
+			FieldModel::find_one(conn, site_id, entity_id)?;
+		}
+		LabelEntityTypeEnum::COMPARTMENT => {
+			CompartmentModel::find_one(conn, site_id, entity_id)?;
+		}
+		LabelEntityTypeEnum::WORKFLOW_STATE => {
+			WorkflowState::find_one(conn, site_id, entity_id)?;
+		}
+	};
+
+	Ok(())
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/labels",
+	responses(
+		(status = 200, body = LabelsDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindOnePathParams)
+)]
+#[get("/{entity_type}/{entity_id}")]
+pub async fn find_one(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindOnePathParams>,
+) -> ApiResponse {
+	ensure_label_permission(&req, &params, false)?;
+	let conn = &mut state.get_conn()?;
+	ensure_entity_exists(conn, params.site_id, params.entity_type, params.entity_id)?;
+	let labels = Label::find(conn, params.entity_id)?;
+
+	let res = response::LabelsDTO::from((params.entity_type, params.entity_id, labels));
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/labels",
+    request_body = UpsertLabelsDTO,
+	responses(
+		(status = 200, body = LabelsDTO),
+		(status = 400, body = AppErrorValue, description = "The language is not enabled for the site"),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindOnePathParams)
+)]
+#[put("/{entity_type}/{entity_id}")]
+pub async fn update(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindOnePathParams>,
+	form: web::Json<request::UpsertLabelsDTO>,
+) -> ApiResponse {
+	ensure_label_permission(&req, &params, true)?;
+	let conn = &mut state.get_conn()?;
+	ensure_entity_exists(conn, params.site_id, params.entity_type, params.entity_id)?;
+
+	let (_, languages) = Site::find_one(conn, params.site_id)?;
+	if let Some(label) = form.labels.iter().find(|label| {
+		!languages
+			.iter()
+			.any(|language| language.key == label.language_key)
+	}) {
+		return Err(AppError::BadRequest(AppErrorValue {
+			message: format!(
+				"Language {} is not enabled for this site",
+				label.language_key
+			),
+			status: StatusCode::BAD_REQUEST.as_u16(),
+			code: "LANGUAGE_NOT_ENABLED".to_owned(),
+			..Default::default()
+		}));
+	}
+
+	let labels = Label::upsert(
+		conn,
+		params.entity_type,
+		params.entity_id,
+		form.labels
+			.iter()
+			.map(|label| CreateLabel {
+				entity_type: params.entity_type,
+				entity_id: params.entity_id,
+				language_key: label.language_key.clone(),
+				name: label.name.clone(),
+				description: label.description.clone(),
+			})
+			.collect(),
+	)?;
+
+	let res = response::LabelsDTO::from((params.entity_type, params.entity_id, labels));
+	Ok(HttpResponse::Ok().json(res))
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertLabelDTO {
+	pub language_key: String,
+	pub name: String,
+	pub description: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertLabelsDTO {
+	pub labels: Vec<UpsertLabelDTO>,
+}
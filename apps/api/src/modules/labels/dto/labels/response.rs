@@ -0,0 +1,48 @@
+use crate::modules::labels::models::label::{Label, LabelEntityTypeEnum};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::convert::From;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelDTO {
+	pub id: Uuid,
+	pub language_key: String,
+	pub name: String,
+	pub description: Option<String>,
+	pub created_at: NaiveDateTime,
+	pub updated_at: NaiveDateTime,
+}
+
+impl From<Label> for LabelDTO {
+	fn from(label: Label) -> Self {
+		Self {
+			id: label.id,
+			language_key: label.language_key,
+			name: label.name,
+			description: label.description,
+			created_at: label.created_at,
+			updated_at: label.updated_at,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelsDTO {
+	pub entity_type: LabelEntityTypeEnum,
+	pub entity_id: Uuid,
+	pub labels: Vec<LabelDTO>,
+}
+
+impl From<(LabelEntityTypeEnum, Uuid, Vec<Label>)> for LabelsDTO {
+	fn from((entity_type, entity_id, labels): (LabelEntityTypeEnum, Uuid, Vec<Label>)) -> Self {
+		Self {
+			entity_type,
+			entity_id,
+			labels: labels.into_iter().map(LabelDTO::from).collect(),
+		}
+	}
+}
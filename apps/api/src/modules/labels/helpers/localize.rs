@@ -0,0 +1,127 @@
+use actix_web::http::header;
+use actix_web::HttpRequest;
+use diesel::PgConnection;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::modules::content_types::models::{
+	compartment::CompartmentModel, content_type::ContentType, field::FieldModel,
+};
+use crate::modules::core::middleware::auth::get_current_user;
+use crate::modules::labels::models::label::Label;
+use crate::modules::workflows::models::workflow_state::WorkflowState;
+
+pub trait Localizable {
+	fn label_entity_id(&self) -> Uuid;
+	fn apply_label(&mut self, label: &Label);
+}
+
+impl Localizable for ContentType {
+	fn label_entity_id(&self) -> Uuid {
+		self.id
+	}
+
+	fn apply_label(&mut self, label: &Label) {
+		self.name = label.name.clone();
+		if label.description.is_some() {
+			self.description = label.description.clone();
+		}
+	}
+}
+
+impl Localizable for FieldModel {
+	fn label_entity_id(&self) -> Uuid {
+		self.id
+	}
+
+	fn apply_label(&mut self, label: &Label) {
+		self.name = label.name.clone();
+		if label.description.is_some() {
+			self.description = label.description.clone();
+		}
+	}
+}
+
+impl Localizable for CompartmentModel {
+	fn label_entity_id(&self) -> Uuid {
+		self.id
+	}
+
+	fn apply_label(&mut self, label: &Label) {
+		self.name = label.name.clone();
+		if label.description.is_some() {
+			self.description = label.description.clone();
+		}
+	}
+}
+
+impl Localizable for WorkflowState {
+	fn label_entity_id(&self) -> Uuid {
+		self.id
+	}
+
+	fn apply_label(&mut self, label: &Label) {
+		self.name = label.name.clone();
+		if label.description.is_some() {
+			self.description = label.description.clone();
+		}
+	}
+}
+
+// The editor's own preference goes first, then whatever the browser asks for.
+// `nl-BE` also falls back to `nl`, entities without a matching label keep their original name.
+pub fn get_language_keys(req: &HttpRequest) -> Vec<String> {
+	let mut language_keys: Vec<String> = vec![];
+	let mut push = |language_key: String| {
+		if !language_key.is_empty() && !language_keys.contains(&language_key) {
+			language_keys.push(language_key);
+		}
+	};
+
+	if let Some(language_key) = get_current_user(req)
+		.ok()
+		.and_then(|user| user.language_key)
+	{
+		push(language_key);
+	}
+
+	let accept_language = req
+		.headers()
+		.get(header::ACCEPT_LANGUAGE)
+		.and_then(|value| value.to_str().ok())
+		.unwrap_or_default();
+	for tag in accept_language.split(',') {
+		let tag = tag.split(';').next().unwrap_or_default().trim();
+		if tag == "*" {
+			continue;
+		}
+
+		push(tag.to_owned());
+		if let Some((primary, _)) = tag.split_once('-') {
+			push(primary.to_lowercase());
+		}
+	}
+
+	language_keys
+}
+
+pub fn localize<'a, T: Localizable + 'a>(
+	conn: &mut PgConnection,
+	language_keys: &[String],
+	items: impl IntoIterator<Item = &'a mut T>,
+) -> Result<(), AppError> {
+	let mut items = items.into_iter().collect::<Vec<&mut T>>();
+	let labels = Label::find_localized(
+		conn,
+		items.iter().map(|item| item.label_entity_id()).collect(),
+		language_keys,
+	)?;
+
+	for item in items.iter_mut() {
+		if let Some(label) = labels.get(&item.label_entity_id()) {
+			item.apply_label(label);
+		}
+	}
+
+	Ok(())
+}
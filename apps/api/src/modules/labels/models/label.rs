@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use chrono::NaiveDateTime;
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::{Pg, PgValue};
+use diesel::prelude::*;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::{AsExpression, FromSqlRow};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::schema::{labels, sql_types::LabelEntityTypes};
+
+// Blocks are fields of a field, their labels use `FIELD` as well
+#[derive(
+	Debug, PartialEq, FromSqlRow, AsExpression, Eq, Clone, Copy, Deserialize, Serialize, ToSchema,
+)]
+#[diesel(sql_type = LabelEntityTypes)]
+#[allow(non_camel_case_types)]
+pub enum LabelEntityTypeEnum {
+	CONTENT_TYPE,
+	FIELD,
+	COMPARTMENT,
+	WORKFLOW_STATE,
+}
+
+impl ToSql<LabelEntityTypes, Pg> for LabelEntityTypeEnum {
+	fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+		match *self {
+			LabelEntityTypeEnum::CONTENT_TYPE => out.write_all(b"CONTENT_TYPE")?,
+			LabelEntityTypeEnum::FIELD => out.write_all(b"FIELD")?,
+			LabelEntityTypeEnum::COMPARTMENT => out.write_all(b"COMPARTMENT")?,
+			LabelEntityTypeEnum::WORKFLOW_STATE => out.write_all(b"WORKFLOW_STATE")?,
+		}
+		Ok(IsNull::No)
+	}
+}
+
+impl FromSql<LabelEntityTypes, Pg> for LabelEntityTypeEnum {
+	fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+		match bytes.as_bytes() {
+			b"CONTENT_TYPE" => Ok(LabelEntityTypeEnum::CONTENT_TYPE),
+			b"FIELD" => Ok(LabelEntityTypeEnum::FIELD),
+			b"COMPARTMENT" => Ok(LabelEntityTypeEnum::COMPARTMENT),
+			b"WORKFLOW_STATE" => Ok(LabelEntityTypeEnum::WORKFLOW_STATE),
+			_ => Err("Unrecognized enum variant".into()),
+		}
+	}
+}
+
+#[derive(Identifiable, Selectable, Queryable, Debug, Clone, Deserialize, Serialize)]
+#[diesel(table_name = labels)]
+#[diesel(primary_key(id))]
+pub struct Label {
+	pub id: Uuid,
+	pub entity_type: LabelEntityTypeEnum,
+	pub entity_id: Uuid,
+	pub language_key: String,
+	pub name: String,
+	pub description: Option<String>,
+	pub created_at: NaiveDateTime,
+	pub updated_at: NaiveDateTime,
+}
+
+impl Label {
+	#[instrument(skip(conn))]
+	pub fn find(conn: &mut PgConnection, entity_id: Uuid) -> Result<Vec<Self>, AppError> {
+		let labels = labels::table
+			.filter(labels::entity_id.eq(entity_id))
+			.order(labels::language_key.asc())
+			.select(Label::as_select())
+			.load::<Label>(conn)?;
+
+		Ok(labels)
+	}
+
+	// Picks one label per entity, the earliest language key in `language_keys` wins
+	#[instrument(skip(conn))]
+	pub fn find_localized(
+		conn: &mut PgConnection,
+		entity_ids: Vec<Uuid>,
+		language_keys: &[String],
+	) -> Result<HashMap<Uuid, Self>, AppError> {
+		if entity_ids.is_empty() || language_keys.is_empty() {
+			return Ok(HashMap::new());
+		}
+
+		let labels = labels::table
+			.filter(labels::entity_id.eq_any(entity_ids))
+			.filter(labels::language_key.eq_any(language_keys))
+			.select(Label::as_select())
+			.load::<Label>(conn)?;
+
+		let rank = |label: &Label| {
+			language_keys
+				.iter()
+				.position(|language_key| *language_key == label.language_key)
+				.unwrap_or(usize::MAX)
+		};
+
+		let mut localized: HashMap<Uuid, Self> = HashMap::new();
+		for label in labels {
+			match localized.get(&label.entity_id) {
+				Some(existing) if rank(existing) <= rank(&label) => {}
+				_ => {
+					localized.insert(label.entity_id, label);
+				}
+			}
+		}
+
+		Ok(localized)
+	}
+
+	// Replaces every label of the entity, languages that are left out lose their label
+	#[instrument(skip(conn))]
+	pub fn upsert(
+		conn: &mut PgConnection,
+		entity_type: LabelEntityTypeEnum,
+		entity_id: Uuid,
+		labels: Vec<CreateLabel>,
+	) -> Result<Vec<Self>, AppError> {
+		conn.transaction::<_, AppError, _>(|conn| {
+			diesel::delete(labels::table.filter(labels::entity_id.eq(entity_id))).execute(conn)?;
+
+			let labels = labels
+				.into_iter()
+				.map(|label| CreateLabel {
+					entity_type,
+					entity_id,
+					..label
+				})
+				.collect::<Vec<CreateLabel>>();
+			diesel::insert_into(labels::table)
+				.values(labels)
+				.execute(conn)?;
+
+			Self::find(conn, entity_id)
+		})
+	}
+
+	// Labels don't reference their entity, so the entities remove them when they are removed themselves
+	#[instrument(skip(conn))]
+	pub fn remove(conn: &mut PgConnection, entity_ids: Vec<Uuid>) -> Result<(), AppError> {
+		diesel::delete(labels::table.filter(labels::entity_id.eq_any(entity_ids))).execute(conn)?;
+
+		Ok(())
+	}
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = labels)]
+pub struct CreateLabel {
+	pub entity_type: LabelEntityTypeEnum,
+	pub entity_id: Uuid,
+	pub language_key: String,
+	pub name: String,
+	pub description: Option<String>,
+}
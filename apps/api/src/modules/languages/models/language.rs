@@ -22,6 +22,14 @@ impl Language {
 		Ok(language)
 	}
 
+	pub fn find_by_key(conn: &mut PgConnection, key: &str) -> Result<Self, AppError> {
+		let language = languages::table
+			.filter(languages::key.eq(key))
+			.select(Language::as_select())
+			.first(conn)?;
+		Ok(language)
+	}
+
 	pub fn find(
 		conn: &mut PgConnection,
 		page: i64,
@@ -1,16 +1,21 @@
+pub mod api_keys;
 pub mod auth;
 pub mod authentication_methods;
 pub mod content;
 pub mod content_components;
 pub mod content_types;
 pub mod core;
+pub mod feeds;
 pub mod iam_actions;
 pub mod iam_conditions;
 pub mod iam_policies;
+pub mod labels;
 pub mod languages;
 pub mod modules;
+pub mod redirects;
 pub mod resources;
 pub mod roles;
+pub mod scim;
 pub mod setup;
 pub mod sites;
 pub mod users;
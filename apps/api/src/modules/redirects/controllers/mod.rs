@@ -0,0 +1,2 @@
+pub mod public_redirects;
+pub mod redirects;
@@ -0,0 +1,63 @@
+use super::super::dto::redirects::response;
+use crate::modules::core::middleware::state::AppState;
+use crate::modules::languages::models::language::Language;
+use crate::modules::redirects::models::redirect::Redirect;
+use crate::{
+	errors::{AppError, AppErrorValue},
+	modules::content::models::_content::content_tree::normalize_path,
+};
+use actix_web::{get, web, HttpResponse};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindPathParams {
+	site_id: Uuid,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct LookupQueryParams {
+	path: String,
+	// Without a language the redirects of every language are searched
+	lang: Option<String>,
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/redirects",
+	responses(
+		(status = 200, body = RedirectLookupDTO),
+		(status = 404, body = AppErrorValue, description = "No redirect matches the path")
+	),
+	params(FindPathParams, LookupQueryParams)
+)]
+#[get("/lookup")]
+pub async fn lookup(
+	state: web::Data<AppState>,
+	params: web::Path<FindPathParams>,
+	query: web::Query<LookupQueryParams>,
+) -> Result<HttpResponse, AppError> {
+	let conn = &mut state.get_conn()?;
+	let path = normalize_path(&query.path);
+	let language_id = match &query.lang {
+		Some(lang) => Some(Language::find_by_key(conn, lang)?.id),
+		None => None,
+	};
+
+	match Redirect::find_match(conn, params.site_id, language_id, &path)? {
+		Some(redirect_match) => {
+			// Frontends look a path up once per visit, so every lookup is counted
+			Redirect::register_hit(conn, redirect_match.redirect.id)?;
+			let res = response::RedirectLookupDTO::from(redirect_match);
+			Ok(HttpResponse::Ok().json(res))
+		}
+		None => Err(AppError::NotFound(AppErrorValue {
+			message: format!("No redirect found for {}", path),
+			status: StatusCode::NOT_FOUND.as_u16(),
+			code: "REDIRECT_NOT_FOUND".to_owned(),
+			..Default::default()
+		})),
+	}
+}
@@ -0,0 +1,342 @@
+use super::super::dto::redirects::{request, response};
+use crate::errors::{AppError, AppErrorValue};
+use crate::modules::auth::helpers::permissions::ensure_permission;
+use crate::modules::core::middleware::state::AppState;
+use crate::modules::core::models::hal::HALPage;
+use crate::modules::redirects::models::redirect::{
+	CreateRedirect, Redirect, RedirectMatchTypeEnum, UpdateRedirect,
+};
+use crate::modules::redirects::services::redirect_import::{apply_import, plan_import};
+use crate::utils::api::ApiResponse;
+use actix_multipart::form::MultipartForm;
+use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse};
+use chrono::Utc;
+use diesel::PgConnection;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindOnePathParams {
+	site_id: Uuid,
+	redirect_id: Uuid,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindPathParams {
+	site_id: Uuid,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindAllQueryParams {
+	page: Option<i64>,
+	pagesize: Option<i64>,
+	search: Option<String>,
+}
+
+// Two rules for the same source would make it random which one wins
+fn ensure_source_available(
+	conn: &mut PgConnection,
+	site_id: Uuid,
+	language_id: Option<Uuid>,
+	match_type: RedirectMatchTypeEnum,
+	source_path: &str,
+	redirect_id: Option<Uuid>,
+) -> Result<(), AppError> {
+	let existing = Redirect::find_by_source(conn, site_id, language_id, match_type, source_path)?;
+	match existing {
+		Some(existing) if Some(existing.id) != redirect_id => {
+			Err(AppError::UnprocessableEntity(AppErrorValue {
+				message: format!("A redirect for {} already exists", source_path),
+				status: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+				code: "REDIRECT_SOURCE_TAKEN".to_owned(),
+				..Default::default()
+			}))
+		}
+		_ => Ok(()),
+	}
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/redirects",
+	responses(
+		(status = 200, body = RedirectsDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindPathParams, FindAllQueryParams)
+)]
+#[get("")]
+pub async fn find_all(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	query: web::Query<FindAllQueryParams>,
+	params: web::Path<FindPathParams>,
+) -> ApiResponse {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:redirects:*"),
+		"sites::redirects:read",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let page = query.page.unwrap_or(1);
+	let pagesize = query.pagesize.unwrap_or(20);
+
+	let (redirects, total_elements) =
+		Redirect::find(conn, params.site_id, page, pagesize, query.search.clone())?;
+
+	let res = response::RedirectsDTO::from((
+		redirects,
+		HALPage {
+			number: page,
+			size: pagesize,
+			total_elements,
+			total_pages: (total_elements / pagesize + (total_elements % pagesize).signum()).max(1),
+		},
+		params.site_id,
+	));
+
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/redirects",
+    request_body = CreateRedirectDTO,
+	responses(
+		(status = 200, body = RedirectDTO),
+		(status = 400, body = AppErrorValue, description = "The redirect is invalid"),
+		(status = 401, body = AppErrorValue, description = "Unauthorized"),
+		(status = 422, body = AppErrorValue, description = "A redirect for the source already exists")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindPathParams)
+)]
+#[post("")]
+pub async fn create(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	form: web::Json<request::CreateRedirectDTO>,
+	params: web::Path<FindPathParams>,
+) -> ApiResponse {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:redirects:*"),
+		"sites::redirects:create",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let source_path = Redirect::validate(
+		conn,
+		params.site_id,
+		form.match_type,
+		&form.source_path,
+		&form.target_path,
+		&form.content_id,
+		form.status_code,
+	)?;
+	ensure_source_available(
+		conn,
+		params.site_id,
+		form.language_id,
+		form.match_type,
+		&source_path,
+		None,
+	)?;
+
+	let redirect = Redirect::create(
+		conn,
+		CreateRedirect {
+			site_id: params.site_id,
+			language_id: form.language_id,
+			source_path,
+			match_type: form.match_type,
+			target_path: form.target_path.clone(),
+			content_id: form.content_id,
+			status_code: form.status_code,
+		},
+	)?;
+
+	let res = response::RedirectDTO::from(redirect);
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/redirects",
+    request_body(content = ImportRedirectsDTO, content_type = "multipart/form-data"),
+	responses(
+		(status = 200, body = RedirectImportReportDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized"),
+		(status = 422, body = RedirectImportReportDTO, description = "Some rows are invalid, nothing was imported")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindPathParams)
+)]
+#[post("/import")]
+pub async fn import(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindPathParams>,
+	MultipartForm(form): MultipartForm<request::ImportRedirectsDTO>,
+) -> ApiResponse {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:redirects:*"),
+		"sites::redirects:create",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let dry_run = form
+		.dry_run
+		.map(|dry_run| dry_run.into_inner())
+		.unwrap_or(false);
+
+	let plan = plan_import(conn, params.site_id, form.file.file.reopen()?)?;
+
+	if plan.is_blocked() {
+		let res = response::RedirectImportReportDTO::from((plan, 0, 0));
+		return Ok(HttpResponse::UnprocessableEntity().json(res));
+	}
+
+	if dry_run {
+		let res = response::RedirectImportReportDTO::from((plan, 0, 0));
+		return Ok(HttpResponse::Ok().json(res));
+	}
+
+	let (created, updated) = apply_import(conn, params.site_id, &plan)?;
+
+	let res = response::RedirectImportReportDTO::from((plan, created, updated));
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/redirects",
+	responses(
+		(status = 200, body = RedirectDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized"),
+		(status = 404, body = AppErrorValue, description = "Not Found")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindOnePathParams)
+)]
+#[get("/{redirect_id}")]
+pub async fn find_one(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindOnePathParams>,
+) -> ApiResponse {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:redirects:{}", params.redirect_id),
+		"sites::redirects:read",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let redirect = Redirect::find_one(conn, params.site_id, params.redirect_id)?;
+
+	let res = response::RedirectDTO::from(redirect);
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/redirects",
+    request_body = UpdateRedirectDTO,
+	responses(
+		(status = 200, body = RedirectDTO),
+		(status = 400, body = AppErrorValue, description = "The redirect is invalid"),
+		(status = 401, body = AppErrorValue, description = "Unauthorized"),
+		(status = 422, body = AppErrorValue, description = "A redirect for the source already exists")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindOnePathParams)
+)]
+#[put("/{redirect_id}")]
+pub async fn update(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindOnePathParams>,
+	form: web::Json<request::UpdateRedirectDTO>,
+) -> ApiResponse {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:redirects:{}", params.redirect_id),
+		"sites::redirects:update",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let source_path = Redirect::validate(
+		conn,
+		params.site_id,
+		form.match_type,
+		&form.source_path,
+		&form.target_path,
+		&form.content_id,
+		form.status_code,
+	)?;
+	ensure_source_available(
+		conn,
+		params.site_id,
+		form.language_id,
+		form.match_type,
+		&source_path,
+		Some(params.redirect_id),
+	)?;
+
+	let redirect = Redirect::update(
+		conn,
+		params.site_id,
+		params.redirect_id,
+		UpdateRedirect {
+			language_id: form.language_id,
+			source_path,
+			match_type: form.match_type,
+			target_path: form.target_path.clone(),
+			content_id: form.content_id,
+			status_code: form.status_code,
+			updated_at: Utc::now().naive_utc(),
+		},
+	)?;
+
+	let res = response::RedirectDTO::from(redirect);
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/redirects",
+	responses(
+		(status = 204),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindOnePathParams)
+)]
+#[delete("/{redirect_id}")]
+pub async fn remove(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindOnePathParams>,
+) -> ApiResponse {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:redirects:{}", params.redirect_id),
+		"sites::redirects:remove",
+	)?;
+	let conn = &mut state.get_conn()?;
+	Redirect::remove(conn, params.site_id, params.redirect_id)?;
+
+	Ok(HttpResponse::NoContent().body(()))
+}
@@ -0,0 +1,39 @@
+use actix_multipart::form::{tempfile::TempFile, text::Text, MultipartForm};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::modules::redirects::models::redirect::RedirectMatchTypeEnum;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRedirectDTO {
+	// Left empty the redirect applies to every language
+	pub language_id: Option<Uuid>,
+	pub source_path: String,
+	pub match_type: RedirectMatchTypeEnum,
+	pub target_path: Option<String>,
+	pub content_id: Option<Uuid>,
+	pub status_code: i32,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRedirectDTO {
+	pub language_id: Option<Uuid>,
+	pub source_path: String,
+	pub match_type: RedirectMatchTypeEnum,
+	pub target_path: Option<String>,
+	pub content_id: Option<Uuid>,
+	pub status_code: i32,
+}
+
+// A CSV file with the columns `source`, `target`, `status`, `match` and `language`
+#[derive(Debug, MultipartForm, ToSchema)]
+pub struct ImportRedirectsDTO {
+	#[multipart(rename = "file")]
+	pub file: TempFile,
+	// Only reports the errors without importing anything
+	#[multipart(rename = "dryRun")]
+	pub dry_run: Option<Text<bool>>,
+}
@@ -0,0 +1,129 @@
+use crate::modules::{
+	core::models::hal::{HALLinkList, HALPage},
+	redirects::models::redirect::{Redirect, RedirectMatch, RedirectMatchTypeEnum},
+	redirects::services::redirect_import::{RedirectImportError, RedirectImportPlan},
+};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::convert::From;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RedirectDTO {
+	pub id: Uuid,
+	pub language_id: Option<Uuid>,
+	pub source_path: String,
+	pub match_type: RedirectMatchTypeEnum,
+	pub target_path: Option<String>,
+	pub content_id: Option<Uuid>,
+	pub status_code: i32,
+	pub hits: i64,
+	pub last_hit_at: Option<NaiveDateTime>,
+	pub created_at: NaiveDateTime,
+	pub updated_at: NaiveDateTime,
+}
+
+impl From<Redirect> for RedirectDTO {
+	fn from(redirect: Redirect) -> Self {
+		Self {
+			id: redirect.id,
+			language_id: redirect.language_id,
+			source_path: redirect.source_path,
+			match_type: redirect.match_type,
+			target_path: redirect.target_path,
+			content_id: redirect.content_id,
+			status_code: redirect.status_code,
+			hits: redirect.hits,
+			last_hit_at: redirect.last_hit_at,
+			created_at: redirect.created_at,
+			updated_at: redirect.updated_at,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RedirectsEmbeddedDTO {
+	pub redirects: Vec<RedirectDTO>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct RedirectsDTO {
+	pub _links: HALLinkList,
+	pub _page: HALPage,
+	pub _embedded: RedirectsEmbeddedDTO,
+}
+
+impl From<(Vec<Redirect>, HALPage, Uuid)> for RedirectsDTO {
+	fn from((redirects, page, site_id): (Vec<Redirect>, HALPage, Uuid)) -> Self {
+		Self {
+			_links: HALLinkList::from((format!("/api/v1/sites/{}/redirects", site_id), &page)),
+			_embedded: RedirectsEmbeddedDTO {
+				redirects: redirects.into_iter().map(RedirectDTO::from).collect(),
+			},
+			_page: page,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RedirectImportErrorDTO {
+	// Line in the CSV file, the header is line 1
+	pub line: u64,
+	pub message: String,
+}
+
+impl From<RedirectImportError> for RedirectImportErrorDTO {
+	fn from(error: RedirectImportError) -> Self {
+		Self {
+			line: error.line,
+			message: error.message,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RedirectImportReportDTO {
+	pub rows: usize,
+	pub created: usize,
+	pub updated: usize,
+	pub errors: Vec<RedirectImportErrorDTO>,
+}
+
+impl From<(RedirectImportPlan, usize, usize)> for RedirectImportReportDTO {
+	fn from((plan, created, updated): (RedirectImportPlan, usize, usize)) -> Self {
+		Self {
+			rows: plan.redirects.len(),
+			created,
+			updated,
+			errors: plan
+				.errors
+				.into_iter()
+				.map(RedirectImportErrorDTO::from)
+				.collect(),
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RedirectLookupDTO {
+	pub source_path: String,
+	// Empty when the status code is 410
+	pub target_path: Option<String>,
+	pub status_code: i32,
+}
+
+impl From<RedirectMatch> for RedirectLookupDTO {
+	fn from(redirect_match: RedirectMatch) -> Self {
+		Self {
+			source_path: redirect_match.redirect.source_path,
+			target_path: redirect_match.target,
+			status_code: redirect_match.redirect.status_code,
+		}
+	}
+}
@@ -0,0 +1,537 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::Mutex;
+
+use chrono::{NaiveDateTime, Utc};
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::{Pg, PgValue};
+use diesel::prelude::*;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::{AsExpression, FromSqlRow};
+use regex::Regex;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::{AppError, AppErrorValue};
+use crate::modules::content::models::_content::content_tree::normalize_path;
+use crate::schema::{content, redirects, sql_types::RedirectMatchTypes};
+
+// Compiled patterns are kept around so lookups don't compile every pattern of a site again
+const PATTERN_CACHE_SIZE: usize = 1000;
+
+lazy_static! {
+	static ref PATTERN_REGEXES: Mutex<HashMap<String, Regex>> = Mutex::new(HashMap::new());
+}
+
+// Status codes a redirect can answer with, `410` tells clients the page is gone for good
+pub const REDIRECT_STATUS_CODES: [i32; 3] = [301, 302, 410];
+
+#[derive(
+	Debug, PartialEq, FromSqlRow, AsExpression, Eq, Clone, Copy, Deserialize, Serialize, ToSchema,
+)]
+#[diesel(sql_type = RedirectMatchTypes)]
+#[allow(non_camel_case_types)]
+pub enum RedirectMatchTypeEnum {
+	EXACT,
+	// `*` matches anything, the target can use what it matched as `$1`, `$2`, ...
+	PATTERN,
+}
+
+impl ToSql<RedirectMatchTypes, Pg> for RedirectMatchTypeEnum {
+	fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+		match *self {
+			RedirectMatchTypeEnum::EXACT => out.write_all(b"EXACT")?,
+			RedirectMatchTypeEnum::PATTERN => out.write_all(b"PATTERN")?,
+		}
+		Ok(IsNull::No)
+	}
+}
+
+impl FromSql<RedirectMatchTypes, Pg> for RedirectMatchTypeEnum {
+	fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+		match bytes.as_bytes() {
+			b"EXACT" => Ok(RedirectMatchTypeEnum::EXACT),
+			b"PATTERN" => Ok(RedirectMatchTypeEnum::PATTERN),
+			_ => Err("Unrecognized enum variant".into()),
+		}
+	}
+}
+
+// Redirects that point to content follow it around, the target path is looked up when the redirect is hit
+#[derive(Identifiable, Selectable, Queryable, Debug, Clone)]
+#[diesel(table_name = redirects)]
+#[diesel(primary_key(id))]
+pub struct Redirect {
+	pub id: Uuid,
+	pub site_id: Uuid,
+	pub language_id: Option<Uuid>,
+	pub source_path: String,
+	pub content_id: Option<Uuid>,
+	pub created_at: NaiveDateTime,
+	pub updated_at: NaiveDateTime,
+	pub match_type: RedirectMatchTypeEnum,
+	pub target_path: Option<String>,
+	pub status_code: i32,
+	pub hits: i64,
+	pub last_hit_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RedirectMatch {
+	pub redirect: Redirect,
+	// Empty for `410`
+	pub target: Option<String>,
+}
+
+fn pattern_regex(source_path: &str) -> Result<Regex, regex::Error> {
+	Regex::new(&format!(
+		"^{}$",
+		regex::escape(source_path).replace(r"\*", "(.*)")
+	))
+}
+
+// Patterns that don't compile never match, they are rejected when stored anyway
+fn cached_pattern_regex(source_path: &str) -> Result<Option<Regex>, AppError> {
+	let mut regexes = PATTERN_REGEXES
+		.lock()
+		.map_err(|_| AppError::from("Could not lock the redirect pattern cache"))?;
+	if let Some(regex) = regexes.get(source_path) {
+		return Ok(Some(regex.clone()));
+	}
+
+	let Ok(regex) = pattern_regex(source_path) else {
+		return Ok(None);
+	};
+	// Patterns of removed or changed redirects stay behind, starting over keeps the cache bounded
+	if regexes.len() >= PATTERN_CACHE_SIZE {
+		regexes.clear();
+	}
+	regexes.insert(source_path.to_owned(), regex.clone());
+
+	Ok(Some(regex))
+}
+
+impl Redirect {
+	#[instrument(skip(conn))]
+	pub fn find(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		page: i64,
+		pagesize: i64,
+		search: Option<String>,
+	) -> Result<(Vec<Self>, i64), AppError> {
+		let query = {
+			let mut query = redirects::table
+				.filter(redirects::site_id.eq(site_id))
+				.order(redirects::source_path.asc())
+				.into_boxed();
+
+			if pagesize != -1 {
+				query = query.offset((page - 1) * pagesize).limit(pagesize);
+			};
+
+			if let Some(search) = &search {
+				query = query.filter(
+					redirects::source_path
+						.ilike(format!("%{}%", search))
+						.or(redirects::target_path.ilike(format!("%{}%", search))),
+				);
+			}
+
+			query
+		};
+
+		let total_query = {
+			let mut query = redirects::table
+				.filter(redirects::site_id.eq(site_id))
+				.into_boxed();
+
+			if let Some(search) = &search {
+				query = query.filter(
+					redirects::source_path
+						.ilike(format!("%{}%", search))
+						.or(redirects::target_path.ilike(format!("%{}%", search))),
+				);
+			}
+
+			query
+		};
+
+		let redirects = query.select(Redirect::as_select()).load::<Self>(conn)?;
+		let total_elements = total_query.count().get_result::<i64>(conn)?;
+
+		Ok((redirects, total_elements))
+	}
+
+	#[instrument(skip(conn))]
+	pub fn find_one(conn: &mut PgConnection, site_id: Uuid, id: Uuid) -> Result<Self, AppError> {
+		let redirect = redirects::table
+			.filter(redirects::site_id.eq(site_id))
+			.filter(redirects::id.eq(id))
+			.select(Redirect::as_select())
+			.first::<Self>(conn)?;
+
+		Ok(redirect)
+	}
+
+	#[instrument(skip(conn))]
+	pub fn find_by_source(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		language_id: Option<Uuid>,
+		match_type: RedirectMatchTypeEnum,
+		source_path: &str,
+	) -> Result<Option<Self>, AppError> {
+		let mut query = redirects::table
+			.filter(redirects::site_id.eq(site_id))
+			.filter(redirects::match_type.eq(match_type))
+			.filter(redirects::source_path.eq(source_path))
+			.into_boxed();
+		query = match language_id {
+			Some(language_id) => query.filter(redirects::language_id.eq(language_id)),
+			None => query.filter(redirects::language_id.is_null()),
+		};
+
+		let redirect = query
+			.select(Redirect::as_select())
+			.first::<Self>(conn)
+			.optional()?;
+
+		Ok(redirect)
+	}
+
+	// Checks the rule makes sense before it's stored, the source path comes back normalized
+	#[instrument(skip(conn))]
+	pub fn validate(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		match_type: RedirectMatchTypeEnum,
+		source_path: &str,
+		target_path: &Option<String>,
+		content_id: &Option<Uuid>,
+		status_code: i32,
+	) -> Result<String, AppError> {
+		if !REDIRECT_STATUS_CODES.contains(&status_code) {
+			return Err(AppError::BadRequest(AppErrorValue {
+				message: format!(
+					"Status code {} is not supported, use one of {:?}",
+					status_code, REDIRECT_STATUS_CODES
+				),
+				status: StatusCode::BAD_REQUEST.as_u16(),
+				code: "INVALID_STATUS_CODE".to_owned(),
+				..Default::default()
+			}));
+		}
+
+		let has_target = target_path.is_some() || content_id.is_some();
+		if status_code == 410 && has_target {
+			return Err(AppError::BadRequest(AppErrorValue {
+				message: "A redirect that answers with 410 can't have a target".to_owned(),
+				status: StatusCode::BAD_REQUEST.as_u16(),
+				code: "UNEXPECTED_REDIRECT_TARGET".to_owned(),
+				..Default::default()
+			}));
+		}
+		if status_code != 410 && (target_path.is_some() == content_id.is_some()) {
+			return Err(AppError::BadRequest(AppErrorValue {
+				message: "A redirect needs either a target path or target content".to_owned(),
+				status: StatusCode::BAD_REQUEST.as_u16(),
+				code: "MISSING_REDIRECT_TARGET".to_owned(),
+				..Default::default()
+			}));
+		}
+
+		let source_path = normalize_path(source_path);
+		if match_type == RedirectMatchTypeEnum::PATTERN {
+			if let Err(error) = pattern_regex(&source_path) {
+				return Err(AppError::BadRequest(AppErrorValue {
+					message: format!("{} is not a valid pattern: {}", source_path, error),
+					status: StatusCode::BAD_REQUEST.as_u16(),
+					code: "INVALID_PATTERN".to_owned(),
+					..Default::default()
+				}));
+			}
+		}
+
+		if let Some(content_id) = content_id {
+			content::table
+				.filter(content::site_id.eq(site_id))
+				.filter(content::id.eq(content_id))
+				.select(content::id)
+				.first::<Uuid>(conn)?;
+		}
+
+		Ok(source_path)
+	}
+
+	#[instrument(skip(conn))]
+	pub fn create(conn: &mut PgConnection, values: CreateRedirect) -> Result<Self, AppError> {
+		let redirect = diesel::insert_into(redirects::table)
+			.values(values)
+			.returning(Redirect::as_returning())
+			.get_result(conn)?;
+
+		Ok(redirect)
+	}
+
+	#[instrument(skip(conn))]
+	pub fn update(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		id: Uuid,
+		changeset: UpdateRedirect,
+	) -> Result<Self, AppError> {
+		let target = redirects::table
+			.filter(redirects::site_id.eq(site_id))
+			.filter(redirects::id.eq(id));
+		let redirect = diesel::update(target)
+			.set(changeset)
+			.returning(Redirect::as_returning())
+			.get_result(conn)?;
+
+		Ok(redirect)
+	}
+
+	#[instrument(skip(conn))]
+	pub fn remove(conn: &mut PgConnection, site_id: Uuid, id: Uuid) -> Result<(), AppError> {
+		let target = redirects::table
+			.filter(redirects::site_id.eq(site_id))
+			.filter(redirects::id.eq(id));
+		diesel::delete(target)
+			.returning(redirects::id)
+			.get_result::<Uuid>(conn)?;
+
+		Ok(())
+	}
+
+	// Rules that already exist for the same source are overwritten, everything or nothing is imported.
+	// Returns how many rules were created and how many were updated.
+	#[instrument(skip(conn, redirects))]
+	pub fn import(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		redirects: Vec<CreateRedirect>,
+	) -> Result<(usize, usize), AppError> {
+		conn.transaction::<_, AppError, _>(|conn| {
+			let mut created = 0;
+			let mut updated = 0;
+
+			for redirect in redirects {
+				let existing = Self::find_by_source(
+					conn,
+					site_id,
+					redirect.language_id,
+					redirect.match_type,
+					&redirect.source_path,
+				)?;
+
+				match existing {
+					Some(existing) => {
+						Self::update(
+							conn,
+							site_id,
+							existing.id,
+							UpdateRedirect {
+								language_id: redirect.language_id,
+								source_path: redirect.source_path,
+								match_type: redirect.match_type,
+								target_path: redirect.target_path,
+								content_id: redirect.content_id,
+								status_code: redirect.status_code,
+								updated_at: Utc::now().naive_utc(),
+							},
+						)?;
+						updated += 1;
+					}
+					None => {
+						Self::create(conn, redirect)?;
+						created += 1;
+					}
+				}
+			}
+
+			Ok((created, updated))
+		})
+	}
+
+	// Exact rules win over patterns, longer patterns over shorter ones. Without a language every
+	// language is searched, with one only its own rules and the ones that apply to every language.
+	#[instrument(skip(conn))]
+	pub fn find_match(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		language_id: Option<Uuid>,
+		path: &str,
+	) -> Result<Option<RedirectMatch>, AppError> {
+		let path = normalize_path(path);
+		let mut query = redirects::table
+			.filter(redirects::site_id.eq(site_id))
+			.into_boxed();
+		if let Some(language_id) = language_id {
+			query = query.filter(
+				redirects::language_id
+					.eq(language_id)
+					.or(redirects::language_id.is_null()),
+			);
+		}
+
+		let candidates = query
+			.filter(
+				redirects::source_path
+					.eq(&path)
+					.or(redirects::match_type.eq(RedirectMatchTypeEnum::PATTERN)),
+			)
+			// Nulls sort last, so a redirect for the language wins over one for every language
+			.order(redirects::language_id.asc())
+			.select(Redirect::as_select())
+			.load::<Self>(conn)?;
+
+		let (exact, mut patterns): (Vec<Self>, Vec<Self>) = candidates
+			.into_iter()
+			.partition(|redirect| redirect.match_type == RedirectMatchTypeEnum::EXACT);
+		patterns.sort_by_key(|redirect| std::cmp::Reverse(redirect.source_path.len()));
+
+		for redirect in exact {
+			if let Some(redirect_match) = Self::resolve_target(conn, redirect, None)? {
+				return Ok(Some(redirect_match));
+			}
+		}
+
+		for redirect in patterns {
+			let Some(regex) = cached_pattern_regex(&redirect.source_path)? else {
+				continue;
+			};
+			if let Some(captures) = regex.captures(&path) {
+				if let Some(redirect_match) = Self::resolve_target(conn, redirect, Some(captures))?
+				{
+					return Ok(Some(redirect_match));
+				}
+			}
+		}
+
+		Ok(None)
+	}
+
+	// Redirects to content that was unpublished or removed don't lead anywhere, so they don't match
+	fn resolve_target(
+		conn: &mut PgConnection,
+		redirect: Self,
+		captures: Option<regex::Captures>,
+	) -> Result<Option<RedirectMatch>, AppError> {
+		let target = match (&redirect.content_id, &redirect.target_path) {
+			(Some(content_id), _) => {
+				let path = content::table
+					.filter(content::id.eq(content_id))
+					.filter(content::published.eq(true))
+					.filter(content::deleted.eq(false))
+					.select(content::path)
+					.first::<Option<String>>(conn)
+					.optional()?
+					.flatten();
+				if path.is_none() {
+					return Ok(None);
+				}
+
+				path
+			}
+			(None, Some(target_path)) => match captures {
+				Some(captures) => {
+					let mut expanded = String::new();
+					captures.expand(target_path, &mut expanded);
+					Some(expanded)
+				}
+				None => Some(target_path.clone()),
+			},
+			(None, None) => None,
+		};
+
+		Ok(Some(RedirectMatch { redirect, target }))
+	}
+
+	// Called by the public lookups on purpose, every request they resolve counts as a hit so
+	// unused rules can be told apart. The update is a single atomic increment on the row.
+	#[instrument(skip(conn))]
+	pub fn register_hit(conn: &mut PgConnection, id: Uuid) -> Result<(), AppError> {
+		diesel::update(redirects::table.find(id))
+			.set((
+				redirects::hits.eq(redirects::hits + 1),
+				redirects::last_hit_at.eq(Utc::now().naive_utc()),
+			))
+			.execute(conn)?;
+
+		Ok(())
+	}
+
+	// Replaces whatever the path redirected to before
+	#[instrument(skip(conn))]
+	pub fn upsert_for_content(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		language_id: Uuid,
+		source_path: &str,
+		content_id: Uuid,
+	) -> Result<Self, AppError> {
+		Self::remove_by_path(conn, site_id, language_id, source_path)?;
+
+		let redirect = diesel::insert_into(redirects::table)
+			.values(CreateRedirect {
+				site_id,
+				language_id: Some(language_id),
+				source_path: source_path.to_owned(),
+				match_type: RedirectMatchTypeEnum::EXACT,
+				target_path: None,
+				content_id: Some(content_id),
+				status_code: 301,
+			})
+			.returning(Redirect::as_returning())
+			.get_result(conn)?;
+
+		Ok(redirect)
+	}
+
+	// Content that takes over a path removes the exact redirects that would hide it
+	#[instrument(skip(conn))]
+	pub fn remove_by_path(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		language_id: Uuid,
+		path: &str,
+	) -> Result<(), AppError> {
+		let target = redirects::table
+			.filter(redirects::site_id.eq(site_id))
+			.filter(redirects::language_id.eq(language_id))
+			.filter(redirects::match_type.eq(RedirectMatchTypeEnum::EXACT))
+			.filter(redirects::source_path.eq(path));
+		diesel::delete(target).execute(conn)?;
+
+		Ok(())
+	}
+}
+
+#[derive(Insertable, Debug, Deserialize, Clone)]
+#[diesel(table_name = redirects)]
+pub struct CreateRedirect {
+	pub site_id: Uuid,
+	pub language_id: Option<Uuid>,
+	pub source_path: String,
+	pub match_type: RedirectMatchTypeEnum,
+	pub target_path: Option<String>,
+	pub content_id: Option<Uuid>,
+	pub status_code: i32,
+}
+
+#[derive(AsChangeset, Debug, Deserialize, Clone)]
+#[diesel(table_name = redirects)]
+#[diesel(treat_none_as_null = true)]
+pub struct UpdateRedirect {
+	pub language_id: Option<Uuid>,
+	pub source_path: String,
+	pub match_type: RedirectMatchTypeEnum,
+	pub target_path: Option<String>,
+	pub content_id: Option<Uuid>,
+	pub status_code: i32,
+	pub updated_at: NaiveDateTime,
+}
@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::fs::File;
+
+use diesel::prelude::*;
+use serde::Deserialize;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+	errors::AppError,
+	modules::redirects::models::redirect::{CreateRedirect, Redirect, RedirectMatchTypeEnum},
+	schema::{languages, sites_languages},
+};
+
+// One line of the CSV file, only `source` and `target` are required
+#[derive(Debug, Deserialize)]
+struct RedirectRow {
+	source: String,
+	target: Option<String>,
+	status: Option<i32>,
+	#[serde(rename = "match")]
+	match_type: Option<String>,
+	language: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct RedirectImportError {
+	pub line: u64,
+	pub message: String,
+}
+
+#[derive(Debug)]
+pub struct RedirectImportPlan {
+	pub redirects: Vec<CreateRedirect>,
+	pub errors: Vec<RedirectImportError>,
+}
+
+impl RedirectImportPlan {
+	pub fn is_blocked(&self) -> bool {
+		!self.errors.is_empty()
+	}
+}
+
+// Every row is checked before anything is stored, so a file with mistakes can be fixed and uploaded again
+#[instrument(skip(conn, file))]
+pub fn plan_import(
+	conn: &mut PgConnection,
+	site_id: Uuid,
+	file: File,
+) -> Result<RedirectImportPlan, AppError> {
+	let language_ids = sites_languages::table
+		.filter(sites_languages::site_id.eq(site_id))
+		.inner_join(languages::table)
+		.select((languages::key, languages::id))
+		.load::<(String, Uuid)>(conn)?
+		.into_iter()
+		.collect::<HashMap<String, Uuid>>();
+
+	let mut plan = RedirectImportPlan {
+		redirects: vec![],
+		errors: vec![],
+	};
+	let mut reader = csv::ReaderBuilder::new()
+		.trim(csv::Trim::All)
+		.flexible(true)
+		.from_reader(file);
+
+	for (index, row) in reader.deserialize::<RedirectRow>().enumerate() {
+		let line = index as u64 + 2;
+		let row = match row {
+			Ok(row) => row,
+			Err(error) => {
+				plan.errors.push(RedirectImportError {
+					line: error
+						.position()
+						.map(|position| position.line())
+						.unwrap_or(line),
+					message: error.to_string(),
+				});
+				continue;
+			}
+		};
+
+		match plan_row(conn, site_id, &language_ids, row) {
+			Ok(redirect) => plan.redirects.push(redirect),
+			Err(message) => plan.errors.push(RedirectImportError { line, message }),
+		}
+	}
+
+	Ok(plan)
+}
+
+fn plan_row(
+	conn: &mut PgConnection,
+	site_id: Uuid,
+	language_ids: &HashMap<String, Uuid>,
+	row: RedirectRow,
+) -> Result<CreateRedirect, String> {
+	let match_type = match row.match_type.as_deref().map(str::to_uppercase).as_deref() {
+		None | Some("") | Some("EXACT") => RedirectMatchTypeEnum::EXACT,
+		Some("PATTERN") => RedirectMatchTypeEnum::PATTERN,
+		Some(match_type) => return Err(format!("{} is not a valid match type", match_type)),
+	};
+
+	let language_id = match row.language.as_deref() {
+		None | Some("") => None,
+		Some(key) => Some(
+			*language_ids
+				.get(key)
+				.ok_or_else(|| format!("Language {} is not enabled for this site", key))?,
+		),
+	};
+
+	let target_path = row.target.filter(|target| !target.is_empty());
+	let status_code = row.status.unwrap_or(301);
+	let source_path = Redirect::validate(
+		conn,
+		site_id,
+		match_type,
+		&row.source,
+		&target_path,
+		&None,
+		status_code,
+	)
+	.map_err(|error| error.value().message.clone())?;
+
+	Ok(CreateRedirect {
+		site_id,
+		language_id,
+		source_path,
+		match_type,
+		target_path,
+		content_id: None,
+		status_code,
+	})
+}
+
+#[instrument(skip(conn, plan))]
+pub fn apply_import(
+	conn: &mut PgConnection,
+	site_id: Uuid,
+	plan: &RedirectImportPlan,
+) -> Result<(usize, usize), AppError> {
+	Redirect::import(conn, site_id, plan.redirects.clone())
+}
@@ -0,0 +1,235 @@
+use actix::prelude::*;
+use chrono::Utc;
+use diesel::Connection;
+use uuid::Uuid;
+
+use crate::{
+	errors::AppError,
+	modules::{
+		content::models::content_field::ContentField,
+		resources::{
+			engines::lib::{
+				find_resource_tree, get_storage_engine, split_path, ResourceItemKind, StorageEngine,
+			},
+			models::{
+				storage_migration::{
+					StorageMigration, StorageMigrationStatusEnum, UpdateStorageMigration,
+				},
+				storage_migration_item::{CreateStorageMigrationItem, StorageMigrationItem},
+			},
+		},
+	},
+	utils::db::DbPool,
+};
+
+pub struct StorageMigrationActor;
+
+#[derive(Message)]
+#[rtype(result = "Result<bool, AppError>")]
+pub struct StorageMigrationMessage {
+	pub pool: DbPool,
+	pub site_id: Uuid,
+	pub storage_migration_id: Uuid,
+}
+
+impl Actor for StorageMigrationActor {
+	type Context = SyncContext<Self>;
+}
+
+impl Handler<StorageMigrationMessage> for StorageMigrationActor {
+	type Result = Result<bool, AppError>;
+
+	fn handle(
+		&mut self,
+		msg: StorageMigrationMessage,
+		_sctx: &mut SyncContext<Self>,
+	) -> Self::Result {
+		// The storage engines are async, so the migration gets its own runtime on this sync worker
+		let runtime = actix_rt::Runtime::new()?;
+		let result = runtime.block_on(run_storage_migration(
+			&msg.pool,
+			msg.site_id,
+			msg.storage_migration_id,
+		));
+
+		if let Err(err) = &result {
+			let conn = &mut msg.pool.get()?;
+			StorageMigration::update(
+				conn,
+				msg.storage_migration_id,
+				UpdateStorageMigration {
+					status: Some(StorageMigrationStatusEnum::FAILED),
+					last_error: Some(Some(err.value().message.clone())),
+					updated_at: Some(Utc::now().naive_utc()),
+					..Default::default()
+				},
+			)?;
+		}
+
+		result.map(|_| true)
+	}
+}
+
+fn get_kind_key(kind: &ResourceItemKind) -> String {
+	match kind {
+		ResourceItemKind::FILE => "FILE".to_owned(),
+		ResourceItemKind::DIRECTORY => "DIRECTORY".to_owned(),
+	}
+}
+
+async fn migrate_item(
+	source: &dyn StorageEngine,
+	target: &dyn StorageEngine,
+	item: &StorageMigrationItem,
+) -> Result<(), AppError> {
+	if item.kind == "DIRECTORY" {
+		let (parent, name) = split_path(&item.path);
+		return target.create_directory(&parent, &name).await;
+	}
+
+	let contents = source.download_file(&item.path).await?;
+	target.write_file(&item.path, contents).await
+}
+
+async fn run_storage_migration(
+	pool: &DbPool,
+	site_id: Uuid,
+	storage_migration_id: Uuid,
+) -> Result<(), AppError> {
+	let conn = &mut pool.get()?;
+	let storage_migration = StorageMigration::find_one(conn, site_id, storage_migration_id)?;
+
+	StorageMigration::update(
+		conn,
+		storage_migration_id,
+		UpdateStorageMigration {
+			status: Some(StorageMigrationStatusEnum::RUNNING),
+			last_error: Some(None),
+			updated_at: Some(Utc::now().naive_utc()),
+			..Default::default()
+		},
+	)?;
+
+	let source = get_storage_engine(conn, storage_migration.source_storage_repository_id)?;
+	let target = get_storage_engine(conn, storage_migration.target_storage_repository_id)?;
+
+	// The tree is only listed once, a resumed migration keeps working through the same items.
+	// Items and their count are written together, so a crash in between can't leave a partial list.
+	if storage_migration.total_items == 0 {
+		let tree = find_resource_tree(source.as_ref()).await?;
+		let total_items = tree.len().try_into()?;
+
+		conn.transaction::<_, AppError, _>(|conn| {
+			StorageMigrationItem::create_many(
+				conn,
+				tree.into_iter()
+					.map(|(path, kind)| CreateStorageMigrationItem {
+						storage_migration_id,
+						path,
+						kind: get_kind_key(&kind),
+					})
+					.collect(),
+			)?;
+			StorageMigration::update(
+				conn,
+				storage_migration_id,
+				UpdateStorageMigration {
+					total_items: Some(total_items),
+					updated_at: Some(Utc::now().naive_utc()),
+					..Default::default()
+				},
+			)?;
+
+			Ok(())
+		})?;
+	}
+
+	if storage_migration.dry_run {
+		StorageMigration::update(
+			conn,
+			storage_migration_id,
+			UpdateStorageMigration {
+				status: Some(StorageMigrationStatusEnum::COMPLETED),
+				updated_at: Some(Utc::now().naive_utc()),
+				..Default::default()
+			},
+		)?;
+
+		return Ok(());
+	}
+
+	// Directories go first, from the top down, so every parent exists before its children
+	let mut items = StorageMigrationItem::find_unprocessed(conn, storage_migration_id)?;
+	items.sort_by_key(|item| {
+		(
+			item.kind != "DIRECTORY",
+			item.path.matches('/').count(),
+			item.path.clone(),
+		)
+	});
+
+	for item in items {
+		match migrate_item(source.as_ref(), target.as_ref(), &item).await {
+			Ok(_) => {
+				StorageMigrationItem::update_status(
+					conn,
+					item.id,
+					StorageMigrationStatusEnum::COMPLETED,
+					None,
+				)?;
+				StorageMigration::increment_progress(conn, storage_migration_id, false)?;
+			}
+			Err(err) => {
+				StorageMigrationItem::update_status(
+					conn,
+					item.id,
+					StorageMigrationStatusEnum::FAILED,
+					Some(err.value().message.clone()),
+				)?;
+				StorageMigration::increment_progress(conn, storage_migration_id, true)?;
+			}
+		}
+	}
+
+	let failed_items = StorageMigrationItem::count_by_status(
+		conn,
+		storage_migration_id,
+		StorageMigrationStatusEnum::FAILED,
+	)?;
+
+	if failed_items > 0 {
+		StorageMigration::update(
+			conn,
+			storage_migration_id,
+			UpdateStorageMigration {
+				status: Some(StorageMigrationStatusEnum::FAILED),
+				last_error: Some(Some(format!("{failed_items} items could not be migrated"))),
+				updated_at: Some(Utc::now().naive_utc()),
+				..Default::default()
+			},
+		)?;
+
+		return Ok(());
+	}
+
+	if storage_migration.rewrite_content_references {
+		ContentField::rewrite_storage_repository_references(
+			conn,
+			site_id,
+			storage_migration.source_storage_repository_id,
+			storage_migration.target_storage_repository_id,
+		)?;
+	}
+
+	StorageMigration::update(
+		conn,
+		storage_migration_id,
+		UpdateStorageMigration {
+			status: Some(StorageMigrationStatusEnum::COMPLETED),
+			updated_at: Some(Utc::now().naive_utc()),
+			..Default::default()
+		},
+	)?;
+
+	Ok(())
+}
@@ -1,10 +1,15 @@
 use super::super::dto::directories::{request, response};
-use crate::errors::AppError;
+use crate::errors::{AppError, AppErrorValue};
 use crate::modules::core::middleware::state::AppState;
 use crate::modules::core::models::hal::HALPage;
 use crate::modules::{
-	auth::helpers::permissions::ensure_permission, resources::engines::lib::get_storage_engine,
+	auth::helpers::permissions::ensure_permission,
+	resources::engines::lib::{
+		ensure_resource_does_not_exist, get_storage_engine, is_same_or_nested_path, join_path,
+		normalize_resource_path, split_path,
+	},
 };
+use actix_web::http::StatusCode;
 use actix_web::{delete, get, post, web, HttpRequest, HttpResponse};
 use serde::Deserialize;
 use utoipa::IntoParams;
@@ -23,6 +28,19 @@ pub struct ResourcesQueryParams {
 	path: String,
 }
 
+fn ensure_not_nested(path: &str, destination: &str) -> Result<(), AppError> {
+	if is_same_or_nested_path(destination, path) {
+		return Err(AppError::BadRequest(AppErrorValue {
+			message: format!("Cannot move or copy {path} into itself"),
+			status: StatusCode::BAD_REQUEST.as_u16(),
+			code: "INVALID_DESTINATION".to_owned(),
+			..Default::default()
+		}));
+	}
+
+	Ok(())
+}
+
 #[utoipa::path(
 	context_path = "/api/v1/sites/{site_id}/storage-repositories/{storage_repository_id}/directories",
 	responses(
@@ -141,3 +159,134 @@ pub async fn remove_directory(
 
 	Ok(HttpResponse::NoContent().finish())
 }
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/storage-repositories/{storage_repository_id}/directories",
+    request_body = MoveDirectoryDTO,
+	responses(
+		(status = 204),
+		(status = 401, body = AppErrorValue, description = "Unauthorized"),
+		(status = 422, body = AppErrorValue, description = "Destination already exists")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(SharedParams, ResourcesQueryParams)
+)]
+#[post("/move")]
+pub async fn move_directory(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	query: web::Query<ResourcesQueryParams>,
+	form: web::Json<request::MoveDirectoryDTO>,
+	params: web::Path<SharedParams>,
+) -> Result<HttpResponse, AppError> {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!(
+			"urn:dcm:storage-repositories:{}:resources:*",
+			params.storage_repository_id
+		),
+		"sites::resources:move-directory",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let engine = get_storage_engine(conn, params.storage_repository_id)?;
+
+	let path = normalize_resource_path(&query.path)?;
+	let (_, name) = split_path(&path);
+	let destination = join_path(&form.destination, &name)?;
+	ensure_not_nested(&path, &destination)?;
+
+	ensure_resource_does_not_exist(engine.as_ref(), &destination).await?;
+	engine.move_directory(&path, &destination).await?;
+
+	Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/storage-repositories/{storage_repository_id}/directories",
+    request_body = RenameDirectoryDTO,
+	responses(
+		(status = 204),
+		(status = 401, body = AppErrorValue, description = "Unauthorized"),
+		(status = 422, body = AppErrorValue, description = "Destination already exists")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(SharedParams, ResourcesQueryParams)
+)]
+#[post("/rename")]
+pub async fn rename_directory(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	query: web::Query<ResourcesQueryParams>,
+	form: web::Json<request::RenameDirectoryDTO>,
+	params: web::Path<SharedParams>,
+) -> Result<HttpResponse, AppError> {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!(
+			"urn:dcm:storage-repositories:{}:resources:*",
+			params.storage_repository_id
+		),
+		"sites::resources:rename-directory",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let engine = get_storage_engine(conn, params.storage_repository_id)?;
+
+	let path = normalize_resource_path(&query.path)?;
+	let (parent, _) = split_path(&path);
+	let destination = join_path(&parent, &form.name)?;
+
+	ensure_resource_does_not_exist(engine.as_ref(), &destination).await?;
+	engine.move_directory(&path, &destination).await?;
+
+	Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/storage-repositories/{storage_repository_id}/directories",
+    request_body = CopyDirectoryDTO,
+	responses(
+		(status = 204),
+		(status = 401, body = AppErrorValue, description = "Unauthorized"),
+		(status = 422, body = AppErrorValue, description = "Destination already exists")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(SharedParams, ResourcesQueryParams)
+)]
+#[post("/copy")]
+pub async fn copy_directory(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	query: web::Query<ResourcesQueryParams>,
+	form: web::Json<request::CopyDirectoryDTO>,
+	params: web::Path<SharedParams>,
+) -> Result<HttpResponse, AppError> {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!(
+			"urn:dcm:storage-repositories:{}:resources:*",
+			params.storage_repository_id
+		),
+		"sites::resources:copy-directory",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let engine = get_storage_engine(conn, params.storage_repository_id)?;
+
+	let path = normalize_resource_path(&query.path)?;
+	let (_, name) = split_path(&path);
+	let destination = join_path(&form.destination, &name)?;
+	ensure_not_nested(&path, &destination)?;
+
+	ensure_resource_does_not_exist(engine.as_ref(), &destination).await?;
+	engine.copy_directory(&path, &destination).await?;
+
+	Ok(HttpResponse::NoContent().finish())
+}
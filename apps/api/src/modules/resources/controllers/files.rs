@@ -1,10 +1,16 @@
 use crate::errors::AppError;
 use crate::modules::core::middleware::state::AppState;
-use crate::modules::resources::engines::lib::get_storage_engine;
+use crate::modules::resources::engines::lib::{
+	ensure_resource_does_not_exist, get_storage_engine, join_path, normalize_resource_path,
+	split_path,
+};
+use crate::modules::resources::helpers::upload_policy::{
+	enforce_upload_policy, get_upload_policy, receive_upload,
+};
+use crate::modules::resources::models::storage_repository::StorageRepository;
 use crate::modules::{
 	auth::helpers::permissions::ensure_permission, resources::dto::files::request,
 };
-use actix_multipart::form::MultipartForm;
 use actix_web::{delete, get, post, web, HttpRequest, HttpResponse};
 use serde::Deserialize;
 use utoipa::IntoParams;
@@ -26,7 +32,8 @@ pub struct FilesQueryParams {
     request_body = CreateAssetDTO,
 	responses(
 		(status = 200, body = AssetDTO),
-		(status = 401, body = AppErrorValue, description = "Unauthorized")
+		(status = 401, body = AppErrorValue, description = "Unauthorized"),
+		(status = 422, body = AppErrorValue, description = "Upload policy violation")
 	),
     security(
         ("jwt_token" = [])
@@ -36,7 +43,7 @@ pub struct FilesQueryParams {
 pub async fn upload_file(
 	req: HttpRequest,
 	state: web::Data<AppState>,
-	MultipartForm(form): MultipartForm<request::CreateFileDTO>,
+	payload: web::Payload,
 	query: web::Query<FilesQueryParams>,
 	params: web::Path<SharedParams>,
 ) -> Result<HttpResponse, AppError> {
@@ -49,8 +56,18 @@ pub async fn upload_file(
 		),
 		"sites::resources:upload-file",
 	)?;
-	let conn = &mut state.get_conn()?;
-	let engine = get_storage_engine(conn, params.storage_repository_id)?;
+	// The connection goes back to the pool before the upload is received
+	let (upload_policy, engine) = {
+		let conn = &mut state.get_conn()?;
+		let storage_repository = StorageRepository::find_one(conn, params.storage_repository_id)?;
+		(
+			get_upload_policy(&storage_repository.configuration)?,
+			get_storage_engine(conn, params.storage_repository_id)?,
+		)
+	};
+
+	let mut form = receive_upload(&req, payload, &upload_policy).await?;
+	enforce_upload_policy(engine.as_ref(), &upload_policy, &query.path, &mut form.file).await?;
 	engine.upload_file(&query.path, form.file).await?;
 
 	Ok(HttpResponse::NoContent().finish())
@@ -114,3 +131,129 @@ pub async fn remove_file(
 
 	Ok(HttpResponse::NoContent().finish())
 }
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/storage-repositories/{storage_repository_id}/files",
+    request_body = MoveFileDTO,
+	responses(
+		(status = 204),
+		(status = 401, body = AppErrorValue, description = "Unauthorized"),
+		(status = 422, body = AppErrorValue, description = "Destination already exists")
+	),
+    security(
+        ("jwt_token" = [])
+    )
+)]
+#[post("/move")]
+pub async fn move_file(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	query: web::Query<FilesQueryParams>,
+	form: web::Json<request::MoveFileDTO>,
+	params: web::Path<SharedParams>,
+) -> Result<HttpResponse, AppError> {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!(
+			"urn:dcm:storage-repositories:{}:resources:*",
+			params.storage_repository_id
+		),
+		"sites::resources:move-file",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let engine = get_storage_engine(conn, params.storage_repository_id)?;
+
+	let path = normalize_resource_path(&query.path)?;
+	let (_, name) = split_path(&path);
+	let destination = join_path(&form.destination, &name)?;
+
+	ensure_resource_does_not_exist(engine.as_ref(), &destination).await?;
+	engine.move_file(&path, &destination).await?;
+
+	Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/storage-repositories/{storage_repository_id}/files",
+    request_body = RenameFileDTO,
+	responses(
+		(status = 204),
+		(status = 401, body = AppErrorValue, description = "Unauthorized"),
+		(status = 422, body = AppErrorValue, description = "Destination already exists")
+	),
+    security(
+        ("jwt_token" = [])
+    )
+)]
+#[post("/rename")]
+pub async fn rename_file(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	query: web::Query<FilesQueryParams>,
+	form: web::Json<request::RenameFileDTO>,
+	params: web::Path<SharedParams>,
+) -> Result<HttpResponse, AppError> {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!(
+			"urn:dcm:storage-repositories:{}:resources:*",
+			params.storage_repository_id
+		),
+		"sites::resources:rename-file",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let engine = get_storage_engine(conn, params.storage_repository_id)?;
+
+	let path = normalize_resource_path(&query.path)?;
+	let (parent, _) = split_path(&path);
+	let destination = join_path(&parent, &form.name)?;
+
+	ensure_resource_does_not_exist(engine.as_ref(), &destination).await?;
+	engine.move_file(&path, &destination).await?;
+
+	Ok(HttpResponse::NoContent().finish())
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/storage-repositories/{storage_repository_id}/files",
+    request_body = CopyFileDTO,
+	responses(
+		(status = 204),
+		(status = 401, body = AppErrorValue, description = "Unauthorized"),
+		(status = 422, body = AppErrorValue, description = "Destination already exists")
+	),
+    security(
+        ("jwt_token" = [])
+    )
+)]
+#[post("/copy")]
+pub async fn copy_file(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	query: web::Query<FilesQueryParams>,
+	form: web::Json<request::CopyFileDTO>,
+	params: web::Path<SharedParams>,
+) -> Result<HttpResponse, AppError> {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!(
+			"urn:dcm:storage-repositories:{}:resources:*",
+			params.storage_repository_id
+		),
+		"sites::resources:copy-file",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let engine = get_storage_engine(conn, params.storage_repository_id)?;
+
+	let path = normalize_resource_path(&query.path)?;
+	let (_, name) = split_path(&path);
+	let destination = join_path(&form.destination, &name)?;
+
+	ensure_resource_does_not_exist(engine.as_ref(), &destination).await?;
+	engine.copy_file(&path, &destination).await?;
+
+	Ok(HttpResponse::NoContent().finish())
+}
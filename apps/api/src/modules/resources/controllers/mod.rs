@@ -1,4 +1,5 @@
 pub mod directories;
 pub mod files;
 pub mod public_files;
+pub mod storage_migrations;
 pub mod storage_repositories;
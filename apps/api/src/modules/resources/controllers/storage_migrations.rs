@@ -0,0 +1,264 @@
+use crate::errors::{AppError, AppErrorValue};
+use crate::modules::auth::helpers::permissions::ensure_permission;
+use crate::modules::core::middleware::state::AppState;
+use crate::modules::core::models::hal::HALPage;
+use crate::modules::resources::actors::storage_migration::StorageMigrationMessage;
+use crate::modules::resources::dto::storage_migrations::{request, response};
+use crate::modules::resources::models::storage_migration::{
+	CreateStorageMigration, StorageMigration,
+};
+use crate::modules::resources::models::storage_migration_item::StorageMigrationItem;
+use crate::modules::resources::models::storage_repository::StorageRepository;
+use actix_web::http::StatusCode;
+use actix_web::{get, post, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindPathParams {
+	site_id: Uuid,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindOnePathParams {
+	site_id: Uuid,
+	storage_migration_id: Uuid,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindAllQueryParams {
+	page: Option<i64>,
+	pagesize: Option<i64>,
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/storage-migrations",
+    request_body = CreateStorageMigrationDTO,
+	responses(
+		(status = 200, body = StorageMigrationDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    )
+)]
+#[post("")]
+pub async fn create(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	form: web::Json<request::CreateStorageMigrationDTO>,
+	params: web::Path<FindPathParams>,
+) -> Result<HttpResponse, AppError> {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:storage-migrations:*"),
+		"sites::storage-migrations:create",
+	)?;
+
+	if form.source_storage_repository_id == form.target_storage_repository_id {
+		return Err(AppError::UnprocessableEntity(AppErrorValue {
+			message: "Source and target storage repository should be different".to_owned(),
+			status: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+			code: "STORAGE_MIGRATION_SAME_REPOSITORY".to_owned(),
+			..Default::default()
+		}));
+	}
+
+	let conn = &mut state.get_conn()?;
+	StorageRepository::find_one(conn, form.source_storage_repository_id)?;
+	StorageRepository::find_one(conn, form.target_storage_repository_id)?;
+
+	let storage_migration = StorageMigration::create(
+		conn,
+		CreateStorageMigration {
+			site_id: params.site_id,
+			source_storage_repository_id: form.source_storage_repository_id,
+			target_storage_repository_id: form.target_storage_repository_id,
+			dry_run: form.dry_run.unwrap_or(false),
+			rewrite_content_references: form.rewrite_content_references.unwrap_or(false),
+		},
+	)?;
+
+	state.migration_addr.do_send(StorageMigrationMessage {
+		pool: state.pool.clone(),
+		site_id: params.site_id,
+		storage_migration_id: storage_migration.id,
+	});
+
+	let res = response::StorageMigrationDTO::from(storage_migration);
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/storage-migrations",
+	responses(
+		(status = 200, body = StorageMigrationsDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindAllQueryParams)
+)]
+#[get("")]
+pub async fn find_all(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	query: web::Query<FindAllQueryParams>,
+	params: web::Path<FindPathParams>,
+) -> Result<HttpResponse, AppError> {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:storage-migrations:*"),
+		"sites::storage-migrations:read",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let page = query.page.unwrap_or(1);
+	let pagesize = query.pagesize.unwrap_or(20);
+
+	let (storage_migrations, total_elements) =
+		StorageMigration::find(conn, params.site_id, page, pagesize)?;
+
+	let res = response::StorageMigrationsDTO::from((
+		storage_migrations,
+		HALPage {
+			number: page,
+			size: pagesize,
+			total_elements,
+			total_pages: (total_elements / pagesize + (total_elements % pagesize).signum()).max(1),
+		},
+		params.site_id,
+	));
+
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/storage-migrations",
+	responses(
+		(status = 200, body = StorageMigrationDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindOnePathParams)
+)]
+#[get("/{storage_migration_id}")]
+pub async fn find_one(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindOnePathParams>,
+) -> Result<HttpResponse, AppError> {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:storage-migrations:{}", params.storage_migration_id),
+		"sites::storage-migrations:read",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let storage_migration =
+		StorageMigration::find_one(conn, params.site_id, params.storage_migration_id)?;
+
+	let res = response::StorageMigrationDTO::from(storage_migration);
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/storage-migrations",
+	responses(
+		(status = 200, body = StorageMigrationItemsDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindOnePathParams, FindAllQueryParams)
+)]
+#[get("/{storage_migration_id}/items")]
+pub async fn find_items(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	query: web::Query<FindAllQueryParams>,
+	params: web::Path<FindOnePathParams>,
+) -> Result<HttpResponse, AppError> {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:storage-migrations:{}", params.storage_migration_id),
+		"sites::storage-migrations:read",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let page = query.page.unwrap_or(1);
+	let pagesize = query.pagesize.unwrap_or(20);
+
+	StorageMigration::find_one(conn, params.site_id, params.storage_migration_id)?;
+	let (items, total_elements) =
+		StorageMigrationItem::find(conn, params.storage_migration_id, page, pagesize)?;
+
+	let res = response::StorageMigrationItemsDTO::from((
+		items,
+		HALPage {
+			number: page,
+			size: pagesize,
+			total_elements,
+			total_pages: (total_elements / pagesize + (total_elements % pagesize).signum()).max(1),
+		},
+		params.site_id,
+		params.storage_migration_id,
+	));
+
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/storage-migrations",
+	responses(
+		(status = 200, body = StorageMigrationDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized"),
+		(status = 422, body = AppErrorValue, description = "Migration is not resumable")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindOnePathParams)
+)]
+#[post("/{storage_migration_id}/resume")]
+pub async fn resume(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindOnePathParams>,
+) -> Result<HttpResponse, AppError> {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:storage-migrations:{}", params.storage_migration_id),
+		"sites::storage-migrations:resume",
+	)?;
+	let conn = &mut state.get_conn()?;
+	StorageMigration::find_one(conn, params.site_id, params.storage_migration_id)?;
+
+	let Some(storage_migration) =
+		StorageMigration::claim_for_resume(conn, params.site_id, params.storage_migration_id)?
+	else {
+		return Err(AppError::UnprocessableEntity(AppErrorValue {
+			message: "Only failed storage migrations, or ones that stopped making progress, can be resumed"
+				.to_owned(),
+			status: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+			code: "STORAGE_MIGRATION_NOT_RESUMABLE".to_owned(),
+			..Default::default()
+		}));
+	};
+
+	state.migration_addr.do_send(StorageMigrationMessage {
+		pool: state.pool.clone(),
+		site_id: params.site_id,
+		storage_migration_id: storage_migration.id,
+	});
+
+	let res = response::StorageMigrationDTO::from(storage_migration);
+	Ok(HttpResponse::Ok().json(res))
+}
@@ -3,6 +3,7 @@ use crate::modules::auth::helpers::permissions::ensure_permission;
 use crate::modules::core::middleware::state::AppState;
 use crate::modules::core::models::hal::HALPage;
 use crate::modules::resources::dto::storage_repositories::{request, response};
+use crate::modules::resources::helpers::upload_policy::get_upload_policy;
 use crate::modules::resources::models::storage_repository::{
 	CreateStorageRepository, StorageRepository, UpdateStorageRepository,
 };
@@ -53,6 +54,7 @@ pub async fn create(
 		format!("urn:dcm:storage-repositories:*"),
 		"sites::storage-repositories:create",
 	)?;
+	get_upload_policy(&form.configuration)?;
 	let conn = &mut state.get_conn()?;
 
 	let storage_repository = StorageRepository::create(
@@ -174,6 +176,9 @@ pub async fn update(
 		),
 		"sites::storage-repositories:update",
 	)?;
+	if let Some(configuration) = &form.configuration {
+		get_upload_policy(configuration)?;
+	}
 	let conn = &mut state.get_conn()?;
 	let storage_repository = StorageRepository::update(
 		conn,
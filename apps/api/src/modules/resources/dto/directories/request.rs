@@ -6,3 +6,21 @@ use utoipa::ToSchema;
 pub struct CreateDirectoryDTO {
 	pub name: String,
 }
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveDirectoryDTO {
+	pub destination: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyDirectoryDTO {
+	pub destination: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameDirectoryDTO {
+	pub name: String,
+}
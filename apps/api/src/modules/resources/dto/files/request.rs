@@ -1,4 +1,5 @@
 use actix_multipart::form::{tempfile::TempFile, MultipartForm};
+use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 #[derive(Debug, MultipartForm, ToSchema)]
@@ -6,3 +7,21 @@ pub struct CreateFileDTO {
 	#[multipart(rename = "file")]
 	pub file: TempFile,
 }
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveFileDTO {
+	pub destination: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyFileDTO {
+	pub destination: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameFileDTO {
+	pub name: String,
+}
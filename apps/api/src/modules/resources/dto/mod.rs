@@ -1,3 +1,4 @@
 pub mod directories;
 pub mod files;
+pub mod storage_migrations;
 pub mod storage_repositories;
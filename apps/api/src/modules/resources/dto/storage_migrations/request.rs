@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateStorageMigrationDTO {
+	pub source_storage_repository_id: Uuid,
+	pub target_storage_repository_id: Uuid,
+	pub dry_run: Option<bool>,
+	pub rewrite_content_references: Option<bool>,
+}
@@ -0,0 +1,142 @@
+use crate::modules::{
+	core::models::hal::{HALLinkList, HALPage},
+	resources::models::{
+		storage_migration::{StorageMigration, StorageMigrationStatusEnum},
+		storage_migration_item::StorageMigrationItem,
+	},
+};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::convert::From;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageMigrationDTO {
+	pub id: Uuid,
+	pub source_storage_repository_id: Uuid,
+	pub target_storage_repository_id: Uuid,
+	pub status: StorageMigrationStatusEnum,
+	pub dry_run: bool,
+	pub rewrite_content_references: bool,
+	pub total_items: i32,
+	pub processed_items: i32,
+	pub failed_items: i32,
+	pub last_error: Option<String>,
+	pub created_at: NaiveDateTime,
+	pub updated_at: NaiveDateTime,
+}
+
+impl From<StorageMigration> for StorageMigrationDTO {
+	fn from(storage_migration: StorageMigration) -> Self {
+		Self {
+			id: storage_migration.id,
+			source_storage_repository_id: storage_migration.source_storage_repository_id,
+			target_storage_repository_id: storage_migration.target_storage_repository_id,
+			status: storage_migration.status,
+			dry_run: storage_migration.dry_run,
+			rewrite_content_references: storage_migration.rewrite_content_references,
+			total_items: storage_migration.total_items,
+			processed_items: storage_migration.processed_items,
+			failed_items: storage_migration.failed_items,
+			last_error: storage_migration.last_error,
+			created_at: storage_migration.created_at,
+			updated_at: storage_migration.updated_at,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageMigrationsEmbeddedDTO {
+	pub storage_migrations: Vec<StorageMigrationDTO>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct StorageMigrationsDTO {
+	pub _links: HALLinkList,
+	pub _page: HALPage,
+	pub _embedded: StorageMigrationsEmbeddedDTO,
+}
+
+impl From<(Vec<StorageMigration>, HALPage, Uuid)> for StorageMigrationsDTO {
+	fn from((storage_migrations, page, site_id): (Vec<StorageMigration>, HALPage, Uuid)) -> Self {
+		Self {
+			_links: HALLinkList::from((
+				format!("/api/v1/sites/{}/storage-migrations", site_id),
+				&page,
+			)),
+			_embedded: StorageMigrationsEmbeddedDTO {
+				storage_migrations: storage_migrations
+					.into_iter()
+					.map(StorageMigrationDTO::from)
+					.collect(),
+			},
+			_page: page,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageMigrationItemDTO {
+	pub id: Uuid,
+	pub path: String,
+	pub kind: String,
+	pub status: StorageMigrationStatusEnum,
+	pub error: Option<String>,
+}
+
+impl From<StorageMigrationItem> for StorageMigrationItemDTO {
+	fn from(item: StorageMigrationItem) -> Self {
+		Self {
+			id: item.id,
+			path: item.path,
+			kind: item.kind,
+			status: item.status,
+			error: item.error,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageMigrationItemsEmbeddedDTO {
+	pub items: Vec<StorageMigrationItemDTO>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct StorageMigrationItemsDTO {
+	pub _links: HALLinkList,
+	pub _page: HALPage,
+	pub _embedded: StorageMigrationItemsEmbeddedDTO,
+}
+
+impl From<(Vec<StorageMigrationItem>, HALPage, Uuid, Uuid)> for StorageMigrationItemsDTO {
+	fn from(
+		(items, page, site_id, storage_migration_id): (
+			Vec<StorageMigrationItem>,
+			HALPage,
+			Uuid,
+			Uuid,
+		),
+	) -> Self {
+		Self {
+			_links: HALLinkList::from((
+				format!(
+					"/api/v1/sites/{}/storage-migrations/{}/items",
+					site_id, storage_migration_id
+				),
+				&page,
+			)),
+			_embedded: StorageMigrationItemsEmbeddedDTO {
+				items: items
+					.into_iter()
+					.map(StorageMigrationItemDTO::from)
+					.collect(),
+			},
+			_page: page,
+		}
+	}
+}
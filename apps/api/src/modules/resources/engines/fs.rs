@@ -3,9 +3,17 @@ use actix_multipart::form::tempfile::TempFile;
 use async_trait::async_trait;
 use chrono::DateTime;
 use serde_json::Value;
-use std::{env::current_dir, fs, path::Path, time::UNIX_EPOCH};
-
-use super::lib::{ResourceItem, ResourceItemKind, StorageEngine};
+use std::{
+	env::current_dir,
+	fs,
+	path::{Path, PathBuf},
+	time::UNIX_EPOCH,
+};
+
+use super::lib::{
+	get_upload_file_name, join_path, normalize_path, normalize_resource_path, ResourceItem,
+	ResourceItemKind, StorageEngine,
+};
 
 #[derive(Debug, Clone)]
 pub struct FsStorageEngine {
@@ -17,16 +25,35 @@ pub struct FsStorageEngineConfig {
 	pub base_path: String,
 }
 
-fn clean_path(path: &str) -> &str {
-	path.trim_start_matches('/')
-}
-
 fn get_config(config: &Value) -> FsStorageEngineConfig {
 	FsStorageEngineConfig {
 		base_path: config["base_path"].as_str().unwrap().to_string(),
 	}
 }
 
+fn get_location(config: &FsStorageEngineConfig, path: &str) -> Result<PathBuf, AppError> {
+	Ok(Path::new(".")
+		.join(&config.base_path)
+		.join(normalize_resource_path(path)?))
+}
+
+fn copy_directory_recursive(source: &Path, destination: &Path) -> Result<(), AppError> {
+	fs::create_dir_all(destination)?;
+
+	for entry in fs::read_dir(source)? {
+		let entry = entry?;
+		let target = destination.join(entry.file_name());
+
+		if entry.metadata()?.is_dir() {
+			copy_directory_recursive(&entry.path(), &target)?;
+		} else {
+			fs::copy(entry.path(), &target)?;
+		}
+	}
+
+	Ok(())
+}
+
 #[async_trait]
 impl StorageEngine for FsStorageEngine {
 	async fn find_all(&self, path: &str) -> Result<(Vec<ResourceItem>, i64), AppError> {
@@ -34,7 +61,7 @@ impl StorageEngine for FsStorageEngine {
 		let current_dir = current_dir()?;
 		let location = Path::new(&current_dir)
 			.join(config.base_path)
-			.join(clean_path(path));
+			.join(normalize_path(path)?);
 		let dir_result = fs::read_dir(&location)?;
 
 		let resource_items = dir_result
@@ -85,36 +112,64 @@ impl StorageEngine for FsStorageEngine {
 		let config = get_config(&self.config);
 		let location = Path::new(".")
 			.join(config.base_path)
-			.join(clean_path(path))
-			.join(file.file_name.unwrap());
+			.join(join_path(path, &get_upload_file_name(&file)?)?);
 		fs::copy(&file.file.path(), &location)?;
-		fs::remove_file(&location)?;
 
 		Ok(())
 	}
 
 	async fn download_file(&self, path: &str) -> Result<Vec<u8>, AppError> {
 		let config = get_config(&self.config);
-		let location = Path::new(".").join(config.base_path).join(clean_path(path));
+		let location = Path::new(".")
+			.join(config.base_path)
+			.join(normalize_resource_path(path)?);
 		let result = fs::read(&location)?;
 
 		Ok(result)
 	}
 
+	async fn write_file(&self, path: &str, contents: Vec<u8>) -> Result<(), AppError> {
+		let config = get_config(&self.config);
+		fs::write(get_location(&config, path)?, contents)?;
+
+		Ok(())
+	}
+
 	async fn remove_file(&self, path: &str) -> Result<(), AppError> {
 		let config = get_config(&self.config);
-		let location = Path::new(".").join(config.base_path).join(clean_path(path));
+		let location = Path::new(".")
+			.join(config.base_path)
+			.join(normalize_resource_path(path)?);
 		fs::remove_file(&location)?;
 
 		Ok(())
 	}
 
+	async fn move_file(&self, path: &str, destination: &str) -> Result<(), AppError> {
+		let config = get_config(&self.config);
+		fs::rename(
+			get_location(&config, path)?,
+			get_location(&config, destination)?,
+		)?;
+
+		Ok(())
+	}
+
+	async fn copy_file(&self, path: &str, destination: &str) -> Result<(), AppError> {
+		let config = get_config(&self.config);
+		fs::copy(
+			get_location(&config, path)?,
+			get_location(&config, destination)?,
+		)?;
+
+		Ok(())
+	}
+
 	async fn create_directory(&self, path: &str, name: &str) -> Result<(), AppError> {
 		let config = get_config(&self.config);
 		let location = Path::new(".")
 			.join(config.base_path)
-			.join(clean_path(path))
-			.join(name);
+			.join(join_path(path, name)?);
 		fs::create_dir_all(&location)?;
 
 		Ok(())
@@ -122,9 +177,79 @@ impl StorageEngine for FsStorageEngine {
 
 	async fn remove_directory(&self, path: &str) -> Result<(), AppError> {
 		let config = get_config(&self.config);
-		let location = Path::new(".").join(config.base_path).join(clean_path(path));
+		let location = Path::new(".")
+			.join(config.base_path)
+			.join(normalize_resource_path(path)?);
 		fs::remove_dir_all(&location)?;
 
 		Ok(())
 	}
+
+	async fn move_directory(&self, path: &str, destination: &str) -> Result<(), AppError> {
+		let config = get_config(&self.config);
+		fs::rename(
+			get_location(&config, path)?,
+			get_location(&config, destination)?,
+		)?;
+
+		Ok(())
+	}
+
+	async fn copy_directory(&self, path: &str, destination: &str) -> Result<(), AppError> {
+		let config = get_config(&self.config);
+		copy_directory_recursive(
+			&get_location(&config, path)?,
+			&get_location(&config, destination)?,
+		)?;
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::path::{Component, Path, PathBuf};
+
+	use serde_json::json;
+
+	use super::{get_config, get_location};
+
+	fn location(path: &str) -> Option<PathBuf> {
+		let config = get_config(&json!({ "base_path": "uploads" }));
+		get_location(&config, path).ok()
+	}
+
+	fn is_inside_base_path(location: &Path) -> bool {
+		location.starts_with(Path::new(".").join("uploads"))
+			&& !location
+				.components()
+				.any(|component| component == Component::ParentDir)
+	}
+
+	// Path normalisation itself is covered in `lib`, these only check where paths end up on disk
+	#[test]
+	fn locations_stay_inside_the_base_path() {
+		for (path, expected) in [
+			("/etc/passwd", "etc/passwd"),
+			("\\etc\\passwd", "etc/passwd"),
+			("..%2Fsecrets", "..%2Fsecrets"),
+		] {
+			let location = location(path).unwrap();
+			assert!(is_inside_base_path(&location), "{path}");
+			assert_eq!(location, Path::new(".").join("uploads").join(expected));
+		}
+	}
+
+	#[test]
+	fn escaping_paths_have_no_location() {
+		for path in [
+			"../secrets",
+			"images\\..\\..\\secrets",
+			"",
+			"/",
+			"images/\0",
+		] {
+			assert_eq!(location(path), None, "{path}");
+		}
+	}
 }
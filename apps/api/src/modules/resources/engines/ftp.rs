@@ -1,60 +1,263 @@
 use actix_multipart::form::tempfile::TempFile;
+use actix_web::http::StatusCode;
+use actix_web::web;
 
 use chrono::DateTime;
+use diesel::r2d2::{ManageConnection, Pool};
 use serde_json::Value;
+use uuid::Uuid;
 
-use crate::errors::AppError;
+use crate::errors::{AppError, AppErrorValue};
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::io::Cursor;
+use std::sync::Mutex;
+use std::time::Duration;
 use std::{str::FromStr, time::UNIX_EPOCH};
-use suppaftp::{list::File, NativeTlsFtpStream};
+use suppaftp::native_tls::TlsConnector;
+use suppaftp::{list::File, FtpError, Mode, NativeTlsConnector, NativeTlsFtpStream};
 
-use path_slash::PathExt as _;
+use super::lib::{
+	get_upload_file_name, join_path, normalize_path, normalize_resource_path, ResourceItem,
+	ResourceItemKind, StorageEngine,
+};
 
-use super::lib::{ResourceItem, ResourceItemKind, StorageEngine};
+const DEFAULT_POOL_SIZE: u32 = 4;
+const DEFAULT_IDLE_TIMEOUT: u64 = 60;
 
-fn clean_path(path: &str) -> &str {
-	path.trim_start_matches('/')
+lazy_static! {
+	static ref FTP_POOLS: Mutex<HashMap<Uuid, (FtpStorageEngineConfig, Pool<FtpConnectionManager>)>> =
+		Mutex::new(HashMap::new());
 }
 
 #[derive(Debug, Clone)]
 pub struct FtpStorageEngine {
+	pub storage_repository_id: Uuid,
 	pub config: Value,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct FtpStorageEngineConfig {
-	pub client: NativeTlsFtpStream,
+	pub server: String,
+	pub username: String,
+	pub password: String,
+	pub secure: bool,
+	pub mode: Mode,
+	pub pool_size: u32,
+	pub idle_timeout: u64,
+}
+
+#[derive(Debug)]
+pub struct FtpConnectionManager {
+	config: FtpStorageEngineConfig,
+}
+
+fn invalid_configuration_error(message: &str) -> AppError {
+	AppError::UnprocessableEntity(AppErrorValue {
+		message: message.to_owned(),
+		status: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+		code: "INVALID_STORAGE_CONFIGURATION".to_owned(),
+		..Default::default()
+	})
+}
+
+fn get_required_string(config: &Value, key: &str) -> Result<String, AppError> {
+	config[key]
+		.as_str()
+		.map(|value| value.to_owned())
+		.ok_or_else(|| {
+			invalid_configuration_error(&format!("FTP configuration is missing \"{key}\""))
+		})
 }
 
 fn get_config(config: &Value) -> Result<FtpStorageEngineConfig, AppError> {
-	let ftp_server = config["server"].as_str().unwrap().to_string();
-	let ftp_username = config["ftp_username"].as_str().unwrap().to_string();
-	let ftp_password = config["ftp_password"].as_str().unwrap().to_string();
+	let mode = match config["mode"].as_str().unwrap_or("PASSIVE") {
+		"PASSIVE" => Mode::Passive,
+		"EXTENDED_PASSIVE" => Mode::ExtendedPassive,
+		"ACTIVE" => Mode::Active,
+		_ => {
+			return Err(invalid_configuration_error(
+				"FTP mode must be one of PASSIVE, EXTENDED_PASSIVE or ACTIVE",
+			))
+		}
+	};
+
+	let pool_size = match config["pool_size"].as_u64() {
+		Some(size) if size > 0 => u32::try_from(size)?,
+		Some(_) => {
+			return Err(invalid_configuration_error(
+				"FTP pool size must be at least 1",
+			))
+		}
+		None => DEFAULT_POOL_SIZE,
+	};
+
+	Ok(FtpStorageEngineConfig {
+		server: get_required_string(config, "server")?,
+		username: get_required_string(config, "ftp_username")?,
+		password: get_required_string(config, "ftp_password")?,
+		secure: config["secure"].as_bool().unwrap_or(false),
+		mode,
+		pool_size,
+		idle_timeout: config["idle_timeout"]
+			.as_u64()
+			.unwrap_or(DEFAULT_IDLE_TIMEOUT),
+	})
+}
+
+impl ManageConnection for FtpConnectionManager {
+	type Connection = NativeTlsFtpStream;
+	type Error = FtpError;
+
+	fn connect(&self) -> Result<Self::Connection, Self::Error> {
+		let mut client = NativeTlsFtpStream::connect(&self.config.server)?;
+
+		if self.config.secure {
+			let domain = self
+				.config
+				.server
+				.rsplit_once(':')
+				.map(|(host, _)| host)
+				.unwrap_or(&self.config.server);
+			let connector =
+				TlsConnector::new().map_err(|err| FtpError::SecureError(err.to_string()))?;
+
+			client = client.into_secure(NativeTlsConnector::from(connector), domain)?;
+		}
+
+		client.login(&self.config.username, &self.config.password)?;
+		client.set_mode(self.config.mode);
+
+		Ok(client)
+	}
+
+	// Runs on every checkout, so connections the server dropped while idle get replaced
+	fn is_valid(&self, conn: &mut Self::Connection) -> Result<(), Self::Error> {
+		conn.noop()
+	}
+
+	fn has_broken(&self, _conn: &mut Self::Connection) -> bool {
+		false
+	}
+}
+
+fn get_pool(
+	storage_repository_id: Uuid,
+	config: &Value,
+) -> Result<Pool<FtpConnectionManager>, AppError> {
+	let config = get_config(config)?;
+	let mut pools = FTP_POOLS
+		.lock()
+		.map_err(|_| AppError::from("Could not lock the FTP connection pools"))?;
+
+	if let Some((pool_config, pool)) = pools.get(&storage_repository_id) {
+		if *pool_config == config {
+			return Ok(pool.clone());
+		}
+	}
+
+	let pool = Pool::builder()
+		.max_size(config.pool_size)
+		.min_idle(Some(0))
+		.idle_timeout(Some(Duration::from_secs(config.idle_timeout)))
+		.test_on_check_out(true)
+		.build_unchecked(FtpConnectionManager {
+			config: config.clone(),
+		});
+
+	pools.insert(storage_repository_id, (config, pool.clone()));
+
+	Ok(pool)
+}
+
+impl FtpStorageEngine {
+	async fn run<T, F>(&self, operation: F) -> Result<T, AppError>
+	where
+		T: Send + 'static,
+		F: FnOnce(&mut NativeTlsFtpStream) -> Result<T, AppError> + Send + 'static,
+	{
+		let pool = get_pool(self.storage_repository_id, &self.config)?;
+
+		web::block(move || {
+			let mut client = pool.get()?;
+			operation(&mut client)
+		})
+		.await?
+	}
+}
+
+// Paths are relative to the directory the FTP user lands in, so absolute paths can't reach the rest of the server
+fn get_location(path: &str) -> Result<String, AppError> {
+	normalize_resource_path(path)
+}
+
+// A directory that already exists is fine, so migrations and copies can write into an existing tree
+fn ensure_directory(client: &mut NativeTlsFtpStream, path: &str) -> Result<(), AppError> {
+	if let Err(err) = client.mkdir(path) {
+		let current_directory = client.pwd()?;
+		if client.cwd(path).is_err() {
+			return Err(err.into());
+		}
+		client.cwd(current_directory)?;
+	}
+
+	Ok(())
+}
+
+fn copy_single_file(
+	client: &mut NativeTlsFtpStream,
+	path: &str,
+	destination: &str,
+) -> Result<(), AppError> {
+	let mut object = client.retr_as_buffer(path)?;
+	client.put_file(destination, &mut object)?;
+
+	Ok(())
+}
+
+fn copy_directory_recursive(
+	client: &mut NativeTlsFtpStream,
+	path: &str,
+	destination: &str,
+) -> Result<(), AppError> {
+	ensure_directory(client, destination)?;
 
-	let mut client = NativeTlsFtpStream::connect(&ftp_server)?;
-	let _ = client.login(ftp_username, ftp_password)?;
-	// let client = client
-	// 	.into_secure(
-	// 		NativeTlsConnector::from(TlsConnector::new().unwrap()),
-	// 		&ftp_server,
-	// 	)?;
+	let entries = client
+		.list(Some(path))?
+		.into_iter()
+		.filter_map(|line| File::from_str(&line).ok())
+		.filter(|entry| entry.name() != "." && entry.name() != "..")
+		.collect::<Vec<File>>();
 
-	Ok(FtpStorageEngineConfig { client })
+	for entry in entries {
+		let source = format!("{path}/{}", entry.name());
+		let target = format!("{destination}/{}", entry.name());
+
+		if entry.is_directory() {
+			copy_directory_recursive(client, &source, &target)?;
+		} else {
+			copy_single_file(client, &source, &target)?;
+		}
+	}
+
+	Ok(())
 }
 
 #[async_trait]
 impl StorageEngine for FtpStorageEngine {
 	async fn find_all(&self, path: &str) -> Result<(Vec<ResourceItem>, i64), AppError> {
-		let mut config = get_config(&self.config)?;
-		let objects: Vec<String> = config.client.list(Some(path))?;
+		let path = normalize_path(path)?;
+		let objects: Vec<String> = self
+			.run(move |client| Ok(client.list(Some(path.as_str()))?))
+			.await?;
 
 		let resource_items = objects
 			.into_iter()
-			.map(|path| {
-				let resource: File = File::from_str(&path).unwrap();
-				let name = (&resource).name().to_owned();
+			.filter_map(|line| File::from_str(&line).ok())
+			.filter(|resource| resource.name() != "." && resource.name() != "..")
+			.map(|resource| {
+				let name = resource.name().to_owned();
 
 				let guess = mime_guess::from_path(&name);
 				let mime_type = if guess.is_empty() {
@@ -93,41 +296,143 @@ impl StorageEngine for FtpStorageEngine {
 	}
 
 	async fn upload_file(&self, path: &str, local_file: TempFile) -> Result<(), AppError> {
-		let key = Path::new(clean_path(path)).join(local_file.file_name.unwrap());
-		let mut config = get_config(&self.config)?;
-		let mut file = fs::File::open(local_file.file.path().to_str().unwrap())?;
+		let key = join_path(path, &get_upload_file_name(&local_file)?)?;
+		let mut file = fs::File::open(local_file.file.path())?;
 
-		let _ = config.client.put_file(key.to_slash().unwrap(), &mut file)?;
-
-		Ok(())
+		self.run(move |client| {
+			client.put_file(key, &mut file)?;
+			Ok(())
+		})
+		.await
 	}
 
 	async fn download_file(&self, path: &str) -> Result<Vec<u8>, AppError> {
-		let mut config = get_config(&self.config)?;
-		let object = config.client.retr_as_buffer(path)?;
+		let path = get_location(path)?;
+
+		self.run(move |client| Ok(client.retr_as_buffer(&path)?.into_inner()))
+			.await
+	}
+
+	async fn write_file(&self, path: &str, contents: Vec<u8>) -> Result<(), AppError> {
+		let path = get_location(path)?;
 
-		Ok(object.into_inner())
+		self.run(move |client| {
+			client.put_file(path, &mut Cursor::new(contents))?;
+			Ok(())
+		})
+		.await
 	}
 
 	async fn remove_file(&self, path: &str) -> Result<(), AppError> {
-		let mut config = get_config(&self.config)?;
-		config.client.rm(path)?;
+		let path = get_location(path)?;
+
+		self.run(move |client| Ok(client.rm(path)?)).await
+	}
+
+	async fn move_file(&self, path: &str, destination: &str) -> Result<(), AppError> {
+		let path = get_location(path)?;
+		let destination = get_location(destination)?;
 
-		Ok(())
+		self.run(move |client| Ok(client.rename(path, destination)?))
+			.await
+	}
+
+	async fn copy_file(&self, path: &str, destination: &str) -> Result<(), AppError> {
+		let path = get_location(path)?;
+		let destination = get_location(destination)?;
+
+		self.run(move |client| copy_single_file(client, &path, &destination))
+			.await
 	}
 
 	async fn create_directory(&self, path: &str, name: &str) -> Result<(), AppError> {
-		let key = Path::new(clean_path(path)).join(name);
-		let mut config = get_config(&self.config)?;
-		config.client.mkdir(key.to_slash().unwrap())?;
+		let key = join_path(path, name)?;
 
-		Ok(())
+		self.run(move |client| ensure_directory(client, &key)).await
 	}
 
 	async fn remove_directory(&self, path: &str) -> Result<(), AppError> {
-		let mut config = get_config(&self.config)?;
-		config.client.rmdir(path)?;
+		let path = get_location(path)?;
+
+		self.run(move |client| Ok(client.rmdir(path)?)).await
+	}
+
+	async fn move_directory(&self, path: &str, destination: &str) -> Result<(), AppError> {
+		let path = get_location(path)?;
+		let destination = get_location(destination)?;
+
+		self.run(move |client| Ok(client.rename(path, destination)?))
+			.await
+	}
+
+	async fn copy_directory(&self, path: &str, destination: &str) -> Result<(), AppError> {
+		let path = get_location(path)?;
+		let destination = get_location(destination)?;
+
+		self.run(move |client| copy_directory_recursive(client, &path, &destination))
+			.await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json::json;
+	use suppaftp::Mode;
+
+	use super::{get_config, get_location, DEFAULT_IDLE_TIMEOUT, DEFAULT_POOL_SIZE};
+
+	fn config(extra: serde_json::Value) -> serde_json::Value {
+		let mut config = json!({
+			"server": "ftp.example.com:21",
+			"ftp_username": "crate",
+			"ftp_password": "secret",
+		});
+		for (key, value) in extra.as_object().unwrap() {
+			config[key] = value.clone();
+		}
+		config
+	}
+
+	#[test]
+	fn config_falls_back_to_passive_defaults() {
+		let config = get_config(&config(json!({}))).unwrap();
+
+		assert_eq!(config.mode, Mode::Passive);
+		assert!(!config.secure);
+		assert_eq!(config.pool_size, DEFAULT_POOL_SIZE);
+		assert_eq!(config.idle_timeout, DEFAULT_IDLE_TIMEOUT);
+	}
+
+	#[test]
+	fn config_reads_mode_and_pool() {
+		let config = get_config(&config(json!({
+			"mode": "EXTENDED_PASSIVE",
+			"secure": true,
+			"pool_size": 8,
+			"idle_timeout": 5,
+		})))
+		.unwrap();
+
+		assert_eq!(config.mode, Mode::ExtendedPassive);
+		assert!(config.secure);
+		assert_eq!(config.pool_size, 8);
+		assert_eq!(config.idle_timeout, 5);
+	}
+
+	#[test]
+	fn invalid_config_is_rejected() {
+		assert!(get_config(&config(json!({ "mode": "SIDEWAYS" }))).is_err());
+		assert!(get_config(&config(json!({ "pool_size": 0 }))).is_err());
+		assert!(get_config(&json!({ "server": "ftp.example.com:21" })).is_err());
+	}
 
-		Ok(())
+	// The server resolves relative paths against the home directory of the FTP user
+	#[test]
+	fn locations_are_relative_to_the_home_directory() {
+		assert_eq!(get_location("/etc/passwd").unwrap(), "etc/passwd");
+		assert_eq!(get_location("images//logo.png").unwrap(), "images/logo.png");
+		for path in ["../secrets", "", "/"] {
+			assert!(get_location(path).is_err(), "{path}");
+		}
 	}
 }
@@ -10,7 +10,7 @@ use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::{fs::FsStorageEngine, ftp::FtpStorageEngine};
+use super::{fs::FsStorageEngine, ftp::FtpStorageEngine, s3::S3StorageEngine};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub enum ResourceItemKind {
@@ -34,10 +34,173 @@ pub trait StorageEngine {
 
 	async fn upload_file(&self, path: &str, file: TempFile) -> Result<(), AppError>;
 	async fn download_file(&self, path: &str) -> Result<Vec<u8>, AppError>;
+	async fn write_file(&self, path: &str, contents: Vec<u8>) -> Result<(), AppError>;
 	async fn remove_file(&self, path: &str) -> Result<(), AppError>;
+	async fn move_file(&self, path: &str, destination: &str) -> Result<(), AppError>;
+	async fn copy_file(&self, path: &str, destination: &str) -> Result<(), AppError>;
 
 	async fn create_directory(&self, path: &str, name: &str) -> Result<(), AppError>;
 	async fn remove_directory(&self, path: &str) -> Result<(), AppError>;
+	async fn move_directory(&self, path: &str, destination: &str) -> Result<(), AppError>;
+	async fn copy_directory(&self, path: &str, destination: &str) -> Result<(), AppError>;
+}
+
+fn invalid_path_error(path: &str) -> AppError {
+	AppError::BadRequest(AppErrorValue {
+		message: format!("Invalid path: {path}"),
+		status: StatusCode::BAD_REQUEST.as_u16(),
+		code: "INVALID_PATH".to_owned(),
+		..Default::default()
+	})
+}
+
+fn invalid_name_error(name: &str) -> AppError {
+	AppError::BadRequest(AppErrorValue {
+		message: format!("Invalid name: {name}"),
+		status: StatusCode::BAD_REQUEST.as_u16(),
+		code: "INVALID_NAME".to_owned(),
+		..Default::default()
+	})
+}
+
+// Turns a user supplied path into a relative path without `.` or empty segments,
+// rejecting anything that would resolve outside of the repository root
+pub fn normalize_path(path: &str) -> Result<String, AppError> {
+	if path.chars().any(|c| c.is_control()) {
+		return Err(invalid_path_error(path));
+	}
+
+	let segments = path
+		.split(|c| c == '/' || c == '\\')
+		.filter(|segment| !segment.is_empty() && *segment != ".")
+		.map(|segment| {
+			if segment == ".." {
+				return Err(invalid_path_error(path));
+			}
+
+			Ok(segment)
+		})
+		.collect::<Result<Vec<&str>, AppError>>()?;
+
+	Ok(segments.join("/"))
+}
+
+// Same as `normalize_path`, but for operations targeting a resource, so the root itself is not allowed
+pub fn normalize_resource_path(path: &str) -> Result<String, AppError> {
+	let normalized_path = normalize_path(path)?;
+
+	if normalized_path.is_empty() {
+		return Err(invalid_path_error(path));
+	}
+
+	Ok(normalized_path)
+}
+
+// Validates a single file or directory name, names can never contain separators
+pub fn sanitize_name(name: &str) -> Result<String, AppError> {
+	let name = name.trim();
+
+	if name.is_empty()
+		|| name == "."
+		|| name == ".."
+		|| name.len() > 255
+		|| name
+			.chars()
+			.any(|c| c.is_control() || c == '/' || c == '\\')
+	{
+		return Err(invalid_name_error(name));
+	}
+
+	Ok(name.to_owned())
+}
+
+// Joins a normalized directory path and a sanitized name into a relative key
+pub fn join_path(path: &str, name: &str) -> Result<String, AppError> {
+	let path = normalize_path(path)?;
+	let name = sanitize_name(name)?;
+
+	if path.is_empty() {
+		return Ok(name);
+	}
+
+	Ok(format!("{path}/{name}"))
+}
+
+// Splits a normalized resource path into its parent path and its name
+pub fn split_path(path: &str) -> (String, String) {
+	match path.rsplit_once('/') {
+		Some((parent, name)) => (parent.to_owned(), name.to_owned()),
+		None => ("".to_owned(), path.to_owned()),
+	}
+}
+
+pub fn is_same_or_nested_path(path: &str, parent: &str) -> bool {
+	path == parent || path.starts_with(&format!("{parent}/"))
+}
+
+pub async fn ensure_resource_does_not_exist(
+	engine: &dyn StorageEngine,
+	path: &str,
+) -> Result<(), AppError> {
+	let (parent, name) = split_path(path);
+	let (resources, _) = engine.find_all(&parent).await?;
+
+	if resources.iter().any(|resource| resource.name == name) {
+		return Err(AppError::UnprocessableEntity(AppErrorValue {
+			message: format!("A resource already exists at {path}"),
+			status: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+			code: "RESOURCE_ALREADY_EXISTS".to_owned(),
+			..Default::default()
+		}));
+	}
+
+	Ok(())
+}
+
+// Lists every file and directory in the repository, parents are always listed before their children
+pub async fn find_resource_tree(
+	engine: &dyn StorageEngine,
+) -> Result<Vec<(String, ResourceItemKind)>, AppError> {
+	let mut pending_directories = vec!["".to_owned()];
+	let mut tree = vec![];
+
+	while let Some(directory) = pending_directories.pop() {
+		let (resources, _) = engine.find_all(&directory).await?;
+
+		for resource in resources {
+			if resource.name == "." || resource.name == ".." {
+				continue;
+			}
+
+			let path = if directory.is_empty() {
+				resource.name.clone()
+			} else {
+				format!("{directory}/{}", resource.name)
+			};
+
+			if matches!(resource.kind, ResourceItemKind::DIRECTORY) {
+				pending_directories.push(path.clone());
+			}
+
+			tree.push((path, resource.kind));
+		}
+	}
+
+	Ok(tree)
+}
+
+pub fn get_upload_file_name(file: &TempFile) -> Result<String, AppError> {
+	let file_name = file
+		.file_name
+		.as_deref()
+		.ok_or(AppError::BadRequest(AppErrorValue {
+			message: "Uploaded file is missing a file name".to_owned(),
+			status: StatusCode::BAD_REQUEST.as_u16(),
+			code: "MISSING_FILE_NAME".to_owned(),
+			..Default::default()
+		}))?;
+
+	sanitize_name(file_name)
 }
 
 pub fn get_storage_engine(
@@ -52,13 +215,14 @@ pub fn get_storage_engine(
 				config: storage_repository.configuration,
 			}
 		})),
-		// "S3_BUCKET" => Ok(Box::new({
-		// 	S3StorageEngine {
-		// 		config: storage_repository.configuration,
-		// 	}
-		// })),
+		"S3_BUCKET" => Ok(Box::new({
+			S3StorageEngine {
+				config: storage_repository.configuration,
+			}
+		})),
 		"FTP" => Ok(Box::new({
 			FtpStorageEngine {
+				storage_repository_id: storage_repository.id,
 				config: storage_repository.configuration,
 			}
 		})),
@@ -70,3 +234,96 @@ pub fn get_storage_engine(
 		})),
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		is_same_or_nested_path, join_path, normalize_path, normalize_resource_path, sanitize_name,
+		split_path,
+	};
+
+	#[test]
+	fn parent_segments_are_rejected() {
+		for path in [
+			"..",
+			"../secrets",
+			"images/../../secrets",
+			"images/..",
+			"..\\secrets",
+			"images\\..\\..\\secrets",
+		] {
+			assert!(normalize_path(path).is_err(), "{path}");
+		}
+	}
+
+	#[test]
+	fn absolute_paths_become_relative() {
+		for (path, expected) in [
+			("/etc/passwd", "etc/passwd"),
+			("//etc//passwd", "etc/passwd"),
+			("\\etc\\passwd", "etc/passwd"),
+			("/./images/./logo.png", "images/logo.png"),
+			("/", ""),
+		] {
+			assert_eq!(normalize_path(path).unwrap(), expected, "{path}");
+		}
+	}
+
+	#[test]
+	fn encoded_separators_are_not_decoded() {
+		for path in [
+			"..%2Fsecrets",
+			"%2e%2e%2fsecrets",
+			"%2E%2E%5Csecrets",
+			"..%252F",
+		] {
+			assert_eq!(normalize_path(path).unwrap(), path);
+		}
+	}
+
+	#[test]
+	fn control_characters_are_rejected() {
+		assert!(normalize_path("images/\0/logo.png").is_err());
+		assert!(normalize_path("images/\n").is_err());
+	}
+
+	#[test]
+	fn the_root_is_not_a_resource() {
+		for path in ["", "/", ".", "./", "\\"] {
+			assert!(normalize_resource_path(path).is_err(), "{path}");
+		}
+	}
+
+	#[test]
+	fn names_cannot_escape_their_directory() {
+		for name in ["..", ".", "../secrets", "a/b", "a\\b", "", "   "] {
+			assert!(sanitize_name(name).is_err(), "{name}");
+			assert!(join_path("images", name).is_err(), "{name}");
+		}
+		assert!(join_path("../images", "logo.png").is_err());
+		assert_eq!(
+			join_path("/images/", "logo.png").unwrap(),
+			"images/logo.png"
+		);
+		assert_eq!(join_path("", "logo.png").unwrap(), "logo.png");
+	}
+
+	#[test]
+	fn paths_split_into_parent_and_name() {
+		assert_eq!(
+			split_path("images/icons/logo.png"),
+			("images/icons".to_owned(), "logo.png".to_owned())
+		);
+		assert_eq!(
+			split_path("logo.png"),
+			("".to_owned(), "logo.png".to_owned())
+		);
+	}
+
+	#[test]
+	fn nested_paths_need_a_separator() {
+		assert!(is_same_or_nested_path("images", "images"));
+		assert!(is_same_or_nested_path("images/icons", "images"));
+		assert!(!is_same_or_nested_path("images-old", "images"));
+	}
+}
@@ -1,4 +1,4 @@
 pub mod fs;
 pub mod ftp;
 pub mod lib;
-// pub mod s3;
+pub mod s3;
@@ -1,19 +1,26 @@
 use actix_multipart::form::tempfile::TempFile;
 use aws_credential_types::Credentials;
-use aws_sdk_s3::{Config, config::Region, Client};
+use aws_sdk_s3::{config::Region, Client, Config};
 use chrono::DateTime;
 use serde_json::Value;
 
-use async_trait::async_trait;
 use crate::errors::AppError;
-use std::path::Path;
+use async_trait::async_trait;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use tokio::{fs, io::AsyncReadExt as _};
 
-use super::lib::{StorageEngine, ResourceItem, ResourceItemKind};
+use super::lib::{
+	get_upload_file_name, join_path, normalize_path, normalize_resource_path, ResourceItem,
+	ResourceItemKind, StorageEngine,
+};
 
-fn clean_path(path: &str) -> &str {
-	path.trim_start_matches('/')
-}
+// Copy sources are sent as a url, so everything but unreserved characters and separators is encoded
+const COPY_SOURCE_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+	.remove(b'/')
+	.remove(b'-')
+	.remove(b'_')
+	.remove(b'.')
+	.remove(b'~');
 
 #[derive(Debug, Clone)]
 pub struct S3StorageEngine {
@@ -80,9 +87,65 @@ async fn put_object_from_file(
 	Ok(size_estimate)
 }
 
+async fn copy_object(
+	client: &aws_sdk_s3::Client,
+	bucket_name: &str,
+	key: &str,
+	destination: &str,
+) -> Result<(), AppError> {
+	client
+		.copy_object()
+		.bucket(bucket_name)
+		.copy_source(
+			utf8_percent_encode(&format!("{bucket_name}/{key}"), COPY_SOURCE_ENCODE_SET)
+				.to_string(),
+		)
+		.key(destination)
+		.send()
+		.await?;
+
+	Ok(())
+}
+
+// Listings are capped at 1000 keys, so the continuation token is followed until every page is read
+async fn find_keys_with_prefix(
+	client: &aws_sdk_s3::Client,
+	bucket_name: &str,
+	prefix: &str,
+) -> Result<Vec<String>, AppError> {
+	let mut keys = vec![];
+	let mut continuation_token = None;
+
+	loop {
+		let objects = client
+			.list_objects_v2()
+			.prefix(format!("{prefix}/"))
+			.bucket(bucket_name)
+			.set_continuation_token(continuation_token)
+			.send()
+			.await?;
+
+		keys.extend(
+			objects
+				.contents()
+				.unwrap_or_default()
+				.iter()
+				.filter_map(|item| item.key().map(|key| key.to_string())),
+		);
+
+		match objects.next_continuation_token() {
+			Some(token) => continuation_token = Some(token.to_string()),
+			None => break,
+		}
+	}
+
+	Ok(keys)
+}
+
 #[async_trait]
 impl StorageEngine for S3StorageEngine {
 	async fn find_all(&self, path: &str) -> Result<(Vec<ResourceItem>, i64), AppError> {
+		let path = normalize_path(path)?;
 		let config = get_config(&self.config);
 		let objects = config
 			.client
@@ -159,12 +222,12 @@ impl StorageEngine for S3StorageEngine {
 	}
 
 	async fn upload_file(&self, path: &str, file: TempFile) -> Result<(), AppError> {
-		let key = Path::new(clean_path(path)).join(file.file_name.unwrap());
+		let key = join_path(path, &get_upload_file_name(&file)?)?;
 		let config = get_config(&self.config);
 		put_object_from_file(
 			config.client,
 			file.file.path().to_str().unwrap(),
-			&key.to_str().unwrap(),
+			&key,
 			&config.bucket_name,
 		)
 		.await?;
@@ -178,7 +241,7 @@ impl StorageEngine for S3StorageEngine {
 			.client
 			.get_object()
 			.bucket(config.bucket_name)
-			.key(path)
+			.key(normalize_resource_path(path)?)
 			.send()
 			.await?;
 
@@ -187,8 +250,43 @@ impl StorageEngine for S3StorageEngine {
 		Ok(data.into_bytes().to_vec())
 	}
 
+	async fn write_file(&self, path: &str, contents: Vec<u8>) -> Result<(), AppError> {
+		let config = get_config(&self.config);
+		config
+			.client
+			.put_object()
+			.bucket(config.bucket_name)
+			.key(normalize_resource_path(path)?)
+			.body(aws_sdk_s3::primitives::ByteStream::from(contents))
+			.send()
+			.await?;
+
+		Ok(())
+	}
+
 	async fn remove_file(&self, path: &str) -> Result<(), AppError> {
 		let config = get_config(&self.config);
+		config
+			.client
+			.delete_object()
+			.bucket(config.bucket_name)
+			.key(normalize_resource_path(path)?)
+			.send()
+			.await?;
+
+		Ok(())
+	}
+
+	async fn move_file(&self, path: &str, destination: &str) -> Result<(), AppError> {
+		let path = normalize_resource_path(path)?;
+		let config = get_config(&self.config);
+		copy_object(
+			&config.client,
+			&config.bucket_name,
+			&path,
+			&normalize_resource_path(destination)?,
+		)
+		.await?;
 		config
 			.client
 			.delete_object()
@@ -200,17 +298,66 @@ impl StorageEngine for S3StorageEngine {
 		Ok(())
 	}
 
+	async fn copy_file(&self, path: &str, destination: &str) -> Result<(), AppError> {
+		let config = get_config(&self.config);
+		copy_object(
+			&config.client,
+			&config.bucket_name,
+			&normalize_resource_path(path)?,
+			&normalize_resource_path(destination)?,
+		)
+		.await?;
+
+		Ok(())
+	}
+
 	async fn create_directory(&self, path: &str, name: &str) -> Result<(), AppError> {
-		let _location = Path::new(".").join(".").join(clean_path(path)).join(name);
+		let _location = join_path(path, name)?;
 		// fs::create_dir_all(&location)?;
 
 		Ok(())
 	}
 
 	async fn remove_directory(&self, path: &str) -> Result<(), AppError> {
-		let _location = Path::new(".").join(".").join(clean_path(path));
+		let _location = normalize_resource_path(path)?;
 		// fs::remove_dir_all(&location)?;
 
 		Ok(())
 	}
+
+	// Directories only exist as key prefixes, so every object below the prefix is handled separately
+	async fn move_directory(&self, path: &str, destination: &str) -> Result<(), AppError> {
+		let path = normalize_resource_path(path)?;
+		let destination = normalize_resource_path(destination)?;
+		let config = get_config(&self.config);
+		let keys = find_keys_with_prefix(&config.client, &config.bucket_name, &path).await?;
+
+		for key in keys {
+			let target = key.replacen(&path, &destination, 1);
+			copy_object(&config.client, &config.bucket_name, &key, &target).await?;
+			config
+				.client
+				.delete_object()
+				.bucket(&config.bucket_name)
+				.key(key)
+				.send()
+				.await?;
+		}
+
+		Ok(())
+	}
+
+	async fn copy_directory(&self, path: &str, destination: &str) -> Result<(), AppError> {
+		let path = normalize_resource_path(path)?;
+		let destination = normalize_resource_path(destination)?;
+		let config = get_config(&self.config);
+		let keys = find_keys_with_prefix(&config.client, &config.bucket_name, &path).await?;
+
+		for key in keys {
+			let target = key.replacen(&path, &destination, 1);
+			copy_object(&config.client, &config.bucket_name, &key, &target).await?;
+		}
+
+		Ok(())
+	}
 }
@@ -0,0 +1,267 @@
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::path::Path;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use actix_multipart::form::tempfile::TempFile;
+use actix_multipart::form::MultipartForm;
+use actix_web::dev::Payload;
+use actix_web::error::PayloadError;
+use actix_web::http::header;
+use actix_web::web::{self, Bytes};
+use actix_web::{FromRequest, HttpRequest};
+use futures::{Stream, StreamExt};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::errors::{AppError, AppErrorValue};
+use crate::modules::resources::dto::files::request::CreateFileDTO;
+use crate::modules::resources::engines::lib::{get_upload_file_name, StorageEngine};
+
+// Boundaries and part headers are sent along with the file, so the body can be a bit larger than the file
+const MULTIPART_OVERHEAD: usize = 64 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+pub enum OverwriteBehaviourEnum {
+	REJECT,
+	RENAME,
+	#[default]
+	REPLACE,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct UploadPolicy {
+	#[serde(default)]
+	pub allowed_mime_types: Vec<String>,
+	#[serde(default)]
+	pub allowed_extensions: Vec<String>,
+	pub max_file_size: Option<usize>,
+	#[serde(default)]
+	pub overwrite: OverwriteBehaviourEnum,
+}
+
+fn policy_violation(message: String, code: &str) -> AppError {
+	AppError::UnprocessableEntity(AppErrorValue {
+		message,
+		status: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+		code: code.to_owned(),
+		..Default::default()
+	})
+}
+
+fn upload_too_large_error(max_file_size: usize) -> AppError {
+	policy_violation(
+		format!(
+			"Upload exceeds the maximum file size of {} bytes",
+			max_file_size
+		),
+		"UPLOAD_FILE_TOO_LARGE",
+	)
+}
+
+// The policy lives next to the engine specific keys in the storage repository configuration
+pub fn get_upload_policy(configuration: &Value) -> Result<UploadPolicy, AppError> {
+	match configuration.get("upload_policy") {
+		Some(policy) if !policy.is_null() => serde_json::from_value::<UploadPolicy>(policy.clone())
+			.map_err(|err| {
+				policy_violation(
+					format!("Invalid upload policy: {err}"),
+					"INVALID_UPLOAD_POLICY",
+				)
+			}),
+		_ => Ok(UploadPolicy::default()),
+	}
+}
+
+fn matches_mime_type(allowed_mime_type: &str, mime_type: &str) -> bool {
+	let allowed_mime_type = allowed_mime_type.trim().to_lowercase();
+	let mime_type = mime_type.to_lowercase();
+
+	if allowed_mime_type == "*/*" || allowed_mime_type == mime_type {
+		return true;
+	}
+
+	match allowed_mime_type.strip_suffix("/*") {
+		Some(prefix) => mime_type.starts_with(&format!("{prefix}/")),
+		None => false,
+	}
+}
+
+fn get_renamed_file_name(file_name: &str, existing_names: &HashSet<String>) -> String {
+	let path = Path::new(file_name);
+	let stem = path
+		.file_stem()
+		.and_then(|stem| stem.to_str())
+		.unwrap_or(file_name);
+	let extension = path.extension().and_then(|extension| extension.to_str());
+
+	(1..)
+		.map(|suffix| match extension {
+			Some(extension) => format!("{stem}-{suffix}.{extension}"),
+			None => format!("{stem}-{suffix}"),
+		})
+		.find(|candidate| !existing_names.contains(candidate))
+		.unwrap()
+}
+
+async fn collect_upload(
+	req: &HttpRequest,
+	mut payload: Payload,
+) -> Result<CreateFileDTO, AppError> {
+	let MultipartForm(form) = MultipartForm::<CreateFileDTO>::from_request(req, &mut payload)
+		.await
+		.map_err(|err| {
+			AppError::BadRequest(AppErrorValue {
+				message: format!("Invalid upload: {err}"),
+				status: StatusCode::BAD_REQUEST.as_u16(),
+				code: "INVALID_UPLOAD".to_owned(),
+				..Default::default()
+			})
+		})?;
+
+	Ok(form)
+}
+
+// Applies the size limit while the upload comes in, so an oversized file never fills the disk.
+// Bodies that announce their size are refused up front, the others are cut off once they go over.
+pub async fn receive_upload(
+	req: &HttpRequest,
+	payload: web::Payload,
+	policy: &UploadPolicy,
+) -> Result<CreateFileDTO, AppError> {
+	let Some(max_file_size) = policy.max_file_size else {
+		return collect_upload(req, payload.into_inner()).await;
+	};
+	let max_body_size = max_file_size.saturating_add(MULTIPART_OVERHEAD);
+
+	let content_length = req
+		.headers()
+		.get(header::CONTENT_LENGTH)
+		.and_then(|value| value.to_str().ok())
+		.and_then(|value| value.parse::<usize>().ok());
+	if content_length.is_some_and(|content_length| content_length > max_body_size) {
+		return Err(upload_too_large_error(max_file_size));
+	}
+
+	let received = Rc::new(Cell::new(0));
+	let counter = received.clone();
+	let limited_payload: Pin<Box<dyn Stream<Item = Result<Bytes, PayloadError>>>> =
+		Box::pin(payload.map(move |chunk| {
+			let chunk = chunk?;
+			counter.set(counter.get() + chunk.len());
+			match counter.get() > max_body_size {
+				true => Err(PayloadError::Overflow),
+				false => Ok(chunk),
+			}
+		}));
+
+	collect_upload(req, Payload::from(limited_payload))
+		.await
+		.map_err(|err| match received.get() > max_body_size {
+			true => upload_too_large_error(max_file_size),
+			false => err,
+		})
+}
+
+// The size was limited while receiving, checking the written file as well catches what the overhead let through
+pub async fn enforce_upload_policy(
+	engine: &dyn StorageEngine,
+	policy: &UploadPolicy,
+	path: &str,
+	file: &mut TempFile,
+) -> Result<(), AppError> {
+	let file_name = get_upload_file_name(file)?;
+
+	if let Some(max_file_size) = policy.max_file_size {
+		if file.size > max_file_size {
+			return Err(policy_violation(
+				format!(
+					"File size of {} bytes exceeds the maximum of {} bytes",
+					file.size, max_file_size
+				),
+				"UPLOAD_FILE_TOO_LARGE",
+			));
+		}
+	}
+
+	if !policy.allowed_extensions.is_empty() {
+		let extension = Path::new(&file_name)
+			.extension()
+			.and_then(|extension| extension.to_str())
+			.map(|extension| extension.to_lowercase());
+
+		let is_allowed = extension.as_ref().is_some_and(|extension| {
+			policy.allowed_extensions.iter().any(|allowed_extension| {
+				allowed_extension
+					.trim()
+					.trim_start_matches('.')
+					.to_lowercase() == *extension
+			})
+		});
+
+		if !is_allowed {
+			return Err(policy_violation(
+				format!("File extension of {file_name} is not allowed"),
+				"UPLOAD_EXTENSION_NOT_ALLOWED",
+			));
+		}
+	}
+
+	if !policy.allowed_mime_types.is_empty() {
+		// Both the mime type sent by the client and the one guessed from the extension have to pass
+		let mime_types = vec![
+			file.content_type
+				.as_ref()
+				.map(|mime_type| mime_type.essence_str().to_owned()),
+			mime_guess::from_path(&file_name)
+				.first()
+				.map(|mime_type| mime_type.essence_str().to_owned()),
+		]
+		.into_iter()
+		.flatten()
+		.collect::<Vec<String>>();
+
+		let is_allowed = !mime_types.is_empty()
+			&& mime_types.iter().all(|mime_type| {
+				policy
+					.allowed_mime_types
+					.iter()
+					.any(|allowed_mime_type| matches_mime_type(allowed_mime_type, mime_type))
+			});
+
+		if !is_allowed {
+			return Err(policy_violation(
+				format!("Mime type of {file_name} is not allowed"),
+				"UPLOAD_MIME_TYPE_NOT_ALLOWED",
+			));
+		}
+	}
+
+	if policy.overwrite == OverwriteBehaviourEnum::REPLACE {
+		return Ok(());
+	}
+
+	let (existing_resources, _) = engine.find_all(path).await?;
+	let existing_names = existing_resources
+		.into_iter()
+		.map(|resource| resource.name)
+		.collect::<HashSet<String>>();
+
+	if !existing_names.contains(&file_name) {
+		return Ok(());
+	}
+
+	match policy.overwrite {
+		OverwriteBehaviourEnum::REJECT => Err(policy_violation(
+			format!("A file named {file_name} already exists"),
+			"UPLOAD_FILE_EXISTS",
+		)),
+		OverwriteBehaviourEnum::RENAME => {
+			file.file_name = Some(get_renamed_file_name(&file_name, &existing_names));
+			Ok(())
+		}
+		OverwriteBehaviourEnum::REPLACE => Ok(()),
+	}
+}
@@ -1,4 +1,6 @@
+pub mod actors;
 pub mod controllers;
 pub mod dto;
 pub mod engines;
+pub mod helpers;
 pub mod models;
@@ -1 +1,3 @@
+pub mod storage_migration;
+pub mod storage_migration_item;
 pub mod storage_repository;
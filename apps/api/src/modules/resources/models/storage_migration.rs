@@ -0,0 +1,228 @@
+use std::io::Write;
+
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::{Pg, PgValue};
+use diesel::prelude::*;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::{AsExpression, FromSqlRow};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::schema::{sql_types::StorageMigrationStatuses, storage_migrations};
+
+// A running migration touches `updated_at` with every item, one that stops doing so has lost its worker
+const STALE_MIGRATION_MINUTES: i64 = 15;
+
+#[derive(
+	Debug, PartialEq, FromSqlRow, AsExpression, Eq, Clone, Deserialize, Serialize, ToSchema,
+)]
+#[diesel(sql_type = StorageMigrationStatuses)]
+pub enum StorageMigrationStatusEnum {
+	PENDING,
+	RUNNING,
+	COMPLETED,
+	FAILED,
+}
+
+impl ToSql<StorageMigrationStatuses, Pg> for StorageMigrationStatusEnum {
+	fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+		match *self {
+			StorageMigrationStatusEnum::PENDING => out.write_all(b"PENDING")?,
+			StorageMigrationStatusEnum::RUNNING => out.write_all(b"RUNNING")?,
+			StorageMigrationStatusEnum::COMPLETED => out.write_all(b"COMPLETED")?,
+			StorageMigrationStatusEnum::FAILED => out.write_all(b"FAILED")?,
+		}
+		Ok(IsNull::No)
+	}
+}
+
+impl FromSql<StorageMigrationStatuses, Pg> for StorageMigrationStatusEnum {
+	fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+		match bytes.as_bytes() {
+			b"PENDING" => Ok(StorageMigrationStatusEnum::PENDING),
+			b"RUNNING" => Ok(StorageMigrationStatusEnum::RUNNING),
+			b"COMPLETED" => Ok(StorageMigrationStatusEnum::COMPLETED),
+			b"FAILED" => Ok(StorageMigrationStatusEnum::FAILED),
+			_ => Err("Unrecognized enum variant".into()),
+		}
+	}
+}
+
+#[derive(Identifiable, Selectable, Queryable, Debug, Clone)]
+#[diesel(table_name = storage_migrations)]
+#[diesel(primary_key(id))]
+pub struct StorageMigration {
+	pub id: Uuid,
+	pub site_id: Uuid,
+	pub source_storage_repository_id: Uuid,
+	pub target_storage_repository_id: Uuid,
+	pub status: StorageMigrationStatusEnum,
+	pub dry_run: bool,
+	pub rewrite_content_references: bool,
+	pub total_items: i32,
+	pub processed_items: i32,
+	pub failed_items: i32,
+	pub last_error: Option<String>,
+	pub created_at: NaiveDateTime,
+	pub updated_at: NaiveDateTime,
+}
+
+impl StorageMigration {
+	#[instrument(skip(conn))]
+	pub fn create(
+		conn: &mut PgConnection,
+		storage_migration: CreateStorageMigration,
+	) -> Result<Self, AppError> {
+		let created_storage_migration = diesel::insert_into(storage_migrations::table)
+			.values(storage_migration)
+			.returning(StorageMigration::as_returning())
+			.get_result(conn)?;
+
+		Ok(created_storage_migration)
+	}
+
+	#[instrument(skip(conn))]
+	pub fn find_one(conn: &mut PgConnection, site_id: Uuid, id: Uuid) -> Result<Self, AppError> {
+		let storage_migration = storage_migrations::table
+			.filter(storage_migrations::site_id.eq(site_id))
+			.find(id)
+			.first::<Self>(conn)?;
+
+		Ok(storage_migration)
+	}
+
+	#[instrument(skip(conn))]
+	pub fn find(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		page: i64,
+		pagesize: i64,
+	) -> Result<(Vec<Self>, i64), AppError> {
+		let query = {
+			let mut query = storage_migrations::table
+				.filter(storage_migrations::site_id.eq(site_id))
+				.order(storage_migrations::created_at.desc())
+				.into_boxed();
+
+			if pagesize != -1 {
+				query = query.offset((page - 1) * pagesize).limit(pagesize);
+			};
+
+			query
+		};
+
+		let storage_migrations = query
+			.select(StorageMigration::as_select())
+			.load::<StorageMigration>(conn)?;
+		let total_elements = storage_migrations::table
+			.filter(storage_migrations::site_id.eq(site_id))
+			.count()
+			.get_result::<i64>(conn)?;
+
+		Ok((storage_migrations, total_elements))
+	}
+
+	#[instrument(skip(conn))]
+	pub fn update(
+		conn: &mut PgConnection,
+		id: Uuid,
+		changeset: UpdateStorageMigration,
+	) -> Result<Self, AppError> {
+		let target = storage_migrations::table.find(id);
+		let updated_storage_migration = diesel::update(target)
+			.set(changeset)
+			.returning(Self::as_returning())
+			.get_result::<Self>(conn)?;
+
+		Ok(updated_storage_migration)
+	}
+
+	// Failed migrations and queued or running ones whose worker died can be picked up again. The status is
+	// checked in the update itself, so two resumes at the same time can't both start a worker.
+	#[instrument(skip(conn))]
+	pub fn claim_for_resume(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		id: Uuid,
+	) -> Result<Option<Self>, AppError> {
+		let now = Utc::now().naive_utc();
+		let stale_before = now - Duration::minutes(STALE_MIGRATION_MINUTES);
+		let target = storage_migrations::table
+			.filter(storage_migrations::site_id.eq(site_id))
+			.filter(storage_migrations::id.eq(id))
+			.filter(
+				storage_migrations::status
+					.eq(StorageMigrationStatusEnum::FAILED)
+					.or(storage_migrations::status
+						.eq_any(vec![
+							StorageMigrationStatusEnum::PENDING,
+							StorageMigrationStatusEnum::RUNNING,
+						])
+						.and(storage_migrations::updated_at.lt(stale_before))),
+			);
+
+		let storage_migration = diesel::update(target)
+			.set((
+				storage_migrations::status.eq(StorageMigrationStatusEnum::PENDING),
+				storage_migrations::failed_items.eq(0),
+				storage_migrations::updated_at.eq(now),
+			))
+			.returning(Self::as_returning())
+			.get_result::<Self>(conn)
+			.optional()?;
+
+		Ok(storage_migration)
+	}
+
+	#[instrument(skip(conn))]
+	pub fn increment_progress(
+		conn: &mut PgConnection,
+		id: Uuid,
+		failed: bool,
+	) -> Result<(), AppError> {
+		let target = storage_migrations::table.find(id);
+
+		if failed {
+			diesel::update(target)
+				.set((
+					storage_migrations::failed_items.eq(storage_migrations::failed_items + 1),
+					storage_migrations::updated_at.eq(Utc::now().naive_utc()),
+				))
+				.execute(conn)?;
+		} else {
+			diesel::update(target)
+				.set((
+					storage_migrations::processed_items.eq(storage_migrations::processed_items + 1),
+					storage_migrations::updated_at.eq(Utc::now().naive_utc()),
+				))
+				.execute(conn)?;
+		}
+
+		Ok(())
+	}
+}
+
+#[derive(Insertable, Debug, Deserialize)]
+#[diesel(table_name = storage_migrations)]
+pub struct CreateStorageMigration {
+	pub site_id: Uuid,
+	pub source_storage_repository_id: Uuid,
+	pub target_storage_repository_id: Uuid,
+	pub dry_run: bool,
+	pub rewrite_content_references: bool,
+}
+
+#[derive(AsChangeset, Debug, Deserialize, Default)]
+#[diesel(table_name = storage_migrations)]
+pub struct UpdateStorageMigration {
+	pub status: Option<StorageMigrationStatusEnum>,
+	pub total_items: Option<i32>,
+	pub processed_items: Option<i32>,
+	pub failed_items: Option<i32>,
+	pub last_error: Option<Option<String>>,
+	pub updated_at: Option<NaiveDateTime>,
+}
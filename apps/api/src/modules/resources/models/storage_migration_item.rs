@@ -0,0 +1,122 @@
+use diesel::prelude::*;
+use serde::Deserialize;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::schema::storage_migration_items;
+
+use super::storage_migration::StorageMigrationStatusEnum;
+
+#[derive(Identifiable, Selectable, Queryable, Debug, Clone)]
+#[diesel(table_name = storage_migration_items)]
+#[diesel(primary_key(id))]
+pub struct StorageMigrationItem {
+	pub id: Uuid,
+	pub storage_migration_id: Uuid,
+	pub path: String,
+	pub kind: String,
+	pub status: StorageMigrationStatusEnum,
+	pub error: Option<String>,
+}
+
+impl StorageMigrationItem {
+	#[instrument(skip(conn, items))]
+	pub fn create_many(
+		conn: &mut PgConnection,
+		items: Vec<CreateStorageMigrationItem>,
+	) -> Result<usize, AppError> {
+		let inserted_rows = diesel::insert_into(storage_migration_items::table)
+			.values(items)
+			.on_conflict_do_nothing()
+			.execute(conn)?;
+
+		Ok(inserted_rows)
+	}
+
+	#[instrument(skip(conn))]
+	pub fn find(
+		conn: &mut PgConnection,
+		storage_migration_id: Uuid,
+		page: i64,
+		pagesize: i64,
+	) -> Result<(Vec<Self>, i64), AppError> {
+		let query = {
+			let mut query = storage_migration_items::table
+				.filter(storage_migration_items::storage_migration_id.eq(storage_migration_id))
+				.order(storage_migration_items::path.asc())
+				.into_boxed();
+
+			if pagesize != -1 {
+				query = query.offset((page - 1) * pagesize).limit(pagesize);
+			};
+
+			query
+		};
+
+		let items = query
+			.select(StorageMigrationItem::as_select())
+			.load::<StorageMigrationItem>(conn)?;
+		let total_elements = storage_migration_items::table
+			.filter(storage_migration_items::storage_migration_id.eq(storage_migration_id))
+			.count()
+			.get_result::<i64>(conn)?;
+
+		Ok((items, total_elements))
+	}
+
+	#[instrument(skip(conn))]
+	pub fn find_unprocessed(
+		conn: &mut PgConnection,
+		storage_migration_id: Uuid,
+	) -> Result<Vec<Self>, AppError> {
+		let items = storage_migration_items::table
+			.filter(storage_migration_items::storage_migration_id.eq(storage_migration_id))
+			.filter(storage_migration_items::status.ne(StorageMigrationStatusEnum::COMPLETED))
+			.order(storage_migration_items::path.asc())
+			.select(StorageMigrationItem::as_select())
+			.load::<StorageMigrationItem>(conn)?;
+
+		Ok(items)
+	}
+
+	#[instrument(skip(conn))]
+	pub fn update_status(
+		conn: &mut PgConnection,
+		id: Uuid,
+		status: StorageMigrationStatusEnum,
+		error: Option<String>,
+	) -> Result<(), AppError> {
+		diesel::update(storage_migration_items::table.find(id))
+			.set((
+				storage_migration_items::status.eq(status),
+				storage_migration_items::error.eq(error),
+			))
+			.execute(conn)?;
+
+		Ok(())
+	}
+
+	#[instrument(skip(conn))]
+	pub fn count_by_status(
+		conn: &mut PgConnection,
+		storage_migration_id: Uuid,
+		status: StorageMigrationStatusEnum,
+	) -> Result<i64, AppError> {
+		let count = storage_migration_items::table
+			.filter(storage_migration_items::storage_migration_id.eq(storage_migration_id))
+			.filter(storage_migration_items::status.eq(status))
+			.count()
+			.get_result::<i64>(conn)?;
+
+		Ok(count)
+	}
+}
+
+#[derive(Insertable, Debug, Deserialize)]
+#[diesel(table_name = storage_migration_items)]
+pub struct CreateStorageMigrationItem {
+	pub storage_migration_id: Uuid,
+	pub path: String,
+	pub kind: String,
+}
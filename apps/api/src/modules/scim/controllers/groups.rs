@@ -0,0 +1,208 @@
+use super::super::dto::{request, response};
+use super::super::services::groups;
+use crate::modules::core::middleware::state::AppState;
+use crate::modules::scim::helpers::error::{ScimResponse, SCIM_CONTENT_TYPE};
+use crate::modules::scim::helpers::pagination::get_list_range;
+use crate::modules::scim::services::filter::parse_filter;
+use actix_web::{delete, get, patch, post, put, web, HttpResponse};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindOnePathParams {
+	group_id: Uuid,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct FindAllQueryParams {
+	filter: Option<String>,
+	start_index: Option<i64>,
+	count: Option<i64>,
+	excluded_attributes: Option<String>,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct FindOneQueryParams {
+	excluded_attributes: Option<String>,
+}
+
+// Large groups are expensive to list, clients leave out the members when they only need the group itself
+fn include_members(excluded_attributes: &Option<String>) -> bool {
+	!excluded_attributes
+		.as_deref()
+		.unwrap_or_default()
+		.split(',')
+		.any(|attribute| attribute.trim().eq_ignore_ascii_case("members"))
+}
+
+#[utoipa::path(
+	context_path = "/scim/v2/Groups",
+	request_body = UpsertScimGroupDTO,
+	responses(
+		(status = 201, body = ScimGroupDTO),
+		(status = 401, body = ScimErrorDTO, description = "Unauthorized"),
+		(status = 409, body = ScimErrorDTO, description = "Conflict")
+	),
+	security(
+		("scim_token" = [])
+	)
+)]
+#[post("")]
+pub async fn create(
+	state: web::Data<AppState>,
+	form: web::Json<request::UpsertScimGroupDTO>,
+) -> ScimResponse {
+	let conn = &mut state.get_conn()?;
+	let group = groups::create(conn, &form)?;
+
+	let res = response::ScimGroupDTO::from(group);
+	Ok(HttpResponse::Created()
+		.content_type(SCIM_CONTENT_TYPE)
+		.json(res))
+}
+
+#[utoipa::path(
+	context_path = "/scim/v2/Groups",
+	responses(
+		(status = 200, body = ScimGroupsDTO),
+		(status = 400, body = ScimErrorDTO, description = "Invalid filter"),
+		(status = 401, body = ScimErrorDTO, description = "Unauthorized")
+	),
+	security(
+		("scim_token" = [])
+	),
+	params(FindAllQueryParams)
+)]
+#[get("")]
+pub async fn find_all(
+	state: web::Data<AppState>,
+	query: web::Query<FindAllQueryParams>,
+) -> ScimResponse {
+	let conn = &mut state.get_conn()?;
+	let filters = query
+		.filter
+		.as_deref()
+		.map(parse_filter)
+		.transpose()?
+		.unwrap_or_default();
+	let (start_index, count) = get_list_range(query.start_index, query.count);
+
+	let (groups, total_results) = groups::find(
+		conn,
+		&filters,
+		start_index,
+		count,
+		include_members(&query.excluded_attributes),
+	)?;
+
+	let res = response::ScimGroupsDTO::from((groups, total_results, start_index));
+	Ok(HttpResponse::Ok().content_type(SCIM_CONTENT_TYPE).json(res))
+}
+
+#[utoipa::path(
+	context_path = "/scim/v2/Groups",
+	responses(
+		(status = 200, body = ScimGroupDTO),
+		(status = 401, body = ScimErrorDTO, description = "Unauthorized"),
+		(status = 404, body = ScimErrorDTO, description = "Not Found")
+	),
+	security(
+		("scim_token" = [])
+	),
+	params(FindOnePathParams, FindOneQueryParams)
+)]
+#[get("/{group_id}")]
+pub async fn find_one(
+	state: web::Data<AppState>,
+	query: web::Query<FindOneQueryParams>,
+	params: web::Path<FindOnePathParams>,
+) -> ScimResponse {
+	let conn = &mut state.get_conn()?;
+	let group = groups::find_one(
+		conn,
+		params.group_id,
+		include_members(&query.excluded_attributes),
+	)?;
+
+	let res = response::ScimGroupDTO::from(group);
+	Ok(HttpResponse::Ok().content_type(SCIM_CONTENT_TYPE).json(res))
+}
+
+#[utoipa::path(
+	context_path = "/scim/v2/Groups",
+	request_body = UpsertScimGroupDTO,
+	responses(
+		(status = 200, body = ScimGroupDTO),
+		(status = 401, body = ScimErrorDTO, description = "Unauthorized"),
+		(status = 404, body = ScimErrorDTO, description = "Not Found")
+	),
+	security(
+		("scim_token" = [])
+	),
+	params(FindOnePathParams)
+)]
+#[put("/{group_id}")]
+pub async fn replace(
+	state: web::Data<AppState>,
+	form: web::Json<request::UpsertScimGroupDTO>,
+	params: web::Path<FindOnePathParams>,
+) -> ScimResponse {
+	let conn = &mut state.get_conn()?;
+	let group = groups::replace(conn, params.group_id, &form)?;
+
+	let res = response::ScimGroupDTO::from(group);
+	Ok(HttpResponse::Ok().content_type(SCIM_CONTENT_TYPE).json(res))
+}
+
+#[utoipa::path(
+	context_path = "/scim/v2/Groups",
+	request_body = ScimPatchDTO,
+	responses(
+		(status = 200, body = ScimGroupDTO),
+		(status = 400, body = ScimErrorDTO, description = "Invalid patch"),
+		(status = 401, body = ScimErrorDTO, description = "Unauthorized"),
+		(status = 404, body = ScimErrorDTO, description = "Not Found")
+	),
+	security(
+		("scim_token" = [])
+	),
+	params(FindOnePathParams)
+)]
+#[patch("/{group_id}")]
+pub async fn update(
+	state: web::Data<AppState>,
+	form: web::Json<request::ScimPatchDTO>,
+	params: web::Path<FindOnePathParams>,
+) -> ScimResponse {
+	let conn = &mut state.get_conn()?;
+	let group = groups::patch(conn, params.group_id, &form)?;
+
+	let res = response::ScimGroupDTO::from(group);
+	Ok(HttpResponse::Ok().content_type(SCIM_CONTENT_TYPE).json(res))
+}
+
+#[utoipa::path(
+	context_path = "/scim/v2/Groups",
+	responses(
+		(status = 204),
+		(status = 401, body = ScimErrorDTO, description = "Unauthorized"),
+		(status = 404, body = ScimErrorDTO, description = "Not Found")
+	),
+	security(
+		("scim_token" = [])
+	),
+	params(FindOnePathParams)
+)]
+#[delete("/{group_id}")]
+pub async fn remove(
+	state: web::Data<AppState>,
+	params: web::Path<FindOnePathParams>,
+) -> ScimResponse {
+	let conn = &mut state.get_conn()?;
+	groups::remove(conn, params.group_id)?;
+
+	Ok(HttpResponse::NoContent().body(()))
+}
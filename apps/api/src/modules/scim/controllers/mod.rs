@@ -0,0 +1,3 @@
+pub mod groups;
+pub mod service_provider_config;
+pub mod users;
@@ -0,0 +1,35 @@
+use crate::constants::scim_schema;
+use crate::modules::scim::helpers::error::{ScimResponse, SCIM_CONTENT_TYPE};
+use actix_web::{get, HttpResponse};
+use serde_json::json;
+
+#[utoipa::path(
+	context_path = "/scim/v2/ServiceProviderConfig",
+	responses(
+		(status = 200, description = "Features of the SCIM implementation"),
+		(status = 401, body = ScimErrorDTO, description = "Unauthorized")
+	),
+	security(
+		("scim_token" = [])
+	)
+)]
+#[get("")]
+pub async fn find_one() -> ScimResponse {
+	let res = json!({
+		"schemas": [scim_schema::SERVICE_PROVIDER_CONFIG],
+		"patch": { "supported": true },
+		"bulk": { "supported": false, "maxOperations": 0, "maxPayloadSize": 0 },
+		"filter": { "supported": true, "maxResults": 1000 },
+		"changePassword": { "supported": false },
+		"sort": { "supported": false },
+		"etag": { "supported": false },
+		"authenticationSchemes": [{
+			"type": "oauthbearertoken",
+			"name": "OAuth Bearer Token",
+			"description": "Authentication with the token configured in SCIM_TOKEN",
+			"primary": true
+		}]
+	});
+
+	Ok(HttpResponse::Ok().content_type(SCIM_CONTENT_TYPE).json(res))
+}
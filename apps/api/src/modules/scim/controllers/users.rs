@@ -0,0 +1,181 @@
+use super::super::dto::{request, response};
+use super::super::services::users;
+use crate::modules::core::middleware::state::AppState;
+use crate::modules::scim::helpers::error::{ScimResponse, SCIM_CONTENT_TYPE};
+use crate::modules::scim::helpers::pagination::get_list_range;
+use crate::modules::scim::services::filter::parse_filter;
+use actix_web::{delete, get, patch, post, put, web, HttpResponse};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindOnePathParams {
+	user_id: Uuid,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct FindAllQueryParams {
+	filter: Option<String>,
+	start_index: Option<i64>,
+	count: Option<i64>,
+}
+
+#[utoipa::path(
+	context_path = "/scim/v2/Users",
+	request_body = UpsertScimUserDTO,
+	responses(
+		(status = 201, body = ScimUserDTO),
+		(status = 401, body = ScimErrorDTO, description = "Unauthorized"),
+		(status = 409, body = ScimErrorDTO, description = "Conflict")
+	),
+	security(
+		("scim_token" = [])
+	)
+)]
+#[post("")]
+pub async fn create(
+	state: web::Data<AppState>,
+	form: web::Json<request::UpsertScimUserDTO>,
+) -> ScimResponse {
+	let conn = &mut state.get_conn()?;
+	let user = users::create(conn, &form).await?;
+
+	let res = response::ScimUserDTO::from(user);
+	Ok(HttpResponse::Created()
+		.content_type(SCIM_CONTENT_TYPE)
+		.json(res))
+}
+
+#[utoipa::path(
+	context_path = "/scim/v2/Users",
+	responses(
+		(status = 200, body = ScimUsersDTO),
+		(status = 400, body = ScimErrorDTO, description = "Invalid filter"),
+		(status = 401, body = ScimErrorDTO, description = "Unauthorized")
+	),
+	security(
+		("scim_token" = [])
+	),
+	params(FindAllQueryParams)
+)]
+#[get("")]
+pub async fn find_all(
+	state: web::Data<AppState>,
+	query: web::Query<FindAllQueryParams>,
+) -> ScimResponse {
+	let conn = &mut state.get_conn()?;
+	let filters = query
+		.filter
+		.as_deref()
+		.map(parse_filter)
+		.transpose()?
+		.unwrap_or_default();
+	let (start_index, count) = get_list_range(query.start_index, query.count);
+
+	let (users, total_results) = users::find(conn, &filters, start_index, count)?;
+
+	let res = response::ScimUsersDTO::from((users, total_results, start_index));
+	Ok(HttpResponse::Ok().content_type(SCIM_CONTENT_TYPE).json(res))
+}
+
+#[utoipa::path(
+	context_path = "/scim/v2/Users",
+	responses(
+		(status = 200, body = ScimUserDTO),
+		(status = 401, body = ScimErrorDTO, description = "Unauthorized"),
+		(status = 404, body = ScimErrorDTO, description = "Not Found")
+	),
+	security(
+		("scim_token" = [])
+	),
+	params(FindOnePathParams)
+)]
+#[get("/{user_id}")]
+pub async fn find_one(
+	state: web::Data<AppState>,
+	params: web::Path<FindOnePathParams>,
+) -> ScimResponse {
+	let conn = &mut state.get_conn()?;
+	let user = users::find_one(conn, params.user_id)?;
+
+	let res = response::ScimUserDTO::from(user);
+	Ok(HttpResponse::Ok().content_type(SCIM_CONTENT_TYPE).json(res))
+}
+
+#[utoipa::path(
+	context_path = "/scim/v2/Users",
+	request_body = UpsertScimUserDTO,
+	responses(
+		(status = 200, body = ScimUserDTO),
+		(status = 401, body = ScimErrorDTO, description = "Unauthorized"),
+		(status = 404, body = ScimErrorDTO, description = "Not Found")
+	),
+	security(
+		("scim_token" = [])
+	),
+	params(FindOnePathParams)
+)]
+#[put("/{user_id}")]
+pub async fn replace(
+	state: web::Data<AppState>,
+	form: web::Json<request::UpsertScimUserDTO>,
+	params: web::Path<FindOnePathParams>,
+) -> ScimResponse {
+	let conn = &mut state.get_conn()?;
+	let user = users::replace(conn, params.user_id, &form)?;
+
+	let res = response::ScimUserDTO::from(user);
+	Ok(HttpResponse::Ok().content_type(SCIM_CONTENT_TYPE).json(res))
+}
+
+#[utoipa::path(
+	context_path = "/scim/v2/Users",
+	request_body = ScimPatchDTO,
+	responses(
+		(status = 200, body = ScimUserDTO),
+		(status = 400, body = ScimErrorDTO, description = "Invalid patch"),
+		(status = 401, body = ScimErrorDTO, description = "Unauthorized"),
+		(status = 404, body = ScimErrorDTO, description = "Not Found")
+	),
+	security(
+		("scim_token" = [])
+	),
+	params(FindOnePathParams)
+)]
+#[patch("/{user_id}")]
+pub async fn update(
+	state: web::Data<AppState>,
+	form: web::Json<request::ScimPatchDTO>,
+	params: web::Path<FindOnePathParams>,
+) -> ScimResponse {
+	let conn = &mut state.get_conn()?;
+	let user = users::patch(conn, params.user_id, &form)?;
+
+	let res = response::ScimUserDTO::from(user);
+	Ok(HttpResponse::Ok().content_type(SCIM_CONTENT_TYPE).json(res))
+}
+
+#[utoipa::path(
+	context_path = "/scim/v2/Users",
+	responses(
+		(status = 204),
+		(status = 401, body = ScimErrorDTO, description = "Unauthorized"),
+		(status = 404, body = ScimErrorDTO, description = "Not Found")
+	),
+	security(
+		("scim_token" = [])
+	),
+	params(FindOnePathParams)
+)]
+#[delete("/{user_id}")]
+pub async fn remove(
+	state: web::Data<AppState>,
+	params: web::Path<FindOnePathParams>,
+) -> ScimResponse {
+	let conn = &mut state.get_conn()?;
+	users::remove(conn, params.user_id)?;
+
+	Ok(HttpResponse::NoContent().body(()))
+}
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertScimNameDTO {
+	pub formatted: Option<String>,
+	pub given_name: Option<String>,
+	pub family_name: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct UpsertScimEmailDTO {
+	pub value: String,
+	pub primary: Option<bool>,
+}
+
+// Attributes we don't store, like `externalId` or `title`, are accepted and ignored
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertScimUserDTO {
+	pub user_name: String,
+	pub name: Option<UpsertScimNameDTO>,
+	pub display_name: Option<String>,
+	pub emails: Option<Vec<UpsertScimEmailDTO>>,
+	pub active: Option<bool>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct UpsertScimMemberDTO {
+	pub value: Uuid,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpsertScimGroupDTO {
+	pub display_name: String,
+	pub members: Option<Vec<UpsertScimMemberDTO>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct ScimPatchOperationDTO {
+	pub op: String,
+	pub path: Option<String>,
+	pub value: Option<Value>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct ScimPatchDTO {
+	pub schemas: Vec<String>,
+	#[serde(rename = "Operations")]
+	pub operations: Vec<ScimPatchOperationDTO>,
+}
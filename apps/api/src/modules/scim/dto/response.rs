@@ -0,0 +1,182 @@
+use crate::constants::scim_schema;
+use crate::modules::{roles::models::role::Role, users::models::user::User};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::convert::From;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimMetaDTO {
+	pub resource_type: String,
+	pub created: NaiveDateTime,
+	pub last_modified: NaiveDateTime,
+	pub location: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct ScimNameDTO {
+	pub formatted: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct ScimEmailDTO {
+	pub value: String,
+	pub primary: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct ScimReferenceDTO {
+	pub value: Uuid,
+	pub display: String,
+	#[serde(rename = "$ref")]
+	pub reference: String,
+}
+
+impl From<&Role> for ScimReferenceDTO {
+	fn from(role: &Role) -> Self {
+		Self {
+			value: role.id,
+			display: role.name.clone(),
+			reference: format!("/scim/v2/Groups/{}", role.id),
+		}
+	}
+}
+
+impl From<&User> for ScimReferenceDTO {
+	fn from(user: &User) -> Self {
+		Self {
+			value: user.id,
+			display: user.name.clone(),
+			reference: format!("/scim/v2/Users/{}", user.id),
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimUserDTO {
+	pub schemas: Vec<String>,
+	pub id: Uuid,
+	pub user_name: String,
+	pub name: ScimNameDTO,
+	pub display_name: String,
+	pub emails: Vec<ScimEmailDTO>,
+	pub active: bool,
+	pub groups: Vec<ScimReferenceDTO>,
+	pub meta: ScimMetaDTO,
+}
+
+impl From<(User, Vec<Role>)> for ScimUserDTO {
+	fn from((user, roles): (User, Vec<Role>)) -> Self {
+		Self {
+			schemas: vec![scim_schema::USER.to_owned()],
+			id: user.id,
+			user_name: user.email.clone(),
+			name: ScimNameDTO {
+				formatted: user.name.clone(),
+			},
+			display_name: user.name,
+			emails: vec![ScimEmailDTO {
+				value: user.email,
+				primary: true,
+			}],
+			active: user.deactivated_at.is_none(),
+			groups: roles.iter().map(ScimReferenceDTO::from).collect(),
+			meta: ScimMetaDTO {
+				resource_type: "User".to_owned(),
+				created: user.created_at,
+				last_modified: user.updated_at,
+				location: format!("/scim/v2/Users/{}", user.id),
+			},
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimGroupDTO {
+	pub schemas: Vec<String>,
+	pub id: Uuid,
+	pub display_name: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub members: Option<Vec<ScimReferenceDTO>>,
+	pub meta: ScimMetaDTO,
+}
+
+// Members are left out when the client asked to exclude them
+impl From<(Role, Option<Vec<User>>)> for ScimGroupDTO {
+	fn from((role, members): (Role, Option<Vec<User>>)) -> Self {
+		Self {
+			schemas: vec![scim_schema::GROUP.to_owned()],
+			id: role.id,
+			display_name: role.name,
+			members: members.map(|members| members.iter().map(ScimReferenceDTO::from).collect()),
+			meta: ScimMetaDTO {
+				resource_type: "Group".to_owned(),
+				created: role.created_at,
+				last_modified: role.updated_at,
+				location: format!("/scim/v2/Groups/{}", role.id),
+			},
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimUsersDTO {
+	pub schemas: Vec<String>,
+	pub total_results: i64,
+	pub start_index: i64,
+	pub items_per_page: i64,
+	#[serde(rename = "Resources")]
+	pub resources: Vec<ScimUserDTO>,
+}
+
+impl From<(Vec<(User, Vec<Role>)>, i64, i64)> for ScimUsersDTO {
+	fn from((users, total_results, start_index): (Vec<(User, Vec<Role>)>, i64, i64)) -> Self {
+		Self {
+			schemas: vec![scim_schema::LIST_RESPONSE.to_owned()],
+			total_results,
+			start_index,
+			items_per_page: users.len() as i64,
+			resources: users.into_iter().map(ScimUserDTO::from).collect(),
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimGroupsDTO {
+	pub schemas: Vec<String>,
+	pub total_results: i64,
+	pub start_index: i64,
+	pub items_per_page: i64,
+	#[serde(rename = "Resources")]
+	pub resources: Vec<ScimGroupDTO>,
+}
+
+impl From<(Vec<(Role, Option<Vec<User>>)>, i64, i64)> for ScimGroupsDTO {
+	fn from(
+		(groups, total_results, start_index): (Vec<(Role, Option<Vec<User>>)>, i64, i64),
+	) -> Self {
+		Self {
+			schemas: vec![scim_schema::LIST_RESPONSE.to_owned()],
+			total_results,
+			start_index,
+			items_per_page: groups.len() as i64,
+			resources: groups.into_iter().map(ScimGroupDTO::from).collect(),
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimErrorDTO {
+	pub schemas: Vec<String>,
+	pub status: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub scim_type: Option<String>,
+	pub detail: String,
+}
@@ -0,0 +1,81 @@
+use crate::constants::scim_schema;
+use crate::errors::AppError;
+use crate::modules::scim::dto::response::ScimErrorDTO;
+use actix_web::http::StatusCode;
+use actix_web::{HttpResponse, ResponseError};
+use core::fmt;
+
+pub const SCIM_CONTENT_TYPE: &str = "application/scim+json";
+
+pub type ScimResponse = Result<HttpResponse, ScimError>;
+
+// Provisioning clients expect the error format of RFC 7644 instead of the one of the admin API
+#[derive(Debug)]
+pub struct ScimError {
+	status: StatusCode,
+	scim_type: Option<&'static str>,
+	detail: String,
+}
+
+impl ScimError {
+	pub fn bad_request(scim_type: &'static str, detail: &str) -> Self {
+		ScimError {
+			status: StatusCode::BAD_REQUEST,
+			scim_type: Some(scim_type),
+			detail: detail.to_owned(),
+		}
+	}
+
+	pub fn conflict(detail: &str) -> Self {
+		ScimError {
+			status: StatusCode::CONFLICT,
+			scim_type: Some("uniqueness"),
+			detail: detail.to_owned(),
+		}
+	}
+}
+
+impl fmt::Display for ScimError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.detail)
+	}
+}
+
+impl From<AppError> for ScimError {
+	fn from(err: AppError) -> Self {
+		let status = err.status_code();
+		let value = err.value();
+
+		match value.code.as_str() {
+			"DB_UNIQUE_VIOLATION" => ScimError::conflict(&value.message),
+			_ => ScimError {
+				status,
+				scim_type: None,
+				detail: value.message.clone(),
+			},
+		}
+	}
+}
+
+impl From<diesel::result::Error> for ScimError {
+	fn from(err: diesel::result::Error) -> Self {
+		AppError::from(err).into()
+	}
+}
+
+impl ResponseError for ScimError {
+	fn error_response(&self) -> HttpResponse {
+		HttpResponse::build(self.status)
+			.content_type(SCIM_CONTENT_TYPE)
+			.json(ScimErrorDTO {
+				schemas: vec![scim_schema::ERROR.to_owned()],
+				status: self.status.as_u16().to_string(),
+				scim_type: self.scim_type.map(|scim_type| scim_type.to_owned()),
+				detail: self.detail.clone(),
+			})
+	}
+
+	fn status_code(&self) -> StatusCode {
+		self.status
+	}
+}
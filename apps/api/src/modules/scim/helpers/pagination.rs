@@ -0,0 +1,10 @@
+const DEFAULT_COUNT: i64 = 100;
+const MAX_COUNT: i64 = 1000;
+
+// SCIM pages with a 1-based `startIndex` and a `count`, out of range values are clamped instead of rejected
+pub fn get_list_range(start_index: Option<i64>, count: Option<i64>) -> (i64, i64) {
+	(
+		start_index.unwrap_or(1).max(1),
+		count.unwrap_or(DEFAULT_COUNT).clamp(0, MAX_COUNT),
+	)
+}
@@ -0,0 +1,4 @@
+pub mod controllers;
+pub mod dto;
+pub mod helpers;
+pub mod services;
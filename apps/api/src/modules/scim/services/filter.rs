@@ -0,0 +1,181 @@
+use crate::modules::scim::helpers::error::ScimError;
+use regex::Regex;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScimFilterOperator {
+	Eq,
+	Ne,
+	Co,
+	Sw,
+	Ew,
+	Pr,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScimFilter {
+	pub attribute: String,
+	pub operator: ScimFilterOperator,
+	pub value: String,
+}
+
+enum Token {
+	Word(String),
+	Text(String),
+}
+
+lazy_static! {
+	// `members[value eq "..."]` means the same as `members.value eq "..."` for the single comparisons we support
+	static ref VALUE_PATH: Regex =
+		Regex::new(r#"(\w+)\[\s*(\w+)\s+(\w+)\s+("(?:[^"\\]|\\.)*"|[^\s\]]+)\s*\]"#).unwrap();
+}
+
+pub fn invalid_filter_error(detail: &str) -> ScimError {
+	ScimError::bad_request("invalidFilter", detail)
+}
+
+fn escape_like(value: &str) -> String {
+	value
+		.replace('\\', "\\\\")
+		.replace('%', "\\%")
+		.replace('_', "\\_")
+}
+
+impl ScimFilter {
+	// Text attributes are compared case insensitively, the flag tells whether the pattern has to be negated
+	pub fn like_pattern(&self) -> (String, bool) {
+		let value = escape_like(&self.value);
+
+		match self.operator {
+			ScimFilterOperator::Eq => (value, false),
+			ScimFilterOperator::Ne => (value, true),
+			ScimFilterOperator::Co => (format!("%{}%", value), false),
+			ScimFilterOperator::Sw => (format!("{}%", value), false),
+			ScimFilterOperator::Ew => (format!("%{}", value), false),
+			ScimFilterOperator::Pr => ("%".to_owned(), false),
+		}
+	}
+
+	// Attributes that aren't text only support equality
+	pub fn is_equal(&self) -> Result<bool, ScimError> {
+		match self.operator {
+			ScimFilterOperator::Eq => Ok(true),
+			ScimFilterOperator::Ne => Ok(false),
+			_ => Err(invalid_filter_error(&format!(
+				"`{}` can only be compared with `eq` or `ne`",
+				self.attribute
+			))),
+		}
+	}
+}
+
+fn tokenize(filter: &str) -> Result<Vec<Token>, ScimError> {
+	let mut tokens = vec![];
+	let mut chars = filter.chars().peekable();
+
+	while let Some(c) = chars.next() {
+		if c.is_whitespace() {
+			continue;
+		}
+
+		if c == '"' {
+			let mut text = String::new();
+			let mut closed = false;
+
+			while let Some(c) = chars.next() {
+				match c {
+					'\\' => text.extend(chars.next()),
+					'"' => {
+						closed = true;
+						break;
+					}
+					_ => text.push(c),
+				}
+			}
+
+			if !closed {
+				return Err(invalid_filter_error(
+					"Filter contains an unterminated string",
+				));
+			}
+
+			tokens.push(Token::Text(text));
+			continue;
+		}
+
+		let mut word = String::from(c);
+		while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+			word.push(c);
+		}
+		tokens.push(Token::Word(word));
+	}
+
+	Ok(tokens)
+}
+
+fn parse_operator(operator: &str) -> Result<ScimFilterOperator, ScimError> {
+	match operator.to_lowercase().as_str() {
+		"eq" => Ok(ScimFilterOperator::Eq),
+		"ne" => Ok(ScimFilterOperator::Ne),
+		"co" => Ok(ScimFilterOperator::Co),
+		"sw" => Ok(ScimFilterOperator::Sw),
+		"ew" => Ok(ScimFilterOperator::Ew),
+		"pr" => Ok(ScimFilterOperator::Pr),
+		_ => Err(invalid_filter_error(&format!(
+			"Filter operator `{}` is not supported",
+			operator
+		))),
+	}
+}
+
+// Attribute names are case insensitive and may be prefixed with the URN of their schema
+fn normalize_attribute(attribute: &str) -> String {
+	attribute
+		.rsplit(':')
+		.next()
+		.unwrap_or(attribute)
+		.to_lowercase()
+}
+
+// Supports comparisons combined with `and`, which covers what provisioning clients send in practice
+pub fn parse_filter(filter: &str) -> Result<Vec<ScimFilter>, ScimError> {
+	let filter = VALUE_PATH.replace_all(filter, "$1.$2 $3 $4");
+	let mut tokens = tokenize(&filter)?.into_iter();
+	let mut filters = vec![];
+
+	loop {
+		let attribute = match tokens.next() {
+			Some(Token::Word(attribute)) => normalize_attribute(&attribute),
+			_ => return Err(invalid_filter_error("Expected an attribute name in filter")),
+		};
+		let operator = match tokens.next() {
+			Some(Token::Word(operator)) => parse_operator(&operator)?,
+			_ => return Err(invalid_filter_error("Expected an operator in filter")),
+		};
+		let value = match (operator, tokens.next()) {
+			(ScimFilterOperator::Pr, None) => String::new(),
+			(ScimFilterOperator::Pr, Some(_)) => {
+				return Err(invalid_filter_error("`pr` does not take a value"))
+			}
+			(_, Some(Token::Text(value))) | (_, Some(Token::Word(value))) => value,
+			(_, None) => return Err(invalid_filter_error("Expected a value in filter")),
+		};
+
+		filters.push(ScimFilter {
+			attribute,
+			operator,
+			value,
+		});
+
+		match tokens.next() {
+			None => break,
+			Some(Token::Word(word)) if word.eq_ignore_ascii_case("and") => continue,
+			_ => {
+				return Err(invalid_filter_error(
+					"Only `and` is supported to combine filter expressions",
+				))
+			}
+		}
+	}
+
+	Ok(filters)
+}
@@ -0,0 +1,346 @@
+use crate::modules::roles::models::role::{Role, UpdateRole};
+use crate::modules::scim::dto::request::{ScimPatchDTO, UpsertScimGroupDTO};
+use crate::modules::scim::helpers::error::ScimError;
+use crate::modules::scim::services::filter::{
+	invalid_filter_error, parse_filter, ScimFilter, ScimFilterOperator,
+};
+use crate::modules::scim::services::patch::{
+	as_member_ids, as_string, as_uuid, invalid_path_error, parse_op, required_value, ScimPatchOp,
+};
+use crate::modules::sites::models::site_user::SiteUser;
+use crate::modules::sites::models::site_user_role::SiteUserRole;
+use crate::modules::users::models::user::User;
+use crate::modules::users::models::user_role::UserRole;
+use crate::schema::{roles, sites_users_roles, users, users_roles};
+use diesel::expression_methods::PgTextExpressionMethods;
+use diesel::pg::{Pg, PgConnection};
+use diesel::prelude::*;
+use serde_json::Value;
+use tracing::instrument;
+use uuid::Uuid;
+
+fn filtered_roles(filters: &[ScimFilter]) -> Result<roles::BoxedQuery<'static, Pg>, ScimError> {
+	let mut query = roles::table.into_boxed();
+
+	for filter in filters {
+		let (pattern, negate) = filter.like_pattern();
+
+		query = match filter.attribute.as_str() {
+			"displayname" if negate => query.filter(roles::name.not_ilike(pattern)),
+			"displayname" => query.filter(roles::name.ilike(pattern)),
+			"id" => {
+				let id = as_uuid(&filter.value)?;
+
+				if filter.is_equal()? {
+					query.filter(roles::id.eq(id))
+				} else {
+					query.filter(roles::id.ne(id))
+				}
+			}
+			"members" | "members.value" if filter.operator == ScimFilterOperator::Eq => {
+				let user_id = as_uuid(&filter.value)?;
+
+				query.filter(
+					roles::id
+						.eq_any(
+							users_roles::table
+								.filter(users_roles::user_id.eq(user_id))
+								.select(users_roles::role_id),
+						)
+						.or(roles::id.eq_any(
+							sites_users_roles::table
+								.filter(sites_users_roles::user_id.eq(user_id))
+								.select(sites_users_roles::role_id),
+						)),
+				)
+			}
+			_ => {
+				return Err(invalid_filter_error(&format!(
+					"Filtering on `{}` with `{:?}` is not supported",
+					filter.attribute, filter.operator
+				)))
+			}
+		};
+	}
+
+	Ok(query)
+}
+
+// A role is either global or bound to a site, so its members come from one of both tables
+fn load_members(
+	conn: &mut PgConnection,
+	roles: Vec<Role>,
+) -> Result<Vec<(Role, Vec<User>)>, ScimError> {
+	let root_members = UserRole::belonging_to(&roles)
+		.inner_join(users::table.on(users::id.eq(users_roles::user_id)))
+		.select((UserRole::as_select(), User::as_select()))
+		.load::<(UserRole, User)>(conn)?
+		.grouped_by(&roles);
+	let site_members = SiteUserRole::belonging_to(&roles)
+		.inner_join(users::table.on(users::id.eq(sites_users_roles::user_id)))
+		.select((SiteUserRole::as_select(), User::as_select()))
+		.load::<(SiteUserRole, User)>(conn)?
+		.grouped_by(&roles);
+
+	let roles_with_members = roles
+		.into_iter()
+		.zip(root_members.into_iter().zip(site_members))
+		.map(|(role, (root_members, site_members))| {
+			let members = root_members
+				.into_iter()
+				.map(|(_, user)| user)
+				.chain(site_members.into_iter().map(|(_, user)| user))
+				.collect();
+
+			(role, members)
+		})
+		.collect();
+
+	Ok(roles_with_members)
+}
+
+fn with_members(
+	conn: &mut PgConnection,
+	roles: Vec<Role>,
+	include_members: bool,
+) -> Result<Vec<(Role, Option<Vec<User>>)>, ScimError> {
+	if !include_members {
+		return Ok(roles.into_iter().map(|role| (role, None)).collect());
+	}
+
+	let roles_with_members = load_members(conn, roles)?
+		.into_iter()
+		.map(|(role, members)| (role, Some(members)))
+		.collect();
+
+	Ok(roles_with_members)
+}
+
+#[instrument(skip(conn))]
+pub fn find(
+	conn: &mut PgConnection,
+	filters: &[ScimFilter],
+	start_index: i64,
+	count: i64,
+	include_members: bool,
+) -> Result<(Vec<(Role, Option<Vec<User>>)>, i64), ScimError> {
+	let total_results = filtered_roles(filters)?.count().get_result::<i64>(conn)?;
+	let roles = filtered_roles(filters)?
+		.order(roles::created_at.asc())
+		.offset(start_index - 1)
+		.limit(count)
+		.select(Role::as_select())
+		.load::<Role>(conn)?;
+
+	Ok((with_members(conn, roles, include_members)?, total_results))
+}
+
+#[instrument(skip(conn))]
+pub fn find_one(
+	conn: &mut PgConnection,
+	id: Uuid,
+	include_members: bool,
+) -> Result<(Role, Option<Vec<User>>), ScimError> {
+	let role = roles::table.find(id).first::<Role>(conn)?;
+	let mut roles = with_members(conn, vec![role], include_members)?;
+
+	Ok(roles.remove(0))
+}
+
+fn member_ids(conn: &mut PgConnection, role: &Role) -> Result<Vec<Uuid>, ScimError> {
+	let member_ids = match role.site_id {
+		Some(_) => sites_users_roles::table
+			.filter(sites_users_roles::role_id.eq(role.id))
+			.select(sites_users_roles::user_id)
+			.load::<Uuid>(conn)?,
+		None => users_roles::table
+			.filter(users_roles::role_id.eq(role.id))
+			.select(users_roles::user_id)
+			.load::<Uuid>(conn)?,
+	};
+
+	Ok(member_ids)
+}
+
+fn add_member(conn: &mut PgConnection, role: &Role, user_id: Uuid) -> Result<(), ScimError> {
+	User::find_one(conn, user_id)?;
+
+	match role.site_id {
+		Some(site_id) => {
+			SiteUser::upsert(conn, site_id, user_id)?;
+			SiteUserRole::upsert(conn, user_id, site_id, role.id)?;
+		}
+		None => {
+			UserRole::upsert(conn, user_id, role.id)?;
+		}
+	}
+
+	Ok(())
+}
+
+fn remove_member(conn: &mut PgConnection, role: &Role, user_id: Uuid) -> Result<(), ScimError> {
+	match role.site_id {
+		Some(site_id) => {
+			SiteUserRole::remove(conn, user_id, site_id, role.id)?;
+
+			// Without any role left the user no longer belongs to the site
+			let remaining_roles = sites_users_roles::table
+				.filter(sites_users_roles::site_id.eq(site_id))
+				.filter(sites_users_roles::user_id.eq(user_id))
+				.count()
+				.get_result::<i64>(conn)?;
+			if remaining_roles == 0 {
+				SiteUser::remove(conn, site_id, user_id)?;
+			}
+		}
+		None => UserRole::remove(conn, user_id, role.id)?,
+	}
+
+	Ok(())
+}
+
+fn set_members(conn: &mut PgConnection, role: &Role, user_ids: &[Uuid]) -> Result<(), ScimError> {
+	let current_ids = member_ids(conn, role)?;
+
+	for user_id in current_ids.iter().filter(|id| !user_ids.contains(*id)) {
+		remove_member(conn, role, *user_id)?;
+	}
+
+	for user_id in user_ids.iter().filter(|id| !current_ids.contains(*id)) {
+		add_member(conn, role, *user_id)?;
+	}
+
+	Ok(())
+}
+
+// Renaming goes through the regular update, which expects the policies to keep
+fn rename(conn: &mut PgConnection, role: &Role, name: String) -> Result<Role, ScimError> {
+	let policy_ids = Role::find_policies(conn, role)?
+		.into_iter()
+		.map(|policy| policy.id)
+		.collect();
+	let (role, _) = Role::update(conn, role.id, UpdateRole { name: Some(name) }, policy_ids)?;
+
+	Ok(role)
+}
+
+#[instrument(skip(conn))]
+pub fn create(
+	conn: &mut PgConnection,
+	dto: &UpsertScimGroupDTO,
+) -> Result<(Role, Option<Vec<User>>), ScimError> {
+	// Provisioned groups become global roles, policies are attached to them afterwards in the admin
+	let (role, _) = Role::create(conn, None, dto.display_name.clone(), vec![])?;
+
+	if let Some(members) = &dto.members {
+		let user_ids = members
+			.iter()
+			.map(|member| member.value)
+			.collect::<Vec<Uuid>>();
+		set_members(conn, &role, &user_ids)?;
+	}
+
+	find_one(conn, role.id, true)
+}
+
+#[instrument(skip(conn))]
+pub fn replace(
+	conn: &mut PgConnection,
+	id: Uuid,
+	dto: &UpsertScimGroupDTO,
+) -> Result<(Role, Option<Vec<User>>), ScimError> {
+	let role = roles::table.find(id).first::<Role>(conn)?;
+	let role = rename(conn, &role, dto.display_name.clone())?;
+	let user_ids = dto
+		.members
+		.iter()
+		.flatten()
+		.map(|member| member.value)
+		.collect::<Vec<Uuid>>();
+	set_members(conn, &role, &user_ids)?;
+
+	find_one(conn, role.id, true)
+}
+
+// `members[value eq "<id>"]` selects a single member to remove
+fn filtered_member_ids(path: &str) -> Result<Vec<Uuid>, ScimError> {
+	parse_filter(path)?
+		.iter()
+		.map(
+			|filter| match (filter.attribute.as_str(), filter.operator) {
+				("members.value", ScimFilterOperator::Eq) => as_uuid(&filter.value),
+				_ => Err(invalid_path_error(path)),
+			},
+		)
+		.collect()
+}
+
+#[instrument(skip(conn))]
+pub fn patch(
+	conn: &mut PgConnection,
+	id: Uuid,
+	dto: &ScimPatchDTO,
+) -> Result<(Role, Option<Vec<User>>), ScimError> {
+	let mut role = roles::table.find(id).first::<Role>(conn)?;
+
+	for operation in &dto.operations {
+		let op = parse_op(operation)?;
+		let path = operation.path.as_deref().map(str::to_lowercase);
+
+		match (op, path.as_deref()) {
+			(ScimPatchOp::Remove, Some("members")) => match &operation.value {
+				Some(value) => {
+					for user_id in as_member_ids(value)? {
+						remove_member(conn, &role, user_id)?;
+					}
+				}
+				None => set_members(conn, &role, &[])?,
+			},
+			(ScimPatchOp::Remove, Some(path)) if path.starts_with("members[") => {
+				for user_id in filtered_member_ids(path)? {
+					remove_member(conn, &role, user_id)?;
+				}
+			}
+			(ScimPatchOp::Add, Some("members")) => {
+				for user_id in as_member_ids(required_value(operation)?)? {
+					add_member(conn, &role, user_id)?;
+				}
+			}
+			(ScimPatchOp::Replace, Some("members")) => {
+				set_members(conn, &role, &as_member_ids(required_value(operation)?)?)?
+			}
+			(ScimPatchOp::Add | ScimPatchOp::Replace, Some("displayname")) => {
+				role = rename(conn, &role, as_string(required_value(operation)?)?)?
+			}
+			(ScimPatchOp::Add | ScimPatchOp::Replace, None) => match required_value(operation)? {
+				Value::Object(attributes) => {
+					for (key, value) in attributes {
+						match (op, key.to_lowercase().as_str()) {
+							(_, "displayname") => role = rename(conn, &role, as_string(value)?)?,
+							(ScimPatchOp::Add, "members") => {
+								for user_id in as_member_ids(value)? {
+									add_member(conn, &role, user_id)?;
+								}
+							}
+							(_, "members") => set_members(conn, &role, &as_member_ids(value)?)?,
+							// Attributes we don't store are ignored so clients can keep sending them
+							_ => {}
+						}
+					}
+				}
+				_ => return Err(invalid_path_error("")),
+			},
+			(_, path) => return Err(invalid_path_error(path.unwrap_or_default())),
+		}
+	}
+
+	find_one(conn, role.id, true)
+}
+
+#[instrument(skip(conn))]
+pub fn remove(conn: &mut PgConnection, id: Uuid) -> Result<(), ScimError> {
+	roles::table.find(id).first::<Role>(conn)?;
+	Role::remove(conn, id)?;
+
+	Ok(())
+}
@@ -0,0 +1,4 @@
+pub mod filter;
+pub mod groups;
+pub mod patch;
+pub mod users;
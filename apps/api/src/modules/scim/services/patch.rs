@@ -0,0 +1,78 @@
+use crate::modules::scim::dto::request::ScimPatchOperationDTO;
+use crate::modules::scim::helpers::error::ScimError;
+use serde_json::Value;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScimPatchOp {
+	Add,
+	Replace,
+	Remove,
+}
+
+pub fn invalid_value_error(detail: &str) -> ScimError {
+	ScimError::bad_request("invalidValue", detail)
+}
+
+pub fn invalid_path_error(path: &str) -> ScimError {
+	ScimError::bad_request("invalidPath", &format!("Path `{}` is not supported", path))
+}
+
+// Operation names are case insensitive, some clients send `Replace` instead of `replace`
+pub fn parse_op(operation: &ScimPatchOperationDTO) -> Result<ScimPatchOp, ScimError> {
+	match operation.op.to_lowercase().as_str() {
+		"add" => Ok(ScimPatchOp::Add),
+		"replace" => Ok(ScimPatchOp::Replace),
+		"remove" => Ok(ScimPatchOp::Remove),
+		_ => Err(ScimError::bad_request(
+			"invalidSyntax",
+			&format!("Patch operation `{}` is not supported", operation.op),
+		)),
+	}
+}
+
+pub fn required_value(operation: &ScimPatchOperationDTO) -> Result<&Value, ScimError> {
+	operation
+		.value
+		.as_ref()
+		.ok_or_else(|| invalid_value_error("Patch operation is missing a value"))
+}
+
+pub fn as_string(value: &Value) -> Result<String, ScimError> {
+	match value {
+		Value::String(value) => Ok(value.to_owned()),
+		_ => Err(invalid_value_error("Expected a string value")),
+	}
+}
+
+// Some clients send booleans as strings
+pub fn as_bool(value: &Value) -> Result<bool, ScimError> {
+	match value {
+		Value::Bool(value) => Ok(*value),
+		Value::String(value) if value.eq_ignore_ascii_case("true") => Ok(true),
+		Value::String(value) if value.eq_ignore_ascii_case("false") => Ok(false),
+		_ => Err(invalid_value_error("Expected a boolean value")),
+	}
+}
+
+pub fn as_uuid(value: &str) -> Result<Uuid, ScimError> {
+	Uuid::parse_str(value)
+		.map_err(|_| invalid_value_error(&format!("`{}` is not a valid id", value)))
+}
+
+// Members are passed as a list of `{ "value": "<id>" }` objects
+pub fn as_member_ids(value: &Value) -> Result<Vec<Uuid>, ScimError> {
+	let members = match value {
+		Value::Array(members) => members.iter().collect::<Vec<_>>(),
+		Value::Object(_) => vec![value],
+		_ => return Err(invalid_value_error("Expected a list of members")),
+	};
+
+	members
+		.into_iter()
+		.map(|member| match member.get("value") {
+			Some(Value::String(id)) => as_uuid(id),
+			_ => Err(invalid_value_error("Member is missing a value")),
+		})
+		.collect()
+}
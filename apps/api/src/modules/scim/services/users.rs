@@ -0,0 +1,312 @@
+use crate::constants;
+use crate::modules::auth::services::register::register_user;
+use crate::modules::roles::models::role::Role;
+use crate::modules::scim::dto::request::{ScimPatchDTO, UpsertScimUserDTO};
+use crate::modules::scim::helpers::error::ScimError;
+use crate::modules::scim::services::filter::{invalid_filter_error, ScimFilter};
+use crate::modules::scim::services::patch::{
+	as_bool, as_string, as_uuid, invalid_path_error, parse_op, required_value, ScimPatchOp,
+};
+use crate::modules::sites::models::site_user_role::SiteUserRole;
+use crate::modules::users::models::user::{UpdateUser, User};
+use crate::modules::users::models::user_role::UserRole;
+use crate::schema::{roles, sites_users_roles, users, users_roles};
+use crate::utils::string::generate_random_string;
+use diesel::expression_methods::PgTextExpressionMethods;
+use diesel::pg::{Pg, PgConnection};
+use diesel::prelude::*;
+use serde_json::Value;
+use std::env;
+use tracing::instrument;
+use uuid::Uuid;
+
+// Provisioned accounts sign in through the identity provider, the password only has to be unguessable
+const GENERATED_PASSWORD_LENGTH: usize = 32;
+
+#[derive(Debug, Default)]
+struct ScimUserChanges {
+	email: Option<String>,
+	name: Option<String>,
+	active: Option<bool>,
+}
+
+impl From<&UpsertScimUserDTO> for ScimUserChanges {
+	// `userName` is what clients filter on, so it is stored as the email address whenever it looks like one
+	fn from(dto: &UpsertScimUserDTO) -> Self {
+		let primary_email = dto.emails.as_ref().and_then(|emails| {
+			emails
+				.iter()
+				.find(|email| email.primary.unwrap_or(false))
+				.or(emails.first())
+				.map(|email| email.value.clone())
+		});
+		let email = if dto.user_name.contains('@') {
+			dto.user_name.clone()
+		} else {
+			primary_email.unwrap_or_else(|| dto.user_name.clone())
+		};
+
+		let name = dto
+			.display_name
+			.clone()
+			.or_else(|| dto.name.as_ref().and_then(|name| name.formatted.clone()))
+			.or_else(|| {
+				dto.name.as_ref().and_then(|name| {
+					let parts = [name.given_name.as_deref(), name.family_name.as_deref()]
+						.into_iter()
+						.flatten()
+						.collect::<Vec<&str>>();
+
+					(!parts.is_empty()).then(|| parts.join(" "))
+				})
+			})
+			.unwrap_or_else(|| dto.user_name.clone());
+
+		Self {
+			email: Some(email),
+			name: Some(name),
+			active: dto.active,
+		}
+	}
+}
+
+fn filtered_users(filters: &[ScimFilter]) -> Result<users::BoxedQuery<'static, Pg>, ScimError> {
+	let mut query = users::table.into_boxed();
+
+	for filter in filters {
+		let (pattern, negate) = filter.like_pattern();
+
+		query = match filter.attribute.as_str() {
+			"username" | "emails" | "emails.value" if negate => {
+				query.filter(users::email.not_ilike(pattern))
+			}
+			"username" | "emails" | "emails.value" => query.filter(users::email.ilike(pattern)),
+			"displayname" | "name.formatted" if negate => {
+				query.filter(users::name.not_ilike(pattern))
+			}
+			"displayname" | "name.formatted" => query.filter(users::name.ilike(pattern)),
+			"id" => {
+				let id = as_uuid(&filter.value)?;
+
+				if filter.is_equal()? {
+					query.filter(users::id.eq(id))
+				} else {
+					query.filter(users::id.ne(id))
+				}
+			}
+			"active" => {
+				let active = as_bool(&Value::String(filter.value.clone()))?;
+
+				if filter.is_equal()? == active {
+					query.filter(users::deactivated_at.is_null())
+				} else {
+					query.filter(users::deactivated_at.is_not_null())
+				}
+			}
+			_ => {
+				return Err(invalid_filter_error(&format!(
+					"Filtering on `{}` is not supported",
+					filter.attribute
+				)))
+			}
+		};
+	}
+
+	Ok(query)
+}
+
+// Groups are the roles of the user, both the global ones and the ones within a site
+fn load_groups(
+	conn: &mut PgConnection,
+	users: Vec<User>,
+) -> Result<Vec<(User, Vec<Role>)>, ScimError> {
+	let root_roles = UserRole::belonging_to(&users)
+		.inner_join(roles::table.on(roles::id.eq(users_roles::role_id)))
+		.select((UserRole::as_select(), Role::as_select()))
+		.load::<(UserRole, Role)>(conn)?
+		.grouped_by(&users);
+	let site_roles = SiteUserRole::belonging_to(&users)
+		.inner_join(roles::table.on(roles::id.eq(sites_users_roles::role_id)))
+		.select((SiteUserRole::as_select(), Role::as_select()))
+		.load::<(SiteUserRole, Role)>(conn)?
+		.grouped_by(&users);
+
+	let users_with_roles = users
+		.into_iter()
+		.zip(root_roles.into_iter().zip(site_roles))
+		.map(|(user, (root_roles, site_roles))| {
+			let roles = root_roles
+				.into_iter()
+				.map(|(_, role)| role)
+				.chain(site_roles.into_iter().map(|(_, role)| role))
+				.collect();
+
+			(user, roles)
+		})
+		.collect();
+
+	Ok(users_with_roles)
+}
+
+#[instrument(skip(conn))]
+pub fn find(
+	conn: &mut PgConnection,
+	filters: &[ScimFilter],
+	start_index: i64,
+	count: i64,
+) -> Result<(Vec<(User, Vec<Role>)>, i64), ScimError> {
+	let total_results = filtered_users(filters)?.count().get_result::<i64>(conn)?;
+	let users = filtered_users(filters)?
+		.order(users::created_at.asc())
+		.offset(start_index - 1)
+		.limit(count)
+		.select(User::as_select())
+		.load::<User>(conn)?;
+
+	Ok((load_groups(conn, users)?, total_results))
+}
+
+#[instrument(skip(conn))]
+pub fn find_one(conn: &mut PgConnection, id: Uuid) -> Result<(User, Vec<Role>), ScimError> {
+	let user = User::find_one(conn, id)?;
+	let mut users = load_groups(conn, vec![user])?;
+
+	Ok(users.remove(0))
+}
+
+#[instrument(skip(conn))]
+pub async fn create(
+	conn: &mut PgConnection,
+	dto: &UpsertScimUserDTO,
+) -> Result<(User, Vec<Role>), ScimError> {
+	let changes = ScimUserChanges::from(dto);
+	let authentication_method_id = env::var(constants::env_key::SCIM_AUTHENTICATION_METHOD_ID)
+		.ok()
+		.and_then(|id| Uuid::parse_str(&id).ok());
+
+	// The email address is unique, an existing account results in a conflict
+	let user = register_user(
+		conn,
+		changes.email.as_deref().unwrap_or_default(),
+		changes.name.as_deref().unwrap_or_default(),
+		&generate_random_string(GENERATED_PASSWORD_LENGTH),
+		None,
+		authentication_method_id,
+	)?;
+	// The identity provider already vouches for the address
+	User::mark_email_verified(conn, user.id)?;
+
+	if changes.active == Some(false) {
+		User::set_active(conn, user.id, false)?;
+	}
+
+	find_one(conn, user.id)
+}
+
+fn apply_changes(
+	conn: &mut PgConnection,
+	id: Uuid,
+	changes: ScimUserChanges,
+) -> Result<(User, Vec<Role>), ScimError> {
+	// Makes sure a missing user results in a 404 before anything is changed
+	User::find_one(conn, id)?;
+
+	if changes.email.is_some() || changes.name.is_some() {
+		User::update(
+			conn,
+			id,
+			UpdateUser {
+				email: changes.email,
+				name: changes.name,
+				password: None,
+				avatar: None,
+				bio: None,
+				language_key: None,
+			},
+		)?;
+	}
+
+	if let Some(active) = changes.active {
+		User::set_active(conn, id, active)?;
+	}
+
+	find_one(conn, id)
+}
+
+#[instrument(skip(conn))]
+pub fn replace(
+	conn: &mut PgConnection,
+	id: Uuid,
+	dto: &UpsertScimUserDTO,
+) -> Result<(User, Vec<Role>), ScimError> {
+	let mut changes = ScimUserChanges::from(dto);
+	// Leaving out `active` on a replace means the account is active
+	changes.active = Some(dto.active.unwrap_or(true));
+
+	apply_changes(conn, id, changes)
+}
+
+fn set_attribute(
+	changes: &mut ScimUserChanges,
+	path: &str,
+	value: &Value,
+) -> Result<(), ScimError> {
+	match path.to_lowercase().as_str() {
+		"active" => changes.active = Some(as_bool(value)?),
+		"username" => changes.email = Some(as_string(value)?),
+		"displayname" | "name.formatted" => changes.name = Some(as_string(value)?),
+		"name" => {
+			if let Some(formatted) = value.get("formatted") {
+				changes.name = Some(as_string(formatted)?)
+			}
+		}
+		// Attributes we don't store are ignored so clients can keep sending them
+		_ => {}
+	}
+
+	Ok(())
+}
+
+#[instrument(skip(conn))]
+pub fn patch(
+	conn: &mut PgConnection,
+	id: Uuid,
+	dto: &ScimPatchDTO,
+) -> Result<(User, Vec<Role>), ScimError> {
+	let mut changes = ScimUserChanges::default();
+
+	for operation in &dto.operations {
+		match (parse_op(operation)?, operation.path.as_deref()) {
+			// None of the attributes we store can be removed
+			(ScimPatchOp::Remove, Some(path)) => match path.to_lowercase().as_str() {
+				"active" | "username" | "displayname" | "name" | "name.formatted" => {
+					return Err(ScimError::bad_request(
+						"mutability",
+						&format!("`{}` can't be removed", path),
+					))
+				}
+				_ => {}
+			},
+			(ScimPatchOp::Remove, None) => return Err(invalid_path_error("")),
+			(_, Some(path)) => set_attribute(&mut changes, path, required_value(operation)?)?,
+			(_, None) => match required_value(operation)? {
+				Value::Object(attributes) => {
+					for (path, value) in attributes {
+						set_attribute(&mut changes, path, value)?;
+					}
+				}
+				_ => return Err(invalid_path_error("")),
+			},
+		}
+	}
+
+	apply_changes(conn, id, changes)
+}
+
+#[instrument(skip(conn))]
+pub fn remove(conn: &mut PgConnection, id: Uuid) -> Result<(), ScimError> {
+	User::find_one(conn, id)?;
+	User::remove(conn, id)?;
+
+	Ok(())
+}
@@ -2,12 +2,14 @@ use super::super::dto::request;
 use crate::errors::{AppError, AppErrorValue};
 use crate::modules::auth::dto::response::MeDTO;
 use crate::modules::auth::helpers::permissions::get_user_permissions;
+use crate::modules::auth::services::password_policy::validate_password;
+use crate::modules::core::helpers::auth::get_session_metadata;
 use crate::modules::users::models::user::User;
 use crate::modules::{
 	core::middleware::state::AppState, setup::services::setup::setup_initial_user,
 };
 use crate::utils::api::ApiResponse;
-use actix_web::{post, web, HttpResponse};
+use actix_web::{post, web, HttpRequest, HttpResponse};
 use reqwest::StatusCode;
 
 #[utoipa::path(
@@ -20,6 +22,7 @@ use reqwest::StatusCode;
 )]
 #[post("/register")]
 pub async fn register(
+	req: HttpRequest,
 	state: web::Data<AppState>,
 	form: web::Json<request::SetupInstanceDTO>,
 ) -> ApiResponse {
@@ -35,10 +38,13 @@ pub async fn register(
 		}));
 	}
 
-	let (user, token) =
+	validate_password(&form.password)?;
+
+	let user =
 		setup_initial_user(conn, &form.email, &form.name, &form.password, None, None).await?;
+	let tokens = user.start_session(conn, None, &get_session_metadata(&req))?;
 	let permissions = get_user_permissions(conn, user.id, None)?;
 
-	let res = MeDTO::from((user, token, permissions));
+	let res = MeDTO::from((user, tokens, permissions));
 	Ok(HttpResponse::Ok().json(res))
 }
@@ -20,11 +20,13 @@ pub async fn setup_initial_user(
 	password: &str,
 	image: Option<&str>,
 	source: Option<&str>,
-) -> Result<(User, String), AppError> {
+) -> Result<User, AppError> {
 	// TODO: move this logic somewhere seperatly? Try to implement the `service` pattern perhaps?
 	// Create the user account
 	let local_auth_method = AuthenticationMethod::find_local(conn)?;
-	let (user, token) = User::signup(conn, email, username, password, image, local_auth_method.id)?;
+	let user = User::signup(conn, email, username, password, image, local_auth_method.id)?;
+	// There is no one to send a verification mail for the very first account
+	let user = User::mark_email_verified(conn, user.id)?;
 
 	// Create a site for the user
 	// let site = Site::create(conn, &format!("{}'s site", pluralize(&user.name, 2, false)))?;
@@ -51,5 +53,5 @@ pub async fn setup_initial_user(
 	// let _site_user = SiteUser::create(conn, user.id, site.id)?;
 	let _site_user_role = UserRole::create(conn, user.id, role.id)?;
 
-	Ok((user, token))
+	Ok(user)
 }
@@ -1 +1,3 @@
+pub mod site_bundles;
+pub mod site_invitations;
 pub mod sites;
@@ -0,0 +1,136 @@
+use super::super::dto::bundles::{request, response};
+use super::super::models::site_bundle::SiteBundle;
+use super::super::services::{
+	site_bundle_archive::{read_archive, write_archive, write_files},
+	site_import::{apply_import, plan_import, SiteImportOptions},
+};
+use crate::modules::auth::helpers::permissions::ensure_permission;
+use crate::modules::core::middleware::state::AppState;
+use crate::utils::api::ApiResponse;
+use actix_multipart::form::MultipartForm;
+use actix_web::http::header::ContentDisposition;
+use actix_web::{get, post, web, HttpRequest, HttpResponse};
+use futures::stream;
+use serde::Deserialize;
+use tokio::io::AsyncReadExt;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindPathParams {
+	site_id: Uuid,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct ExportQueryParams {
+	include_files: Option<bool>,
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites",
+	responses(
+		(status = 200, content_type = "application/zip", description = "Site bundle archive"),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindPathParams, ExportQueryParams)
+)]
+#[get("/{site_id}/export")]
+pub async fn export(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindPathParams>,
+	query: web::Query<ExportQueryParams>,
+) -> ApiResponse {
+	ensure_permission(
+		&req,
+		None,
+		format!("urn:dcm:sites:{}", params.site_id),
+		"root::sites:read",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let bundle = SiteBundle::find(conn, params.site_id)?;
+	let archive = write_archive(conn, &bundle, query.include_files.unwrap_or(false)).await?;
+	let body = stream::unfold(
+		Some(tokio::fs::File::from_std(archive)),
+		|archive| async move {
+			let mut archive = archive?;
+			let mut chunk = vec![0; STREAM_CHUNK_SIZE];
+			match archive.read(&mut chunk).await {
+				Ok(0) => None,
+				Ok(length) => {
+					chunk.truncate(length);
+					Some((Ok(web::Bytes::from(chunk)), Some(archive)))
+				}
+				Err(err) => Some((Err::<web::Bytes, std::io::Error>(err), None)),
+			}
+		},
+	);
+
+	Ok(HttpResponse::Ok()
+		.content_type("application/zip")
+		.insert_header(ContentDisposition::attachment(format!(
+			"{}.zip",
+			bundle.site.slug
+		)))
+		.streaming(body))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites",
+    request_body(content = ImportSiteDTO, content_type = "multipart/form-data"),
+	responses(
+		(status = 200, body = SiteImportReportDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized"),
+		(status = 422, body = SiteImportReportDTO, description = "The bundle has blocking conflicts")
+	),
+    security(
+        ("jwt_token" = [])
+    )
+)]
+#[post("/import")]
+pub async fn import(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	MultipartForm(form): MultipartForm<request::ImportSiteDTO>,
+) -> ApiResponse {
+	let user_id = ensure_permission(&req, None, format!("urn:dcm:sites:*"), "root::sites:create")?;
+	let conn = &mut state.get_conn()?;
+	let archive = read_archive(form.file.file.reopen()?)?;
+	let dry_run = form
+		.dry_run
+		.map(|dry_run| dry_run.into_inner())
+		.unwrap_or(false);
+
+	let plan = plan_import(
+		conn,
+		archive.bundle,
+		SiteImportOptions {
+			slug: form.slug.map(|slug| slug.into_inner()),
+			name: form.name.map(|name| name.into_inner()),
+		},
+		user_id,
+	)?;
+
+	if dry_run {
+		let res = response::SiteImportReportDTO::from((plan, None));
+		return Ok(HttpResponse::Ok().json(res));
+	}
+
+	if plan.is_blocked() {
+		let res = response::SiteImportReportDTO::from((plan, None));
+		return Ok(HttpResponse::UnprocessableEntity().json(res));
+	}
+
+	// Repositories are never created by an import, so the files can land before the records do
+	// and a failing write doesn't leave a half imported site behind
+	write_files(conn, archive.files, &plan.storage_repository_ids).await?;
+	let site_id = apply_import(conn, &plan)?;
+
+	let res = response::SiteImportReportDTO::from((plan, Some(site_id)));
+	Ok(HttpResponse::Ok().json(res))
+}
@@ -0,0 +1,173 @@
+use super::super::dto::invitations::{request, response};
+use crate::errors::{AppError, AppErrorValue};
+use crate::modules::auth::helpers::permissions::{ensure_assignable_roles, ensure_permission};
+use crate::modules::auth::services::account::send_site_invitation;
+use crate::modules::core::middleware::state::AppState;
+use crate::modules::core::models::hal::HALPage;
+use crate::modules::sites::models::site::Site;
+use crate::modules::sites::models::site_invitation::SiteInvitation;
+use crate::utils::api::ApiResponse;
+use actix_web::http::StatusCode;
+use actix_web::{delete, get, post, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindOnePathParams {
+	site_id: Uuid,
+	invitation_id: Uuid,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindPathParams {
+	site_id: Uuid,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindAllQueryParams {
+	page: Option<i64>,
+	pagesize: Option<i64>,
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/invitations",
+    request_body = CreateSiteInvitationDTO,
+	responses(
+		(status = 200, body = SiteInvitationDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    )
+)]
+#[post("")]
+pub async fn create(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	form: web::Json<request::CreateSiteInvitationDTO>,
+	params: web::Path<FindPathParams>,
+) -> ApiResponse {
+	// Root administrators can hand out every role of the site, others only the ones they hold
+	let (user_id, root_actor) = match ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:users:*"),
+		"sites::users:create",
+	) {
+		Ok(user_id) => (user_id, false),
+		Err(_) => (
+			ensure_permission(&req, None, format!("urn:dcm:users:*"), "root::users:create")?,
+			true,
+		),
+	};
+	let conn = &mut state.get_conn()?;
+
+	if form.role_ids.is_empty() {
+		return Err(AppError::UnprocessableEntity(AppErrorValue {
+			message: "Invitations need at least one role".to_owned(),
+			status: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+			code: "INVALID_SITE_INVITATION_ROLES".to_owned(),
+			..Default::default()
+		}));
+	}
+	let role_ids = ensure_assignable_roles(&req, conn, params.site_id, &form.role_ids, root_actor)?;
+
+	let email = form.email.trim();
+	if email.is_empty() {
+		return Err(AppError::UnprocessableEntity(AppErrorValue {
+			message: "Email missing".to_owned(),
+			status: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+			code: "INVALID_SITE_INVITATION_EMAIL".to_owned(),
+			..Default::default()
+		}));
+	}
+
+	let (site, _) = Site::find_one(conn, params.site_id)?;
+	let (site_invitation, token) = SiteInvitation::create(
+		conn,
+		params.site_id,
+		Some(user_id),
+		email.to_owned(),
+		role_ids,
+	)?;
+	send_site_invitation(&state.mail_addr, email, &site.name, &token)?;
+
+	let res = response::SiteInvitationDTO::from(site_invitation);
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/invitations",
+	responses(
+		(status = 200, body = SiteInvitationsDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindAllQueryParams)
+)]
+#[get("")]
+pub async fn find_all(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	query: web::Query<FindAllQueryParams>,
+	params: web::Path<FindPathParams>,
+) -> ApiResponse {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:users:*"),
+		"sites::users:read",
+	)
+	.or_else(|_| ensure_permission(&req, None, format!("urn:dcm:users:*"), "root::users:read"))?;
+	let conn = &mut state.get_conn()?;
+	let page = query.page.unwrap_or(1);
+	let pagesize = query.pagesize.unwrap_or(20);
+
+	let (site_invitations, total_elements) =
+		SiteInvitation::find(conn, params.site_id, page, pagesize)?;
+
+	let res = response::SiteInvitationsDTO::from((
+		site_invitations,
+		HALPage {
+			number: page,
+			size: pagesize,
+			total_elements,
+			total_pages: (total_elements / pagesize + (total_elements % pagesize).signum()).max(1),
+		},
+		params.site_id,
+	));
+
+	Ok(HttpResponse::Ok().json(res))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/invitations",
+	responses(
+		(status = 204),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindOnePathParams)
+)]
+#[delete("/{invitation_id}")]
+pub async fn remove(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindOnePathParams>,
+) -> ApiResponse {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:users:*"),
+		"sites::users:remove",
+	)
+	.or_else(|_| ensure_permission(&req, None, format!("urn:dcm:users:*"), "root::users:remove"))?;
+	let conn = &mut state.get_conn()?;
+	SiteInvitation::remove(conn, params.site_id, params.invitation_id)?;
+	Ok(HttpResponse::NoContent().body(()))
+}
@@ -2,6 +2,7 @@ use super::super::dto::{request, response};
 use super::super::models::site::{Site, UpdateSite};
 use crate::errors::AppError;
 use crate::modules::auth::helpers::permissions::ensure_permission;
+use crate::modules::content::models::content::Content;
 use crate::modules::core::helpers::auth::get_user_id_from_req;
 use crate::modules::core::middleware::state::AppState;
 use crate::modules::core::models::hal::HALPage;
@@ -156,7 +157,7 @@ pub async fn update(
 	params: web::Path<FindPathParams>,
 	form: web::Json<request::UpdateSiteDTO>,
 ) -> ApiResponse {
-	ensure_permission(
+	let user_id = ensure_permission(
 		&req,
 		None,
 		format!("urn:dcm:sites:{}", params.site_id),
@@ -171,6 +172,8 @@ pub async fn update(
 		},
 	)?;
 	let languages = SiteLanguage::upsert(conn, site.id, form.languages.clone())?;
+	Content::create_site_singletons(conn, user_id, site.id)?;
+
 	let res = response::SiteWithLanguagesDTO::from((site, None, languages));
 	Ok(HttpResponse::Ok().json(res))
 }
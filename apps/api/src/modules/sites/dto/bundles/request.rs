@@ -0,0 +1,14 @@
+use actix_multipart::form::{tempfile::TempFile, text::Text, MultipartForm};
+use utoipa::ToSchema;
+
+#[derive(Debug, MultipartForm, ToSchema)]
+pub struct ImportSiteDTO {
+	#[multipart(rename = "file")]
+	pub file: TempFile,
+	// Only reports the conflicts without importing anything
+	#[multipart(rename = "dryRun")]
+	pub dry_run: Option<Text<bool>>,
+	// Overrides for when the site is imported next to the original
+	pub slug: Option<Text<String>>,
+	pub name: Option<Text<String>>,
+}
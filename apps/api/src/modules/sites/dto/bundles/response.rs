@@ -0,0 +1,49 @@
+use crate::modules::sites::services::site_import::{
+	SiteImportConflict, SiteImportConflictKindEnum, SiteImportPlan,
+};
+use serde::{Deserialize, Serialize};
+use std::convert::From;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SiteImportConflictDTO {
+	pub kind: SiteImportConflictKindEnum,
+	pub key: String,
+	pub message: String,
+	pub blocking: bool,
+}
+
+impl From<SiteImportConflict> for SiteImportConflictDTO {
+	fn from(conflict: SiteImportConflict) -> Self {
+		Self {
+			kind: conflict.kind,
+			key: conflict.key,
+			message: conflict.message,
+			blocking: conflict.blocking,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SiteImportReportDTO {
+	pub site_id: Option<Uuid>,
+	pub imported: bool,
+	pub conflicts: Vec<SiteImportConflictDTO>,
+}
+
+impl From<(SiteImportPlan, Option<Uuid>)> for SiteImportReportDTO {
+	fn from((plan, site_id): (SiteImportPlan, Option<Uuid>)) -> Self {
+		Self {
+			site_id,
+			imported: site_id.is_some(),
+			conflicts: plan
+				.conflicts
+				.into_iter()
+				.map(SiteImportConflictDTO::from)
+				.collect(),
+		}
+	}
+}
@@ -0,0 +1,66 @@
+use crate::modules::{
+	core::models::hal::{HALLinkList, HALPage},
+	roles::{dto::response::RoleDTO, models::role::Role},
+	sites::models::site_invitation::SiteInvitation,
+};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::convert::From;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SiteInvitationDTO {
+	pub id: Uuid,
+	pub email: String,
+	pub roles: Vec<RoleDTO>,
+	pub invited_by_id: Option<Uuid>,
+	pub expires_at: NaiveDateTime,
+	pub created_at: NaiveDateTime,
+	pub updated_at: NaiveDateTime,
+}
+
+impl From<(SiteInvitation, Vec<Role>)> for SiteInvitationDTO {
+	fn from((site_invitation, roles): (SiteInvitation, Vec<Role>)) -> Self {
+		Self {
+			id: site_invitation.id,
+			email: site_invitation.email,
+			roles: roles.into_iter().map(RoleDTO::from).collect(),
+			invited_by_id: site_invitation.invited_by_id,
+			expires_at: site_invitation.expires_at,
+			created_at: site_invitation.created_at,
+			updated_at: site_invitation.updated_at,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SiteInvitationsEmbeddedDTO {
+	pub invitations: Vec<SiteInvitationDTO>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct SiteInvitationsDTO {
+	pub _links: HALLinkList,
+	pub _page: HALPage,
+	pub _embedded: SiteInvitationsEmbeddedDTO,
+}
+
+impl From<(Vec<(SiteInvitation, Vec<Role>)>, HALPage, Uuid)> for SiteInvitationsDTO {
+	fn from(
+		(site_invitations, page, site_id): (Vec<(SiteInvitation, Vec<Role>)>, HALPage, Uuid),
+	) -> Self {
+		Self {
+			_links: HALLinkList::from((format!("/api/v1/sites/{}/invitations", site_id), &page)),
+			_embedded: SiteInvitationsEmbeddedDTO {
+				invitations: site_invitations
+					.into_iter()
+					.map(SiteInvitationDTO::from)
+					.collect(),
+			},
+			_page: page,
+		}
+	}
+}
@@ -1,3 +1,5 @@
+pub mod bundles;
+pub mod invitations;
 pub mod languages;
 pub mod request;
 pub mod response;
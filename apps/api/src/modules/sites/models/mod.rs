@@ -1,4 +1,7 @@
 pub mod site;
+pub mod site_bundle;
+pub mod site_invitation;
+pub mod site_invitation_role;
 pub mod site_language;
 pub mod site_user;
 pub mod site_user_role;
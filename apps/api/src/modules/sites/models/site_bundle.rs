@@ -0,0 +1,802 @@
+use std::collections::HashSet;
+
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::modules::content_components::enums::data_type::DataTypeEnum;
+use crate::modules::content_types::models::content_type::ContentTypeKindEnum;
+use crate::modules::content_types::models::field::FieldTypeEnum;
+use crate::modules::content_types::models::field_config::FieldConfigTypeEnum;
+use crate::modules::labels::models::label::LabelEntityTypeEnum;
+use crate::modules::redirects::models::redirect::RedirectMatchTypeEnum;
+use crate::modules::workflows::models::workflow_state::WorkflowTechnicalStateEnum;
+use crate::schema::{
+	compartments, config_items, content, content_components, content_fields, content_revisions,
+	content_types, content_types_field_groups, field_config, fields, iam_policies, labels,
+	languages, modules, permissions, permissions_iam_actions, permissions_iam_conditions,
+	redirects, roles, roles_iam_policies, sites, sites_content_components, sites_content_types,
+	sites_languages, sites_storage_repositories, storage_repositories, webhooks, workflow_states,
+	workflow_transition_requirements, workflow_transitions, workflows,
+};
+
+// Bumped whenever the layout of the bundle changes in a way older importers can't read
+pub const SITE_BUNDLE_VERSION: i32 = 1;
+
+// Keeps batch inserts of content well below the bind parameter limit of Postgres
+const INSERT_CHUNK_SIZE: usize = 1000;
+
+// The records mirror their tables so they can be inserted again once their ids are remapped,
+// timestamps are left to the database except for content where the history matters
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = sites)]
+pub struct SiteRecord {
+	pub id: Uuid,
+	pub slug: String,
+	pub name: String,
+	pub url: Option<String>,
+	pub image: Option<String>,
+	pub description: Option<String>,
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = languages)]
+pub struct LanguageRecord {
+	pub id: Uuid,
+	pub key: String,
+}
+
+#[derive(Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = storage_repositories)]
+pub struct StorageRepositoryRecord {
+	pub id: Uuid,
+	pub name: String,
+	pub kind: String,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = workflow_states)]
+pub struct WorkflowStateRecord {
+	pub id: Uuid,
+	pub name: String,
+	pub slug: String,
+	pub description: Option<String>,
+	pub technical_state: WorkflowTechnicalStateEnum,
+	pub internal: bool,
+	pub removable: bool,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = workflows)]
+pub struct WorkflowRecord {
+	pub id: Uuid,
+	pub name: String,
+	pub slug: String,
+	pub description: Option<String>,
+	pub default_workflow_state_id: Uuid,
+	pub internal: bool,
+	pub removable: bool,
+	pub active: bool,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = workflow_transitions)]
+pub struct WorkflowTransitionRecord {
+	pub id: Uuid,
+	pub workflow_id: Uuid,
+	pub from_workflow_state_id: Uuid,
+	pub to_workflow_state_id: Uuid,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = workflow_transition_requirements)]
+pub struct WorkflowTransitionRequirementRecord {
+	pub id: Uuid,
+	pub workflow_transition_id: Uuid,
+	#[serde(rename = "type")]
+	pub type_: String,
+	pub value: Value,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = content_components)]
+pub struct ContentComponentRecord {
+	pub id: Uuid,
+	pub name: String,
+	pub slug: String,
+	pub description: Option<String>,
+	pub data_type: DataTypeEnum,
+	pub hidden: bool,
+	pub internal: bool,
+	pub removeable: bool,
+	pub component_name: String,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = content_types)]
+pub struct ContentTypeRecord {
+	pub id: Uuid,
+	pub name: String,
+	pub description: Option<String>,
+	pub kind: ContentTypeKindEnum,
+	pub workflow_id: Uuid,
+	pub slug: String,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = content_types_field_groups)]
+pub struct ContentTypeFieldGroupRecord {
+	pub content_type_id: Uuid,
+	pub field_group_id: Uuid,
+	pub sequence_number: i32,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = compartments)]
+pub struct CompartmentRecord {
+	pub id: Uuid,
+	pub name: String,
+	pub description: Option<String>,
+	pub content_type_id: Uuid,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = fields)]
+pub struct FieldRecord {
+	pub id: Uuid,
+	pub name: String,
+	pub slug: String,
+	pub description: Option<String>,
+	pub min: i32,
+	pub max: i32,
+	pub hidden: bool,
+	pub multi_language: bool,
+	pub field_type: FieldTypeEnum,
+	pub parent_id: Uuid,
+	pub content_component_id: Uuid,
+	pub sequence_number: Option<i32>,
+	pub compartment_id: Option<Uuid>,
+	pub validation: Option<Value>,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = field_config)]
+pub struct FieldConfigRecord {
+	pub id: Uuid,
+	pub field_id: Uuid,
+	pub config_key: String,
+	pub config_type: FieldConfigTypeEnum,
+	pub content: Option<String>,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = roles)]
+pub struct RoleRecord {
+	pub id: Uuid,
+	pub name: String,
+	pub slug: String,
+	pub description: Option<String>,
+	pub site_id: Option<Uuid>,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = iam_policies)]
+pub struct IAMPolicyRecord {
+	pub id: Uuid,
+	pub name: String,
+	pub site_id: Option<Uuid>,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = roles_iam_policies)]
+pub struct RoleIAMPolicyRecord {
+	pub role_id: Uuid,
+	pub iam_policy_id: Uuid,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = permissions)]
+pub struct PermissionRecord {
+	pub id: Uuid,
+	pub iam_policy_id: Uuid,
+	pub resources: Value,
+	pub effect: String,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = permissions_iam_actions)]
+pub struct PermissionIAMActionRecord {
+	pub permission_id: Uuid,
+	pub iam_action_key: String,
+	pub active: Option<bool>,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = permissions_iam_conditions)]
+pub struct PermissionIAMConditionRecord {
+	pub permission_id: Uuid,
+	pub iam_condition_key: String,
+	pub value: Value,
+	pub active: Option<bool>,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = webhooks)]
+pub struct WebhookRecord {
+	pub id: Uuid,
+	pub event: String,
+	pub url: String,
+	pub active: bool,
+	pub site_id: Uuid,
+	pub request_configuration: Option<Value>,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = modules)]
+pub struct ModuleRecord {
+	pub id: Uuid,
+	pub name: String,
+	pub entry_url: String,
+	pub active: bool,
+	pub site_id: Uuid,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = config_items)]
+pub struct ConfigItemRecord {
+	pub id: Uuid,
+	pub key: String,
+	pub module_name: Option<String>,
+	pub site_id: Option<Uuid>,
+	pub value: Option<Value>,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = content)]
+pub struct ContentRecord {
+	pub id: Uuid,
+	pub name: String,
+	pub slug: String,
+	pub workflow_state_id: Uuid,
+	pub translation_id: Uuid,
+	pub language_id: Uuid,
+	pub site_id: Uuid,
+	pub content_type_id: Uuid,
+	pub published: bool,
+	pub deleted: bool,
+	pub created_at: NaiveDateTime,
+	pub updated_at: NaiveDateTime,
+	#[serde(default)]
+	pub parent_id: Option<Uuid>,
+	#[serde(default)]
+	pub path: Option<String>,
+	#[serde(default)]
+	pub singleton: bool,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = content_revisions)]
+pub struct ContentRevisionRecord {
+	pub id: Uuid,
+	pub workflow_state_id: Uuid,
+	pub revision_translation_id: Uuid,
+	pub content_id: Uuid,
+	pub site_id: Uuid,
+	pub user_id: Uuid,
+	pub published: bool,
+	pub created_at: NaiveDateTime,
+	pub updated_at: NaiveDateTime,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = content_fields)]
+pub struct ContentFieldRecord {
+	pub id: Uuid,
+	pub name: String,
+	pub value: Option<Value>,
+	pub parent_id: Option<Uuid>,
+	pub source_id: Uuid,
+	pub content_component_id: Option<Uuid>,
+	pub sequence_number: Option<i32>,
+	pub data_type: DataTypeEnum,
+}
+
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = labels)]
+pub struct LabelRecord {
+	pub id: Uuid,
+	pub entity_type: LabelEntityTypeEnum,
+	pub entity_id: Uuid,
+	pub language_key: String,
+	pub name: String,
+	pub description: Option<String>,
+}
+
+// Hits are statistics of the source instance and start over after an import
+#[derive(Queryable, Selectable, Insertable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = redirects)]
+pub struct RedirectRecord {
+	pub id: Uuid,
+	pub site_id: Uuid,
+	pub language_id: Option<Uuid>,
+	pub source_path: String,
+	pub content_id: Option<Uuid>,
+	pub match_type: RedirectMatchTypeEnum,
+	pub target_path: Option<String>,
+	pub status_code: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SiteBundle {
+	pub version: i32,
+	pub exported_at: NaiveDateTime,
+	pub site: SiteRecord,
+	pub languages: Vec<LanguageRecord>,
+	pub site_language_ids: Vec<Uuid>,
+	pub storage_repositories: Vec<StorageRepositoryRecord>,
+	pub site_storage_repository_ids: Vec<Uuid>,
+	pub workflow_states: Vec<WorkflowStateRecord>,
+	pub workflows: Vec<WorkflowRecord>,
+	pub workflow_transitions: Vec<WorkflowTransitionRecord>,
+	pub workflow_transition_requirements: Vec<WorkflowTransitionRequirementRecord>,
+	pub content_components: Vec<ContentComponentRecord>,
+	pub site_content_component_ids: Vec<Uuid>,
+	pub content_types: Vec<ContentTypeRecord>,
+	pub site_content_type_ids: Vec<Uuid>,
+	#[serde(default)]
+	pub content_types_field_groups: Vec<ContentTypeFieldGroupRecord>,
+	pub compartments: Vec<CompartmentRecord>,
+	pub fields: Vec<FieldRecord>,
+	pub field_config: Vec<FieldConfigRecord>,
+	#[serde(default)]
+	pub labels: Vec<LabelRecord>,
+	pub roles: Vec<RoleRecord>,
+	pub iam_policies: Vec<IAMPolicyRecord>,
+	pub roles_iam_policies: Vec<RoleIAMPolicyRecord>,
+	pub permissions: Vec<PermissionRecord>,
+	pub permissions_iam_actions: Vec<PermissionIAMActionRecord>,
+	pub permissions_iam_conditions: Vec<PermissionIAMConditionRecord>,
+	pub webhooks: Vec<WebhookRecord>,
+	pub modules: Vec<ModuleRecord>,
+	pub config_items: Vec<ConfigItemRecord>,
+	pub content: Vec<ContentRecord>,
+	pub content_revisions: Vec<ContentRevisionRecord>,
+	pub content_fields: Vec<ContentFieldRecord>,
+	#[serde(default)]
+	pub redirects: Vec<RedirectRecord>,
+}
+
+// Media fields store a `{ storageRepositoryId, path }` object
+fn find_storage_repository_reference(value: &Value) -> Option<Uuid> {
+	value
+		.get("storageRepositoryId")
+		.and_then(Value::as_str)
+		.and_then(|id| Uuid::parse_str(id).ok())
+}
+
+impl SiteBundle {
+	// Fields hang below content types and custom components, block fields below other fields and
+	// custom components can use other custom components, so the tree is walked until nothing new turns up
+	fn find_fields(
+		conn: &mut PgConnection,
+		content_type_ids: &[Uuid],
+		content_component_ids: &[Uuid],
+	) -> Result<(Vec<FieldRecord>, Vec<ContentComponentRecord>), AppError> {
+		let mut content_component_ids = content_component_ids.to_vec();
+		let mut visited_parents: HashSet<Uuid> = HashSet::new();
+		let mut pending_parents = content_type_ids.to_vec();
+		let mut fields: Vec<FieldRecord> = vec![];
+		let mut components: Vec<ContentComponentRecord> = vec![];
+
+		loop {
+			let new_component_ids = content_components::table
+				.filter(content_components::id.eq_any(&content_component_ids))
+				.filter(content_components::deleted.eq(false))
+				.filter(
+					content_components::id.ne_all(
+						components
+							.iter()
+							.map(|component| component.id)
+							.collect::<Vec<Uuid>>(),
+					),
+				)
+				.select(ContentComponentRecord::as_select())
+				.load::<ContentComponentRecord>(conn)?;
+
+			// The fields of internal components are seeded in every installation
+			pending_parents.extend(
+				new_component_ids
+					.iter()
+					.filter(|component| !component.internal)
+					.map(|component| component.id),
+			);
+			components.extend(new_component_ids);
+			pending_parents.retain(|id| visited_parents.insert(*id));
+
+			if pending_parents.is_empty() {
+				break;
+			}
+
+			let new_fields = fields::table
+				.filter(fields::parent_id.eq_any(&pending_parents))
+				.select(FieldRecord::as_select())
+				.load::<FieldRecord>(conn)?;
+
+			pending_parents = new_fields.iter().map(|field| field.id).collect();
+			content_component_ids.extend(new_fields.iter().map(|field| field.content_component_id));
+			fields.extend(new_fields);
+		}
+
+		Ok((fields, components))
+	}
+
+	#[instrument(skip(conn))]
+	pub fn find(conn: &mut PgConnection, site_id: Uuid) -> Result<Self, AppError> {
+		let site = sites::table
+			.find(site_id)
+			.select(SiteRecord::as_select())
+			.first::<SiteRecord>(conn)?;
+
+		// Definitions
+		let content_type_ids = sites_content_types::table
+			.filter(sites_content_types::site_id.eq(site_id))
+			.select(sites_content_types::content_type_id)
+			.load::<Uuid>(conn)?;
+		let site_content_component_ids = sites_content_components::table
+			.filter(sites_content_components::site_id.eq(site_id))
+			.select(sites_content_components::content_component_id)
+			.load::<Uuid>(conn)?;
+		let content_types = content_types::table
+			.filter(content_types::id.eq_any(&content_type_ids))
+			.filter(content_types::deleted.eq(false))
+			.select(ContentTypeRecord::as_select())
+			.load::<ContentTypeRecord>(conn)?;
+		let content_types_field_groups = content_types_field_groups::table
+			.filter(content_types_field_groups::content_type_id.eq_any(&content_type_ids))
+			.filter(content_types_field_groups::field_group_id.eq_any(&content_type_ids))
+			.select(ContentTypeFieldGroupRecord::as_select())
+			.load::<ContentTypeFieldGroupRecord>(conn)?;
+		let compartments = compartments::table
+			.filter(compartments::content_type_id.eq_any(&content_type_ids))
+			.select(CompartmentRecord::as_select())
+			.load::<CompartmentRecord>(conn)?;
+		let (fields, content_components) =
+			Self::find_fields(conn, &content_type_ids, &site_content_component_ids)?;
+		let field_config = field_config::table
+			.filter(field_config::field_id.eq_any(fields.iter().map(|field| field.id)))
+			.select(FieldConfigRecord::as_select())
+			.load::<FieldConfigRecord>(conn)?;
+
+		// Workflows
+		let workflow_ids = content_types
+			.iter()
+			.map(|content_type| content_type.workflow_id)
+			.collect::<HashSet<Uuid>>();
+		let workflows = workflows::table
+			.filter(workflows::id.eq_any(workflow_ids))
+			.filter(workflows::deleted.eq(false))
+			.select(WorkflowRecord::as_select())
+			.load::<WorkflowRecord>(conn)?;
+		let workflow_transitions = workflow_transitions::table
+			.filter(workflow_transitions::workflow_id.eq_any(workflows.iter().map(|w| w.id)))
+			.select(WorkflowTransitionRecord::as_select())
+			.load::<WorkflowTransitionRecord>(conn)?;
+		let workflow_transition_requirements = workflow_transition_requirements::table
+			.filter(
+				workflow_transition_requirements::workflow_transition_id
+					.eq_any(workflow_transitions.iter().map(|transition| transition.id)),
+			)
+			.select(WorkflowTransitionRequirementRecord::as_select())
+			.load::<WorkflowTransitionRequirementRecord>(conn)?;
+
+		// Access
+		let roles = roles::table
+			.filter(roles::site_id.eq(site_id))
+			.select(RoleRecord::as_select())
+			.load::<RoleRecord>(conn)?;
+		let iam_policies = iam_policies::table
+			.filter(iam_policies::site_id.eq(site_id))
+			.select(IAMPolicyRecord::as_select())
+			.load::<IAMPolicyRecord>(conn)?;
+		let roles_iam_policies = roles_iam_policies::table
+			.filter(roles_iam_policies::role_id.eq_any(roles.iter().map(|role| role.id)))
+			.select(RoleIAMPolicyRecord::as_select())
+			.load::<RoleIAMPolicyRecord>(conn)?;
+		let permissions = permissions::table
+			.filter(permissions::iam_policy_id.eq_any(iam_policies.iter().map(|policy| policy.id)))
+			.select(PermissionRecord::as_select())
+			.load::<PermissionRecord>(conn)?;
+		let permission_ids = permissions
+			.iter()
+			.map(|permission| permission.id)
+			.collect::<Vec<Uuid>>();
+		let permissions_iam_actions = permissions_iam_actions::table
+			.filter(permissions_iam_actions::permission_id.eq_any(&permission_ids))
+			.select(PermissionIAMActionRecord::as_select())
+			.load::<PermissionIAMActionRecord>(conn)?;
+		let permissions_iam_conditions = permissions_iam_conditions::table
+			.filter(permissions_iam_conditions::permission_id.eq_any(&permission_ids))
+			.select(PermissionIAMConditionRecord::as_select())
+			.load::<PermissionIAMConditionRecord>(conn)?;
+
+		// Integrations
+		let webhooks = webhooks::table
+			.filter(webhooks::site_id.eq(site_id))
+			.select(WebhookRecord::as_select())
+			.load::<WebhookRecord>(conn)?;
+		let modules = modules::table
+			.filter(modules::site_id.eq(site_id))
+			.select(ModuleRecord::as_select())
+			.load::<ModuleRecord>(conn)?;
+		let config_items = config_items::table
+			.filter(config_items::site_id.eq(site_id))
+			.select(ConfigItemRecord::as_select())
+			.load::<ConfigItemRecord>(conn)?;
+
+		// Content
+		let content = content::table
+			.filter(content::site_id.eq(site_id))
+			.filter(content::deleted.eq(false))
+			.select(ContentRecord::as_select())
+			.load::<ContentRecord>(conn)?;
+		let content_revisions = content_revisions::table
+			.filter(
+				content_revisions::content_id
+					.eq_any(content.iter().map(|content_item| content_item.id)),
+			)
+			.order(content_revisions::created_at.asc())
+			.select(ContentRevisionRecord::as_select())
+			.load::<ContentRevisionRecord>(conn)?;
+		// Multi language fields belong to the revision, the others to the revision translation
+		let source_ids = content_revisions
+			.iter()
+			.flat_map(|revision| [revision.id, revision.revision_translation_id])
+			.collect::<HashSet<Uuid>>();
+		let content_fields = content_fields::table
+			.filter(content_fields::source_id.eq_any(source_ids))
+			.select(ContentFieldRecord::as_select())
+			.load::<ContentFieldRecord>(conn)?;
+
+		// Workflow states are shared, only the ones used by the workflows and content are needed
+		let workflow_state_ids = workflows
+			.iter()
+			.map(|workflow| workflow.default_workflow_state_id)
+			.chain(workflow_transitions.iter().flat_map(|transition| {
+				[
+					transition.from_workflow_state_id,
+					transition.to_workflow_state_id,
+				]
+			}))
+			.chain(
+				content
+					.iter()
+					.map(|content_item| content_item.workflow_state_id),
+			)
+			.chain(
+				content_revisions
+					.iter()
+					.map(|revision| revision.workflow_state_id),
+			)
+			.collect::<HashSet<Uuid>>();
+		let workflow_states = workflow_states::table
+			.filter(workflow_states::id.eq_any(workflow_state_ids))
+			.select(WorkflowStateRecord::as_select())
+			.load::<WorkflowStateRecord>(conn)?;
+
+		// Labels of everything above that can carry one, blocks are fields as well
+		let label_entity_ids = content_types
+			.iter()
+			.map(|content_type| content_type.id)
+			.chain(fields.iter().map(|field| field.id))
+			.chain(compartments.iter().map(|compartment| compartment.id))
+			.chain(workflow_states.iter().map(|state| state.id))
+			.collect::<HashSet<Uuid>>();
+		let labels = labels::table
+			.filter(labels::entity_id.eq_any(label_entity_ids))
+			.select(LabelRecord::as_select())
+			.load::<LabelRecord>(conn)?;
+		let redirects = redirects::table
+			.filter(redirects::site_id.eq(site_id))
+			.select(RedirectRecord::as_select())
+			.load::<RedirectRecord>(conn)?;
+
+		let site_language_ids = sites_languages::table
+			.filter(sites_languages::site_id.eq(site_id))
+			.select(sites_languages::language_id)
+			.load::<Uuid>(conn)?;
+		let language_ids = site_language_ids
+			.iter()
+			.copied()
+			.chain(content.iter().map(|content_item| content_item.language_id))
+			.collect::<HashSet<Uuid>>();
+		let languages = languages::table
+			.filter(languages::id.eq_any(language_ids))
+			.select(LanguageRecord::as_select())
+			.load::<LanguageRecord>(conn)?;
+
+		let site_storage_repository_ids = sites_storage_repositories::table
+			.filter(sites_storage_repositories::site_id.eq(site_id))
+			.select(sites_storage_repositories::storage_repository_id)
+			.load::<Uuid>(conn)?;
+		let storage_repository_ids = site_storage_repository_ids
+			.iter()
+			.copied()
+			.chain(content_fields.iter().filter_map(|content_field| {
+				content_field
+					.value
+					.as_ref()
+					.and_then(find_storage_repository_reference)
+			}))
+			.collect::<HashSet<Uuid>>();
+		let storage_repositories = storage_repositories::table
+			.filter(storage_repositories::id.eq_any(storage_repository_ids))
+			.select(StorageRepositoryRecord::as_select())
+			.load::<StorageRepositoryRecord>(conn)?;
+
+		Ok(Self {
+			version: SITE_BUNDLE_VERSION,
+			exported_at: Utc::now().naive_utc(),
+			site,
+			languages,
+			site_language_ids,
+			storage_repositories,
+			site_storage_repository_ids,
+			workflow_states,
+			workflows,
+			workflow_transitions,
+			workflow_transition_requirements,
+			content_components,
+			site_content_component_ids,
+			site_content_type_ids: content_types
+				.iter()
+				.map(|content_type| content_type.id)
+				.collect(),
+			content_types,
+			content_types_field_groups,
+			compartments,
+			fields,
+			field_config,
+			labels,
+			roles,
+			iam_policies,
+			roles_iam_policies,
+			permissions,
+			permissions_iam_actions,
+			permissions_iam_conditions,
+			webhooks,
+			modules,
+			config_items,
+			content,
+			content_revisions,
+			content_fields,
+			redirects,
+		})
+	}
+	// Inserts the records as they are, the ids are expected to be remapped by the importer already.
+	// Links to shared entities (languages, content types, components, repositories) are handled by the importer
+	#[instrument(skip(conn, self))]
+	pub fn insert(&self, conn: &mut PgConnection) -> Result<(), AppError> {
+		diesel::insert_into(sites::table)
+			.values(&self.site)
+			.execute(conn)?;
+
+		diesel::insert_into(workflow_states::table)
+			.values(&self.workflow_states)
+			.execute(conn)?;
+		diesel::insert_into(workflows::table)
+			.values(&self.workflows)
+			.execute(conn)?;
+		diesel::insert_into(workflow_transitions::table)
+			.values(&self.workflow_transitions)
+			.execute(conn)?;
+		diesel::insert_into(workflow_transition_requirements::table)
+			.values(&self.workflow_transition_requirements)
+			.execute(conn)?;
+
+		diesel::insert_into(content_components::table)
+			.values(&self.content_components)
+			.execute(conn)?;
+		diesel::insert_into(content_types::table)
+			.values(&self.content_types)
+			.execute(conn)?;
+		diesel::insert_into(content_types_field_groups::table)
+			.values(&self.content_types_field_groups)
+			.execute(conn)?;
+		diesel::insert_into(compartments::table)
+			.values(&self.compartments)
+			.execute(conn)?;
+		for chunk in self.fields.chunks(INSERT_CHUNK_SIZE) {
+			diesel::insert_into(fields::table)
+				.values(chunk)
+				.execute(conn)?;
+		}
+		for chunk in self.field_config.chunks(INSERT_CHUNK_SIZE) {
+			diesel::insert_into(field_config::table)
+				.values(chunk)
+				.execute(conn)?;
+		}
+		// Reused workflow states keep the labels they already have
+		for chunk in self.labels.chunks(INSERT_CHUNK_SIZE) {
+			diesel::insert_into(labels::table)
+				.values(chunk)
+				.on_conflict_do_nothing()
+				.execute(conn)?;
+		}
+
+		diesel::insert_into(iam_policies::table)
+			.values(&self.iam_policies)
+			.execute(conn)?;
+		diesel::insert_into(roles::table)
+			.values(&self.roles)
+			.execute(conn)?;
+		diesel::insert_into(roles_iam_policies::table)
+			.values(&self.roles_iam_policies)
+			.execute(conn)?;
+		diesel::insert_into(permissions::table)
+			.values(&self.permissions)
+			.execute(conn)?;
+		diesel::insert_into(permissions_iam_actions::table)
+			.values(&self.permissions_iam_actions)
+			.execute(conn)?;
+		diesel::insert_into(permissions_iam_conditions::table)
+			.values(&self.permissions_iam_conditions)
+			.execute(conn)?;
+
+		diesel::insert_into(webhooks::table)
+			.values(&self.webhooks)
+			.execute(conn)?;
+		diesel::insert_into(modules::table)
+			.values(&self.modules)
+			.execute(conn)?;
+		diesel::insert_into(config_items::table)
+			.values(&self.config_items)
+			.execute(conn)?;
+
+		for chunk in self.content.chunks(INSERT_CHUNK_SIZE) {
+			diesel::insert_into(content::table)
+				.values(chunk)
+				.execute(conn)?;
+		}
+		for chunk in self.content_revisions.chunks(INSERT_CHUNK_SIZE) {
+			diesel::insert_into(content_revisions::table)
+				.values(chunk)
+				.execute(conn)?;
+		}
+		for chunk in self.content_fields.chunks(INSERT_CHUNK_SIZE) {
+			diesel::insert_into(content_fields::table)
+				.values(chunk)
+				.execute(conn)?;
+		}
+		for chunk in self.redirects.chunks(INSERT_CHUNK_SIZE) {
+			diesel::insert_into(redirects::table)
+				.values(chunk)
+				.execute(conn)?;
+		}
+
+		diesel::insert_into(sites_storage_repositories::table)
+			.values(
+				self.site_storage_repository_ids
+					.iter()
+					.map(|storage_repository_id| CreateSiteStorageRepository {
+						site_id: self.site.id,
+						storage_repository_id: *storage_repository_id,
+					})
+					.collect::<Vec<CreateSiteStorageRepository>>(),
+			)
+			.execute(conn)?;
+
+		Ok(())
+	}
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = sites_storage_repositories)]
+pub struct CreateSiteStorageRepository {
+	pub site_id: Uuid,
+	pub storage_repository_id: Uuid,
+}
@@ -0,0 +1,205 @@
+use actix_web::http::StatusCode;
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::errors::{AppError, AppErrorValue};
+use crate::modules::roles::models::role::Role;
+use crate::modules::sites::models::site::Site;
+use crate::modules::sites::models::site_invitation_role::SiteInvitationRole;
+use crate::schema::{roles, site_invitations, site_invitations_roles};
+use crate::utils::hasher;
+use crate::utils::string::generate_random_string;
+
+const SITE_INVITATION_TOKEN_LENGTH: usize = 48;
+const SITE_INVITATION_LIFETIME_DAYS: i64 = 7;
+
+#[derive(Identifiable, Selectable, Queryable, Associations, Debug, Clone)]
+#[diesel(belongs_to(Site))]
+#[diesel(table_name = site_invitations)]
+#[diesel(primary_key(id))]
+pub struct SiteInvitation {
+	pub id: Uuid,
+	pub site_id: Uuid,
+	pub invited_by_id: Option<Uuid>,
+	pub email: String,
+	pub token_hash: String,
+	pub expires_at: NaiveDateTime,
+	pub accepted_at: Option<NaiveDateTime>,
+	pub created_at: NaiveDateTime,
+	pub updated_at: NaiveDateTime,
+}
+
+fn invalid_invitation_error() -> AppError {
+	AppError::BadRequest(AppErrorValue {
+		message: "Invitation is invalid or has expired".to_owned(),
+		status: StatusCode::BAD_REQUEST.as_u16(),
+		code: "INVALID_SITE_INVITATION".to_owned(),
+		..Default::default()
+	})
+}
+
+impl SiteInvitation {
+	// Returns the created invitation together with its plain token, only its hash is stored.
+	// Earlier pending invitations for the same address stop working.
+	#[instrument(skip(conn))]
+	pub fn create(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		invited_by_id: Option<Uuid>,
+		email: String,
+		role_ids: Vec<Uuid>,
+	) -> Result<((Self, Vec<Role>), String), AppError> {
+		let token = generate_random_string(SITE_INVITATION_TOKEN_LENGTH);
+
+		diesel::delete(
+			site_invitations::table
+				.filter(site_invitations::site_id.eq(site_id))
+				.filter(site_invitations::email.eq(&email))
+				.filter(site_invitations::accepted_at.is_null()),
+		)
+		.execute(conn)?;
+
+		let site_invitation = diesel::insert_into(site_invitations::table)
+			.values(CreateSiteInvitation {
+				site_id,
+				invited_by_id,
+				email,
+				token_hash: hasher::hash_token(&token),
+				expires_at: Utc::now().naive_utc() + Duration::days(SITE_INVITATION_LIFETIME_DAYS),
+			})
+			.returning(SiteInvitation::as_returning())
+			.get_result::<SiteInvitation>(conn)?;
+
+		SiteInvitationRole::upsert_many(conn, site_invitation.id, role_ids)?;
+		let roles = site_invitation.get_roles(conn)?;
+
+		Ok(((site_invitation, roles), token))
+	}
+
+	// Only lists invitations that have not been accepted yet, expired ones included so they can be sent again
+	#[instrument(skip(conn))]
+	pub fn find(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		page: i64,
+		pagesize: i64,
+	) -> Result<(Vec<(Self, Vec<Role>)>, i64), AppError> {
+		let query = {
+			let mut query = site_invitations::table
+				.filter(site_invitations::site_id.eq(site_id))
+				.filter(site_invitations::accepted_at.is_null())
+				.order(site_invitations::created_at.desc())
+				.into_boxed();
+
+			if pagesize != -1 {
+				query = query.offset((page - 1) * pagesize).limit(pagesize);
+			};
+
+			query
+		};
+
+		let site_invitations = query
+			.select(SiteInvitation::as_select())
+			.load::<SiteInvitation>(conn)?;
+		let site_invitation_roles = SiteInvitationRole::belonging_to(&site_invitations)
+			.inner_join(roles::table)
+			.select((SiteInvitationRole::as_select(), Role::as_select()))
+			.load::<(SiteInvitationRole, Role)>(conn)?;
+
+		let site_invitations_with_roles = site_invitation_roles
+			.grouped_by(&site_invitations)
+			.into_iter()
+			.zip(site_invitations)
+			.map(|(roles, site_invitation)| {
+				(
+					site_invitation,
+					roles.into_iter().map(|(_, role)| role).collect(),
+				)
+			})
+			.collect::<Vec<(SiteInvitation, Vec<Role>)>>();
+
+		let total_elements = site_invitations::table
+			.filter(site_invitations::site_id.eq(site_id))
+			.filter(site_invitations::accepted_at.is_null())
+			.count()
+			.get_result::<i64>(conn)?;
+
+		Ok((site_invitations_with_roles, total_elements))
+	}
+
+	#[instrument(skip(conn, token))]
+	pub fn find_pending_by_token(
+		conn: &mut PgConnection,
+		token: &str,
+	) -> Result<(Self, Vec<Role>), AppError> {
+		let site_invitation = site_invitations::table
+			.filter(site_invitations::token_hash.eq(hasher::hash_token(token)))
+			.filter(site_invitations::accepted_at.is_null())
+			.filter(site_invitations::expires_at.gt(Utc::now().naive_utc()))
+			.first::<SiteInvitation>(conn)
+			.optional()?
+			.ok_or_else(invalid_invitation_error)?;
+		let roles = site_invitation.get_roles(conn)?;
+
+		Ok((site_invitation, roles))
+	}
+
+	// Marking the invitation as accepted in the same statement that checks it keeps it single-use
+	#[instrument(skip(conn))]
+	pub fn accept(conn: &mut PgConnection, id: Uuid) -> Result<Self, AppError> {
+		let now = Utc::now().naive_utc();
+		let target = site_invitations::table
+			.find(id)
+			.filter(site_invitations::accepted_at.is_null())
+			.filter(site_invitations::expires_at.gt(now));
+
+		let site_invitation = diesel::update(target)
+			.set((
+				site_invitations::accepted_at.eq(now),
+				site_invitations::updated_at.eq(now),
+			))
+			.returning(SiteInvitation::as_returning())
+			.get_result::<SiteInvitation>(conn)
+			.optional()?
+			.ok_or_else(invalid_invitation_error)?;
+
+		Ok(site_invitation)
+	}
+
+	#[instrument(skip(conn))]
+	pub fn remove(conn: &mut PgConnection, site_id: Uuid, id: Uuid) -> Result<(), AppError> {
+		diesel::delete(
+			site_invitations::table
+				.filter(site_invitations::site_id.eq(site_id))
+				.filter(site_invitations::id.eq(id))
+				.filter(site_invitations::accepted_at.is_null()),
+		)
+		.get_result::<SiteInvitation>(conn)?;
+
+		Ok(())
+	}
+}
+
+impl SiteInvitation {
+	pub fn get_roles(&self, conn: &mut PgConnection) -> Result<Vec<Role>, AppError> {
+		let roles = site_invitations_roles::table
+			.filter(site_invitations_roles::site_invitation_id.eq(self.id))
+			.inner_join(roles::table)
+			.select(Role::as_select())
+			.load::<Role>(conn)?;
+
+		Ok(roles)
+	}
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = site_invitations)]
+pub struct CreateSiteInvitation {
+	pub site_id: Uuid,
+	pub invited_by_id: Option<Uuid>,
+	pub email: String,
+	pub token_hash: String,
+	pub expires_at: NaiveDateTime,
+}
@@ -0,0 +1,56 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::modules::roles::models::role::Role;
+use crate::modules::sites::models::site_invitation::SiteInvitation;
+
+use crate::errors::AppError;
+use crate::schema::site_invitations_roles;
+
+#[derive(Identifiable, Selectable, Queryable, Associations, Debug)]
+#[diesel(belongs_to(SiteInvitation))]
+#[diesel(belongs_to(Role))]
+#[diesel(table_name = site_invitations_roles)]
+#[diesel(primary_key(site_invitation_id, role_id))]
+pub struct SiteInvitationRole {
+	pub site_invitation_id: Uuid,
+	pub role_id: Uuid,
+	pub created_at: NaiveDateTime,
+	pub updated_at: NaiveDateTime,
+}
+
+impl SiteInvitationRole {
+	pub fn upsert_many(
+		conn: &mut PgConnection,
+		site_invitation_id: Uuid,
+		role_ids: Vec<Uuid>,
+	) -> Result<Vec<Self>, AppError> {
+		let target = site_invitations_roles::table
+			.filter(site_invitations_roles::site_invitation_id.eq(site_invitation_id));
+		diesel::delete(target).execute(conn)?;
+
+		let insert_items: Vec<CreateSiteInvitationRole> = role_ids
+			.into_iter()
+			.map(|role_id| CreateSiteInvitationRole {
+				site_invitation_id,
+				role_id,
+			})
+			.collect();
+
+		let site_invitation_roles = diesel::insert_into(site_invitations_roles::table)
+			.values(insert_items)
+			.returning(SiteInvitationRole::as_returning())
+			.get_results(conn)?;
+
+		Ok(site_invitation_roles)
+	}
+}
+
+#[derive(Insertable, Debug, Deserialize)]
+#[diesel(table_name = site_invitations_roles)]
+pub struct CreateSiteInvitationRole {
+	pub site_invitation_id: Uuid,
+	pub role_id: Uuid,
+}
@@ -22,6 +22,8 @@ pub struct SiteUserRole {
 	pub role_id: Uuid,
 	pub created_at: NaiveDateTime,
 	pub updated_at: NaiveDateTime,
+	// Set when an identity provider group granted the role, so it can be taken away with the group
+	pub granted_by_group: bool,
 }
 
 impl SiteUserRole {
@@ -67,6 +69,33 @@ impl SiteUserRole {
 		Ok(permissions_iam_actions)
 	}
 
+	// A role that was already assigned by hand stays that way
+	pub fn upsert_from_group(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+		site_id: Uuid,
+		role_id: Uuid,
+	) -> Result<(), AppError> {
+		diesel::insert_into(sites_users_roles::table)
+			.values((
+				CreateSiteUserRole {
+					user_id,
+					site_id,
+					role_id,
+				},
+				sites_users_roles::granted_by_group.eq(true),
+			))
+			.on_conflict((
+				sites_users_roles::user_id,
+				sites_users_roles::site_id,
+				sites_users_roles::role_id,
+			))
+			.do_nothing()
+			.execute(conn)?;
+
+		Ok(())
+	}
+
 	pub fn upsert_many(
 		conn: &mut PgConnection,
 		site_id: Uuid,
@@ -94,6 +123,50 @@ impl SiteUserRole {
 
 		Ok(permissions_iam_actions)
 	}
+
+	pub fn remove(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+		site_id: Uuid,
+		role_id: Uuid,
+	) -> Result<(), AppError> {
+		let target = sites_users_roles::table
+			.filter(sites_users_roles::user_id.eq(user_id))
+			.filter(sites_users_roles::site_id.eq(site_id))
+			.filter(sites_users_roles::role_id.eq(role_id));
+		diesel::delete(target).execute(conn)?;
+
+		Ok(())
+	}
+
+	pub fn remove_from_group(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+		site_id: Uuid,
+		role_id: Uuid,
+	) -> Result<(), AppError> {
+		let target = sites_users_roles::table
+			.filter(sites_users_roles::user_id.eq(user_id))
+			.filter(sites_users_roles::site_id.eq(site_id))
+			.filter(sites_users_roles::role_id.eq(role_id))
+			.filter(sites_users_roles::granted_by_group.eq(true));
+		diesel::delete(target).execute(conn)?;
+
+		Ok(())
+	}
+
+	pub fn remove_all(
+		conn: &mut PgConnection,
+		site_id: Uuid,
+		user_id: Uuid,
+	) -> Result<(), AppError> {
+		let target = sites_users_roles::table
+			.filter(sites_users_roles::site_id.eq(site_id))
+			.filter(sites_users_roles::user_id.eq(user_id));
+		diesel::delete(target).execute(conn)?;
+
+		Ok(())
+	}
 }
 
 #[derive(Insertable, Debug, Deserialize)]
@@ -0,0 +1,2 @@
+pub mod site_bundle_archive;
+pub mod site_import;
@@ -0,0 +1,226 @@
+use std::collections::{hash_map::Entry, HashMap};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use diesel::PgConnection;
+use reqwest::StatusCode;
+use tracing::instrument;
+use uuid::Uuid;
+use zip::{write::FileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::{
+	errors::{AppError, AppErrorValue},
+	modules::{
+		resources::engines::lib::{
+			ensure_resource_does_not_exist, find_resource_tree, get_storage_engine,
+			normalize_resource_path, split_path, ResourceItemKind,
+		},
+		sites::models::site_bundle::{SiteBundle, SITE_BUNDLE_VERSION},
+	},
+};
+
+const BUNDLE_ENTRY_NAME: &str = "bundle.json";
+const FILES_ENTRY_PREFIX: &str = "files/";
+
+// Entries are unpacked into memory, the limits keep a small archive from expanding into something huge
+const MAX_ENTRY_SIZE: u64 = 100 * 1024 * 1024;
+const MAX_ARCHIVE_SIZE: u64 = 1024 * 1024 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct SiteBundleFile {
+	pub storage_repository_id: Uuid,
+	pub path: String,
+	pub kind: ResourceItemKind,
+	pub contents: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SiteBundleArchive {
+	pub bundle: SiteBundle,
+	pub files: Vec<SiteBundleFile>,
+}
+
+fn invalid_bundle_error(reason: &str) -> AppError {
+	AppError::BadRequest(AppErrorValue {
+		message: format!("The uploaded file is not a valid site bundle: {reason}"),
+		status: StatusCode::BAD_REQUEST.as_u16(),
+		code: "INVALID_SITE_BUNDLE".to_owned(),
+		..Default::default()
+	})
+}
+
+// Files are stored below `files/<storage repository id>/`, directories keep their trailing slash
+fn get_entry_name(storage_repository_id: Uuid, path: &str, kind: &ResourceItemKind) -> String {
+	match kind {
+		ResourceItemKind::DIRECTORY => {
+			format!("{FILES_ENTRY_PREFIX}{storage_repository_id}/{path}/")
+		}
+		ResourceItemKind::FILE => format!("{FILES_ENTRY_PREFIX}{storage_repository_id}/{path}"),
+	}
+}
+
+// The declared size of an entry can't be trusted, so the reader is cut off one byte past the limit
+fn read_entry(entry: &mut impl Read, name: &str, remaining: &mut u64) -> Result<Vec<u8>, AppError> {
+	let limit = MAX_ENTRY_SIZE.min(*remaining);
+	let mut contents = vec![];
+	entry.take(limit + 1).read_to_end(&mut contents)?;
+
+	if contents.len() as u64 > limit {
+		return Err(invalid_bundle_error(&format!(
+			"{name} exceeds the size limit of the archive"
+		)));
+	}
+
+	*remaining -= contents.len() as u64;
+	Ok(contents)
+}
+
+fn parse_entry_name(name: &str) -> Result<Option<(Uuid, String)>, AppError> {
+	let Some(name) = name.strip_prefix(FILES_ENTRY_PREFIX) else {
+		return Ok(None);
+	};
+	let Some((storage_repository_id, path)) = name.split_once('/') else {
+		return Ok(None);
+	};
+	let storage_repository_id = Uuid::parse_str(storage_repository_id)
+		.map_err(|_| invalid_bundle_error(&format!("unexpected entry {name}")))?;
+	let path = normalize_resource_path(path)
+		.map_err(|_| invalid_bundle_error(&format!("unexpected entry {name}")))?;
+
+	Ok(Some((storage_repository_id, path)))
+}
+
+// The archive is written to a temporary file that is removed as soon as it is dropped,
+// so exports with files don't have to fit in memory
+#[instrument(skip(conn, bundle))]
+pub async fn write_archive(
+	conn: &mut PgConnection,
+	bundle: &SiteBundle,
+	include_files: bool,
+) -> Result<File, AppError> {
+	let mut writer = ZipWriter::new(tempfile::tempfile()?);
+	let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+	writer.start_file(BUNDLE_ENTRY_NAME, options)?;
+	writer.write_all(&serde_json::to_vec_pretty(bundle)?)?;
+
+	if include_files {
+		for storage_repository in &bundle.storage_repositories {
+			let engine = get_storage_engine(conn, storage_repository.id)?;
+			let tree = find_resource_tree(engine.as_ref()).await?;
+
+			for (path, kind) in tree {
+				let name = get_entry_name(storage_repository.id, &path, &kind);
+
+				match kind {
+					ResourceItemKind::DIRECTORY => writer.add_directory(name, options)?,
+					ResourceItemKind::FILE => {
+						let contents = engine.download_file(&path).await?;
+						writer.start_file(name, options)?;
+						writer.write_all(&contents)?;
+					}
+				}
+			}
+		}
+	}
+
+	let mut archive = writer.finish()?;
+	archive.seek(SeekFrom::Start(0))?;
+	Ok(archive)
+}
+
+#[instrument(skip(file))]
+pub fn read_archive(file: File) -> Result<SiteBundleArchive, AppError> {
+	let mut archive =
+		ZipArchive::new(file).map_err(|_| invalid_bundle_error("the archive can't be read"))?;
+
+	let mut remaining = MAX_ARCHIVE_SIZE;
+	let bundle: SiteBundle = {
+		let mut entry = archive
+			.by_name(BUNDLE_ENTRY_NAME)
+			.map_err(|_| invalid_bundle_error(&format!("{BUNDLE_ENTRY_NAME} is missing")))?;
+		let contents = read_entry(&mut entry, BUNDLE_ENTRY_NAME, &mut remaining)?;
+
+		serde_json::from_slice(&contents).map_err(|err| invalid_bundle_error(&err.to_string()))?
+	};
+
+	if bundle.version != SITE_BUNDLE_VERSION {
+		return Err(AppError::UnprocessableEntity(AppErrorValue {
+			message: format!(
+				"Site bundle version {} is not supported, expected version {SITE_BUNDLE_VERSION}",
+				bundle.version
+			),
+			status: StatusCode::UNPROCESSABLE_ENTITY.as_u16(),
+			code: "UNSUPPORTED_SITE_BUNDLE_VERSION".to_owned(),
+			..Default::default()
+		}));
+	}
+
+	let mut files = vec![];
+	for index in 0..archive.len() {
+		let mut entry = archive.by_index(index)?;
+		let Some((storage_repository_id, path)) = parse_entry_name(entry.name())? else {
+			continue;
+		};
+
+		if entry.is_dir() {
+			files.push(SiteBundleFile {
+				storage_repository_id,
+				path,
+				kind: ResourceItemKind::DIRECTORY,
+				contents: vec![],
+			});
+			continue;
+		}
+
+		let name = entry.name().to_owned();
+		let contents = read_entry(&mut entry, &name, &mut remaining)?;
+		files.push(SiteBundleFile {
+			storage_repository_id,
+			path,
+			kind: ResourceItemKind::FILE,
+			contents,
+		});
+	}
+
+	Ok(SiteBundleArchive { bundle, files })
+}
+
+// Writes the bundled files into the repositories they were mapped to, files of unmapped repositories are skipped.
+// Directories come before their contents in the archive, existing ones are left alone and existing files are overwritten
+#[instrument(skip(conn, files))]
+pub async fn write_files(
+	conn: &mut PgConnection,
+	files: Vec<SiteBundleFile>,
+	storage_repository_ids: &HashMap<Uuid, Uuid>,
+) -> Result<(), AppError> {
+	let mut engines = HashMap::new();
+
+	for file in files {
+		let Some(storage_repository_id) = storage_repository_ids.get(&file.storage_repository_id)
+		else {
+			continue;
+		};
+
+		let engine = match engines.entry(*storage_repository_id) {
+			Entry::Occupied(entry) => entry.into_mut(),
+			Entry::Vacant(entry) => entry.insert(get_storage_engine(conn, *storage_repository_id)?),
+		}
+		.as_ref();
+
+		match file.kind {
+			ResourceItemKind::DIRECTORY => {
+				if ensure_resource_does_not_exist(engine, &file.path)
+					.await
+					.is_ok()
+				{
+					let (parent, name) = split_path(&file.path);
+					engine.create_directory(&parent, &name).await?;
+				}
+			}
+			ResourceItemKind::FILE => engine.write_file(&file.path, file.contents).await?,
+		}
+	}
+
+	Ok(())
+}
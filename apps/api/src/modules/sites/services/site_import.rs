@@ -0,0 +1,787 @@
+use std::collections::{HashMap, HashSet};
+use std::mem::take;
+
+use diesel::prelude::*;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::instrument;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+	errors::AppError,
+	modules::{
+		content_components::models::site_content_component::SiteContentComponent,
+		content_types::models::content_type::ContentType,
+		sites::models::{
+			site_bundle::{FieldRecord, SiteBundle, StorageRepositoryRecord},
+			site_language::SiteLanguage,
+		},
+	},
+	schema::{
+		content_components, content_types, iam_actions, iam_conditions, languages, sites,
+		storage_repositories, workflow_states, workflows,
+	},
+};
+
+lazy_static! {
+	// Ids don't only live in id columns, references, iam resources and field config carry them as text
+	static ref UUID_PATTERN: Regex = Regex::new(
+		r"[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}"
+	)
+	.unwrap();
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[allow(non_camel_case_types)]
+pub enum SiteImportConflictKindEnum {
+	SITE,
+	LANGUAGE,
+	STORAGE_REPOSITORY,
+	WORKFLOW_STATE,
+	WORKFLOW,
+	CONTENT_COMPONENT,
+	CONTENT_TYPE,
+	IAM_ACTION,
+	IAM_CONDITION,
+}
+
+#[derive(Debug, Clone)]
+pub struct SiteImportConflict {
+	pub kind: SiteImportConflictKindEnum,
+	pub key: String,
+	pub message: String,
+	// Blocking conflicts prevent the import, the others describe what the import will do differently
+	pub blocking: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SiteImportOptions {
+	pub slug: Option<String>,
+	pub name: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SiteImportPlan {
+	// The bundle with remapped ids, reduced to the records that still have to be created
+	pub bundle: SiteBundle,
+	// Maps the repositories of the bundle onto the repositories of this instance
+	pub storage_repository_ids: HashMap<Uuid, Uuid>,
+	pub conflicts: Vec<SiteImportConflict>,
+}
+
+impl SiteImportPlan {
+	pub fn is_blocked(&self) -> bool {
+		self.conflicts.iter().any(|conflict| conflict.blocking)
+	}
+}
+
+#[derive(Debug, Default)]
+struct IdMap(HashMap<Uuid, Uuid>);
+
+impl IdMap {
+	// Records created by the import get a fresh id, the same source id always maps to the same new id
+	fn remap(&mut self, id: Uuid) -> Uuid {
+		*self.0.entry(id).or_insert_with(Uuid::new_v4)
+	}
+
+	fn reuse(&mut self, id: Uuid, target_id: Uuid) {
+		self.0.insert(id, target_id);
+	}
+
+	fn get(&self, id: Uuid) -> Option<Uuid> {
+		self.0.get(&id).copied()
+	}
+
+	fn remap_text(&self, text: &str) -> String {
+		UUID_PATTERN
+			.replace_all(text, |captures: &Captures| {
+				let matched = &captures[0];
+
+				Uuid::parse_str(matched)
+					.ok()
+					.and_then(|id| self.get(id))
+					.map(|id| id.to_string())
+					.unwrap_or_else(|| matched.to_owned())
+			})
+			.into_owned()
+	}
+
+	fn remap_value(&self, value: Value) -> Value {
+		match value {
+			Value::String(text) => Value::String(self.remap_text(&text)),
+			Value::Array(items) => Value::Array(
+				items
+					.into_iter()
+					.map(|item| self.remap_value(item))
+					.collect(),
+			),
+			Value::Object(entries) => Value::Object(
+				entries
+					.into_iter()
+					.map(|(key, item)| (key, self.remap_value(item)))
+					.collect(),
+			),
+			value => value,
+		}
+	}
+}
+
+fn conflict(
+	kind: SiteImportConflictKindEnum,
+	key: &str,
+	message: String,
+	blocking: bool,
+) -> SiteImportConflict {
+	SiteImportConflict {
+		kind,
+		key: key.to_owned(),
+		message,
+		blocking,
+	}
+}
+
+// Shared entities are matched on id first (the same instance or a previous import) and on slug second
+fn find_existing(existing: &[(Uuid, String)], id: Uuid, slug: &str) -> Option<Uuid> {
+	existing
+		.iter()
+		.find(|(existing_id, _)| *existing_id == id)
+		.or_else(|| {
+			existing
+				.iter()
+				.find(|(_, existing_slug)| existing_slug == slug)
+		})
+		.map(|(existing_id, _)| *existing_id)
+}
+
+// Works out what importing the bundle would do without writing anything. Languages, storage repositories and
+// iam keys are never created, workflows, workflow states, content components and content types are shared
+// between sites so existing ones are reused, everything else is created with new ids.
+// Records pointing at something that isn't part of the bundle (e.g. deleted in the source) are dropped.
+#[instrument(skip(conn, bundle))]
+pub fn plan_import(
+	conn: &mut PgConnection,
+	bundle: SiteBundle,
+	options: SiteImportOptions,
+	user_id: Uuid,
+) -> Result<SiteImportPlan, AppError> {
+	let mut bundle = bundle;
+	let mut ids = IdMap::default();
+	let mut conflicts = vec![];
+
+	// Site
+	let site_id = ids.remap(bundle.site.id);
+	let slug = options.slug.unwrap_or_else(|| bundle.site.slug.clone());
+	let slug_taken = sites::table
+		.filter(sites::slug.eq(&slug))
+		.count()
+		.get_result::<i64>(conn)?
+		> 0;
+	if slug_taken {
+		conflicts.push(conflict(
+			SiteImportConflictKindEnum::SITE,
+			&slug,
+			format!("A site with the slug {slug} already exists, import it under another slug"),
+			true,
+		));
+	}
+	bundle.site.id = site_id;
+	bundle.site.slug = slug;
+	if let Some(name) = options.name {
+		bundle.site.name = name;
+	}
+
+	// Languages
+	let existing_languages = languages::table
+		.filter(
+			languages::key.eq_any(
+				bundle
+					.languages
+					.iter()
+					.map(|language| language.key.clone())
+					.collect::<Vec<String>>(),
+			),
+		)
+		.select((languages::key, languages::id))
+		.load::<(String, Uuid)>(conn)?
+		.into_iter()
+		.collect::<HashMap<String, Uuid>>();
+	for language in &bundle.languages {
+		match existing_languages.get(&language.key) {
+			Some(language_id) => ids.reuse(language.id, *language_id),
+			None => conflicts.push(conflict(
+				SiteImportConflictKindEnum::LANGUAGE,
+				&language.key,
+				format!("Language {} does not exist in this instance", language.key),
+				true,
+			)),
+		}
+	}
+	bundle.site_language_ids = bundle
+		.site_language_ids
+		.iter()
+		.filter_map(|id| ids.get(*id))
+		.collect();
+
+	// Storage repositories
+	let existing_storage_repositories = storage_repositories::table
+		.select(StorageRepositoryRecord::as_select())
+		.load::<StorageRepositoryRecord>(conn)?;
+	let mut storage_repository_ids = HashMap::new();
+	for storage_repository in &bundle.storage_repositories {
+		let existing_storage_repository = existing_storage_repositories
+			.iter()
+			.find(|existing| existing.id == storage_repository.id)
+			.or_else(|| {
+				existing_storage_repositories.iter().find(|existing| {
+					existing.name == storage_repository.name
+						&& existing.kind == storage_repository.kind
+				})
+			});
+
+		match existing_storage_repository {
+			Some(existing) => {
+				ids.reuse(storage_repository.id, existing.id);
+				storage_repository_ids.insert(storage_repository.id, existing.id);
+			}
+			None => conflicts.push(conflict(
+				SiteImportConflictKindEnum::STORAGE_REPOSITORY,
+				&storage_repository.name,
+				format!(
+					"Storage repository {} ({}) does not exist in this instance, its files are skipped",
+					storage_repository.name, storage_repository.kind
+				),
+				false,
+			)),
+		}
+	}
+	bundle.site_storage_repository_ids = bundle
+		.site_storage_repository_ids
+		.iter()
+		.filter_map(|id| ids.get(*id))
+		.collect();
+
+	// Workflow states
+	let existing_workflow_states = workflow_states::table
+		.filter(workflow_states::deleted.eq(false))
+		.filter(
+			workflow_states::id
+				.eq_any(
+					bundle
+						.workflow_states
+						.iter()
+						.map(|state| state.id)
+						.collect::<Vec<Uuid>>(),
+				)
+				.or(workflow_states::slug.eq_any(
+					bundle
+						.workflow_states
+						.iter()
+						.map(|state| state.slug.clone())
+						.collect::<Vec<String>>(),
+				)),
+		)
+		.select((workflow_states::id, workflow_states::slug))
+		.load::<(Uuid, String)>(conn)?;
+	bundle.workflow_states = take(&mut bundle.workflow_states)
+		.into_iter()
+		.filter_map(|mut state| {
+			if let Some(existing_id) =
+				find_existing(&existing_workflow_states, state.id, &state.slug)
+			{
+				ids.reuse(state.id, existing_id);
+				if !state.internal {
+					conflicts.push(conflict(
+						SiteImportConflictKindEnum::WORKFLOW_STATE,
+						&state.slug,
+						format!("Workflow state {} already exists and is reused", state.slug),
+						false,
+					));
+				}
+				return None;
+			}
+
+			state.id = ids.remap(state.id);
+			Some(state)
+		})
+		.collect();
+
+	// Workflows, reused workflows keep their own transitions
+	let existing_workflows = workflows::table
+		.filter(workflows::deleted.eq(false))
+		.filter(
+			workflows::id
+				.eq_any(
+					bundle
+						.workflows
+						.iter()
+						.map(|workflow| workflow.id)
+						.collect::<Vec<Uuid>>(),
+				)
+				.or(workflows::slug.eq_any(
+					bundle
+						.workflows
+						.iter()
+						.map(|workflow| workflow.slug.clone())
+						.collect::<Vec<String>>(),
+				)),
+		)
+		.select((workflows::id, workflows::slug))
+		.load::<(Uuid, String)>(conn)?;
+	let mut created_workflow_ids = HashSet::new();
+	bundle.workflows = take(&mut bundle.workflows)
+		.into_iter()
+		.filter_map(|mut workflow| {
+			if let Some(existing_id) =
+				find_existing(&existing_workflows, workflow.id, &workflow.slug)
+			{
+				ids.reuse(workflow.id, existing_id);
+				if !workflow.internal {
+					conflicts.push(conflict(
+						SiteImportConflictKindEnum::WORKFLOW,
+						&workflow.slug,
+						format!("Workflow {} already exists and is reused", workflow.slug),
+						false,
+					));
+				}
+				return None;
+			}
+
+			created_workflow_ids.insert(workflow.id);
+			workflow.default_workflow_state_id = ids.get(workflow.default_workflow_state_id)?;
+			workflow.id = ids.remap(workflow.id);
+			Some(workflow)
+		})
+		.collect();
+	bundle.workflow_transitions = take(&mut bundle.workflow_transitions)
+		.into_iter()
+		.filter(|transition| created_workflow_ids.contains(&transition.workflow_id))
+		.filter_map(|mut transition| {
+			transition.workflow_id = ids.get(transition.workflow_id)?;
+			transition.from_workflow_state_id = ids.get(transition.from_workflow_state_id)?;
+			transition.to_workflow_state_id = ids.get(transition.to_workflow_state_id)?;
+			transition.id = ids.remap(transition.id);
+			Some(transition)
+		})
+		.collect();
+	bundle.workflow_transition_requirements = take(&mut bundle.workflow_transition_requirements)
+		.into_iter()
+		.filter_map(|mut requirement| {
+			requirement.workflow_transition_id = ids.get(requirement.workflow_transition_id)?;
+			requirement.id = ids.remap(requirement.id);
+			Some(requirement)
+		})
+		.collect();
+
+	// Content components, internal ones are seeded with the same id in every instance
+	let existing_content_components = content_components::table
+		.filter(content_components::deleted.eq(false))
+		.filter(
+			content_components::id
+				.eq_any(
+					bundle
+						.content_components
+						.iter()
+						.map(|component| component.id)
+						.collect::<Vec<Uuid>>(),
+				)
+				.or(content_components::slug.eq_any(
+					bundle
+						.content_components
+						.iter()
+						.map(|component| component.slug.clone())
+						.collect::<Vec<String>>(),
+				)),
+		)
+		.select((content_components::id, content_components::slug))
+		.load::<(Uuid, String)>(conn)?;
+	let mut created_parent_ids = HashSet::new();
+	bundle.content_components = take(&mut bundle.content_components)
+		.into_iter()
+		.filter_map(|mut component| {
+			if let Some(existing_id) =
+				find_existing(&existing_content_components, component.id, &component.slug)
+			{
+				ids.reuse(component.id, existing_id);
+				if !component.internal {
+					conflicts.push(conflict(
+						SiteImportConflictKindEnum::CONTENT_COMPONENT,
+						&component.slug,
+						format!(
+							"Content component {} already exists and is reused",
+							component.slug
+						),
+						false,
+					));
+				}
+				return None;
+			}
+
+			if component.internal {
+				conflicts.push(conflict(
+					SiteImportConflictKindEnum::CONTENT_COMPONENT,
+					&component.slug,
+					format!(
+						"Internal content component {} is missing, this instance needs to be migrated first",
+						component.slug
+					),
+					true,
+				));
+				return None;
+			}
+
+			created_parent_ids.insert(component.id);
+			component.id = ids.remap(component.id);
+			Some(component)
+		})
+		.collect();
+	bundle.site_content_component_ids = bundle
+		.site_content_component_ids
+		.iter()
+		.filter_map(|id| ids.get(*id))
+		.collect();
+
+	// Content types, reused content types keep their own fields
+	let existing_content_types = content_types::table
+		.filter(content_types::deleted.eq(false))
+		.filter(
+			content_types::id
+				.eq_any(
+					bundle
+						.content_types
+						.iter()
+						.map(|content_type| content_type.id)
+						.collect::<Vec<Uuid>>(),
+				)
+				.or(content_types::slug.eq_any(
+					bundle
+						.content_types
+						.iter()
+						.map(|content_type| content_type.slug.clone())
+						.collect::<Vec<String>>(),
+				)),
+		)
+		.select((content_types::id, content_types::slug))
+		.load::<(Uuid, String)>(conn)?;
+	bundle.content_types = take(&mut bundle.content_types)
+		.into_iter()
+		.filter_map(|mut content_type| {
+			if let Some(existing_id) =
+				find_existing(&existing_content_types, content_type.id, &content_type.slug)
+			{
+				ids.reuse(content_type.id, existing_id);
+				conflicts.push(conflict(
+					SiteImportConflictKindEnum::CONTENT_TYPE,
+					&content_type.slug,
+					format!(
+						"Content type {} already exists and is reused",
+						content_type.slug
+					),
+					false,
+				));
+				return None;
+			}
+
+			created_parent_ids.insert(content_type.id);
+			content_type.workflow_id = ids.get(content_type.workflow_id)?;
+			content_type.id = ids.remap(content_type.id);
+			Some(content_type)
+		})
+		.collect();
+	bundle.site_content_type_ids = bundle
+		.site_content_type_ids
+		.iter()
+		.filter_map(|id| ids.get(*id))
+		.collect();
+	bundle.content_types_field_groups = take(&mut bundle.content_types_field_groups)
+		.into_iter()
+		.filter_map(|mut included| {
+			included.content_type_id = ids.get(included.content_type_id)?;
+			included.field_group_id = ids.get(included.field_group_id)?;
+			Some(included)
+		})
+		.collect();
+	bundle.compartments = take(&mut bundle.compartments)
+		.into_iter()
+		.filter(|compartment| created_parent_ids.contains(&compartment.content_type_id))
+		.filter_map(|mut compartment| {
+			compartment.content_type_id = ids.get(compartment.content_type_id)?;
+			compartment.id = ids.remap(compartment.id);
+			Some(compartment)
+		})
+		.collect();
+
+	// Fields of the created content types and components, including the fields of their blocks
+	let mut pending_fields = take(&mut bundle.fields);
+	let mut created_fields: Vec<FieldRecord> = vec![];
+	loop {
+		let (matched_fields, other_fields): (Vec<FieldRecord>, Vec<FieldRecord>) = pending_fields
+			.into_iter()
+			.partition(|field| created_parent_ids.contains(&field.parent_id));
+		if matched_fields.is_empty() {
+			break;
+		}
+
+		created_parent_ids.extend(matched_fields.iter().map(|field| field.id));
+		created_fields.extend(matched_fields);
+		pending_fields = other_fields;
+	}
+	for field in &created_fields {
+		ids.remap(field.id);
+	}
+	bundle.fields = created_fields
+		.into_iter()
+		.filter_map(|mut field| {
+			field.id = ids.get(field.id)?;
+			field.parent_id = ids.get(field.parent_id)?;
+			field.content_component_id = ids.get(field.content_component_id)?;
+			field.compartment_id = field.compartment_id.and_then(|id| ids.get(id));
+			Some(field)
+		})
+		.collect();
+	bundle.field_config = take(&mut bundle.field_config)
+		.into_iter()
+		.filter(|config| created_parent_ids.contains(&config.field_id))
+		.filter_map(|mut config| {
+			config.field_id = ids.get(config.field_id)?;
+			config.id = ids.remap(config.id);
+			Some(config)
+		})
+		.collect();
+
+	// Roles and policies
+	for policy in &mut bundle.iam_policies {
+		policy.id = ids.remap(policy.id);
+		policy.site_id = Some(site_id);
+	}
+	for role in &mut bundle.roles {
+		role.id = ids.remap(role.id);
+		role.site_id = Some(site_id);
+	}
+	bundle.roles_iam_policies = take(&mut bundle.roles_iam_policies)
+		.into_iter()
+		.filter_map(|mut role_policy| {
+			role_policy.role_id = ids.get(role_policy.role_id)?;
+			role_policy.iam_policy_id = ids.get(role_policy.iam_policy_id)?;
+			Some(role_policy)
+		})
+		.collect();
+	bundle.permissions = take(&mut bundle.permissions)
+		.into_iter()
+		.filter_map(|mut permission| {
+			permission.iam_policy_id = ids.get(permission.iam_policy_id)?;
+			permission.id = ids.remap(permission.id);
+			Some(permission)
+		})
+		.collect();
+
+	let existing_iam_actions = iam_actions::table
+		.select(iam_actions::key)
+		.load::<String>(conn)?
+		.into_iter()
+		.collect::<HashSet<String>>();
+	let mut missing_iam_actions = HashSet::new();
+	bundle.permissions_iam_actions = take(&mut bundle.permissions_iam_actions)
+		.into_iter()
+		.filter_map(|mut action| {
+			if !existing_iam_actions.contains(&action.iam_action_key) {
+				if missing_iam_actions.insert(action.iam_action_key.clone()) {
+					conflicts.push(conflict(
+						SiteImportConflictKindEnum::IAM_ACTION,
+						&action.iam_action_key,
+						format!(
+							"IAM action {} does not exist in this instance and is left out of the policies",
+							action.iam_action_key
+						),
+						false,
+					));
+				}
+				return None;
+			}
+
+			action.permission_id = ids.get(action.permission_id)?;
+			Some(action)
+		})
+		.collect();
+
+	let existing_iam_conditions = iam_conditions::table
+		.select(iam_conditions::key)
+		.load::<String>(conn)?
+		.into_iter()
+		.collect::<HashSet<String>>();
+	let mut missing_iam_conditions = HashSet::new();
+	bundle.permissions_iam_conditions = take(&mut bundle.permissions_iam_conditions)
+		.into_iter()
+		.filter_map(|mut condition| {
+			if !existing_iam_conditions.contains(&condition.iam_condition_key) {
+				if missing_iam_conditions.insert(condition.iam_condition_key.clone()) {
+					conflicts.push(conflict(
+						SiteImportConflictKindEnum::IAM_CONDITION,
+						&condition.iam_condition_key,
+						format!(
+							"IAM condition {} does not exist in this instance and is left out of the policies",
+							condition.iam_condition_key
+						),
+						false,
+					));
+				}
+				return None;
+			}
+
+			condition.permission_id = ids.get(condition.permission_id)?;
+			Some(condition)
+		})
+		.collect();
+
+	// Integrations
+	for webhook in &mut bundle.webhooks {
+		webhook.id = ids.remap(webhook.id);
+		webhook.site_id = site_id;
+	}
+	for module in &mut bundle.modules {
+		module.id = ids.remap(module.id);
+		module.site_id = site_id;
+	}
+	for config_item in &mut bundle.config_items {
+		config_item.id = ids.remap(config_item.id);
+		config_item.site_id = Some(site_id);
+	}
+
+	// Content, the revisions are attributed to the importing user since users aren't part of the bundle
+	bundle.content = take(&mut bundle.content)
+		.into_iter()
+		.filter_map(|mut content_item| {
+			content_item.content_type_id = ids.get(content_item.content_type_id)?;
+			content_item.language_id = ids.get(content_item.language_id)?;
+			content_item.workflow_state_id = ids.get(content_item.workflow_state_id)?;
+			content_item.id = ids.remap(content_item.id);
+			content_item.translation_id = ids.remap(content_item.translation_id);
+			content_item.site_id = site_id;
+			Some(content_item)
+		})
+		.collect();
+	// Parents that didn't make it into the site leave their children at the root
+	for content_item in bundle.content.iter_mut() {
+		content_item.parent_id = content_item.parent_id.and_then(|id| ids.get(id));
+	}
+	bundle.content_revisions = take(&mut bundle.content_revisions)
+		.into_iter()
+		.filter_map(|mut revision| {
+			revision.content_id = ids.get(revision.content_id)?;
+			revision.workflow_state_id = ids.get(revision.workflow_state_id)?;
+			revision.id = ids.remap(revision.id);
+			revision.revision_translation_id = ids.remap(revision.revision_translation_id);
+			revision.site_id = site_id;
+			revision.user_id = user_id;
+			Some(revision)
+		})
+		.collect();
+	for content_field in &bundle.content_fields {
+		if ids.get(content_field.source_id).is_some() {
+			ids.remap(content_field.id);
+		}
+	}
+	bundle.content_fields = take(&mut bundle.content_fields)
+		.into_iter()
+		.filter_map(|mut content_field| {
+			content_field.id = ids.get(content_field.id)?;
+			content_field.source_id = ids.get(content_field.source_id)?;
+			content_field.parent_id = match content_field.parent_id {
+				Some(parent_id) => Some(ids.get(parent_id)?),
+				None => None,
+			};
+			content_field.content_component_id = content_field
+				.content_component_id
+				.map(|id| ids.get(id).unwrap_or(id));
+			Some(content_field)
+		})
+		.collect();
+
+	// Labels follow the entity they describe, redirects to content that didn't make it are left out
+	bundle.labels = take(&mut bundle.labels)
+		.into_iter()
+		.filter_map(|mut label| {
+			label.entity_id = ids.get(label.entity_id)?;
+			label.id = ids.remap(label.id);
+			Some(label)
+		})
+		.collect();
+	bundle.redirects = take(&mut bundle.redirects)
+		.into_iter()
+		.filter_map(|mut redirect| {
+			redirect.language_id = match redirect.language_id {
+				Some(language_id) => Some(ids.get(language_id)?),
+				None => None,
+			};
+			redirect.content_id = match redirect.content_id {
+				Some(content_id) => Some(ids.get(content_id)?),
+				None => None,
+			};
+			redirect.id = ids.remap(redirect.id);
+			redirect.site_id = site_id;
+			Some(redirect)
+		})
+		.collect();
+
+	// Now that every id is known, the ids hidden in values can be remapped as well
+	for requirement in &mut bundle.workflow_transition_requirements {
+		requirement.value = ids.remap_value(take(&mut requirement.value));
+	}
+	for config in &mut bundle.field_config {
+		config.content = config
+			.content
+			.as_deref()
+			.map(|content| ids.remap_text(content));
+	}
+	for permission in &mut bundle.permissions {
+		permission.resources = ids.remap_value(take(&mut permission.resources));
+	}
+	for condition in &mut bundle.permissions_iam_conditions {
+		condition.value = ids.remap_value(take(&mut condition.value));
+	}
+	for webhook in &mut bundle.webhooks {
+		webhook.request_configuration = webhook
+			.request_configuration
+			.take()
+			.map(|value| ids.remap_value(value));
+	}
+	for config_item in &mut bundle.config_items {
+		config_item.value = config_item.value.take().map(|value| ids.remap_value(value));
+	}
+	for content_field in &mut bundle.content_fields {
+		content_field.value = content_field
+			.value
+			.take()
+			.map(|value| ids.remap_value(value));
+	}
+
+	Ok(SiteImportPlan {
+		bundle,
+		storage_repository_ids,
+		conflicts,
+	})
+}
+
+// Creates everything the plan describes, in one transaction so a failing import leaves nothing behind
+#[instrument(skip(conn, plan))]
+pub fn apply_import(conn: &mut PgConnection, plan: &SiteImportPlan) -> Result<Uuid, AppError> {
+	let bundle = &plan.bundle;
+	let site_id = bundle.site.id;
+
+	conn.transaction::<_, AppError, _>(|conn| {
+		bundle.insert(conn)?;
+
+		SiteLanguage::upsert(conn, site_id, bundle.site_language_ids.clone())?;
+		for content_type_id in &bundle.site_content_type_ids {
+			ContentType::enable_site(conn, *content_type_id, site_id)?;
+		}
+		for content_component_id in &bundle.site_content_component_ids {
+			SiteContentComponent::create(conn, site_id, *content_component_id)?;
+		}
+
+		Ok(())
+	})?;
+
+	Ok(site_id)
+}
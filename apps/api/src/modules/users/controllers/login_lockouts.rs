@@ -0,0 +1,53 @@
+use super::super::dto::login_lockouts::response;
+
+use crate::errors::AppError;
+use crate::modules::auth::helpers::permissions::ensure_permission;
+use crate::modules::core::middleware::state::AppState;
+use crate::modules::core::models::hal::HALPage;
+use crate::modules::users::models::login_lockout::LoginLockout;
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+#[derive(Deserialize, IntoParams)]
+pub struct FindAllQueryParams {
+	page: Option<i64>,
+	pagesize: Option<i64>,
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/login-lockouts",
+	responses(
+		(status = 200, body = LoginLockoutsDTO),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindAllQueryParams)
+)]
+#[get("")]
+pub async fn find_all(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	query: web::Query<FindAllQueryParams>,
+) -> Result<HttpResponse, AppError> {
+	ensure_permission(&req, None, format!("urn:dcm:users:*"), "root::users:read")?;
+	let conn = &mut state.get_conn()?;
+	let page = query.page.unwrap_or(1);
+	let pagesize = query.pagesize.unwrap_or(20);
+
+	let (login_lockouts, total_elements) = LoginLockout::find(conn, page, pagesize)?;
+
+	let res = response::LoginLockoutsDTO::from((
+		login_lockouts,
+		HALPage {
+			number: page,
+			size: pagesize,
+			total_elements,
+			total_pages: (total_elements / pagesize + (total_elements % pagesize).signum()).max(1),
+		},
+	));
+
+	Ok(HttpResponse::Ok().json(res))
+}
@@ -1,2 +1,3 @@
+pub mod login_lockouts;
 pub mod site_users;
 pub mod users;
@@ -1,11 +1,12 @@
 use super::super::dto::users::{request, response};
 use crate::errors::AppError;
+use crate::modules::api_keys::models::api_key::ApiKey;
 use crate::modules::auth::helpers::permissions::ensure_permission;
 use crate::modules::core::middleware::state::AppState;
 use crate::modules::core::models::hal::HALPage;
 use crate::modules::sites::models::{site_user::SiteUser, site_user_role::SiteUserRole};
 use crate::modules::users::models::user::User;
-use actix_web::{get, put, web, HttpRequest, HttpResponse};
+use actix_web::{delete, get, put, web, HttpRequest, HttpResponse};
 use serde::Deserialize;
 use utoipa::IntoParams;
 use uuid::Uuid;
@@ -155,3 +156,43 @@ pub async fn update(
 	let user = User::find_one_with_roles_in_site(conn, params.site_id, params.user_id)?;
 	Ok(HttpResponse::Ok().json(user))
 }
+
+#[utoipa::path(
+	context_path = "/api/v1/sites/{site_id}/users",
+	responses(
+		(status = 204),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindPathParams)
+)]
+#[delete("/{user_id}")]
+pub async fn remove(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindPathParams>,
+) -> Result<HttpResponse, AppError> {
+	ensure_permission(
+		&req,
+		Some(params.site_id),
+		format!("urn:dcm:users:{}", params.user_id),
+		"sites::users:remove",
+	)
+	.or_else(|_| {
+		ensure_permission(
+			&req,
+			None,
+			format!("urn:dcm:users:{}", params.user_id),
+			"root::users:remove",
+		)
+	})?;
+	let conn = &mut state.get_conn()?;
+
+	SiteUserRole::remove_all(conn, params.site_id, params.user_id)?;
+	SiteUser::remove(conn, params.site_id, params.user_id)?;
+	ApiKey::remove_for_user(conn, params.site_id, params.user_id)?;
+
+	Ok(HttpResponse::NoContent().body(()))
+}
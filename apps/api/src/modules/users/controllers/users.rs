@@ -1,10 +1,15 @@
 use super::super::dto::users::{request, response};
 
 use crate::modules::auth::helpers::permissions::ensure_permission;
+use crate::modules::auth::services::account;
+use crate::modules::auth::services::login_protection;
+use crate::modules::auth::services::password_policy::validate_password;
+use crate::modules::auth::services::two_factor;
 use crate::modules::authentication_methods::models::authentication_method::AuthenticationMethod;
 use crate::modules::core::middleware::state::AppState;
 use crate::modules::core::models::hal::HALPage;
 use crate::modules::users::models::user::{UpdateUser, User};
+use crate::modules::users::models::user_session::UserSession;
 use crate::{errors::AppError, modules::users::models::user_role::UserRole};
 use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse};
 use serde::Deserialize;
@@ -41,9 +46,10 @@ pub async fn create(
 ) -> Result<HttpResponse, AppError> {
 	ensure_permission(&req, None, format!("urn:dcm:users:*"), "root::users:create")?;
 	let conn = &mut state.get_conn()?;
+	validate_password(&form.password)?;
 
 	let local_auth_method = AuthenticationMethod::find_local(conn)?;
-	let (user, _token) = User::signup(
+	let user = User::signup(
 		conn,
 		&form.email,
 		&form.name,
@@ -52,6 +58,7 @@ pub async fn create(
 		local_auth_method.id,
 	)?;
 	UserRole::upsert_many(conn, user.id, form.roles.clone())?;
+	account::send_email_verification(conn, &state.mail_addr, &user)?;
 	Ok(HttpResponse::Ok().json(user))
 }
 
@@ -99,6 +106,123 @@ pub async fn find_all(
 	Ok(HttpResponse::Ok().json(res))
 }
 
+#[utoipa::path(
+	context_path = "/api/v1/users",
+	responses(
+		(status = 204),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindPathParams)
+)]
+#[delete("/{user_id}/sessions")]
+pub async fn revoke_sessions(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindPathParams>,
+) -> Result<HttpResponse, AppError> {
+	ensure_permission(
+		&req,
+		None,
+		format!("urn:dcm:users:{}", params.user_id),
+		"root::users:update",
+	)?;
+	let conn = &mut state.get_conn()?;
+	UserSession::revoke_all(conn, params.user_id, None)?;
+	Ok(HttpResponse::NoContent().body(()))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/users",
+	responses(
+		(status = 204),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindPathParams)
+)]
+#[post("/{user_id}/password-reset")]
+pub async fn send_password_reset(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindPathParams>,
+) -> Result<HttpResponse, AppError> {
+	ensure_permission(
+		&req,
+		None,
+		format!("urn:dcm:users:{}", params.user_id),
+		"root::users:update",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let user = User::find_one(conn, params.user_id)?;
+	account::send_password_reset(conn, &state.mail_addr, &user)?;
+	Ok(HttpResponse::NoContent().body(()))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/users",
+	responses(
+		(status = 204),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindPathParams)
+)]
+#[delete("/{user_id}/two-factor")]
+pub async fn reset_two_factor(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindPathParams>,
+) -> Result<HttpResponse, AppError> {
+	ensure_permission(
+		&req,
+		None,
+		format!("urn:dcm:users:{}", params.user_id),
+		"root::users:update",
+	)?;
+	let conn = &mut state.get_conn()?;
+
+	// Users that lost their device and recovery codes enrol again on their next login if their roles require it
+	two_factor::disable(conn, params.user_id)?;
+	UserSession::revoke_all(conn, params.user_id, None)?;
+	Ok(HttpResponse::NoContent().body(()))
+}
+
+#[utoipa::path(
+	context_path = "/api/v1/users",
+	responses(
+		(status = 204),
+		(status = 401, body = AppErrorValue, description = "Unauthorized")
+	),
+    security(
+        ("jwt_token" = [])
+    ),
+	params(FindPathParams)
+)]
+#[delete("/{user_id}/lockout")]
+pub async fn unlock(
+	req: HttpRequest,
+	state: web::Data<AppState>,
+	params: web::Path<FindPathParams>,
+) -> Result<HttpResponse, AppError> {
+	ensure_permission(
+		&req,
+		None,
+		format!("urn:dcm:users:{}", params.user_id),
+		"root::users:update",
+	)?;
+	let conn = &mut state.get_conn()?;
+	let user = User::find_one(conn, params.user_id)?;
+	login_protection::clear_account_lockout(conn, user.authentication_method_id, &user.email)?;
+	Ok(HttpResponse::NoContent().body(()))
+}
+
 #[utoipa::path(
 	context_path = "/api/v1/users",
 	responses(
@@ -165,6 +289,7 @@ pub async fn update(
 			avatar: None,
 			password: None,
 			bio: None,
+			language_key: None,
 		},
 	)?;
 	UserRole::upsert_many(conn, params.user_id, form.roles.clone())?;
@@ -0,0 +1,65 @@
+use crate::modules::{
+	core::models::hal::{HALLinkList, HALPage},
+	users::models::{login_lockout::LoginLockout, login_throttle::LoginThrottleScopeEnum},
+};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::convert::From;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginLockoutDTO {
+	pub id: Uuid,
+	pub scope: LoginThrottleScopeEnum,
+	pub identifier: String,
+	pub failed_attempts: i32,
+	pub ip_address: Option<String>,
+	pub user_agent: Option<String>,
+	pub locked_until: NaiveDateTime,
+	pub created_at: NaiveDateTime,
+}
+
+impl From<LoginLockout> for LoginLockoutDTO {
+	fn from(login_lockout: LoginLockout) -> Self {
+		Self {
+			id: login_lockout.id,
+			scope: login_lockout.scope,
+			identifier: login_lockout.identifier,
+			failed_attempts: login_lockout.failed_attempts,
+			ip_address: login_lockout.ip_address,
+			user_agent: login_lockout.user_agent,
+			locked_until: login_lockout.locked_until,
+			created_at: login_lockout.created_at,
+		}
+	}
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginLockoutsEmbeddedDTO {
+	pub login_lockouts: Vec<LoginLockoutDTO>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, ToSchema)]
+pub struct LoginLockoutsDTO {
+	pub _links: HALLinkList,
+	pub _page: HALPage,
+	pub _embedded: LoginLockoutsEmbeddedDTO,
+}
+
+impl From<(Vec<LoginLockout>, HALPage)> for LoginLockoutsDTO {
+	fn from((login_lockouts, page): (Vec<LoginLockout>, HALPage)) -> Self {
+		Self {
+			_links: HALLinkList::from(("/api/v1/login-lockouts".to_owned(), &page)),
+			_embedded: LoginLockoutsEmbeddedDTO {
+				login_lockouts: login_lockouts
+					.into_iter()
+					.map(LoginLockoutDTO::from)
+					.collect(),
+			},
+			_page: page,
+		}
+	}
+}
@@ -1 +1,2 @@
+pub mod login_lockouts;
 pub mod users;
@@ -0,0 +1,74 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::modules::users::models::login_throttle::LoginThrottleScopeEnum;
+use crate::schema::login_lockouts;
+
+#[derive(Identifiable, Selectable, Queryable, Debug, Clone)]
+#[diesel(table_name = login_lockouts)]
+#[diesel(primary_key(id))]
+pub struct LoginLockout {
+	pub id: Uuid,
+	pub scope: LoginThrottleScopeEnum,
+	pub identifier: String,
+	pub failed_attempts: i32,
+	pub ip_address: Option<String>,
+	pub user_agent: Option<String>,
+	pub locked_until: NaiveDateTime,
+	pub created_at: NaiveDateTime,
+}
+
+impl LoginLockout {
+	#[instrument(skip(conn))]
+	pub fn create(
+		conn: &mut PgConnection,
+		login_lockout: CreateLoginLockout,
+	) -> Result<Self, AppError> {
+		let login_lockout = diesel::insert_into(login_lockouts::table)
+			.values(login_lockout)
+			.returning(LoginLockout::as_returning())
+			.get_result(conn)?;
+
+		Ok(login_lockout)
+	}
+
+	#[instrument(skip(conn))]
+	pub fn find(
+		conn: &mut PgConnection,
+		page: i64,
+		pagesize: i64,
+	) -> Result<(Vec<Self>, i64), AppError> {
+		let query = {
+			let mut query = login_lockouts::table
+				.order(login_lockouts::created_at.desc())
+				.into_boxed();
+
+			if pagesize != -1 {
+				query = query.offset((page - 1) * pagesize).limit(pagesize);
+			};
+
+			query
+		};
+
+		let login_lockouts = query
+			.select(LoginLockout::as_select())
+			.load::<LoginLockout>(conn)?;
+		let total_elements = login_lockouts::table.count().get_result::<i64>(conn)?;
+
+		Ok((login_lockouts, total_elements))
+	}
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = login_lockouts)]
+pub struct CreateLoginLockout {
+	pub scope: LoginThrottleScopeEnum,
+	pub identifier: String,
+	pub failed_attempts: i32,
+	pub ip_address: Option<String>,
+	pub user_agent: Option<String>,
+	pub locked_until: NaiveDateTime,
+}
@@ -0,0 +1,170 @@
+use std::io::Write;
+
+use chrono::{NaiveDateTime, Utc};
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::{Pg, PgValue};
+use diesel::prelude::*;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::{AsExpression, FromSqlRow};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::schema::{login_throttles, sql_types::LoginThrottleScopes};
+
+#[derive(
+	Debug, PartialEq, FromSqlRow, AsExpression, Eq, Clone, Copy, Deserialize, Serialize, ToSchema,
+)]
+#[diesel(sql_type = LoginThrottleScopes)]
+#[allow(non_camel_case_types)]
+pub enum LoginThrottleScopeEnum {
+	ACCOUNT,
+	IP_ADDRESS,
+}
+
+impl ToSql<LoginThrottleScopes, Pg> for LoginThrottleScopeEnum {
+	fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+		match *self {
+			LoginThrottleScopeEnum::ACCOUNT => out.write_all(b"ACCOUNT")?,
+			LoginThrottleScopeEnum::IP_ADDRESS => out.write_all(b"IP_ADDRESS")?,
+		}
+		Ok(IsNull::No)
+	}
+}
+
+impl FromSql<LoginThrottleScopes, Pg> for LoginThrottleScopeEnum {
+	fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+		match bytes.as_bytes() {
+			b"ACCOUNT" => Ok(LoginThrottleScopeEnum::ACCOUNT),
+			b"IP_ADDRESS" => Ok(LoginThrottleScopeEnum::IP_ADDRESS),
+			_ => Err("Unrecognized enum variant".into()),
+		}
+	}
+}
+
+#[derive(Identifiable, Selectable, Queryable, Debug, Clone)]
+#[diesel(table_name = login_throttles)]
+#[diesel(primary_key(id))]
+pub struct LoginThrottle {
+	pub id: Uuid,
+	pub scope: LoginThrottleScopeEnum,
+	pub identifier: String,
+	pub failed_attempts: i32,
+	pub locked_until: Option<NaiveDateTime>,
+	pub last_failed_at: NaiveDateTime,
+}
+
+impl LoginThrottle {
+	// Counts the attempt in the same statement that reads the counter, so concurrent attempts
+	// can never see the same number
+	#[instrument(skip(conn))]
+	pub fn increment(
+		conn: &mut PgConnection,
+		scope: LoginThrottleScopeEnum,
+		identifier: &str,
+	) -> Result<Self, AppError> {
+		let now = Utc::now().naive_utc();
+		let login_throttle = diesel::insert_into(login_throttles::table)
+			.values((
+				login_throttles::scope.eq(scope),
+				login_throttles::identifier.eq(identifier),
+				login_throttles::failed_attempts.eq(1),
+				login_throttles::last_failed_at.eq(now),
+			))
+			.on_conflict((login_throttles::scope, login_throttles::identifier))
+			.do_update()
+			.set((
+				login_throttles::failed_attempts.eq(login_throttles::failed_attempts + 1),
+				login_throttles::last_failed_at.eq(now),
+			))
+			.returning(LoginThrottle::as_returning())
+			.get_result::<LoginThrottle>(conn)?;
+
+		Ok(login_throttle)
+	}
+
+	#[instrument(skip(conn))]
+	pub fn decrement(
+		conn: &mut PgConnection,
+		scope: LoginThrottleScopeEnum,
+		identifier: &str,
+	) -> Result<(), AppError> {
+		diesel::update(
+			login_throttles::table
+				.filter(login_throttles::scope.eq(scope))
+				.filter(login_throttles::identifier.eq(identifier))
+				.filter(login_throttles::failed_attempts.gt(0)),
+		)
+		.set(login_throttles::failed_attempts.eq(login_throttles::failed_attempts - 1))
+		.execute(conn)?;
+
+		Ok(())
+	}
+
+	#[instrument(skip(conn))]
+	pub fn set_locked_until(
+		conn: &mut PgConnection,
+		scope: LoginThrottleScopeEnum,
+		identifier: &str,
+		locked_until: Option<NaiveDateTime>,
+	) -> Result<(), AppError> {
+		diesel::update(
+			login_throttles::table
+				.filter(login_throttles::scope.eq(scope))
+				.filter(login_throttles::identifier.eq(identifier)),
+		)
+		.set(login_throttles::locked_until.eq(locked_until))
+		.execute(conn)?;
+
+		Ok(())
+	}
+
+	// Unlocked counters without failures since `failed_before` start over
+	#[instrument(skip(conn))]
+	pub fn remove_stale(
+		conn: &mut PgConnection,
+		scope: LoginThrottleScopeEnum,
+		identifier: &str,
+		failed_before: NaiveDateTime,
+	) -> Result<(), AppError> {
+		let now = Utc::now().naive_utc();
+		diesel::delete(
+			login_throttles::table
+				.filter(login_throttles::scope.eq(scope))
+				.filter(login_throttles::identifier.eq(identifier))
+				.filter(login_throttles::last_failed_at.le(failed_before))
+				.filter(
+					login_throttles::locked_until
+						.is_null()
+						.or(login_throttles::locked_until.le(now)),
+				),
+		)
+		.execute(conn)?;
+
+		Ok(())
+	}
+
+	#[instrument(skip(conn))]
+	pub fn remove(
+		conn: &mut PgConnection,
+		scope: LoginThrottleScopeEnum,
+		identifier: &str,
+	) -> Result<(), AppError> {
+		diesel::delete(
+			login_throttles::table
+				.filter(login_throttles::scope.eq(scope))
+				.filter(login_throttles::identifier.eq(identifier)),
+		)
+		.execute(conn)?;
+
+		Ok(())
+	}
+
+	pub fn is_locked(&self) -> bool {
+		self.locked_until
+			.map(|locked_until| locked_until > Utc::now().naive_utc())
+			.unwrap_or(false)
+	}
+}
@@ -1,2 +1,7 @@
+pub mod login_lockout;
+pub mod login_throttle;
 pub mod user;
+pub mod user_recovery_code;
 pub mod user_role;
+pub mod user_session;
+pub mod user_token;
@@ -1,3 +1,4 @@
+use crate::constants;
 use crate::errors::{AppError, AppErrorValue};
 use crate::modules::authentication_methods::models::authentication_method::AuthenticationMethod;
 use crate::modules::iam_policies::models::iam_policy::IAMPolicy;
@@ -11,22 +12,31 @@ use crate::modules::sites::models::site_language::SiteLanguage;
 use crate::modules::sites::models::site_user::SiteUser;
 use crate::modules::sites::models::site_user_role::SiteUserRole;
 use crate::modules::users::models::user_role::UserRole;
+use crate::modules::users::models::user_session::{SessionMetadata, UserSession};
 use crate::schema::{
 	authentication_methods, iam_policies, languages, roles, roles_iam_policies, sites,
 	sites_languages, sites_users, sites_users_roles, users, users_roles,
 };
-use crate::utils::{hasher, token};
+use crate::utils::hasher;
+use crate::utils::token::AuthTokens;
 use actix_web::http::StatusCode;
-use chrono::prelude::*;
-use chrono::NaiveDateTime;
+use chrono::{NaiveDateTime, Utc};
 use diesel::backend::Backend;
 use diesel::dsl::{AsSelect, Eq, Filter, Select};
 use diesel::pg::PgConnection;
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::env;
 use tracing::instrument;
 use uuid::Uuid;
 
+// Existing installations keep working without a mail server unless verification is turned on
+fn email_verification_required() -> bool {
+	env::var(constants::env_key::REQUIRE_EMAIL_VERIFICATION)
+		.map(|value| value == "true")
+		.unwrap_or(false)
+}
+
 #[derive(Identifiable, Queryable, Selectable, Serialize, Deserialize, Debug, Clone)]
 #[diesel(table_name = users)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
@@ -40,10 +50,17 @@ pub struct User {
 	pub authentication_method_id: Uuid,
 	pub created_at: NaiveDateTime,
 	pub updated_at: NaiveDateTime,
+	pub email_verified_at: Option<NaiveDateTime>,
+	#[serde(skip_serializing)]
+	pub totp_secret: Option<String>,
+	pub totp_enabled_at: Option<NaiveDateTime>,
+	#[serde(skip_serializing)]
+	pub totp_last_step: Option<i64>,
+	pub deactivated_at: Option<NaiveDateTime>,
+	// Key of the language labels are shown in, see `labels`
+	pub language_key: Option<String>,
 }
 
-type Token = String;
-
 type All<DB> = Select<users::table, AsSelect<User, DB>>;
 type WithName<T> = Eq<users::name, T>;
 type ByUsername<DB, T> = Filter<All<DB>, WithName<T>>;
@@ -67,7 +84,7 @@ impl User {
 		Self::all().filter(Self::with_name(name))
 	}
 
-	fn find_by_email_and_source(
+	pub fn find_by_email_and_source(
 		conn: &mut PgConnection,
 		email: &str,
 		authentication_method_id: Uuid,
@@ -91,7 +108,7 @@ impl User {
 		naive_password: &'a str,
 		avatar: Option<&'a str>,
 		authentication_method_id: Uuid,
-	) -> Result<(User, Token), AppError> {
+	) -> Result<User, AppError> {
 		use diesel::prelude::*;
 		let hashed_password = hasher::hash_password(naive_password)?;
 
@@ -108,17 +125,17 @@ impl User {
 			.returning(User::as_returning())
 			.get_result::<User>(conn)?;
 
-		let token = user.generate_token(None)?;
-		Ok((user, token))
+		Ok(user)
 	}
 
+	// Checks the credentials without starting a session, a second factor might still be needed
 	#[instrument(skip(conn, naive_password))]
-	pub fn signin_local(
+	pub fn authenticate_local(
 		conn: &mut PgConnection,
 		email: &str,
 		naive_password: &str,
 		authentication_method_id: Uuid,
-	) -> Result<(User, Token), AppError> {
+	) -> Result<User, AppError> {
 		let user = Self::find_by_email_and_source(conn, email, authentication_method_id)?;
 
 		match user {
@@ -140,19 +157,28 @@ impl User {
 					}));
 				}
 
-				let token = user.generate_token(None)?;
-				Ok((user, token))
+				if user.email_verified_at.is_none() && email_verification_required() {
+					return Err(AppError::Forbidden(AppErrorValue {
+						message: "Email address has not been verified yet".to_owned(),
+						status: StatusCode::FORBIDDEN.as_u16(),
+						code: "EMAIL_NOT_VERIFIED".to_owned(),
+						..Default::default()
+					}));
+				}
+
+				Ok(user)
 			}
 		}
 	}
 
-	#[instrument(skip(conn))]
+	#[instrument(skip(conn, external_token))]
 	pub fn signin_social(
 		conn: &mut PgConnection,
 		email: &str,
 		authentication_method_id: Uuid,
 		external_token: Option<String>,
-	) -> Result<(User, Token), AppError> {
+		metadata: &SessionMetadata,
+	) -> Result<(User, AuthTokens), AppError> {
 		let user = Self::find_by_email_and_source(conn, email, authentication_method_id)?;
 
 		match user {
@@ -163,8 +189,8 @@ impl User {
 				..Default::default()
 			})),
 			Some(user) => {
-				let token = user.generate_token(external_token)?;
-				Ok((user, token))
+				let tokens = user.start_session(conn, external_token, metadata)?;
+				Ok((user, tokens))
 			}
 		}
 	}
@@ -358,13 +384,159 @@ impl User {
 		let user = t.first::<User>(conn)?;
 		Ok(user)
 	}
+
+	// Only accounts of the local authentication method have a password we manage
+	pub fn find_local_by_email(
+		conn: &mut PgConnection,
+		email: &str,
+	) -> Result<Option<Self>, AppError> {
+		let local_auth_method = AuthenticationMethod::find_local(conn)?;
+		let user = Self::find_by_email_and_source(conn, email, local_auth_method.id)?;
+		Ok(user)
+	}
+
+	#[instrument(skip(conn, naive_password))]
+	pub fn set_password(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+		naive_password: &str,
+	) -> Result<Self, AppError> {
+		let hashed_password = hasher::hash_password(naive_password)?;
+		let user = diesel::update(users::table.find(user_id))
+			.set((
+				users::password.eq(hashed_password),
+				users::updated_at.eq(Utc::now().naive_utc()),
+			))
+			.returning(User::as_returning())
+			.get_result::<User>(conn)?;
+		Ok(user)
+	}
+
+	// Setting a new secret, or none at all, always leaves two-factor authentication disabled until it is confirmed
+	#[instrument(skip(conn, totp_secret))]
+	pub fn set_totp_secret(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+		totp_secret: Option<String>,
+	) -> Result<Self, AppError> {
+		let user = diesel::update(users::table.find(user_id))
+			.set((
+				users::totp_secret.eq(totp_secret),
+				users::totp_enabled_at.eq(None::<NaiveDateTime>),
+				users::updated_at.eq(Utc::now().naive_utc()),
+			))
+			.returning(User::as_returning())
+			.get_result::<User>(conn)?;
+		Ok(user)
+	}
+
+	pub fn enable_totp(conn: &mut PgConnection, user_id: Uuid) -> Result<Self, AppError> {
+		let user = diesel::update(users::table.find(user_id))
+			.set(users::totp_enabled_at.eq(Utc::now().naive_utc()))
+			.returning(User::as_returning())
+			.get_result::<User>(conn)?;
+		Ok(user)
+	}
+
+	// Only moves forward, so two requests with the same code can't both get through
+	#[instrument(skip(conn))]
+	pub fn use_totp_step(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+		step: i64,
+	) -> Result<bool, AppError> {
+		let updated_rows = diesel::update(
+			users::table.find(user_id).filter(
+				users::totp_last_step
+					.is_null()
+					.or(users::totp_last_step.lt(step)),
+			),
+		)
+		.set(users::totp_last_step.eq(step))
+		.execute(conn)?;
+		Ok(updated_rows > 0)
+	}
+
+	// Deactivated accounts are kept with their roles, but can't sign in until they are activated again
+	#[instrument(skip(conn))]
+	pub fn set_active(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+		active: bool,
+	) -> Result<Self, AppError> {
+		let deactivated_at = if active {
+			None
+		} else {
+			Some(Utc::now().naive_utc())
+		};
+		let target = users::table
+			.find(user_id)
+			.filter(users::deactivated_at.is_null().eq(!active));
+		let user = diesel::update(target)
+			.set((
+				users::deactivated_at.eq(deactivated_at),
+				users::updated_at.eq(Utc::now().naive_utc()),
+			))
+			.returning(User::as_returning())
+			.get_result::<User>(conn)
+			.optional()?;
+
+		match user {
+			Some(user) => {
+				if !active {
+					UserSession::revoke_all(conn, user.id, None)?;
+				}
+				Ok(user)
+			}
+			// Already in the requested state
+			None => Self::find_one(conn, user_id),
+		}
+	}
+
+	pub fn mark_email_verified(conn: &mut PgConnection, user_id: Uuid) -> Result<Self, AppError> {
+		let user = diesel::update(users::table.find(user_id))
+			.set(users::email_verified_at.eq(Utc::now().naive_utc()))
+			.returning(User::as_returning())
+			.get_result::<User>(conn)?;
+		Ok(user)
+	}
+
+	pub fn clear_email_verification(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+	) -> Result<Self, AppError> {
+		let user = diesel::update(users::table.find(user_id))
+			.set(users::email_verified_at.eq(None::<NaiveDateTime>))
+			.returning(User::as_returning())
+			.get_result::<User>(conn)?;
+		Ok(user)
+	}
 }
 
 impl User {
-	pub fn generate_token(&self, external_token: Option<String>) -> Result<String, AppError> {
-		let now = Utc::now().timestamp_millis() / 1_000; // milli -> second
-		let token = token::generate(self.id, external_token, now)?;
-		Ok(token)
+	pub fn ensure_active(&self) -> Result<(), AppError> {
+		if self.deactivated_at.is_some() {
+			return Err(AppError::Forbidden(AppErrorValue {
+				message: "Account has been deactivated".to_owned(),
+				status: StatusCode::FORBIDDEN.as_u16(),
+				code: "ACCOUNT_DEACTIVATED".to_owned(),
+				..Default::default()
+			}));
+		}
+
+		Ok(())
+	}
+
+	pub fn start_session(
+		&self,
+		conn: &mut PgConnection,
+		external_token: Option<String>,
+		metadata: &SessionMetadata,
+	) -> Result<AuthTokens, AppError> {
+		self.ensure_active()?;
+
+		let (_session, tokens) = UserSession::start(conn, self.id, metadata, external_token)?;
+		Ok(tokens)
 	}
 }
 
@@ -593,4 +765,5 @@ pub struct UpdateUser {
 	pub password: Option<String>,
 	pub avatar: Option<String>,
 	pub bio: Option<String>,
+	pub language_key: Option<String>,
 }
@@ -0,0 +1,110 @@
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::modules::users::models::user::User;
+use crate::schema::user_recovery_codes;
+use crate::utils::hasher;
+use crate::utils::string::generate_random_string;
+
+const RECOVERY_CODE_COUNT: usize = 10;
+const RECOVERY_CODE_LENGTH: usize = 12;
+
+#[derive(Identifiable, Selectable, Queryable, Associations, Debug, Clone)]
+#[diesel(belongs_to(User))]
+#[diesel(table_name = user_recovery_codes)]
+#[diesel(primary_key(id))]
+pub struct UserRecoveryCode {
+	pub id: Uuid,
+	pub user_id: Uuid,
+	pub code_hash: String,
+	pub used_at: Option<NaiveDateTime>,
+	pub created_at: NaiveDateTime,
+}
+
+// Codes are shown in groups of four characters, dashes and casing typed back by the user are ignored
+fn normalize_code(code: &str) -> String {
+	code.chars()
+		.filter(|character| character.is_ascii_alphanumeric())
+		.collect::<String>()
+		.to_ascii_uppercase()
+}
+
+impl UserRecoveryCode {
+	// Returns the plain codes, only their hashes are stored. Earlier codes stop working.
+	#[instrument(skip(conn))]
+	pub fn regenerate(conn: &mut PgConnection, user_id: Uuid) -> Result<Vec<String>, AppError> {
+		let codes: Vec<String> = (0..RECOVERY_CODE_COUNT)
+			.map(|_| normalize_code(&generate_random_string(RECOVERY_CODE_LENGTH)))
+			.collect();
+
+		Self::remove_all(conn, user_id)?;
+
+		diesel::insert_into(user_recovery_codes::table)
+			.values(
+				codes
+					.iter()
+					.map(|code| CreateUserRecoveryCode {
+						user_id,
+						code_hash: hasher::hash_token(code),
+					})
+					.collect::<Vec<CreateUserRecoveryCode>>(),
+			)
+			.execute(conn)?;
+
+		Ok(codes
+			.into_iter()
+			.map(|code| {
+				code.chars()
+					.collect::<Vec<char>>()
+					.chunks(4)
+					.map(|chunk| chunk.iter().collect::<String>())
+					.collect::<Vec<String>>()
+					.join("-")
+			})
+			.collect())
+	}
+
+	// Marking the code as used in the same statement that looks it up keeps it single-use
+	#[instrument(skip(conn, code))]
+	pub fn consume(conn: &mut PgConnection, user_id: Uuid, code: &str) -> Result<bool, AppError> {
+		let target = user_recovery_codes::table
+			.filter(user_recovery_codes::user_id.eq(user_id))
+			.filter(user_recovery_codes::code_hash.eq(hasher::hash_token(&normalize_code(code))))
+			.filter(user_recovery_codes::used_at.is_null());
+
+		let consumed = diesel::update(target)
+			.set(user_recovery_codes::used_at.eq(Utc::now().naive_utc()))
+			.execute(conn)?;
+
+		Ok(consumed > 0)
+	}
+
+	#[instrument(skip(conn))]
+	pub fn count_remaining(conn: &mut PgConnection, user_id: Uuid) -> Result<i64, AppError> {
+		let count = user_recovery_codes::table
+			.filter(user_recovery_codes::user_id.eq(user_id))
+			.filter(user_recovery_codes::used_at.is_null())
+			.count()
+			.get_result::<i64>(conn)?;
+
+		Ok(count)
+	}
+
+	#[instrument(skip(conn))]
+	pub fn remove_all(conn: &mut PgConnection, user_id: Uuid) -> Result<(), AppError> {
+		diesel::delete(user_recovery_codes::table.filter(user_recovery_codes::user_id.eq(user_id)))
+			.execute(conn)?;
+
+		Ok(())
+	}
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = user_recovery_codes)]
+pub struct CreateUserRecoveryCode {
+	pub user_id: Uuid,
+	pub code_hash: String,
+}
@@ -19,6 +19,8 @@ pub struct UserRole {
 	pub role_id: Uuid,
 	pub created_at: NaiveDateTime,
 	pub updated_at: NaiveDateTime,
+	// Set when an identity provider group granted the role, so it can be taken away with the group
+	pub granted_by_group: bool,
 }
 
 impl UserRole {
@@ -70,6 +72,47 @@ impl UserRole {
 
 		Ok(permissions_iam_actions)
 	}
+
+	// A role that was already assigned by hand stays that way
+	pub fn upsert_from_group(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+		role_id: Uuid,
+	) -> Result<(), AppError> {
+		diesel::insert_into(users_roles::table)
+			.values((
+				CreateUserRole { user_id, role_id },
+				users_roles::granted_by_group.eq(true),
+			))
+			.on_conflict((users_roles::user_id, users_roles::role_id))
+			.do_nothing()
+			.execute(conn)?;
+
+		Ok(())
+	}
+
+	pub fn remove_from_group(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+		role_id: Uuid,
+	) -> Result<(), AppError> {
+		let target = users_roles::table
+			.filter(users_roles::user_id.eq(user_id))
+			.filter(users_roles::role_id.eq(role_id))
+			.filter(users_roles::granted_by_group.eq(true));
+		diesel::delete(target).execute(conn)?;
+
+		Ok(())
+	}
+
+	pub fn remove(conn: &mut PgConnection, user_id: Uuid, role_id: Uuid) -> Result<(), AppError> {
+		let target = users_roles::table
+			.filter(users_roles::user_id.eq(user_id))
+			.filter(users_roles::role_id.eq(role_id));
+		diesel::delete(target).execute(conn)?;
+
+		Ok(())
+	}
 }
 
 #[derive(Insertable, Debug, Deserialize)]
@@ -0,0 +1,232 @@
+use actix_web::http::StatusCode;
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::errors::{AppError, AppErrorValue};
+use crate::modules::users::models::user::User;
+use crate::schema::user_sessions;
+use crate::utils::hasher;
+use crate::utils::string::generate_random_string;
+use crate::utils::token::{self, AuthTokens};
+
+const SESSION_LIFETIME_DAYS: i64 = 30;
+const REFRESH_TOKEN_SECRET_LENGTH: usize = 64;
+
+#[derive(Debug, Clone, Default)]
+pub struct SessionMetadata {
+	pub user_agent: Option<String>,
+	pub ip_address: Option<String>,
+}
+
+#[derive(Identifiable, Selectable, Queryable, Associations, Debug, Clone)]
+#[diesel(belongs_to(User))]
+#[diesel(table_name = user_sessions)]
+#[diesel(primary_key(id))]
+pub struct UserSession {
+	pub id: Uuid,
+	pub user_id: Uuid,
+	pub refresh_token_hash: String,
+	// Kept after a rotation, so a replayed refresh token can be told apart from a wrong one
+	pub previous_refresh_token_hash: Option<String>,
+	// Token of the external provider the user logged in with, carried into every refreshed access token
+	pub external_token: Option<String>,
+	pub user_agent: Option<String>,
+	pub ip_address: Option<String>,
+	pub expires_at: NaiveDateTime,
+	pub revoked_at: Option<NaiveDateTime>,
+	pub last_used_at: NaiveDateTime,
+	pub created_at: NaiveDateTime,
+	pub updated_at: NaiveDateTime,
+}
+
+fn invalid_refresh_token_error() -> AppError {
+	AppError::Unauthorized(AppErrorValue {
+		message: "Refresh token is invalid or expired".to_owned(),
+		status: StatusCode::UNAUTHORIZED.as_u16(),
+		code: "INVALID_REFRESH_TOKEN".to_owned(),
+		..Default::default()
+	})
+}
+
+impl UserSession {
+	#[instrument(skip(conn, external_token))]
+	pub fn start(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+		metadata: &SessionMetadata,
+		external_token: Option<String>,
+	) -> Result<(Self, AuthTokens), AppError> {
+		let secret = generate_random_string(REFRESH_TOKEN_SECRET_LENGTH);
+		let now = Utc::now().naive_utc();
+
+		let session = diesel::insert_into(user_sessions::table)
+			.values(CreateUserSession {
+				user_id,
+				refresh_token_hash: hasher::hash_password(&secret)?,
+				external_token: external_token.clone(),
+				user_agent: metadata.user_agent.clone(),
+				ip_address: metadata.ip_address.clone(),
+				expires_at: now + Duration::days(SESSION_LIFETIME_DAYS),
+			})
+			.returning(UserSession::as_returning())
+			.get_result::<UserSession>(conn)?;
+
+		let tokens = session.issue_tokens(&secret, external_token)?;
+		Ok((session, tokens))
+	}
+
+	#[instrument(skip(conn, refresh_token))]
+	pub fn refresh(
+		conn: &mut PgConnection,
+		refresh_token: &str,
+	) -> Result<(Self, AuthTokens), AppError> {
+		let (session_id, secret) =
+			token::parse_refresh_token(refresh_token).ok_or_else(invalid_refresh_token_error)?;
+
+		let session = user_sessions::table
+			.find(session_id)
+			.first::<UserSession>(conn)
+			.optional()?
+			.ok_or_else(invalid_refresh_token_error)?;
+
+		if !session.is_active() {
+			return Err(invalid_refresh_token_error());
+		}
+
+		if !hasher::verify(secret, &session.refresh_token_hash)? {
+			// Only the token that was rotated away counts as a replay, anything else is just a wrong token.
+			// Session ids end up in every access token, so revoking on any mismatch would let anyone log users out.
+			let replayed = match &session.previous_refresh_token_hash {
+				Some(previous_refresh_token_hash) => {
+					hasher::verify(secret, previous_refresh_token_hash)?
+				}
+				None => false,
+			};
+			if replayed {
+				Self::revoke(conn, session.user_id, session.id)?;
+			}
+
+			return Err(invalid_refresh_token_error());
+		}
+
+		let new_secret = generate_random_string(REFRESH_TOKEN_SECRET_LENGTH);
+		let now = Utc::now().naive_utc();
+
+		// Matching on the previous hash makes concurrent refreshes with the same token fail
+		let target = user_sessions::table
+			.find(session.id)
+			.filter(user_sessions::refresh_token_hash.eq(&session.refresh_token_hash));
+		let session = diesel::update(target)
+			.set((
+				user_sessions::refresh_token_hash.eq(hasher::hash_password(&new_secret)?),
+				user_sessions::previous_refresh_token_hash.eq(&session.refresh_token_hash),
+				user_sessions::expires_at.eq(now + Duration::days(SESSION_LIFETIME_DAYS)),
+				user_sessions::last_used_at.eq(now),
+				user_sessions::updated_at.eq(now),
+			))
+			.returning(UserSession::as_returning())
+			.get_result::<UserSession>(conn)
+			.optional()?
+			.ok_or_else(invalid_refresh_token_error)?;
+
+		let tokens = session.issue_tokens(&new_secret, session.external_token.clone())?;
+		Ok((session, tokens))
+	}
+
+	#[instrument(skip(conn))]
+	pub fn find_one(conn: &mut PgConnection, id: Uuid) -> Result<Self, AppError> {
+		let session = user_sessions::table.find(id).first::<UserSession>(conn)?;
+
+		Ok(session)
+	}
+
+	#[instrument(skip(conn))]
+	pub fn find_active(conn: &mut PgConnection, user_id: Uuid) -> Result<Vec<Self>, AppError> {
+		let sessions = user_sessions::table
+			.filter(user_sessions::user_id.eq(user_id))
+			.filter(user_sessions::revoked_at.is_null())
+			.filter(user_sessions::expires_at.gt(Utc::now().naive_utc()))
+			.order(user_sessions::last_used_at.desc())
+			.select(UserSession::as_select())
+			.load::<UserSession>(conn)?;
+
+		Ok(sessions)
+	}
+
+	#[instrument(skip(conn))]
+	pub fn revoke(conn: &mut PgConnection, user_id: Uuid, id: Uuid) -> Result<(), AppError> {
+		let now = Utc::now().naive_utc();
+		let target = user_sessions::table
+			.find(id)
+			.filter(user_sessions::user_id.eq(user_id))
+			.filter(user_sessions::revoked_at.is_null());
+		diesel::update(target)
+			.set((
+				user_sessions::revoked_at.eq(now),
+				user_sessions::updated_at.eq(now),
+			))
+			.execute(conn)?;
+
+		Ok(())
+	}
+
+	#[instrument(skip(conn))]
+	pub fn revoke_all(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+		except_session_id: Option<Uuid>,
+	) -> Result<(), AppError> {
+		let now = Utc::now().naive_utc();
+		let target = user_sessions::table
+			.filter(user_sessions::user_id.eq(user_id))
+			.filter(user_sessions::revoked_at.is_null())
+			.filter(user_sessions::id.ne_all(except_session_id.into_iter().collect::<Vec<Uuid>>()));
+		diesel::update(target)
+			.set((
+				user_sessions::revoked_at.eq(now),
+				user_sessions::updated_at.eq(now),
+			))
+			.execute(conn)?;
+
+		Ok(())
+	}
+}
+
+impl UserSession {
+	pub fn is_active(&self) -> bool {
+		self.revoked_at.is_none() && self.expires_at > Utc::now().naive_utc()
+	}
+
+	pub fn generate_access_token(
+		&self,
+		external_token: Option<String>,
+	) -> Result<String, AppError> {
+		let now = Utc::now().timestamp_millis() / 1_000; // milli -> second
+		let access_token = token::generate(self.user_id, self.id, external_token, now)?;
+		Ok(access_token)
+	}
+
+	fn issue_tokens(
+		&self,
+		secret: &str,
+		external_token: Option<String>,
+	) -> Result<AuthTokens, AppError> {
+		Ok(AuthTokens {
+			access_token: self.generate_access_token(external_token)?,
+			refresh_token: token::format_refresh_token(self.id, secret),
+		})
+	}
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = user_sessions)]
+pub struct CreateUserSession {
+	pub user_id: Uuid,
+	pub refresh_token_hash: String,
+	pub external_token: Option<String>,
+	pub user_agent: Option<String>,
+	pub ip_address: Option<String>,
+	pub expires_at: NaiveDateTime,
+}
@@ -0,0 +1,162 @@
+use std::io::Write;
+
+use actix_web::http::StatusCode;
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::deserialize::{self, FromSql};
+use diesel::pg::{Pg, PgValue};
+use diesel::prelude::*;
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::{AsExpression, FromSqlRow};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::errors::{AppError, AppErrorValue};
+use crate::modules::users::models::user::User;
+use crate::schema::{sql_types::UserTokenKinds, user_tokens};
+use crate::utils::hasher;
+use crate::utils::string::generate_random_string;
+
+const USER_TOKEN_LENGTH: usize = 48;
+
+#[derive(Debug, PartialEq, FromSqlRow, AsExpression, Eq, Clone, Copy, Deserialize, Serialize)]
+#[diesel(sql_type = UserTokenKinds)]
+#[allow(non_camel_case_types)]
+pub enum UserTokenKindEnum {
+	PASSWORD_RESET,
+	EMAIL_VERIFICATION,
+	TWO_FACTOR_CHALLENGE,
+}
+
+impl UserTokenKindEnum {
+	pub fn lifetime(&self) -> Duration {
+		match *self {
+			UserTokenKindEnum::PASSWORD_RESET => Duration::hours(1),
+			UserTokenKindEnum::EMAIL_VERIFICATION => Duration::days(2),
+			UserTokenKindEnum::TWO_FACTOR_CHALLENGE => Duration::minutes(5),
+		}
+	}
+}
+
+impl ToSql<UserTokenKinds, Pg> for UserTokenKindEnum {
+	fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+		match *self {
+			UserTokenKindEnum::PASSWORD_RESET => out.write_all(b"PASSWORD_RESET")?,
+			UserTokenKindEnum::EMAIL_VERIFICATION => out.write_all(b"EMAIL_VERIFICATION")?,
+			UserTokenKindEnum::TWO_FACTOR_CHALLENGE => out.write_all(b"TWO_FACTOR_CHALLENGE")?,
+		}
+		Ok(IsNull::No)
+	}
+}
+
+impl FromSql<UserTokenKinds, Pg> for UserTokenKindEnum {
+	fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+		match bytes.as_bytes() {
+			b"PASSWORD_RESET" => Ok(UserTokenKindEnum::PASSWORD_RESET),
+			b"EMAIL_VERIFICATION" => Ok(UserTokenKindEnum::EMAIL_VERIFICATION),
+			b"TWO_FACTOR_CHALLENGE" => Ok(UserTokenKindEnum::TWO_FACTOR_CHALLENGE),
+			_ => Err("Unrecognized enum variant".into()),
+		}
+	}
+}
+
+#[derive(Identifiable, Selectable, Queryable, Associations, Debug, Clone)]
+#[diesel(belongs_to(User))]
+#[diesel(table_name = user_tokens)]
+#[diesel(primary_key(id))]
+pub struct UserToken {
+	pub id: Uuid,
+	pub user_id: Uuid,
+	pub kind: UserTokenKindEnum,
+	pub token_hash: String,
+	pub expires_at: NaiveDateTime,
+	pub used_at: Option<NaiveDateTime>,
+	pub created_at: NaiveDateTime,
+	pub login_identifier: Option<String>,
+}
+
+fn invalid_token_error() -> AppError {
+	AppError::BadRequest(AppErrorValue {
+		message: "Token is invalid or has expired".to_owned(),
+		status: StatusCode::BAD_REQUEST.as_u16(),
+		code: "INVALID_USER_TOKEN".to_owned(),
+		..Default::default()
+	})
+}
+
+impl UserToken {
+	// Returns the plain token, only its hash is stored. Earlier unused tokens of the same kind stop working.
+	#[instrument(skip(conn))]
+	pub fn issue(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+		kind: UserTokenKindEnum,
+	) -> Result<String, AppError> {
+		Self::issue_for_login(conn, user_id, kind, None)
+	}
+
+	// Two-factor challenges remember the name the password step was checked under
+	#[instrument(skip(conn))]
+	pub fn issue_for_login(
+		conn: &mut PgConnection,
+		user_id: Uuid,
+		kind: UserTokenKindEnum,
+		login_identifier: Option<&str>,
+	) -> Result<String, AppError> {
+		let token = generate_random_string(USER_TOKEN_LENGTH);
+
+		diesel::delete(
+			user_tokens::table
+				.filter(user_tokens::user_id.eq(user_id))
+				.filter(user_tokens::kind.eq(kind))
+				.filter(user_tokens::used_at.is_null()),
+		)
+		.execute(conn)?;
+
+		diesel::insert_into(user_tokens::table)
+			.values(CreateUserToken {
+				user_id,
+				kind,
+				token_hash: hasher::hash_token(&token),
+				expires_at: Utc::now().naive_utc() + kind.lifetime(),
+				login_identifier: login_identifier.map(|value| value.to_owned()),
+			})
+			.execute(conn)?;
+
+		Ok(token)
+	}
+
+	// Marking the token as used in the same statement that looks it up keeps it single-use
+	#[instrument(skip(conn, token))]
+	pub fn consume(
+		conn: &mut PgConnection,
+		kind: UserTokenKindEnum,
+		token: &str,
+	) -> Result<Self, AppError> {
+		let now = Utc::now().naive_utc();
+		let target = user_tokens::table
+			.filter(user_tokens::token_hash.eq(hasher::hash_token(token)))
+			.filter(user_tokens::kind.eq(kind))
+			.filter(user_tokens::used_at.is_null())
+			.filter(user_tokens::expires_at.gt(now));
+
+		let user_token = diesel::update(target)
+			.set(user_tokens::used_at.eq(now))
+			.returning(UserToken::as_returning())
+			.get_result::<UserToken>(conn)
+			.optional()?
+			.ok_or_else(invalid_token_error)?;
+
+		Ok(user_token)
+	}
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = user_tokens)]
+pub struct CreateUserToken {
+	pub user_id: Uuid,
+	pub kind: UserTokenKindEnum,
+	pub token_hash: String,
+	pub expires_at: NaiveDateTime,
+	pub login_identifier: Option<String>,
+}
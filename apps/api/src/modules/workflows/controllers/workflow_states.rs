@@ -2,6 +2,7 @@ use super::super::dto::workflow_states::{request, response};
 use crate::modules::auth::helpers::permissions::ensure_permission;
 use crate::modules::core::middleware::state::AppState;
 use crate::modules::core::models::hal::HALPage;
+use crate::modules::labels::helpers::localize::{get_language_keys, localize};
 use crate::modules::workflows::models::workflow_state::{CreateWorkflowState, UpdateWorkflowState};
 use crate::utils::api::ApiResponse;
 use crate::{errors::AppError, modules::workflows::models::workflow_state::WorkflowState};
@@ -104,7 +105,9 @@ pub async fn find_all(
 	let page = query.page.unwrap_or(1);
 	let pagesize = query.pagesize.unwrap_or(20);
 
-	let (workflows, total_elements) = WorkflowState::find(conn, params.site_id, page, pagesize)?;
+	let (mut workflows, total_elements) =
+		WorkflowState::find(conn, params.site_id, page, pagesize)?;
+	localize(conn, &get_language_keys(&req), workflows.iter_mut())?;
 
 	let res = response::WorkflowStatesDTO::from((
 		workflows,
@@ -152,7 +155,8 @@ pub async fn find_one(
 		)
 	})?;
 	let conn = &mut state.get_conn()?;
-	let workflow = WorkflowState::find_one(conn, params.site_id, params.workflow_id)?;
+	let mut workflow = WorkflowState::find_one(conn, params.site_id, params.workflow_id)?;
+	localize(conn, &get_language_keys(&req), [&mut workflow])?;
 
 	let res = response::WorkflowStateDTO::from(workflow);
 	Ok(HttpResponse::Ok().json(res))
@@ -13,6 +13,7 @@ use diesel::{AsExpression, FromSqlRow};
 use serde::{Deserialize, Serialize};
 
 use crate::errors::AppError;
+use crate::modules::labels::models::label::Label;
 use crate::schema::{sql_types::WorkflowStateTechnicalStates, workflow_states};
 
 #[derive(
@@ -140,6 +141,7 @@ impl WorkflowState {
 	pub fn remove(conn: &mut PgConnection, content_id: Uuid) -> Result<(), AppError> {
 		diesel::delete(workflow_states::table.filter(workflow_states::id.eq(content_id)))
 			.get_result::<WorkflowState>(conn)?;
+		Label::remove(conn, vec![content_id])?;
 
 		Ok(())
 	}
@@ -16,6 +16,10 @@ impl Modify for SecurityAddon {
 					.bearer_format("JWT")
 					.build(),
 			),
+		);
+		components.add_security_scheme(
+			"scim_token",
+			SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
 		)
 	}
 }
@@ -27,6 +31,20 @@ impl Modify for SecurityAddon {
 
 		super::modules::auth::controllers::auth::me,
 		super::modules::auth::controllers::auth::update,
+		super::modules::auth::controllers::auth::refresh,
+		super::modules::auth::controllers::auth::logout,
+		super::modules::auth::controllers::auth::logout_all,
+		super::modules::auth::controllers::auth::revoke_session,
+		super::modules::auth::controllers::auth::request_password_reset,
+		super::modules::auth::controllers::auth::reset_password,
+		super::modules::auth::controllers::auth::request_email_verification,
+		super::modules::auth::controllers::auth::verify_email,
+		super::modules::auth::controllers::auth::enrol_two_factor,
+		super::modules::auth::controllers::auth::confirm_two_factor,
+		super::modules::auth::controllers::auth::regenerate_recovery_codes,
+		super::modules::auth::controllers::auth::disable_two_factor,
+		super::modules::auth::controllers::auth::find_site_invitation,
+		super::modules::auth::controllers::auth::accept_site_invitation,
 
 		super::modules::sites::controllers::sites::create,
 		super::modules::sites::controllers::sites::find_all,
@@ -58,6 +76,8 @@ impl Modify for SecurityAddon {
 		super::modules::content_types::controllers::fields::find_one,
 		super::modules::content_types::controllers::fields::update,
 		super::modules::content_types::controllers::fields::remove,
+		super::modules::content_types::controllers::fields::impact,
+		super::modules::content_types::controllers::fields::migrate,
 
 		super::modules::content_types::controllers::blocks::create,
 		super::modules::content_types::controllers::blocks::find_all,
@@ -74,6 +94,17 @@ impl Modify for SecurityAddon {
 
 		super::modules::content_types::controllers::field_order::update_order,
 
+		super::modules::content_types::controllers::field_groups::find_all,
+		super::modules::content_types::controllers::field_groups::update,
+		super::modules::content_types::controllers::field_groups::remove,
+
+		super::modules::labels::controllers::labels::find_one,
+		super::modules::labels::controllers::labels::update,
+
+		super::modules::content_types::controllers::content_model::find_one,
+		super::modules::content_types::controllers::content_model::plan,
+		super::modules::content_types::controllers::content_model::apply,
+
 		super::modules::content::controllers::content::create,
 		super::modules::content::controllers::content::find_all,
 		super::modules::content::controllers::content::find_one,
@@ -110,12 +141,44 @@ impl Modify for SecurityAddon {
 		super::modules::webhooks::controllers::webhooks::find_one,
 		super::modules::webhooks::controllers::webhooks::update,
 		super::modules::webhooks::controllers::webhooks::remove,
+		super::modules::redirects::controllers::redirects::create,
+		super::modules::redirects::controllers::redirects::find_all,
+		super::modules::redirects::controllers::redirects::find_one,
+		super::modules::redirects::controllers::redirects::update,
+		super::modules::redirects::controllers::redirects::remove,
+		super::modules::redirects::controllers::redirects::import,
+		super::modules::feeds::controllers::feeds::find_all,
+		super::modules::feeds::controllers::feeds::update,
+		super::modules::api_keys::controllers::api_keys::create,
+		super::modules::api_keys::controllers::api_keys::find_all,
+		super::modules::api_keys::controllers::api_keys::find_one,
+		super::modules::api_keys::controllers::api_keys::update,
+		super::modules::api_keys::controllers::api_keys::remove,
+		super::modules::sites::controllers::site_invitations::create,
+		super::modules::sites::controllers::site_invitations::find_all,
+		super::modules::sites::controllers::site_invitations::remove,
+		super::modules::sites::controllers::site_bundles::export,
+		super::modules::sites::controllers::site_bundles::import,
 
 		super::modules::iam_actions::controllers::iam_actions::find_all,
 		super::modules::iam_actions::controllers::iam_actions::find_one,
 
 		super::modules::iam_conditions::controllers::iam_conditions::find_all,
 		super::modules::iam_conditions::controllers::iam_conditions::find_one,
+
+		super::modules::scim::controllers::users::create,
+		super::modules::scim::controllers::users::find_all,
+		super::modules::scim::controllers::users::find_one,
+		super::modules::scim::controllers::users::replace,
+		super::modules::scim::controllers::users::update,
+		super::modules::scim::controllers::users::remove,
+		super::modules::scim::controllers::groups::create,
+		super::modules::scim::controllers::groups::find_all,
+		super::modules::scim::controllers::groups::find_one,
+		super::modules::scim::controllers::groups::replace,
+		super::modules::scim::controllers::groups::update,
+		super::modules::scim::controllers::groups::remove,
+		super::modules::scim::controllers::service_provider_config::find_one,
 	),
 	components(
 		schemas(
@@ -128,9 +191,21 @@ impl Modify for SecurityAddon {
 			// Auth
 			super::modules::auth::dto::response::UserDTO,
 			super::modules::auth::dto::response::AuthDTO,
+			super::modules::auth::dto::response::SessionDTO,
+			super::modules::auth::dto::response::TokensDTO,
+			super::modules::auth::dto::response::TwoFactorEnrolmentDTO,
+			super::modules::auth::dto::response::TwoFactorChallengeDTO,
+			super::modules::auth::dto::response::RecoveryCodesDTO,
+			super::modules::auth::dto::response::SiteInvitationPreviewDTO,
 			super::modules::auth::dto::request::LoginUserDTO,
 			super::modules::auth::dto::request::RegisterUserDTO,
 			super::modules::auth::dto::request::UpdateUserDTO,
+			super::modules::auth::dto::request::RefreshTokenDTO,
+			super::modules::auth::dto::request::RequestPasswordResetDTO,
+			super::modules::auth::dto::request::ResetPasswordDTO,
+			super::modules::auth::dto::request::VerifyEmailDTO,
+			super::modules::auth::dto::request::TwoFactorCodeDTO,
+			super::modules::auth::dto::request::AcceptSiteInvitationDTO,
 
 			// Sites
 			super::modules::sites::dto::response::SiteDTO,
@@ -138,6 +213,29 @@ impl Modify for SecurityAddon {
 			super::modules::sites::dto::response::SitesEmbeddedDTO,
 			super::modules::sites::dto::request::CreateSiteDTO,
 			super::modules::sites::dto::request::UpdateSiteDTO,
+			super::modules::sites::dto::invitations::response::SiteInvitationDTO,
+			super::modules::sites::dto::invitations::response::SiteInvitationsDTO,
+			super::modules::sites::dto::invitations::response::SiteInvitationsEmbeddedDTO,
+			super::modules::sites::dto::invitations::request::CreateSiteInvitationDTO,
+			super::modules::sites::dto::bundles::response::SiteImportReportDTO,
+			super::modules::sites::dto::bundles::response::SiteImportConflictDTO,
+			super::modules::sites::services::site_import::SiteImportConflictKindEnum,
+
+			// Redirects
+			super::modules::redirects::dto::redirects::response::RedirectDTO,
+			super::modules::redirects::dto::redirects::response::RedirectsDTO,
+			super::modules::redirects::dto::redirects::response::RedirectsEmbeddedDTO,
+			super::modules::redirects::dto::redirects::response::RedirectImportReportDTO,
+			super::modules::redirects::dto::redirects::response::RedirectImportErrorDTO,
+			super::modules::redirects::dto::redirects::request::CreateRedirectDTO,
+			super::modules::redirects::dto::redirects::request::UpdateRedirectDTO,
+			super::modules::redirects::models::redirect::RedirectMatchTypeEnum,
+
+			// Feeds
+			super::modules::feeds::dto::feeds::response::FeedsDTO,
+			super::modules::feeds::dto::feeds::response::FeedConfigDTO,
+			super::modules::feeds::dto::feeds::response::FeedFieldsDTO,
+			super::modules::feeds::dto::feeds::request::UpdateFeedsDTO,
 
 			// Roles
 			super::modules::roles::dto::response::RoleDTO,
@@ -172,6 +270,18 @@ impl Modify for SecurityAddon {
 			super::modules::content_types::dto::fields::response::FieldsDTO,
 			super::modules::content_types::dto::fields::response::FieldsEmbeddedDTO,
 
+			// Field groups
+			super::modules::content_types::dto::field_groups::request::UpsertFieldGroupDTO,
+			super::modules::content_types::dto::field_groups::response::FieldGroupDTO,
+			super::modules::content_types::dto::field_groups::response::FieldGroupsDTO,
+
+			// Field migrations
+			super::modules::content_types::dto::field_migrations::request::MigrateFieldDTO,
+			super::modules::content_types::dto::field_migrations::response::FieldImpactDTO,
+			super::modules::content_types::dto::field_migrations::response::FieldMigrationStepDTO,
+			super::modules::content_types::dto::field_migrations::response::FieldMigrationReportDTO,
+			super::modules::content_types::services::field_migration::FieldMigrationStepKindEnum,
+
 			// Compartments
 			super::modules::content_types::dto::compartments::request::CreateCompartmentDTO,
 			super::modules::content_types::dto::compartments::request::UpdateCompartmentDTO,
@@ -179,6 +289,23 @@ impl Modify for SecurityAddon {
 			super::modules::content_types::dto::compartments::response::CompartmentDTO,
 			super::modules::content_types::dto::compartments::response::CompartmentsEmbeddedDTO,
 
+			// Labels
+			super::modules::labels::dto::labels::request::UpsertLabelsDTO,
+			super::modules::labels::dto::labels::request::UpsertLabelDTO,
+			super::modules::labels::dto::labels::response::LabelsDTO,
+			super::modules::labels::dto::labels::response::LabelDTO,
+			super::modules::labels::models::label::LabelEntityTypeEnum,
+
+			// Content Model
+			super::modules::content_types::dto::content_model::request::ContentModelDTO,
+			super::modules::content_types::dto::content_model::request::ContentModelContentTypeDTO,
+			super::modules::content_types::dto::content_model::request::ContentModelCompartmentDTO,
+			super::modules::content_types::dto::content_model::request::ContentModelFieldDTO,
+			super::modules::content_types::dto::content_model::response::ContentModelPlanDTO,
+			super::modules::content_types::dto::content_model::response::ContentModelChangeDTO,
+			super::modules::content_types::services::content_model::ContentModelChangeActionEnum,
+			super::modules::content_types::services::content_model::ContentModelResourceEnum,
+
 			// Content
 			super::modules::content::dto::content::response::ContentDTO,
 			super::modules::content::dto::content::response::ContentWithFieldsDTO,
@@ -221,6 +348,24 @@ impl Modify for SecurityAddon {
 			super::modules::iam_conditions::dto::response::IAMConditionDTO,
 			super::modules::iam_conditions::dto::response::IAMConditionsDTO,
 			super::modules::iam_conditions::dto::response::IAMConditionsEmbeddedDTO,
+
+			// SCIM
+			super::modules::scim::dto::response::ScimMetaDTO,
+			super::modules::scim::dto::response::ScimNameDTO,
+			super::modules::scim::dto::response::ScimEmailDTO,
+			super::modules::scim::dto::response::ScimReferenceDTO,
+			super::modules::scim::dto::response::ScimUserDTO,
+			super::modules::scim::dto::response::ScimUsersDTO,
+			super::modules::scim::dto::response::ScimGroupDTO,
+			super::modules::scim::dto::response::ScimGroupsDTO,
+			super::modules::scim::dto::response::ScimErrorDTO,
+			super::modules::scim::dto::request::UpsertScimNameDTO,
+			super::modules::scim::dto::request::UpsertScimEmailDTO,
+			super::modules::scim::dto::request::UpsertScimUserDTO,
+			super::modules::scim::dto::request::UpsertScimMemberDTO,
+			super::modules::scim::dto::request::UpsertScimGroupDTO,
+			super::modules::scim::dto::request::ScimPatchOperationDTO,
+			super::modules::scim::dto::request::ScimPatchDTO,
 		)
 	),
 	modifiers(&SecurityAddon)
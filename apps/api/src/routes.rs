@@ -9,6 +9,17 @@ pub fn api(cfg: &mut web::ServiceConfig) {
 					.service(modules::content::controllers::public_content::find_one)
 					.service(modules::content::controllers::public_content::find)
 				)
+				.service(web::scope("/sites/{site_id}/singletons").service(modules::content::controllers::public_content::find_singleton))
+				.service(web::scope("/sites/{site_id}/pages")
+					.service(modules::content::controllers::public_pages::resolve)
+					.service(modules::content::controllers::public_pages::navigation)
+				)
+				.service(web::scope("/sites/{site_id}/redirects").service(modules::redirects::controllers::public_redirects::lookup))
+				.service(web::scope("/sites/{site_id}/sitemap.xml").service(modules::feeds::controllers::public_feeds::sitemap))
+				.service(web::scope("/sites/{site_id}/feeds")
+					.service(modules::feeds::controllers::public_feeds::rss)
+					.service(modules::feeds::controllers::public_feeds::atom)
+				)
 				.service(web::scope("/sites/{site_id}/files").service(modules::resources::controllers::public_files::read_file))
 			)
 			.service(web::scope("/admin-api/v1")
@@ -27,6 +38,20 @@ pub fn api(cfg: &mut web::ServiceConfig) {
 					web::scope("/auth")
 						.service(modules::auth::controllers::auth::me)
 						.service(modules::auth::controllers::auth::update)
+						.service(modules::auth::controllers::auth::refresh)
+						.service(modules::auth::controllers::auth::logout)
+						.service(modules::auth::controllers::auth::logout_all)
+						.service(modules::auth::controllers::auth::revoke_session)
+						.service(modules::auth::controllers::auth::request_password_reset)
+						.service(modules::auth::controllers::auth::reset_password)
+						.service(modules::auth::controllers::auth::request_email_verification)
+						.service(modules::auth::controllers::auth::verify_email)
+						.service(modules::auth::controllers::auth::enrol_two_factor)
+						.service(modules::auth::controllers::auth::confirm_two_factor)
+						.service(modules::auth::controllers::auth::regenerate_recovery_codes)
+						.service(modules::auth::controllers::auth::disable_two_factor)
+						.service(modules::auth::controllers::auth::find_site_invitation)
+						.service(modules::auth::controllers::auth::accept_site_invitation)
 						.service(
 							web::scope("/{auth_id}")
 								.service(modules::auth::controllers::dynamic_auth::login)
@@ -45,6 +70,8 @@ pub fn api(cfg: &mut web::ServiceConfig) {
 						.service(modules::sites::controllers::sites::find_one)
 						.service(modules::sites::controllers::sites::update)
 						.service(modules::sites::controllers::sites::remove)
+						.service(modules::sites::controllers::site_bundles::export)
+						.service(modules::sites::controllers::site_bundles::import)
 						.service(
 							web::scope("/{site_id}/roles")
 								.service(modules::roles::controllers::site_roles::create)
@@ -59,7 +86,13 @@ pub fn api(cfg: &mut web::ServiceConfig) {
 								.service(modules::users::controllers::site_users::find_all)
 								.service(modules::users::controllers::site_users::find_one)
 								.service(modules::users::controllers::site_users::update)
-								// .service(modules::roles::controllers::roles::remove),
+								.service(modules::users::controllers::site_users::remove),
+						)
+						.service(
+							web::scope("/{site_id}/invitations")
+								.service(modules::sites::controllers::site_invitations::create)
+								.service(modules::sites::controllers::site_invitations::find_all)
+								.service(modules::sites::controllers::site_invitations::remove),
 						)
 						.service(
 							web::scope("/{site_id}/iam-policies")
@@ -91,6 +124,8 @@ pub fn api(cfg: &mut web::ServiceConfig) {
 										.service(modules::content_types::controllers::fields::find_one)
 										.service(modules::content_types::controllers::fields::update)
 										.service(modules::content_types::controllers::fields::remove)
+										.service(modules::content_types::controllers::fields::impact)
+										.service(modules::content_types::controllers::fields::migrate)
 										.service(
 											web::scope("/{field_id}/blocks")
 												.service(modules::content_types::controllers::blocks::create)
@@ -108,11 +143,28 @@ pub fn api(cfg: &mut web::ServiceConfig) {
 										.service(modules::content_types::controllers::compartments::update)
 										.service(modules::content_types::controllers::compartments::remove),
 								)
+								.service(
+									web::scope("/{content_type_id}/field-groups")
+										.service(modules::content_types::controllers::field_groups::find_all)
+										.service(modules::content_types::controllers::field_groups::update)
+										.service(modules::content_types::controllers::field_groups::remove),
+								)
 								.service(
 									web::scope("/{content_type_id}/field-order")
 										.service(modules::content_types::controllers::field_order::update_order),
 								)
 						)
+						.service(
+							web::scope("/{site_id}/labels")
+								.service(modules::labels::controllers::labels::find_one)
+								.service(modules::labels::controllers::labels::update),
+						)
+						.service(
+							web::scope("/{site_id}/content-model")
+								.service(modules::content_types::controllers::content_model::find_one)
+								.service(modules::content_types::controllers::content_model::plan)
+								.service(modules::content_types::controllers::content_model::apply),
+						)
 						.service(
 							web::scope("/{site_id}/content")
 								.service(modules::content::controllers::content::create)
@@ -161,6 +213,33 @@ pub fn api(cfg: &mut web::ServiceConfig) {
 								.service(modules::webhooks::controllers::webhooks::update)
 								.service(modules::webhooks::controllers::webhooks::remove)
 						)
+						.service(
+							web::scope("/{site_id}/redirects")
+								.service(modules::redirects::controllers::redirects::import)
+								.service(modules::redirects::controllers::redirects::create)
+								.service(modules::redirects::controllers::redirects::find_all)
+								.service(modules::redirects::controllers::redirects::find_one)
+								.service(modules::redirects::controllers::redirects::update)
+								.service(modules::redirects::controllers::redirects::remove)
+						)
+						.service(
+							web::scope("/{site_id}/api-keys")
+								.service(modules::api_keys::controllers::api_keys::create)
+								.service(modules::api_keys::controllers::api_keys::find_all)
+								.service(modules::api_keys::controllers::api_keys::find_one)
+								.service(modules::api_keys::controllers::api_keys::update)
+								.service(modules::api_keys::controllers::api_keys::remove)
+						)
+						.service(
+							web::scope("/{site_id}/config")
+								.service(modules::core::controllers::site_config::find_all)
+								.service(modules::core::controllers::site_config::update)
+						)
+						.service(
+							web::scope("/{site_id}/feeds")
+								.service(modules::feeds::controllers::feeds::find_all)
+								.service(modules::feeds::controllers::feeds::update)
+						)
 						.service(
 							web::scope("/{site_id}/workflow-states")
 								.service(modules::workflows::controllers::workflow_states::create)
@@ -175,6 +254,14 @@ pub fn api(cfg: &mut web::ServiceConfig) {
 						// 		.service(modules::assets::controllers::assets::find_all)
 						// 		.service(modules::assets::controllers::assets::find_one)
 						// )
+						.service(
+							web::scope("/{site_id}/storage-migrations")
+								.service(modules::resources::controllers::storage_migrations::create)
+								.service(modules::resources::controllers::storage_migrations::find_all)
+								.service(modules::resources::controllers::storage_migrations::find_one)
+								.service(modules::resources::controllers::storage_migrations::find_items)
+								.service(modules::resources::controllers::storage_migrations::resume)
+						)
 						.service(
 							web::scope("/{site_id}/storage-repositories")
 								.service(modules::resources::controllers::storage_repositories::create)
@@ -187,12 +274,18 @@ pub fn api(cfg: &mut web::ServiceConfig) {
 										.service(modules::resources::controllers::files::upload_file)
 										.service(modules::resources::controllers::files::read_file)
 										.service(modules::resources::controllers::files::remove_file)
+										.service(modules::resources::controllers::files::move_file)
+										.service(modules::resources::controllers::files::rename_file)
+										.service(modules::resources::controllers::files::copy_file)
 								)
 								.service(
 									web::scope("/{storage_repository_id}/directories")
 										.service(modules::resources::controllers::directories::read_directory)
 										.service(modules::resources::controllers::directories::create_directory)
 										.service(modules::resources::controllers::directories::remove_directory)
+										.service(modules::resources::controllers::directories::move_directory)
+										.service(modules::resources::controllers::directories::rename_directory)
+										.service(modules::resources::controllers::directories::copy_directory)
 								)
 						),
 				)
@@ -230,6 +323,14 @@ pub fn api(cfg: &mut web::ServiceConfig) {
 						.service(modules::users::controllers::users::update)
 						.service(modules::users::controllers::users::remove)
 						.service(modules::users::controllers::users::find_sites)
+						.service(modules::users::controllers::users::revoke_sessions)
+						.service(modules::users::controllers::users::send_password_reset)
+						.service(modules::users::controllers::users::reset_two_factor)
+						.service(modules::users::controllers::users::unlock)
+				)
+				.service(
+					web::scope("/login-lockouts")
+						.service(modules::users::controllers::login_lockouts::find_all)
 				)
 				.service(
 					web::scope("/roles")
@@ -253,5 +354,29 @@ pub fn api(cfg: &mut web::ServiceConfig) {
 						.service(modules::core::controllers::config::update)
 				)
 			)
+			.service(web::scope("/scim/v2")
+				.service(
+					web::scope("/Users")
+						.service(modules::scim::controllers::users::create)
+						.service(modules::scim::controllers::users::find_all)
+						.service(modules::scim::controllers::users::find_one)
+						.service(modules::scim::controllers::users::replace)
+						.service(modules::scim::controllers::users::update)
+						.service(modules::scim::controllers::users::remove),
+				)
+				.service(
+					web::scope("/Groups")
+						.service(modules::scim::controllers::groups::create)
+						.service(modules::scim::controllers::groups::find_all)
+						.service(modules::scim::controllers::groups::find_one)
+						.service(modules::scim::controllers::groups::replace)
+						.service(modules::scim::controllers::groups::update)
+						.service(modules::scim::controllers::groups::remove),
+				)
+				.service(
+					web::scope("/ServiceProviderConfig")
+						.service(modules::scim::controllers::service_provider_config::find_one)
+				)
+			)
 	);
 }
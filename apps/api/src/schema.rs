@@ -17,11 +17,55 @@ pub mod sql_types {
 	#[diesel(postgres_type(name = "field_types"))]
 	pub struct FieldTypes;
 
+	#[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+	#[diesel(postgres_type(name = "label_entity_types"))]
+	pub struct LabelEntityTypes;
+
+	#[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+	#[diesel(postgres_type(name = "login_throttle_scopes"))]
+	pub struct LoginThrottleScopes;
+
+	#[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+	#[diesel(postgres_type(name = "redirect_match_types"))]
+	pub struct RedirectMatchTypes;
+
+	#[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+	#[diesel(postgres_type(name = "storage_migration_statuses"))]
+	pub struct StorageMigrationStatuses;
+
+	#[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+	#[diesel(postgres_type(name = "user_token_kinds"))]
+	pub struct UserTokenKinds;
+
 	#[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
 	#[diesel(postgres_type(name = "workflow_state_technical_states"))]
 	pub struct WorkflowStateTechnicalStates;
 }
 
+diesel::table! {
+	api_keys (id) {
+		id -> Uuid,
+		site_id -> Uuid,
+		user_id -> Uuid,
+		name -> Text,
+		prefix -> Text,
+		key_hash -> Text,
+		last_used_at -> Nullable<Timestamp>,
+		expires_at -> Nullable<Timestamp>,
+		created_at -> Timestamp,
+		updated_at -> Timestamp,
+	}
+}
+
+diesel::table! {
+	api_keys_roles (api_key_id, role_id) {
+		api_key_id -> Uuid,
+		role_id -> Uuid,
+		created_at -> Timestamp,
+		updated_at -> Timestamp,
+	}
+}
+
 diesel::table! {
 	asset_metadata (id) {
 		id -> Uuid,
@@ -55,6 +99,7 @@ diesel::table! {
 		authentication_method_id -> Uuid,
 		site_id -> Nullable<Uuid>,
 		role_id -> Uuid,
+		group_name -> Nullable<Text>,
 	}
 }
 
@@ -104,6 +149,9 @@ diesel::table! {
 		deleted -> Bool,
 		created_at -> Timestamp,
 		updated_at -> Timestamp,
+		parent_id -> Nullable<Uuid>,
+		path -> Nullable<Text>,
+		singleton -> Bool,
 	}
 }
 
@@ -157,6 +205,15 @@ diesel::table! {
 	}
 }
 
+diesel::table! {
+	content_types_field_groups (content_type_id, field_group_id) {
+		content_type_id -> Uuid,
+		field_group_id -> Uuid,
+		sequence_number -> Int4,
+		created_at -> Timestamp,
+	}
+}
+
 diesel::table! {
 	use diesel::sql_types::*;
 	use super::sql_types::ContentTypeKinds;
@@ -244,6 +301,22 @@ diesel::table! {
 	}
 }
 
+diesel::table! {
+	use diesel::sql_types::*;
+	use super::sql_types::LabelEntityTypes;
+
+	labels (id) {
+		id -> Uuid,
+		entity_type -> LabelEntityTypes,
+		entity_id -> Uuid,
+		language_key -> Text,
+		name -> Text,
+		description -> Nullable<Text>,
+		created_at -> Timestamp,
+		updated_at -> Timestamp,
+	}
+}
+
 diesel::table! {
 	languages (id) {
 		id -> Uuid,
@@ -252,6 +325,36 @@ diesel::table! {
 	}
 }
 
+diesel::table! {
+	use diesel::sql_types::*;
+	use super::sql_types::LoginThrottleScopes;
+
+	login_lockouts (id) {
+		id -> Uuid,
+		scope -> LoginThrottleScopes,
+		identifier -> Text,
+		failed_attempts -> Int4,
+		ip_address -> Nullable<Text>,
+		user_agent -> Nullable<Text>,
+		locked_until -> Timestamp,
+		created_at -> Timestamp,
+	}
+}
+
+diesel::table! {
+	use diesel::sql_types::*;
+	use super::sql_types::LoginThrottleScopes;
+
+	login_throttles (id) {
+		id -> Uuid,
+		scope -> LoginThrottleScopes,
+		identifier -> Text,
+		failed_attempts -> Int4,
+		locked_until -> Nullable<Timestamp>,
+		last_failed_at -> Timestamp,
+	}
+}
+
 diesel::table! {
 	modules (id) {
 		id -> Uuid,
@@ -262,6 +365,17 @@ diesel::table! {
 	}
 }
 
+diesel::table! {
+	oauth2_login_states (state) {
+		state -> Text,
+		authentication_method_id -> Uuid,
+		pkce_verifier -> Text,
+		nonce -> Text,
+		expires_at -> Timestamp,
+		created_at -> Timestamp,
+	}
+}
+
 diesel::table! {
 	permissions (id) {
 		id -> Uuid,
@@ -294,6 +408,26 @@ diesel::table! {
 	}
 }
 
+diesel::table! {
+	use diesel::sql_types::*;
+	use super::sql_types::RedirectMatchTypes;
+
+	redirects (id) {
+		id -> Uuid,
+		site_id -> Uuid,
+		language_id -> Nullable<Uuid>,
+		source_path -> Text,
+		content_id -> Nullable<Uuid>,
+		created_at -> Timestamp,
+		updated_at -> Timestamp,
+		match_type -> RedirectMatchTypes,
+		target_path -> Nullable<Text>,
+		status_code -> Int4,
+		hits -> Int8,
+		last_hit_at -> Nullable<Timestamp>,
+	}
+}
+
 diesel::table! {
 	roles (id) {
 		id -> Uuid,
@@ -315,6 +449,29 @@ diesel::table! {
 	}
 }
 
+diesel::table! {
+	site_invitations (id) {
+		id -> Uuid,
+		site_id -> Uuid,
+		invited_by_id -> Nullable<Uuid>,
+		email -> Text,
+		token_hash -> Text,
+		expires_at -> Timestamp,
+		accepted_at -> Nullable<Timestamp>,
+		created_at -> Timestamp,
+		updated_at -> Timestamp,
+	}
+}
+
+diesel::table! {
+	site_invitations_roles (site_invitation_id, role_id) {
+		site_invitation_id -> Uuid,
+		role_id -> Uuid,
+		created_at -> Timestamp,
+		updated_at -> Timestamp,
+	}
+}
+
 diesel::table! {
 	sites (id) {
 		id -> Uuid,
@@ -377,6 +534,42 @@ diesel::table! {
 		role_id -> Uuid,
 		created_at -> Timestamp,
 		updated_at -> Timestamp,
+		granted_by_group -> Bool,
+	}
+}
+
+diesel::table! {
+	use diesel::sql_types::*;
+	use super::sql_types::StorageMigrationStatuses;
+
+	storage_migration_items (id) {
+		id -> Uuid,
+		storage_migration_id -> Uuid,
+		path -> Text,
+		kind -> Text,
+		status -> StorageMigrationStatuses,
+		error -> Nullable<Text>,
+	}
+}
+
+diesel::table! {
+	use diesel::sql_types::*;
+	use super::sql_types::StorageMigrationStatuses;
+
+	storage_migrations (id) {
+		id -> Uuid,
+		site_id -> Uuid,
+		source_storage_repository_id -> Uuid,
+		target_storage_repository_id -> Uuid,
+		status -> StorageMigrationStatuses,
+		dry_run -> Bool,
+		rewrite_content_references -> Bool,
+		total_items -> Int4,
+		processed_items -> Int4,
+		failed_items -> Int4,
+		last_error -> Nullable<Text>,
+		created_at -> Timestamp,
+		updated_at -> Timestamp,
 	}
 }
 
@@ -402,6 +595,55 @@ diesel::table! {
 		authentication_method_id -> Uuid,
 		created_at -> Timestamp,
 		updated_at -> Timestamp,
+		email_verified_at -> Nullable<Timestamp>,
+		totp_secret -> Nullable<Text>,
+		totp_enabled_at -> Nullable<Timestamp>,
+		totp_last_step -> Nullable<Int8>,
+		deactivated_at -> Nullable<Timestamp>,
+		language_key -> Nullable<Text>,
+	}
+}
+
+diesel::table! {
+	user_recovery_codes (id) {
+		id -> Uuid,
+		user_id -> Uuid,
+		code_hash -> Text,
+		used_at -> Nullable<Timestamp>,
+		created_at -> Timestamp,
+	}
+}
+
+diesel::table! {
+	user_sessions (id) {
+		id -> Uuid,
+		user_id -> Uuid,
+		refresh_token_hash -> Text,
+		previous_refresh_token_hash -> Nullable<Text>,
+		external_token -> Nullable<Text>,
+		user_agent -> Nullable<Text>,
+		ip_address -> Nullable<Text>,
+		expires_at -> Timestamp,
+		revoked_at -> Nullable<Timestamp>,
+		last_used_at -> Timestamp,
+		created_at -> Timestamp,
+		updated_at -> Timestamp,
+	}
+}
+
+diesel::table! {
+	use diesel::sql_types::*;
+	use super::sql_types::UserTokenKinds;
+
+	user_tokens (id) {
+		id -> Uuid,
+		user_id -> Uuid,
+		kind -> UserTokenKinds,
+		token_hash -> Text,
+		expires_at -> Timestamp,
+		used_at -> Nullable<Timestamp>,
+		created_at -> Timestamp,
+		login_identifier -> Nullable<Text>,
 	}
 }
 
@@ -411,6 +653,7 @@ diesel::table! {
 		role_id -> Uuid,
 		created_at -> Timestamp,
 		updated_at -> Timestamp,
+		granted_by_group -> Bool,
 	}
 }
 
@@ -478,6 +721,10 @@ diesel::table! {
 	}
 }
 
+diesel::joinable!(api_keys -> sites (site_id));
+diesel::joinable!(api_keys -> users (user_id));
+diesel::joinable!(api_keys_roles -> api_keys (api_key_id));
+diesel::joinable!(api_keys_roles -> roles (role_id));
 diesel::joinable!(asset_metadata -> assets (asset_id));
 diesel::joinable!(authentication_method_roles -> authentication_methods (authentication_method_id));
 diesel::joinable!(authentication_method_roles -> roles (role_id));
@@ -493,14 +740,22 @@ diesel::joinable!(content_revisions -> users (user_id));
 diesel::joinable!(content_revisions -> workflow_states (workflow_state_id));
 diesel::joinable!(iam_policies -> sites (site_id));
 diesel::joinable!(modules -> sites (site_id));
+diesel::joinable!(oauth2_login_states -> authentication_methods (authentication_method_id));
 diesel::joinable!(permissions -> iam_policies (iam_policy_id));
 diesel::joinable!(permissions_iam_actions -> iam_actions (iam_action_key));
 diesel::joinable!(permissions_iam_actions -> permissions (permission_id));
 diesel::joinable!(permissions_iam_conditions -> iam_conditions (iam_condition_key));
 diesel::joinable!(permissions_iam_conditions -> permissions (permission_id));
+diesel::joinable!(redirects -> content (content_id));
+diesel::joinable!(redirects -> languages (language_id));
+diesel::joinable!(redirects -> sites (site_id));
 diesel::joinable!(roles -> sites (site_id));
 diesel::joinable!(roles_iam_policies -> iam_policies (iam_policy_id));
 diesel::joinable!(roles_iam_policies -> roles (role_id));
+diesel::joinable!(site_invitations -> sites (site_id));
+diesel::joinable!(site_invitations -> users (invited_by_id));
+diesel::joinable!(site_invitations_roles -> roles (role_id));
+diesel::joinable!(site_invitations_roles -> site_invitations (site_invitation_id));
 diesel::joinable!(sites_content_components -> content_components (content_component_id));
 diesel::joinable!(sites_content_components -> sites (site_id));
 diesel::joinable!(sites_content_types -> content_types (content_type_id));
@@ -514,6 +769,11 @@ diesel::joinable!(sites_users -> users (user_id));
 diesel::joinable!(sites_users_roles -> roles (role_id));
 diesel::joinable!(sites_users_roles -> sites (site_id));
 diesel::joinable!(sites_users_roles -> users (user_id));
+diesel::joinable!(storage_migration_items -> storage_migrations (storage_migration_id));
+diesel::joinable!(storage_migrations -> sites (site_id));
+diesel::joinable!(user_recovery_codes -> users (user_id));
+diesel::joinable!(user_sessions -> users (user_id));
+diesel::joinable!(user_tokens -> users (user_id));
 diesel::joinable!(users_roles -> roles (role_id));
 diesel::joinable!(users_roles -> users (user_id));
 diesel::joinable!(webhooks -> sites (site_id));
@@ -521,6 +781,8 @@ diesel::joinable!(workflow_transition_requirements -> workflow_transitions (work
 diesel::joinable!(workflow_transitions -> workflows (workflow_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+	api_keys,
+	api_keys_roles,
 	asset_metadata,
 	assets,
 	authentication_method_roles,
@@ -532,18 +794,26 @@ diesel::allow_tables_to_appear_in_same_query!(
 	content_fields,
 	content_revisions,
 	content_types,
+	content_types_field_groups,
 	field_config,
 	fields,
 	iam_actions,
 	iam_conditions,
 	iam_policies,
+	labels,
 	languages,
+	login_lockouts,
+	login_throttles,
 	modules,
+	oauth2_login_states,
 	permissions,
 	permissions_iam_actions,
 	permissions_iam_conditions,
+	redirects,
 	roles,
 	roles_iam_policies,
+	site_invitations,
+	site_invitations_roles,
 	sites,
 	sites_content_components,
 	sites_content_types,
@@ -551,7 +821,12 @@ diesel::allow_tables_to_appear_in_same_query!(
 	sites_storage_repositories,
 	sites_users,
 	sites_users_roles,
+	storage_migration_items,
+	storage_migrations,
 	storage_repositories,
+	user_recovery_codes,
+	user_sessions,
+	user_tokens,
 	users,
 	users_roles,
 	webhooks,
@@ -1,8 +1,15 @@
 pub use bcrypt::verify;
 use bcrypt::{hash, BcryptResult, DEFAULT_COST};
+use sha2::{Digest, Sha256};
 use tracing::instrument;
 
 #[instrument(skip_all)]
 pub fn hash_password(naive_pw: &str) -> BcryptResult<String> {
 	hash(naive_pw, DEFAULT_COST)
 }
+
+// Fast, deterministic hash for high entropy tokens that need to be looked up by value
+#[instrument(skip_all)]
+pub fn hash_token(token: &str) -> String {
+	format!("{:x}", Sha256::digest(token.as_bytes()))
+}
@@ -6,7 +6,7 @@ use uuid::Uuid;
 
 use crate::constants;
 
-static SEVEN_DAYS: i64 = 60 * 60 * 24 * 7; // in seconds
+static FIFTEEN_MINUTES: i64 = 60 * 15; // in seconds
 static ISSUER: &str = "dcm-auth";
 static AUDIENCE: &str = "dcm";
 
@@ -20,10 +20,15 @@ pub fn decode(token: &str) -> jsonwebtoken::errors::Result<TokenData<Claims>> {
 	)
 }
 
-pub fn generate(user_id: Uuid, external_token: Option<String>, now: i64) -> Result<String, Error> {
+pub fn generate(
+	user_id: Uuid,
+	session_id: Uuid,
+	external_token: Option<String>,
+	now: i64,
+) -> Result<String, Error> {
 	let secret = env::var(constants::env_key::JWT_SECRET).expect("JWT Secret should be defined");
 	let jwt_secret: &[u8] = secret.as_bytes();
-	let claims = Claims::new(user_id, external_token, now);
+	let claims = Claims::new(user_id, session_id, external_token, now);
 	jsonwebtoken::encode(
 		&Header::default(),
 		&claims,
@@ -39,15 +44,17 @@ pub struct Claims {
 	iss: String, // Optional. Issuer
 	nbf: i64, // Optional. Not Before (as UTC timestamp)
 	pub sub: Uuid, // Optional. Subject (whom token refers to)
+	pub sid: Uuid, // Session the token was issued for, checked against revocation
 	pub external_token: Option<String>,
 }
 
 impl Claims {
-	pub fn new(user_id: Uuid, external_token: Option<String>, now: i64) -> Self {
+	pub fn new(user_id: Uuid, session_id: Uuid, external_token: Option<String>, now: i64) -> Self {
 		Claims {
 			iat: now,
-			exp: now + SEVEN_DAYS,
+			exp: now + FIFTEEN_MINUTES,
 			sub: user_id,
+			sid: session_id,
 			iss: ISSUER.to_owned(),
 			aud: AUDIENCE.to_owned(),
 			nbf: now,
@@ -55,3 +62,25 @@ impl Claims {
 		}
 	}
 }
+
+#[derive(Debug, Clone)]
+pub struct AuthTokens {
+	pub access_token: String,
+	pub refresh_token: String,
+}
+
+// Refresh tokens are formatted as `<session id>.<secret>`, only a hash of the secret is stored
+pub fn format_refresh_token(session_id: Uuid, secret: &str) -> String {
+	format!("{}.{}", session_id, secret)
+}
+
+pub fn parse_refresh_token(refresh_token: &str) -> Option<(Uuid, &str)> {
+	let (session_id, secret) = refresh_token.split_once('.')?;
+	let session_id = Uuid::parse_str(session_id).ok()?;
+
+	if secret.is_empty() {
+		return None;
+	}
+
+	Some((session_id, secret))
+}
@@ -2,12 +2,32 @@ import { RouteObject } from "react-router-dom";
 
 import { LoginPage } from "./pages/login/login.page";
 import { CallbackPage } from "./pages/callback/callback.page";
+import { ForgotPasswordPage } from "./pages/forgot-password/forgot-password.page";
+import { ResetPasswordPage } from "./pages/reset-password/reset-password.page";
+import { VerifyEmailPage } from "./pages/verify-email/verify-email.page";
+import { AcceptInvitationPage } from "./pages/accept-invitation/accept-invitation.page";
 
 export const AUTH_ROUTES: RouteObject[] = [
 	{
 		path: 'login',
 		element: <LoginPage />
 	},
+	{
+		path: 'forgot-password',
+		element: <ForgotPasswordPage />
+	},
+	{
+		path: 'reset-password',
+		element: <ResetPasswordPage />
+	},
+	{
+		path: 'verify-email',
+		element: <VerifyEmailPage />
+	},
+	{
+		path: 'accept-invitation',
+		element: <AcceptInvitationPage />
+	},
 	{
 		path: ':authenticationMethodId/callback',
 		element: <CallbackPage />
@@ -0,0 +1,8 @@
+import * as yup from "yup"
+
+export const acceptInvitationSchema = yup
+	.object({
+		name: yup.string().required(),
+		password: yup.string().required().min(8),
+		passwordConfirmation: yup.string().required().oneOf([yup.ref('password')], 'Passwords must match'),
+	})
@@ -0,0 +1,112 @@
+import cx from 'classnames/bind';
+import { useEffect, useState } from 'react';
+import { FormProvider, useForm } from "react-hook-form";
+import { yupResolver } from "@hookform/resolvers/yup"
+import { useNavigate, useSearchParams } from "react-router-dom";
+import { useTranslation } from "react-i18next";
+
+import { Alert, AlertTypes, Button, ButtonLink, ButtonTypes, HTMLButtonTypes, Loading } from "~components"
+import { TextField, TextFieldTypes } from "~components";
+
+import styles from '../login/login.module.scss';
+
+import { acceptInvitationSchema } from "./accept-invitation.const";
+
+import { IAPIError, ISiteInvitationPreview, useAuthStore, useThemeStore } from "~shared";
+const cxBind = cx.bind(styles);
+
+interface IAcceptInvitationForm {
+	name: string;
+	password: string;
+	passwordConfirmation: string;
+}
+
+export const AcceptInvitationPage = () => {
+	const [fetchSiteInvitation, acceptSiteInvitation] = useAuthStore((state) => [state.fetchSiteInvitation, state.acceptSiteInvitation]);
+	const [theme] = useThemeStore((state) => [state.theme]);
+	const [invitation, setInvitation] = useState<ISiteInvitationPreview>();
+	const [invitationError, setInvitationError] = useState('');
+	const [searchParams] = useSearchParams();
+	const navigate = useNavigate();
+	const formMethods = useForm<IAcceptInvitationForm>({ resolver: yupResolver(acceptInvitationSchema) });
+	const { handleSubmit, setError, formState: { errors } } = formMethods;
+	const { t } = useTranslation();
+	const token = searchParams.get('token') || '';
+
+	useEffect(() => {
+		fetchSiteInvitation(token)
+			.then((result) => setInvitation(result))
+			.catch((error: IAPIError) => setInvitationError(t(`API_MESSAGES.${error.code}`)));
+	}, []);
+
+	const accept = (values?: IAcceptInvitationForm) => {
+		acceptSiteInvitation({ token, name: values?.name, password: values?.password })
+			.then(() => navigate('/auth/login'))
+			.catch((error: IAPIError) => {
+				setError('root', {
+					message: t(`API_MESSAGES.${error.code}`)
+				})
+			});
+	}
+
+	return (
+		<div className={cxBind('p-login')}>
+			<div className={cxBind('p-login__content')}>
+				<div className={cxBind('p-login__logo')}>
+					<img src={`/assets/img/logo-alternative-${theme}.svg`} alt="Logo" />
+				</div>
+				<div className={cxBind('p-login__form')}>
+					<Loading loading={!invitation && !invitationError}>
+						<Alert className="u-margin-bottom" type={AlertTypes.DANGER}>{invitationError || errors?.root?.message}</Alert>
+						{invitationError && (
+							<ButtonLink to="/auth/login" type={ButtonTypes.PRIMARY} block>
+								Go to login
+							</ButtonLink>
+						)}
+						{invitation && (
+							<>
+								<p className="u-margin-bottom">
+									You have been invited to join <b>{invitation.siteName}</b> as <b>{invitation.email}</b>.
+								</p>
+								{invitation.existingAccount ? (
+									<Button type={ButtonTypes.PRIMARY} onClick={() => accept()}>Accept invitation</Button>
+								) : (
+									<FormProvider {...formMethods}>
+										<form onSubmit={handleSubmit(accept)}>
+											<div className="u-margin-bottom">
+												<TextField name="name" label="Name" fieldOptions={{ required: true }} />
+											</div>
+											<div className="u-margin-bottom">
+												<TextField name="password" label="Password" type={TextFieldTypes.PASSWORD} fieldOptions={{ required: true }} />
+											</div>
+											<div className="u-margin-bottom">
+												<TextField name="passwordConfirmation" label="Confirm password" type={TextFieldTypes.PASSWORD} fieldOptions={{ required: true }} />
+											</div>
+											<div>
+												<Button type={ButtonTypes.PRIMARY} htmlType={HTMLButtonTypes.SUBMIT}>Create account</Button>
+											</div>
+										</form>
+									</FormProvider>
+								)}
+							</>
+						)}
+					</Loading>
+				</div>
+			</div>
+			<div className={cxBind('p-login__aside')}>
+				<div
+					className={cxBind('p-login__aside__lazy')}
+					style={{
+						backgroundImage: `url(https://images.unsplash.com/photo-1579033014049-f33d9b14d37e?auto=format&fit=crop&w=300&q=10)`,
+					}}
+				></div>
+				<div
+					className={cxBind('p-login__aside__background')}
+					style={{
+						backgroundImage: `url(https://images.unsplash.com/photo-1579033014049-f33d9b14d37e?auto=format&fit=crop&w=1920&q=100)`,
+					}}
+				></div>
+			</div>
+		</div>
+	);
+};
@@ -20,7 +20,7 @@ export const CallbackPage = () => {
 	const { t } = useTranslation();
 
 	useEffect(() => {
-		callback(authenticationMethodId!, searchParams.get('code')!)
+		callback(authenticationMethodId!, searchParams.get('code')!, searchParams.get('state'))
 			.then(() => navigate('/'))
 			.catch((error: IAPIError) => setError(t(`API_MESSAGES.${error.code}`)));
 	}, []);
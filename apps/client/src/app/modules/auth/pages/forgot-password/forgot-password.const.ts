@@ -0,0 +1,6 @@
+import * as yup from "yup"
+
+export const forgotPasswordSchema = yup
+	.object({
+		email: yup.string().required().email(),
+	})
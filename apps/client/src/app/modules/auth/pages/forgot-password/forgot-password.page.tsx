@@ -0,0 +1,84 @@
+import cx from 'classnames/bind';
+import { FormProvider, useForm } from "react-hook-form";
+import { yupResolver } from "@hookform/resolvers/yup"
+import { useTranslation } from "react-i18next";
+import { useState } from "react";
+
+import { Alert, AlertTypes, Button, ButtonLink, ButtonTypes, HTMLButtonTypes } from "~components"
+import { TextField, TextFieldTypes } from "~components";
+
+import styles from '../login/login.module.scss';
+
+import { forgotPasswordSchema } from "./forgot-password.const";
+
+import { IAPIError, useAuthStore, useThemeStore } from "~shared";
+const cxBind = cx.bind(styles);
+
+interface IForgotPasswordForm {
+	email: string;
+}
+
+export const ForgotPasswordPage = () => {
+	const [requestPasswordReset] = useAuthStore((state) => [state.requestPasswordReset]);
+	const [theme] = useThemeStore((state) => [state.theme]);
+	const [requested, setRequested] = useState(false);
+	const formMethods = useForm<IForgotPasswordForm>({ resolver: yupResolver(forgotPasswordSchema) });
+	const { handleSubmit, setError, formState: { errors } } = formMethods;
+	const { t } = useTranslation();
+
+	const onSubmit = ({ email }: IForgotPasswordForm) => {
+		requestPasswordReset(email)
+			.then(() => setRequested(true))
+			.catch((error: IAPIError) => {
+				setError('root', {
+					message: t(`API_MESSAGES.${error.code}`)
+				})
+			});
+	}
+
+	return (
+		<div className={cxBind('p-login')}>
+			<div className={cxBind('p-login__content')}>
+				<div className={cxBind('p-login__logo')}>
+					<img src={`/assets/img/logo-alternative-${theme}.svg`} alt="Logo" />
+				</div>
+				<div className={cxBind('p-login__form')}>
+					{requested ? (
+						<Alert closable={false} className="u-margin-bottom" type={AlertTypes.INFO}>
+							If an account exists for this email address, you will receive a link to reset your password shortly.
+						</Alert>
+					) : (
+						<>
+							<Alert className="u-margin-bottom" type={AlertTypes.DANGER}>{errors?.root?.message}</Alert>
+							<FormProvider {...formMethods}>
+								<form onSubmit={handleSubmit(onSubmit)}>
+									<div className="u-margin-bottom">
+										<TextField name="email" label="Email" type={TextFieldTypes.EMAIL} fieldOptions={{ required: true }} />
+									</div>
+									<div>
+										<Button className="u-margin-right-sm" type={ButtonTypes.PRIMARY} htmlType={HTMLButtonTypes.SUBMIT}>Send reset link</Button>
+									</div>
+								</form>
+							</FormProvider>
+						</>
+					)}
+					<ButtonLink to="/auth/login" type={ButtonTypes.OUTLINE}>Back to login</ButtonLink>
+				</div>
+			</div>
+			<div className={cxBind('p-login__aside')}>
+				<div
+					className={cxBind('p-login__aside__lazy')}
+					style={{
+						backgroundImage: `url(https://images.unsplash.com/photo-1579033014049-f33d9b14d37e?auto=format&fit=crop&w=300&q=10)`,
+					}}
+				></div>
+				<div
+					className={cxBind('p-login__aside__background')}
+					style={{
+						backgroundImage: `url(https://images.unsplash.com/photo-1579033014049-f33d9b14d37e?auto=format&fit=crop&w=1920&q=100)`,
+					}}
+				></div>
+			</div>
+		</div>
+	);
+};
@@ -5,3 +5,8 @@ export const loginSchema = yup
 		email: yup.string().required().email(),
 		password: yup.string().required().min(5)
 	})
+
+export const twoFactorSchema = yup
+	.object({
+		code: yup.string().required(),
+	})
@@ -3,15 +3,15 @@ import { FormProvider, useForm } from "react-hook-form";
 import { yupResolver } from "@hookform/resolvers/yup"
 import { useNavigate } from "react-router-dom";
 import { useTranslation } from "react-i18next";
-import { FormEvent, useEffect } from "react";
+import { FormEvent, useEffect, useState } from "react";
 
-import { Alert, AlertTypes, Button, ButtonTypes, HTMLButtonTypes, Loading } from "~components"
+import { Alert, AlertTypes, Button, ButtonLink, ButtonTypes, HTMLButtonTypes, Loading } from "~components"
 import { TextField, TextFieldTypes } from "~components";
 
 import styles from './login.module.scss';
-import { loginSchema } from "./login.const";
+import { loginSchema, twoFactorSchema } from "./login.const";
 
-import { IAPIError, IAuthenticationMethod, useAuthStore, useAuthenticationMethodStore, useThemeStore, useConfigStore } from "~shared";
+import { IAPIError, IAuthenticationMethod, ITwoFactorChallenge, useAuthStore, useAuthenticationMethodStore, useThemeStore, useConfigStore } from "~shared";
 const cxBind = cx.bind(styles);
 
 interface ILoginForm {
@@ -19,11 +19,17 @@ interface ILoginForm {
 	password: string;
 }
 
+interface ITwoFactorForm {
+	code: string;
+}
+
 export const LoginPage = () => {
 	const authStore = useAuthStore();
 	const navigate = useNavigate();
 	const formMethods = useForm<ILoginForm>({ resolver: yupResolver(loginSchema) });
 	const { handleSubmit, setError, formState: { errors } } = formMethods;
+	const twoFactorFormMethods = useForm<ITwoFactorForm>({ resolver: yupResolver(twoFactorSchema) });
+	const [challenge, setChallenge] = useState<ITwoFactorChallenge & { authenticationMethodId: string }>();
 	const [theme] = useThemeStore((state) => [state.theme]);
 	const [config] = useConfigStore((state) => [state.config]);
 	const { t } = useTranslation();
@@ -61,7 +67,7 @@ export const LoginPage = () => {
 	const renderLocalAuthForm = (authenticationMethod: IAuthenticationMethod) => {
 		const onSubmit = ({ email, password }: ILoginForm) => {
 			authStore.loginLocal(authenticationMethod.id, email, password)
-				.then(() => navigate('/'))
+				.then((result) => result ? setChallenge({ ...result, authenticationMethodId: authenticationMethod.id }) : navigate('/'))
 				.catch((error: IAPIError) => {
 					setError('root', {
 						message: t(`API_MESSAGES.${error.code}`)
@@ -82,7 +88,36 @@ export const LoginPage = () => {
 						</div>
 						<div>
 							<Button className="u-margin-right-sm" type={ButtonTypes.PRIMARY} htmlType={HTMLButtonTypes.SUBMIT}>Login</Button>
-							<Button type={ButtonTypes.OUTLINE}>Forgot password</Button>
+							<ButtonLink to="/auth/forgot-password" type={ButtonTypes.OUTLINE}>Forgot password</ButtonLink>
+						</div>
+					</form>
+				</FormProvider>
+			</div>
+		)
+	}
+
+	const renderTwoFactorForm = ({ authenticationMethodId, challenge: challengeToken }: ITwoFactorChallenge & { authenticationMethodId: string }) => {
+		const onSubmit = ({ code }: ITwoFactorForm) => {
+			authStore.verifyTwoFactor(authenticationMethodId, challengeToken, code)
+				.then(() => navigate('/'))
+				.catch((error: IAPIError) => {
+					// Every attempt uses up the challenge, so a wrong code means logging in again
+					setChallenge(undefined);
+					setError('root', {
+						message: t(`API_MESSAGES.${error.code}`)
+					})
+				});
+		}
+
+		return (
+			<div className={cxBind('p-login__form')}>
+				<FormProvider {...twoFactorFormMethods}>
+					<form onSubmit={twoFactorFormMethods.handleSubmit(onSubmit)}>
+						<div className="u-margin-bottom">
+							<TextField name="code" label="Code or recovery code" fieldOptions={{ required: true }} />
+						</div>
+						<div>
+							<Button type={ButtonTypes.PRIMARY} htmlType={HTMLButtonTypes.SUBMIT}>Verify</Button>
 						</div>
 					</form>
 				</FormProvider>
@@ -90,21 +125,26 @@ export const LoginPage = () => {
 		)
 	}
 
+	const renderAuthenticationMethods = () => (
+		<Loading loading={authenticationMethodsLoading}>
+			{(authenticationMethods || []).sort((a, b) => b.weight - a.weight).map((method) => {
+				if (method.kind === 'LOCAL' || method.kind === 'LDAP') {
+					return renderLocalAuthForm(method);
+				}
+
+				return renderAuthButton(method);
+			})}
+		</Loading>
+	)
+
 	return (
 		<div className={cxBind('p-login')}>
 			<div className={cxBind('p-login__content')}>
 				<div className={cxBind('p-login__logo')}>
 					<img src={config?.rootLogoUrl as string || `/assets/img/logo-alternative-${theme}.svg`} alt="Logo" />
 				</div>
-				<Loading loading={authenticationMethodsLoading}>
-					{(authenticationMethods || []).sort((a, b) => b.weight - a.weight).map((method) => {
-						if (method.kind === 'LOCAL') {
-							return renderLocalAuthForm(method);
-						}
-
-						return renderAuthButton(method);
-					})}
-				</Loading>
+				{challenge && renderTwoFactorForm(challenge)}
+				{!challenge && renderAuthenticationMethods()}
 			</div>
 			<div className={cxBind('p-login__aside')}>
 				<div
@@ -0,0 +1,7 @@
+import * as yup from "yup"
+
+export const resetPasswordSchema = yup
+	.object({
+		password: yup.string().required().min(8),
+		passwordConfirmation: yup.string().required().oneOf([yup.ref('password')], 'Passwords must match'),
+	})
@@ -0,0 +1,80 @@
+import cx from 'classnames/bind';
+import { FormProvider, useForm } from "react-hook-form";
+import { yupResolver } from "@hookform/resolvers/yup"
+import { useNavigate, useSearchParams } from "react-router-dom";
+import { useTranslation } from "react-i18next";
+
+import { Alert, AlertTypes, Button, ButtonTypes, HTMLButtonTypes } from "~components"
+import { TextField, TextFieldTypes } from "~components";
+
+import styles from '../login/login.module.scss';
+
+import { resetPasswordSchema } from "./reset-password.const";
+
+import { IAPIError, useAuthStore, useThemeStore } from "~shared";
+const cxBind = cx.bind(styles);
+
+interface IResetPasswordForm {
+	password: string;
+	passwordConfirmation: string;
+}
+
+export const ResetPasswordPage = () => {
+	const [resetPassword] = useAuthStore((state) => [state.resetPassword]);
+	const [theme] = useThemeStore((state) => [state.theme]);
+	const [searchParams] = useSearchParams();
+	const navigate = useNavigate();
+	const formMethods = useForm<IResetPasswordForm>({ resolver: yupResolver(resetPasswordSchema) });
+	const { handleSubmit, setError, formState: { errors } } = formMethods;
+	const { t } = useTranslation();
+
+	const onSubmit = ({ password }: IResetPasswordForm) => {
+		resetPassword(searchParams.get('token') || '', password)
+			.then(() => navigate('/auth/login'))
+			.catch((error: IAPIError) => {
+				setError('root', {
+					message: t(`API_MESSAGES.${error.code}`)
+				})
+			});
+	}
+
+	return (
+		<div className={cxBind('p-login')}>
+			<div className={cxBind('p-login__content')}>
+				<div className={cxBind('p-login__logo')}>
+					<img src={`/assets/img/logo-alternative-${theme}.svg`} alt="Logo" />
+				</div>
+				<div className={cxBind('p-login__form')}>
+					<Alert className="u-margin-bottom" type={AlertTypes.DANGER}>{errors?.root?.message}</Alert>
+					<FormProvider {...formMethods}>
+						<form onSubmit={handleSubmit(onSubmit)}>
+							<div className="u-margin-bottom">
+								<TextField name="password" label="New password" type={TextFieldTypes.PASSWORD} fieldOptions={{ required: true }} />
+							</div>
+							<div className="u-margin-bottom">
+								<TextField name="passwordConfirmation" label="Confirm new password" type={TextFieldTypes.PASSWORD} fieldOptions={{ required: true }} />
+							</div>
+							<div>
+								<Button type={ButtonTypes.PRIMARY} htmlType={HTMLButtonTypes.SUBMIT}>Reset password</Button>
+							</div>
+						</form>
+					</FormProvider>
+				</div>
+			</div>
+			<div className={cxBind('p-login__aside')}>
+				<div
+					className={cxBind('p-login__aside__lazy')}
+					style={{
+						backgroundImage: `url(https://images.unsplash.com/photo-1579033014049-f33d9b14d37e?auto=format&fit=crop&w=300&q=10)`,
+					}}
+				></div>
+				<div
+					className={cxBind('p-login__aside__background')}
+					style={{
+						backgroundImage: `url(https://images.unsplash.com/photo-1579033014049-f33d9b14d37e?auto=format&fit=crop&w=1920&q=100)`,
+					}}
+				></div>
+			</div>
+		</div>
+	);
+};
@@ -0,0 +1,63 @@
+import cx from 'classnames/bind';
+import { useSearchParams } from 'react-router-dom';
+import { useTranslation } from 'react-i18next';
+import { useEffect, useState } from 'react';
+
+import { Alert, AlertTypes, ButtonLink, ButtonTypes, Loading } from '~components';
+
+import styles from '../callback/callback.module.scss';
+
+import { IAPIError, useAuthStore, useThemeStore } from '~shared';
+const cxBind = cx.bind(styles);
+
+export const VerifyEmailPage = () => {
+	const [verifyEmail] = useAuthStore((state) => [state.verifyEmail]);
+	const [error, setError] = useState('');
+	const [verified, setVerified] = useState(false);
+	const [theme] = useThemeStore((state) => [state.theme]);
+	const [searchParams] = useSearchParams();
+	const { t } = useTranslation();
+
+	useEffect(() => {
+		verifyEmail(searchParams.get('token') || '')
+			.then(() => setVerified(true))
+			.catch((error: IAPIError) => setError(t(`API_MESSAGES.${error.code}`)));
+	}, []);
+
+	return (
+		<div className={cxBind('p-callback')}>
+			<div className={cxBind('p-callback__content')}>
+				<div className={cxBind('p-callback__logo')}>
+					<img src={`/assets/img/logo-alternative-${theme}.svg`} alt="Logo" />
+				</div>
+				<Loading className={cxBind('p-callback__loading')} loading={!error && !verified}></Loading>
+				{(error || verified) && (
+					<>
+						<Alert closable={false} type={error ? AlertTypes.DANGER : AlertTypes.INFO} className={cxBind('p-callback__loading')}>
+							{error || 'Your email address has been verified.'}
+						</Alert>
+						<div className={cxBind('p-callback__action')}>
+							<ButtonLink to="/auth/login" type={ButtonTypes.PRIMARY} block>
+								Go to login
+							</ButtonLink>
+						</div>
+					</>
+				)}
+			</div>
+			<div className={cxBind('p-callback__aside')}>
+				<div
+					className={cxBind('p-callback__aside__lazy')}
+					style={{
+						backgroundImage: `url(https://images.unsplash.com/photo-1579033014049-f33d9b14d37e?auto=format&fit=crop&w=300&q=10)`,
+					}}
+				></div>
+				<div
+					className={cxBind('p-callback__aside__background')}
+					style={{
+						backgroundImage: `url(https://images.unsplash.com/photo-1579033014049-f33d9b14d37e?auto=format&fit=crop&w=1920&q=100)`,
+					}}
+				></div>
+			</div>
+		</div>
+	);
+};
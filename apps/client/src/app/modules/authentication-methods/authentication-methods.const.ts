@@ -8,6 +8,14 @@ export const AUTHENTICATION_METHOD_OPTIONS = [
 	{
 		label: 'OAuth2',
 		value: AuthenticationMethod.OAUTH2
+	},
+	{
+		label: 'OpenID Connect',
+		value: AuthenticationMethod.OIDC
+	},
+	{
+		label: 'LDAP',
+		value: AuthenticationMethod.LDAP
 	}
 ]
 
@@ -108,5 +116,270 @@ export const AUTHENTICATION_METHOD_FIELDS: Record<AuthenticationMethod, IField[]
 				fields: [],
 			}
 		}
+	],
+	[AuthenticationMethod.OIDC]: [
+		{
+			id: '5cf292a2-8a19-4bab-a637-dde0199c5d8b',
+			name: 'Issuer',
+			slug: 'issuer',
+			min: 1,
+			max: 1,
+			contentComponent: {
+				id: 'f665034b-1dbc-4fda-ab62-da1ef6d4b054',
+				name: 'Text',
+				slug: 'text',
+				componentName: FieldKeys.TEXT,
+				configurationFields: [],
+				fields: [],
+			}
+		},
+		{
+			id: '5cf292a2-8a19-4bab-a637-dde0199c5d8b',
+			name: 'Client ID',
+			slug: 'client_id',
+			min: 1,
+			max: 1,
+			contentComponent: {
+				id: 'f665034b-1dbc-4fda-ab62-da1ef6d4b054',
+				name: 'Text',
+				slug: 'text',
+				componentName: FieldKeys.TEXT,
+				configurationFields: [],
+				fields: [],
+			}
+		},
+		{
+			id: '5cf292a2-8a19-4bab-a637-dde0199c5d8b',
+			name: 'Client Secret',
+			slug: 'client_secret',
+			min: 1,
+			max: 1,
+			config: {
+				type: 'password'
+			},
+			contentComponent: {
+				id: 'f665034b-1dbc-4fda-ab62-da1ef6d4b054',
+				name: 'Text',
+				slug: 'text',
+				componentName: FieldKeys.TEXT,
+				configurationFields: [],
+				fields: [],
+			}
+		},
+		{
+			id: '5cf292a2-8a19-4bab-a637-dde0199c5d8b',
+			name: 'Scopes',
+			slug: 'scopes',
+			min: 1,
+			max: 0,
+			contentComponent: {
+				id: 'f665034b-1dbc-4fda-ab62-da1ef6d4b054',
+				name: 'Text',
+				slug: 'text',
+				componentName: FieldKeys.TEXT,
+				configurationFields: [],
+				fields: [],
+			}
+		},
+		{
+			id: '5cf292a2-8a19-4bab-a637-dde0199c5d8b',
+			name: 'Email Claim',
+			slug: 'email_claim',
+			min: 1,
+			max: 1,
+			contentComponent: {
+				id: 'f665034b-1dbc-4fda-ab62-da1ef6d4b054',
+				name: 'Text',
+				slug: 'text',
+				componentName: FieldKeys.TEXT,
+				configurationFields: [],
+				fields: [],
+			}
+		},
+		{
+			id: '5cf292a2-8a19-4bab-a637-dde0199c5d8b',
+			name: 'Name Claim',
+			slug: 'name_claim',
+			min: 1,
+			max: 1,
+			contentComponent: {
+				id: 'f665034b-1dbc-4fda-ab62-da1ef6d4b054',
+				name: 'Text',
+				slug: 'text',
+				componentName: FieldKeys.TEXT,
+				configurationFields: [],
+				fields: [],
+			}
+		},
+		{
+			id: '5cf292a2-8a19-4bab-a637-dde0199c5d8b',
+			name: 'Picture Claim',
+			slug: 'picture_claim',
+			min: 1,
+			max: 1,
+			contentComponent: {
+				id: 'f665034b-1dbc-4fda-ab62-da1ef6d4b054',
+				name: 'Text',
+				slug: 'text',
+				componentName: FieldKeys.TEXT,
+				configurationFields: [],
+				fields: [],
+			}
+		},
+		{
+			id: '5cf292a2-8a19-4bab-a637-dde0199c5d8b',
+			name: 'Groups Claim',
+			slug: 'groups_claim',
+			min: 1,
+			max: 1,
+			contentComponent: {
+				id: 'f665034b-1dbc-4fda-ab62-da1ef6d4b054',
+				name: 'Text',
+				slug: 'text',
+				componentName: FieldKeys.TEXT,
+				configurationFields: [],
+				fields: [],
+			}
+		}
+	],
+	[AuthenticationMethod.LDAP]: [
+		{
+			id: '5cf292a2-8a19-4bab-a637-dde0199c5d8b',
+			name: 'URL',
+			slug: 'url',
+			min: 1,
+			max: 1,
+			contentComponent: {
+				id: 'f665034b-1dbc-4fda-ab62-da1ef6d4b054',
+				name: 'Text',
+				slug: 'text',
+				componentName: FieldKeys.TEXT,
+				configurationFields: [],
+				fields: [],
+			}
+		},
+		{
+			id: '5cf292a2-8a19-4bab-a637-dde0199c5d8b',
+			name: 'Bind DN Template',
+			slug: 'bind_dn_template',
+			min: 1,
+			max: 1,
+			contentComponent: {
+				id: 'f665034b-1dbc-4fda-ab62-da1ef6d4b054',
+				name: 'Text',
+				slug: 'text',
+				componentName: FieldKeys.TEXT,
+				configurationFields: [],
+				fields: [],
+			}
+		},
+		{
+			id: '5cf292a2-8a19-4bab-a637-dde0199c5d8b',
+			name: 'Search Base',
+			slug: 'search_base',
+			min: 1,
+			max: 1,
+			contentComponent: {
+				id: 'f665034b-1dbc-4fda-ab62-da1ef6d4b054',
+				name: 'Text',
+				slug: 'text',
+				componentName: FieldKeys.TEXT,
+				configurationFields: [],
+				fields: [],
+			}
+		},
+		{
+			id: '5cf292a2-8a19-4bab-a637-dde0199c5d8b',
+			name: 'Search Filter',
+			slug: 'search_filter',
+			min: 1,
+			max: 1,
+			contentComponent: {
+				id: 'f665034b-1dbc-4fda-ab62-da1ef6d4b054',
+				name: 'Text',
+				slug: 'text',
+				componentName: FieldKeys.TEXT,
+				configurationFields: [],
+				fields: [],
+			}
+		},
+		{
+			id: '5cf292a2-8a19-4bab-a637-dde0199c5d8b',
+			name: 'Service Bind DN',
+			slug: 'service_bind_dn',
+			min: 1,
+			max: 1,
+			contentComponent: {
+				id: 'f665034b-1dbc-4fda-ab62-da1ef6d4b054',
+				name: 'Text',
+				slug: 'text',
+				componentName: FieldKeys.TEXT,
+				configurationFields: [],
+				fields: [],
+			}
+		},
+		{
+			id: '5cf292a2-8a19-4bab-a637-dde0199c5d8b',
+			name: 'Service Bind Password',
+			slug: 'service_bind_password',
+			min: 1,
+			max: 1,
+			config: {
+				type: 'password'
+			},
+			contentComponent: {
+				id: 'f665034b-1dbc-4fda-ab62-da1ef6d4b054',
+				name: 'Text',
+				slug: 'text',
+				componentName: FieldKeys.TEXT,
+				configurationFields: [],
+				fields: [],
+			}
+		},
+		{
+			id: '5cf292a2-8a19-4bab-a637-dde0199c5d8b',
+			name: 'Email Attribute',
+			slug: 'email_attribute',
+			min: 1,
+			max: 1,
+			contentComponent: {
+				id: 'f665034b-1dbc-4fda-ab62-da1ef6d4b054',
+				name: 'Text',
+				slug: 'text',
+				componentName: FieldKeys.TEXT,
+				configurationFields: [],
+				fields: [],
+			}
+		},
+		{
+			id: '5cf292a2-8a19-4bab-a637-dde0199c5d8b',
+			name: 'Name Attribute',
+			slug: 'name_attribute',
+			min: 1,
+			max: 1,
+			contentComponent: {
+				id: 'f665034b-1dbc-4fda-ab62-da1ef6d4b054',
+				name: 'Text',
+				slug: 'text',
+				componentName: FieldKeys.TEXT,
+				configurationFields: [],
+				fields: [],
+			}
+		},
+		{
+			id: '5cf292a2-8a19-4bab-a637-dde0199c5d8b',
+			name: 'Group Attribute',
+			slug: 'group_attribute',
+			min: 1,
+			max: 1,
+			contentComponent: {
+				id: 'f665034b-1dbc-4fda-ab62-da1ef6d4b054',
+				name: 'Text',
+				slug: 'text',
+				componentName: FieldKeys.TEXT,
+				configurationFields: [],
+				fields: [],
+			}
+		}
 	]
 }
@@ -8,6 +8,7 @@ import { IAuthenticationMethodRoleAssignment } from '~shared';
 export const addRoleAssignmentSchema = yup.object({
 	siteId: yup.string().optional(),
 	roleId: yup.string().required(),
+	groupName: yup.string().optional(),
 });
 
 
@@ -24,6 +25,11 @@ export const AUTHENTICATION_METHOD_ROLE_ASSIGNMENTS_COLUMNS = (
 		id: 'role.name',
 		label: 'Role Name',
 	},
+	{
+		id: 'groupName',
+		label: 'Group',
+		format: (value: string | null) => value || <span className="u-text--light u-text--italic">Everyone</span>
+	},
 	{
 		id: 'actions',
 		label: '',
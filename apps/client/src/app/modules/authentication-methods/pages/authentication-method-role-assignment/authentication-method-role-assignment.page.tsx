@@ -5,7 +5,7 @@ import { useTranslation } from 'react-i18next';
 import { useEffect, useState } from 'react';
 
 import { Alert, AlertTypes, Button, ButtonTypes, Card, HTMLButtonTypes, Loading, Pagination, Table, Tabs } from '~components';
-import { SelectField } from '~components';
+import { SelectField, TextField } from '~components';
 
 import { useSiteStore } from '../../../sites/stores/site';
 
@@ -16,6 +16,7 @@ import { IAPIError, getPageParams, getPaginationProps, useAuthenticationMethodRo
 interface AddAssignmentForm {
 	siteId: string | undefined;
 	roleId: string;
+	groupName: string | undefined;
 }
 
 export const AuthenticationMethodRoleAssignmentPage = () => {
@@ -79,7 +80,7 @@ export const AuthenticationMethodRoleAssignmentPage = () => {
 			return;
 		}
 	
-		createAuthenticationMethodRoleAssignment(authenticationMethodId, values)
+		createAuthenticationMethodRoleAssignment(authenticationMethodId, { ...values, groupName: values.groupName || undefined })
 			.then(() => fetchAuthenticationMethodRoleAssignments(authenticationMethodId!))
 			.catch((error: IAPIError) => {
 				setError('root', {
@@ -139,6 +140,9 @@ export const AuthenticationMethodRoleAssignmentPage = () => {
 						>
 							{errors?.root?.message}
 						</Alert>
+						<div className="u-margin-bottom">
+							<TextField name="groupName" label="Group (optional, only assigned to members of this group)" />
+						</div>
 						<div className="u-row">
 							{assignmentType === 'site' && (
 								<>
@@ -22,7 +22,7 @@ export const TopBar = () => {
 	const [user, activeSite] = useAuthStore((state) => [state.user, state.activeSite,]);
 	const [setTheme, theme] = useThemeStore((state) => [state.setTheme, state.theme]);
 	const navigate = useNavigate();
-	const [clear] = useAuthStore((state) => [state.clear]);
+	const [logout] = useAuthStore((state) => [state.logout]);
 
 	return (
 		<div className={cxBind('o-top-bar')}>
@@ -62,8 +62,7 @@ export const TopBar = () => {
 							to=""
 							onClick={(e) => {
 								e.preventDefault();
-								clear();
-								navigate('/auth/login');
+								logout().then(() => navigate('/auth/login'));
 							}}
 						>
 							<i className="las la-sign-out-alt"></i>
@@ -0,0 +1,6 @@
+import * as yup from 'yup';
+
+export const inviteUserSchema = yup.object({
+	email: yup.string().email().required(),
+	roleIds: yup.array().of(yup.string().required()).required().min(1),
+});
@@ -0,0 +1,92 @@
+import { useEffect } from 'react';
+import { useTranslation } from 'react-i18next';
+import { FormProvider, useForm } from 'react-hook-form';
+import { yupResolver } from '@hookform/resolvers/yup';
+import { generatePath, useNavigate, useParams } from 'react-router-dom';
+
+import { Alert, AlertTypes, Button, ButtonTypes, HTMLButtonTypes, Header, Loading } from '~components';
+import { CheckboxField, TextField } from '~components';
+
+import { useSiteUserStore } from '../../stores/site-user';
+import { SITE_USER_PATHS } from '../../site-users.routes';
+
+import { inviteUserSchema } from './user-invite.const';
+
+import { IAPIError, useHeaderStore, useSiteRoleStore } from '~shared';
+
+interface InviteUserForm {
+	email: string;
+	roleIds: string[];
+}
+
+export const UserInvitePage = () => {
+	const navigate = useNavigate();
+	const [createInvitationLoading, createInvitation] = useSiteUserStore((state) => [
+		state.createInvitationLoading,
+		state.createInvitation,
+	]);
+	const [roles, rolesLoading, fetchRoles] = useSiteRoleStore((state) => [
+		state.roles,
+		state.rolesLoading,
+		state.fetchRoles
+	]);
+	const { siteId } = useParams();
+	const { t } = useTranslation();
+	const [breadcrumbs, setBreadcrumbs] = useHeaderStore((state) => [state.breadcrumbs, state.setBreadcrumbs]);
+	const formMethods = useForm<InviteUserForm>({
+		resolver: yupResolver(inviteUserSchema),
+	});
+
+	const {
+		handleSubmit,
+		formState: { errors },
+		setError,
+	} = formMethods;
+
+	useEffect(() => {
+		fetchRoles(siteId!, { pagesize: -1 });
+		setBreadcrumbs([
+			{ label: t(`BREADCRUMBS.SITE_USERS`), to: SITE_USER_PATHS.ROOT },
+			{ label: t(`BREADCRUMBS.INVITE`) },
+		]);
+	}, []);
+
+	const onSubmit = (values: InviteUserForm) => {
+		createInvitation(siteId!, values)
+			.then(() => navigate(generatePath(SITE_USER_PATHS.ROOT, { siteId })))
+			.catch((error: IAPIError) => {
+				setError('root', {
+					message: error.code,
+				});
+			});
+	};
+
+	return (
+		<>
+			<Header
+				breadcrumbs={breadcrumbs}
+				title={t('SITE_USERS.TITLES.INVITE')}
+			></Header>
+			<div className="u-margin-top">
+				<Loading loading={rolesLoading}>
+					<FormProvider {...formMethods}>
+						<Alert className="u-margin-bottom" type={AlertTypes.DANGER}>
+							{errors?.root?.message}
+						</Alert>
+						<form onSubmit={handleSubmit(onSubmit)}>
+							<div className="u-margin-bottom">
+								<TextField name="email" label="Email" />
+							</div>
+							<div className="u-margin-bottom">
+								<CheckboxField name="roleIds" label="Roles" fieldConfiguration={{ options: roles.map((role) => ({ label: role.name, value: role.id })) }} />
+							</div>
+							<Button type={ButtonTypes.PRIMARY} htmlType={HTMLButtonTypes.SUBMIT} disabled={!!Object.keys(errors).length}>
+								{createInvitationLoading && <i className="las la-redo-alt la-spin"></i>} Send invitation
+							</Button>
+						</form>
+					</FormProvider>
+				</Loading>
+			</div>
+		</>
+	);
+};
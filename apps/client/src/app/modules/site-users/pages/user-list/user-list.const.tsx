@@ -1,10 +1,13 @@
 import { TFunction } from 'i18next';
+import dayjs from 'dayjs';
 
 import { Badge, Button, ButtonLink, ButtonSizes, ButtonTypes, ITableColumn } from '~components';
 
+import { ISiteInvitation } from '../../stores/site-user';
+
 import { IRole, IUser } from '~shared';
 
-export const USER_LIST_COLUMNS = (t: TFunction, handleRemove: (workflowStateId: string) => void): ITableColumn<IUser>[] => [
+export const USER_LIST_COLUMNS = (t: TFunction, handleRemove: (userId: string) => void): ITableColumn<IUser>[] => [
 	{
 		id: 'name',
 		label: 'Name',
@@ -29,3 +32,31 @@ export const USER_LIST_COLUMNS = (t: TFunction, handleRemove: (workflowStateId:
 		),
 	},
 ];
+
+export const INVITATION_LIST_COLUMNS = (t: TFunction, handleRemove: (invitationId: string) => void): ITableColumn<ISiteInvitation>[] => [
+	{
+		id: 'email',
+		label: 'Email',
+	},
+	{
+		id: 'roles',
+		label: 'Roles',
+		format: (roles: IRole[]) => roles.map((role) => <Badge className='u-margin-right-xs u-margin-top-xxxs u-margin-bottom-xxxs' key={role.id}>{role.name}</Badge>)
+	},
+	{
+		id: 'expiresAt',
+		label: 'Expires',
+		format: (value) => dayjs.utc(value as string).fromNow(),
+	},
+	{
+		id: 'actions',
+		label: '',
+		format: (value, key, item) => (
+			<div className="u-display-flex">
+				<Button size={ButtonSizes.SMALL} className="u-margin-left-auto" onClick={() => handleRemove(item.id)}>
+					<i className="las la-trash"></i>
+				</Button>
+			</div>
+		),
+	},
+];
@@ -2,33 +2,49 @@ import { useEffect } from 'react';
 import { useTranslation } from 'react-i18next';
 import { useParams, useSearchParams } from 'react-router-dom';
 
-import { Header, Loading, Pagination, Table } from '~components';
+import { ButtonLink, ButtonTypes, Header, Loading, Pagination, Table } from '~components';
 
 import { useSiteUserStore } from '../../stores/site-user';
 
-import { USER_LIST_COLUMNS } from './user-list.const';
+import { INVITATION_LIST_COLUMNS, USER_LIST_COLUMNS } from './user-list.const';
 
-import { getPaginationProps, useHeaderStore } from '~shared';
+import { getPageParams, getPaginationProps, useHeaderStore } from '~shared';
 
 export const UserListPage = () => {
 	const [searchParams, setSearchParams] = useSearchParams();
-	const [users, usersPagination, usersLoading, fetchWorkflowStates] = useSiteUserStore((state) => [
+	const [users, usersPagination, usersLoading, fetchUsers] = useSiteUserStore((state) => [
 		state.users,
 		state.usersPagination,
 		state.usersLoading,
 		state.fetchUsers,
 	]);
+	const [invitations, fetchInvitations] = useSiteUserStore((state) => [
+		state.invitations,
+		state.fetchInvitations,
+	]);
+	const [removeUser, removeInvitation] = useSiteUserStore((state) => [
+		state.removeUser,
+		state.removeInvitation,
+	]);
 	const { t } = useTranslation();
-	const { kind, siteId } = useParams();
+	const { siteId } = useParams();
 	const [breadcrumbs, setBreadcrumbs] = useHeaderStore((state) => [state.breadcrumbs, state.setBreadcrumbs]);
 
 	useEffect(() => {
-		fetchWorkflowStates(siteId!);
 		setBreadcrumbs([{ label: t(`BREADCRUMBS.SITE_USERS`) }]);
-	}, [kind]);
+		fetchInvitations(siteId!, { pagesize: -1 });
+	}, []);
+
+	useEffect(() => {
+		fetchUsers(siteId!, { ...getPageParams(searchParams) });
+	}, [searchParams]);
+
+	const handleRemove = (userId: string): void => {
+		removeUser(siteId!, userId).then(() => fetchUsers(siteId!, { ...getPageParams(searchParams) }));
+	}
 
-	const handleRemove = (workflowStateId: string): void => {
-		// removeWorkflowState(workflowStateId).then(() => fetchWorkflowStates());
+	const handleRemoveInvitation = (invitationId: string): void => {
+		removeInvitation(siteId!, invitationId).then(() => fetchInvitations(siteId!, { pagesize: -1 }));
 	}
 
 	return (
@@ -36,6 +52,11 @@ export const UserListPage = () => {
 			<Header
 				breadcrumbs={breadcrumbs}
 				title={t(`SITE_USERS.TITLES.LIST`)}
+				action={
+					<ButtonLink to="invite" type={ButtonTypes.PRIMARY}>
+						<span className="las la-envelope"></span> {t(`SITE_USERS.ACTIONS.INVITE`)}
+					</ButtonLink>
+				}
 			></Header>
 			<Loading loading={usersLoading} text={t(`GENERAL.LABELS.LOADING`)}>
 				<Table columns={USER_LIST_COLUMNS(t, handleRemove)} rows={users || []}></Table>
@@ -47,6 +68,12 @@ export const UserListPage = () => {
 					{...getPaginationProps(searchParams, setSearchParams)}
 				/>
 			</Loading>
+			{!!invitations.length && (
+				<div className="u-margin-top">
+					<h3 className="u-margin-bottom">{t(`SITE_USERS.TITLES.INVITATIONS`)}</h3>
+					<Table columns={INVITATION_LIST_COLUMNS(t, handleRemoveInvitation)} rows={invitations}></Table>
+				</div>
+			)}
 		</>
 	);
 };
@@ -7,6 +7,7 @@ const DETAIL_PATH = `${ROOT_PATH}/:userId`;
 
 export const SITE_USER_PATHS = {
 	ROOT: `${ROOT_PATH}`,
+	INVITE: `${ROOT_PATH}/invite`,
 	DETAIL: `${DETAIL_PATH}`,
 }
 
@@ -15,6 +16,10 @@ export const SITE_USER_ROUTES: RouteObject[] = [
 		path: SITE_USER_PATHS.ROOT,
 		lazy: async () => ({ Component: (await import('./pages/user-list/user-list.page')).UserListPage }),
 	},
+	{
+		path: SITE_USER_PATHS.INVITE,
+		lazy: async () => ({ Component: (await import('./pages/user-invite/user-invite.page')).UserInvitePage }),
+	},
 	{
 		path: SITE_USER_PATHS.DETAIL,
 		lazy: async () => ({ Component: (await import('./pages/user-detail/user-detail.page')).UserDetailPage }),
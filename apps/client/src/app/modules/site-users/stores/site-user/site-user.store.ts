@@ -1,7 +1,7 @@
 import { create } from 'zustand';
 import { devtools } from 'zustand/middleware'
 
-import { ISiteUserStoreState, ISiteUsersResponse } from './site-user.types';
+import { ISiteInvitation, ISiteInvitationsResponse, ISiteUserStoreState, ISiteUsersResponse } from './site-user.types';
 
 import { DEFAULT_PAGINATION_OPTIONS, IUser, kyInstance, wrapApi } from '~shared';
 
@@ -84,5 +84,52 @@ export const useSiteUserStore = create<ISiteUserStoreState>()(devtools(
 			return result;
 		},
 		removeUserLoading: false,
+
+		fetchInvitations: async (siteId, searchParams) => {
+			set(() => ({ invitationsLoading: true }));
+			const [result, error] = await wrapApi(kyInstance.get(`/admin-api/v1/sites/${siteId}/invitations`, {
+				searchParams: {
+					...DEFAULT_PAGINATION_OPTIONS,
+					...searchParams,
+				}
+			}).json<ISiteInvitationsResponse>());
+
+			if (error) {
+				return set(() => ({ invitations: [], invitationsLoading: false }))
+			}
+
+			set(() => ({ invitations: result._embedded.invitations, invitationsPagination: result._page, invitationsLoading: false }));
+		},
+		invitations: [],
+		invitationsLoading: false,
+
+		createInvitation: async (siteId, invitation) => {
+			set(() => ({ createInvitationLoading: true }));
+			const [result, error] = await wrapApi(kyInstance.post(`/admin-api/v1/sites/${siteId}/invitations`, {
+				json: invitation,
+			}).json<ISiteInvitation>());
+			set(() => ({ createInvitationLoading: false }));
+
+			if (error) {
+				throw error;
+			}
+
+			return result;
+		},
+		createInvitationLoading: false,
+
+		removeInvitation: async (siteId, invitationId) => {
+			set(() => ({ removeInvitationLoading: true }));
+			const [result, error] = await wrapApi(kyInstance.delete(`/admin-api/v1/sites/${siteId}/invitations/${invitationId}`).json<void>());
+
+			if (error) {
+				set(() => ({ removeInvitationLoading: false }));
+				throw error;
+			}
+
+			set(() => ({ removeInvitationLoading: false }));
+			return result;
+		},
+		removeInvitationLoading: false,
 	}), { name: 'userStore' }
 ))
@@ -1,6 +1,7 @@
-import { IAPIHALResponse, IAPIPagination, IPageParameters, IUser } from "~shared";
+import { IAPIHALResponse, IAPIPagination, IPageParameters, IRole, IUser } from "~shared";
 
 export type ISiteUsersResponse = IAPIHALResponse<'users', IUser>
+export type ISiteInvitationsResponse = IAPIHALResponse<'invitations', ISiteInvitation>
 
 export interface ISiteUserStoreState {
 	fetchUsers: (siteId: string, params?: IPageParameters) => Promise<void>;
@@ -20,6 +21,32 @@ export interface ISiteUserStoreState {
 
 	removeUser: (siteId: string, userId: string) => Promise<void>;
 	removeUserLoading: boolean;
+
+	fetchInvitations: (siteId: string, params?: IPageParameters) => Promise<void>;
+	invitations: ISiteInvitation[];
+	invitationsPagination?: IAPIPagination;
+	invitationsLoading: boolean;
+
+	createInvitation: (siteId: string, invitation: ISiteInvitationCreateDTO) => Promise<ISiteInvitation>;
+	createInvitationLoading: boolean;
+
+	removeInvitation: (siteId: string, invitationId: string) => Promise<void>;
+	removeInvitationLoading: boolean;
+}
+
+export interface ISiteInvitation {
+	id: string;
+	email: string;
+	roles: IRole[];
+	invitedById?: string;
+	expiresAt: string;
+	createdAt: string;
+	updatedAt: string;
+}
+
+export interface ISiteInvitationCreateDTO {
+	email: string;
+	roleIds: string[];
 }
 
 export interface ISiteUserCreateDTO {
@@ -14,6 +14,22 @@ export const kyInstance = ky.extend({
 
 			return request;
 		}],
+		afterResponse: [async (request, _options, response) => {
+			if (response.status !== 401 || request.headers.get('X-Retried-After-Refresh')) {
+				return response;
+			}
+
+			const token = await useAuthStore.getState().refresh();
+
+			if (!token) {
+				return response;
+			}
+
+			request.headers.set('Authorization', `Bearer ${token}`);
+			request.headers.set('X-Retried-After-Refresh', 'true');
+
+			return ky(request);
+		}],
 		beforeError: [async (error) => {
 			const errorBody = await error.response.json();
 
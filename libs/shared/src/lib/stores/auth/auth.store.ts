@@ -4,28 +4,107 @@ import { devtools, persist } from 'zustand/middleware'
 import { kyAuthInstance, kyInstance, wrapApi } from '../../services';
 import { ISite } from '../../types';
 
-import { IMeReponse, IPermission, IUser } from './auth.types';
+import { IAcceptSiteInvitationDTO, IMeReponse, IPermission, IRefreshResponse, ISiteInvitationPreview, ITwoFactorChallenge, IUser } from './auth.types';
 
 interface IAuthStoreState {
 	user?: IUser;
 	permissions: IPermission[];
 	token?: string;
+	refreshToken?: string;
 	activeSite?: ISite;
 	fetchUser: (siteId?: string) => Promise<void>;
 	fetchSite: (siteId: string) => Promise<void>;
 	setup: (values: any) => Promise<void>;
-	loginLocal: (authenticationMethodId: string, email: string, password: string) => Promise<void>;
+	loginLocal: (authenticationMethodId: string, email: string, password: string) => Promise<ITwoFactorChallenge | void>;
+	verifyTwoFactor: (authenticationMethodId: string, challenge: string, code: string) => Promise<void>;
 	login: (authenticationMethodId: string) => Promise<{ redirect: string }>;
-	callback: (authenticationMethodId: string, code: string) => Promise<void>;
+	callback: (authenticationMethodId: string, code: string, state: string | null) => Promise<void>;
+	refresh: () => Promise<string | undefined>;
+	logout: () => Promise<void>;
+	requestPasswordReset: (email: string) => Promise<void>;
+	resetPassword: (token: string, password: string) => Promise<void>;
+	verifyEmail: (token: string) => Promise<void>;
+	fetchSiteInvitation: (token: string) => Promise<ISiteInvitationPreview>;
+	acceptSiteInvitation: (values: IAcceptSiteInvitationDTO) => Promise<void>;
 	clear: () => void;
 }
 
 export const useAuthStore = create<IAuthStoreState>()(devtools(
 	persist(
-		(set) => ({
+		(set, get) => ({
 			sites: [],
 			permissions: [],
-			clear: () => set({ permissions: [], activeSite: undefined, token: undefined, user: undefined }),
+			clear: () => set({ permissions: [], activeSite: undefined, token: undefined, refreshToken: undefined, user: undefined }),
+			refresh: async () => {
+				const refreshToken = get().refreshToken;
+
+				if (!refreshToken) {
+					return;
+				}
+
+				const [result, error] = await wrapApi(kyAuthInstance.post('/admin-api/v1/auth/refresh', {
+					json: { refreshToken }
+				}).json<IRefreshResponse>());
+
+				if (error) {
+					set(() => ({ token: undefined, refreshToken: undefined }));
+					return;
+				}
+
+				set(() => ({ ...result }));
+				return result.token;
+			},
+			logout: async () => {
+				await wrapApi(kyInstance.post('/admin-api/v1/auth/logout'));
+				get().clear();
+			},
+			requestPasswordReset: async (email) => {
+				const [, error] = await wrapApi(kyAuthInstance.post('/admin-api/v1/auth/password-reset', {
+					json: { email }
+				}));
+
+				if (error) {
+					throw error;
+				}
+			},
+			resetPassword: async (token, password) => {
+				const [, error] = await wrapApi(kyAuthInstance.post('/admin-api/v1/auth/password-reset/confirm', {
+					json: { token, password }
+				}));
+
+				if (error) {
+					throw error;
+				}
+			},
+			verifyEmail: async (token) => {
+				const [, error] = await wrapApi(kyAuthInstance.post('/admin-api/v1/auth/verify-email/confirm', {
+					json: { token }
+				}));
+
+				if (error) {
+					throw error;
+				}
+			},
+			fetchSiteInvitation: async (token) => {
+				const [result, error] = await wrapApi(kyAuthInstance.get('/admin-api/v1/auth/invitations', {
+					searchParams: { token }
+				}).json<ISiteInvitationPreview>());
+
+				if (error) {
+					throw error;
+				}
+
+				return result;
+			},
+			acceptSiteInvitation: async (values) => {
+				const [, error] = await wrapApi(kyAuthInstance.post('/admin-api/v1/auth/invitations/accept', {
+					json: values
+				}));
+
+				if (error) {
+					throw error;
+				}
+			},
 			fetchUser: async (siteId) => {
 				const [result, error] = await wrapApi(kyInstance.get('/admin-api/v1/auth/me', {
 					searchParams: {
@@ -50,10 +129,10 @@ export const useAuthStore = create<IAuthStoreState>()(devtools(
 				
 				set(() => ({ activeSite }));
 			},
-			callback: async (authenticationMethodId, code) => {
+			callback: async (authenticationMethodId, code, state) => {
 				const [result, error] = await wrapApi(kyAuthInstance.post(`/admin-api/v1/auth/${authenticationMethodId}/callback`, {
 					json: {},
-					searchParams: { code }
+					searchParams: { code, state: state || '' }
 				}).json<IMeReponse>());
 
 				if (error) {
@@ -65,6 +144,21 @@ export const useAuthStore = create<IAuthStoreState>()(devtools(
 			loginLocal: async (authenticationMethodId, email, password) => {
 				const [result, error] = await wrapApi(kyAuthInstance.post(`/admin-api/v1/auth/${authenticationMethodId}/login`, {
 					json: { email, password }
+				}).json<IMeReponse | ITwoFactorChallenge>());
+
+				if (error) {
+					throw error;
+				}
+
+				if ('challenge' in result) {
+					return result;
+				}
+
+				set(() => ({ ...result }));
+			},
+			verifyTwoFactor: async (authenticationMethodId, challenge, code) => {
+				const [result, error] = await wrapApi(kyAuthInstance.post(`/admin-api/v1/auth/${authenticationMethodId}/login`, {
+					json: { challenge, code }
 				}).json<IMeReponse>());
 
 				if (error) {
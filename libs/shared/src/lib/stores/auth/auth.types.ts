@@ -36,10 +36,51 @@ export interface IUser {
 	avatar: string;
 	roles: IRole[];
 	authenticationMethod: IAuthenticationMethod;
+	twoFactorEnabled?: boolean;
+}
+
+export interface ISession {
+	id: string;
+	userAgent?: string;
+	ipAddress?: string;
+	current: boolean;
+	expiresAt: string;
+	lastUsedAt: string;
+	createdAt: string;
 }
 
 export interface IMeReponse {
 	token: string;
+	refreshToken?: string;
 	user: IUser;
 	permissions: IPermission[];
+	sessions?: ISession[];
+}
+
+export interface ITwoFactorEnrolment {
+	secret: string;
+	provisioningUri: string;
+}
+
+export interface ITwoFactorChallenge {
+	challenge: string;
+}
+
+export interface IRefreshResponse {
+	token: string;
+	refreshToken: string;
+}
+
+export interface ISiteInvitationPreview {
+	email: string;
+	siteId: string;
+	siteName: string;
+	existingAccount: boolean;
+	expiresAt: string;
+}
+
+export interface IAcceptSiteInvitationDTO {
+	token: string;
+	name?: string;
+	password?: string;
 }
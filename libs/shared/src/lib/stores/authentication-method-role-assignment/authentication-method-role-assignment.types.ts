@@ -5,6 +5,7 @@ export interface IAuthenticationMethodRoleAssignment {
 	id: string;
 	siteId?: string;
 	roleId: string;
+	groupName?: string;
 	role: IRole,
 	site?: ISite,
 }
@@ -34,9 +35,11 @@ export interface IAuthenticationMethodRoleAssignmentStoreState {
 export interface IAuthenticationMethodRoleAssignmentCreateDTO {
 	siteId?: string;
 	roleId: string;
+	groupName?: string;
 }
 
 export interface IAuthenticationMethodRoleAssignmentUpdateDTO {
 	siteId?: string;
 	roleId: string;
+	groupName?: string | null;
 }
@@ -2,7 +2,9 @@ import { IAPIHALResponse, IAPIPagination, IPageParameters } from "../../types";
 
 export enum AuthenticationMethod {
 	LOCAL = 'LOCAL',
-	OAUTH2 = 'OAUTH2'
+	OAUTH2 = 'OAUTH2',
+	OIDC = 'OIDC',
+	LDAP = 'LDAP'
 }
 
 export interface IAuthenticationMethod {